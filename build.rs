@@ -1,8 +1,48 @@
 fn main() {
+    let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+
+    // Browsers and edge runtimes have no file system, so only buffer-based persistence is compiled.
+    // The same applies to builds without the `io` feature.
+    println!("cargo:rustc-check-cfg=cfg(no_fs)");
+    let wasm = target_arch == "wasm32";
+    if (wasm && target_os == "unknown") || !cfg!(feature = "io") {
+        println!("cargo:rustc-cfg=no_fs");
+    }
+
+    // Builds with only the `pure-rust` backend compile no C++ and link no `cxx` runtime.
+    #[cfg(feature = "native")]
+    build_native(&target_arch, wasm);
+
+    // The gRPC service is generated with a vendored `protoc`, so no system toolchain is needed.
+    #[cfg(feature = "server")]
+    {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
+        tonic_prost_build::compile_protos("rust/proto/usearch.proto").unwrap();
+        println!("cargo:rerun-if-changed=rust/proto/usearch.proto");
+    }
+}
+
+#[cfg(feature = "native")]
+fn build_native(target_arch: &str, wasm: bool) {
+    // A pre-built `libusearch` can be linked instead of compiling the vendored C++ core every time.
+    // Only the generated bridge glue is compiled then, into a separate archive.
+    println!("cargo:rerun-if-env-changed=USEARCH_SYS_LIB");
+    println!("cargo:rerun-if-env-changed=USEARCH_SYS_LIB_KIND");
+    let system_lib = std::env::var("USEARCH_SYS_LIB").ok();
+    let use_system_lib = cfg!(feature = "system-lib") || system_lib.is_some();
+    let library = if use_system_lib {
+        "usearch_bridge"
+    } else {
+        "usearch"
+    };
+
     let mut build = cxx_build::bridge("rust/lib.rs");
+    if !use_system_lib {
+        build.file("rust/lib.cpp");
+    }
 
     build
-        .file("rust/lib.cpp")
         .flag_if_supported("-Wno-unknown-pragmas")
         .warnings(false)
         .include("include")
@@ -11,7 +51,7 @@ fn main() {
         .include("simsimd/include");
 
     // Check for optional features
-    if cfg!(feature = "openmp") {
+    if cfg!(feature = "openmp") && !wasm {
         build.define("USEARCH_USE_OPENMP", "1");
     } else {
         build.define("USEARCH_USE_OPENMP", "0");
@@ -23,7 +63,7 @@ fn main() {
         build.define("USEARCH_USE_FP16LIB", "0");
     }
 
-    if cfg!(feature = "simsimd") {
+    if cfg!(feature = "simsimd") && !wasm {
         build
             .define("USEARCH_USE_SIMSIMD", "1")
             .define("SIMSIMD_DYNAMIC_DISPATCH", "1")
@@ -32,8 +72,48 @@ fn main() {
         build.define("USEARCH_USE_SIMSIMD", "0");
     }
 
+    // Instruction sets can be excluded from the kernels, to benchmark the same code on every machine.
+    println!("cargo:rerun-if-env-changed=USEARCH_DISABLE_ISA");
+    let mut disabled_isa = Vec::new();
+    for name in std::env::var("USEARCH_DISABLE_ISA")
+        .unwrap_or_default()
+        .split(',')
+        .map(|name| name.trim().to_lowercase())
+        .filter(|name| !name.is_empty())
+    {
+        let targets: &[&str] = match name.as_str() {
+            "neon" => &["SIMSIMD_TARGET_NEON"],
+            "sve" => &["SIMSIMD_TARGET_SVE"],
+            "avx2" => &["SIMSIMD_TARGET_HASWELL"],
+            "avx512" => &[
+                "SIMSIMD_TARGET_SKYLAKE",
+                "SIMSIMD_TARGET_ICE",
+                "SIMSIMD_TARGET_SAPPHIRE",
+            ],
+            _ => panic!(
+                "USEARCH_DISABLE_ISA must list `neon`, `sve`, `avx2` or `avx512`, got `{}`",
+                name
+            ),
+        };
+        for target in targets {
+            build.define(target, "0");
+        }
+        disabled_isa.push(name);
+    }
+    println!(
+        "cargo:rustc-env=USEARCH_DISABLE_ISA={}",
+        disabled_isa.join(",")
+    );
+
     // Conditional compilation depending on the target operating system.
-    if cfg!(target_os = "linux") {
+    // WebAssembly builds need a Clang with a wasm32 C++ sysroot, like the one in `wasi-sdk`,
+    // passed through the `CXX` and `CXXFLAGS_wasm32_unknown_unknown` variables.
+    if wasm {
+        build
+            .flag_if_supported("-std=c++17")
+            .flag_if_supported("-O3")
+            .flag_if_supported("-fwasm-exceptions");
+    } else if cfg!(target_os = "linux") {
         build
             .flag_if_supported("-std=c++17")
             .flag_if_supported("-O3")
@@ -56,12 +136,11 @@ fn main() {
             .flag_if_supported("/W1"); // Reduce warnings verbosity
     }
 
-    let mut result = build.try_compile("usearch");
+    let mut result = build.try_compile(library);
     if result.is_err() {
         print!("cargo:warning=Failed to compile with all SIMD backends...");
 
-        let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
-        let flags_to_try = match target_arch.as_str() {
+        let flags_to_try = match target_arch {
             "arm" | "aarch64" => vec!["SIMSIMD_TARGET_NEON", "SIMSIMD_TARGET_SVE"],
             _ => vec![
                 "SIMSIMD_TARGET_SAPPHIRE",
@@ -73,7 +152,7 @@ fn main() {
 
         for flag in flags_to_try {
             build.define(flag, "0");
-            result = build.try_compile("usearch");
+            result = build.try_compile(library);
             if result.is_err() {
                 println!(
                     "cargo:warning=Failed to compile after disabling {}, trying next configuration...",
@@ -88,6 +167,30 @@ fn main() {
     // Ensure one build has been successful
     result.unwrap();
 
+    // Link the pre-built core after the bridge, which depends on it.
+    let linkage = if use_system_lib {
+        let kind = std::env::var("USEARCH_SYS_LIB_KIND").unwrap_or_else(|_| "dylib".to_owned());
+        if kind != "static" && kind != "dylib" {
+            panic!(
+                "USEARCH_SYS_LIB_KIND must be `static` or `dylib`, got `{}`",
+                kind
+            );
+        }
+        if let Some(directory) = &system_lib {
+            println!("cargo:rustc-link-search=native={}", directory);
+        }
+        check_system_lib(system_lib.as_deref(), &kind);
+        println!("cargo:rustc-link-lib={}=usearch", kind);
+        kind
+    } else {
+        "vendored".to_owned()
+    };
+    println!("cargo:rustc-env=USEARCH_LINKAGE={}", linkage);
+    println!(
+        "cargo:rustc-env=USEARCH_SYS_LIB={}",
+        system_lib.unwrap_or_default()
+    );
+
     println!("cargo:rerun-if-changed=rust/lib.rs");
     println!("cargo:rerun-if-changed=rust/lib.cpp");
     println!("cargo:rerun-if-changed=rust/lib.hpp");
@@ -95,3 +198,51 @@ fn main() {
     println!("cargo:rerun-if-changed=include/index_dense.hpp");
     println!("cargo:rerun-if-changed=include/usearch/index.hpp");
 }
+
+/// Checks that a pre-built `libusearch` was compiled from the `rust/lib.cpp` of this crate version,
+/// whose bridge layout it must match, by finding the version marker that `rust/lib.cpp` embeds.
+/// Libraries outside of `USEARCH_SYS_LIB` and the usual directories can't be checked.
+#[cfg(feature = "native")]
+fn check_system_lib(directory: Option<&str>, kind: &str) {
+    let names: &[&str] = match kind {
+        "static" => &["libusearch.a", "usearch.lib"],
+        _ => &["libusearch.so", "libusearch.dylib", "usearch.dll"],
+    };
+    let mut directories: Vec<std::path::PathBuf> = Vec::new();
+    match directory {
+        Some(directory) => directories.push(directory.into()),
+        None => {
+            println!("cargo:rerun-if-env-changed=LIBRARY_PATH");
+            if let Some(paths) = std::env::var_os("LIBRARY_PATH") {
+                directories.extend(std::env::split_paths(&paths));
+            }
+            directories.extend(["/usr/local/lib", "/usr/lib", "/usr/lib64"].map(Into::into));
+        }
+    }
+    let Some(library) = directories
+        .iter()
+        .flat_map(|directory| names.iter().map(move |name| directory.join(name)))
+        .find(|path| path.is_file())
+    else {
+        println!("cargo:warning=Couldn't find the pre-built libusearch to check its version");
+        return;
+    };
+
+    println!("cargo:rerun-if-changed={}", library.display());
+    let version = std::env::var("CARGO_PKG_VERSION").unwrap();
+    // The marker is a C string, so the terminator tells `2.1.1` from `2.1.10`.
+    let expected = format!("usearch-rust-abi/{}\0", version);
+    let contents = std::fs::read(&library).unwrap();
+    let found = contents
+        .windows(expected.len())
+        .any(|window| window == expected.as_bytes());
+    if !found {
+        panic!(
+            "{} wasn't compiled from the rust/lib.cpp of usearch {}, and its bridge may not match. \
+             Rebuild it from the sources of this version, or unset USEARCH_SYS_LIB and the \
+             `system-lib` feature to compile the vendored core.",
+            library.display(),
+            version
+        );
+    }
+}
@@ -631,8 +631,40 @@ template <typename key_at, typename slot_at> void test_strings() {
     }
 }
 
+/**
+ * Tests the bookkeeping of the ring buffer used for task queues, when it wraps around
+ * and when it is filled to its capacity, where the head catches up with the tail.
+ */
+void test_ring() {
+    ring_gt<std::size_t> ring;
+    expect(ring.reserve(10));
+    std::size_t const capacity = ring.capacity();
+    expect(ring.size() == 0);
+
+    for (std::size_t i = 0; i != capacity; ++i)
+        ring.push(i);
+    expect(!ring.empty());
+    expect(ring.size() == capacity);
+
+    // Wrap around, and fill the ring once more.
+    std::size_t popped = 0;
+    expect(ring.try_pop(popped) && popped == 0);
+    expect(ring.size() == capacity - 1);
+    ring.push(capacity);
+    expect(ring.size() == capacity);
+    expect(ring[0] == 1 && ring[capacity - 1] == capacity);
+
+    while (ring.try_pop(popped))
+        continue;
+    expect(ring.empty() && ring.size() == 0);
+    expect(popped == capacity);
+}
+
 int main(int, char**) {
 
+    std::printf("Testing the ring buffer\n");
+    test_ring();
+
     // Exact search without constructing indexes.
     // Great for validating the distance functions.
     std::printf("Testing exact search\n");
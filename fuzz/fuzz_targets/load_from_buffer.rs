@@ -0,0 +1,25 @@
+//! Feeds arbitrary bytes to `load_from_buffer` and `view_from_buffer`, which must reject
+//! malformed or truncated serialized indexes with an error rather than crash.
+//!
+//! Run with `cargo +nightly fuzz run load_from_buffer` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use usearch::{Index, IndexOptions};
+
+fuzz_target!(|data: &[u8]| {
+    let options = IndexOptions {
+        dimensions: 4,
+        ..Default::default()
+    };
+    let mut index = Index::new(&options).unwrap();
+    if index.load_from_buffer(data).is_ok() {
+        let _ = index.search(&[0.0_f32; 4], 4);
+    }
+
+    let mut viewed = Index::new(&options).unwrap();
+    if viewed.view_from_buffer(data).is_ok() {
+        let _ = viewed.search(&[0.0_f32; 4], 4);
+    }
+});
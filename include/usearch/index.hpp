@@ -74,6 +74,7 @@
 #include <algorithm> // `std::sort_heap`
 #include <atomic>    // `std::atomic`
 #include <bitset>    // `std::bitset`
+#include <chrono>    // `std::chrono::steady_clock`
 #include <climits>   // `CHAR_BIT`
 #include <cmath>     // `std::sqrt`
 #include <cstring>   // `std::memset`
@@ -1019,7 +1020,7 @@ class ring_gt {
     size_t size() const noexcept {
         if (empty_)
             return 0;
-        else if (head_ >= tail_)
+        else if (head_ > tail_)
             return head_ - tail_;
         else
             return capacity_ - (tail_ - head_);
@@ -1124,6 +1125,15 @@ struct index_config_t {
     /// > It is called `M0` in the paper.
     std::size_t connectivity_base = default_connectivity() * 2;
 
+    /// @brief Multiplier of the random levels drawn for new nodes.
+    /// Defaults to `1 / ln(connectivity)` if zero.
+    /// > It is called `mL` in the paper.
+    double level_multiplier = 0;
+
+    /// @brief Seed of the per-thread generators drawing the levels of new nodes.
+    /// The generator of thread `i` is seeded with `level_seed + i`.
+    std::uint64_t level_seed = 0;
+
     inline index_config_t() = default;
     inline index_config_t(std::size_t c) noexcept
         : connectivity(c ? c : default_connectivity()), connectivity_base(c ? c * 2 : default_connectivity() * 2) {}
@@ -1163,6 +1173,10 @@ struct index_search_config_t {
 
     /// @brief Brute-forces exhaustive search over all entries in the index.
     bool exact = false;
+
+    /// @brief Optional point in time, after which the traversal stops with the best results found so far.
+    /// The default, the clock's epoch, disables the check.
+    std::chrono::steady_clock::time_point deadline{};
 };
 
 struct index_cluster_config_t {
@@ -1892,6 +1906,17 @@ class index_gt {
         std::default_random_engine level_generator{};
         std::size_t iteration_cycles{};
         std::size_t computed_distances_count{};
+        std::size_t hops_count{};
+        std::chrono::steady_clock::time_point deadline{};
+        bool truncated{};
+
+        /// @brief Checks the clock every few steps, flagging the search as truncated once past the deadline.
+        inline bool expired(std::size_t step) noexcept {
+            if (deadline == std::chrono::steady_clock::time_point{} || (step % 64) != 0)
+                return false;
+            truncated = truncated || std::chrono::steady_clock::now() >= deadline;
+            return truncated;
+        }
 
         template <typename value_at, typename metric_at, typename entry_at> //
         inline distance_t measure(value_at const& first, entry_at const& second, metric_at&& metric) noexcept {
@@ -2032,6 +2057,10 @@ class index_gt {
 
     dynamic_allocator_t const& dynamic_allocator() const noexcept { return dynamic_allocator_; }
     tape_allocator_t const& tape_allocator() const noexcept { return tape_allocator_; }
+    tape_allocator_t& tape_allocator() noexcept { return tape_allocator_; }
+
+    /// @brief  The memory-mapped file or buffer a viewed index is served from, empty otherwise.
+    span_gt<byte_t const> viewed_bytes() const noexcept { return {viewed_file_.data(), viewed_file_.size()}; }
 
 #pragma region Adjusting Configuration
 
@@ -2114,6 +2143,14 @@ class index_gt {
         if (!new_nodes || !new_contexts || !new_mutexes)
             return false;
 
+        // Level generators continue their sequences, so that growing doesn't replay drawn levels.
+        using seed_t = typename std::default_random_engine::result_type;
+        for (std::size_t i = 0; i != new_contexts.size(); ++i)
+            if (i < contexts_.size())
+                new_contexts[i].level_generator = contexts_[i].level_generator;
+            else
+                new_contexts[i].level_generator.seed(static_cast<seed_t>(config_.level_seed + i));
+
         // Move the nodes info, and deallocate previous buffers.
         if (nodes_)
             std::memcpy(new_nodes.data(), nodes_.data(), sizeof(node_t) * size());
@@ -2190,6 +2227,10 @@ class index_gt {
         std::size_t visited_members{};
         /** @brief  Number of times the distances were computed. */
         std::size_t computed_distances{};
+        /** @brief  Number of moves of the greedy descent through the upper levels. */
+        std::size_t hops{};
+        /** @brief  Whether the traversal was stopped by the deadline before converging. */
+        bool truncated{};
         error_t error{};
 
         inline search_result_t() noexcept {}
@@ -2502,11 +2543,15 @@ class index_gt {
         // Go down the level, tracking only the closest match
         result.computed_distances = context.computed_distances_count;
         result.visited_members = context.iteration_cycles;
+        result.hops = context.hops_count;
+        context.truncated = false;
 
         if (config.exact) {
             if (!top.reserve(wanted))
                 return result.failed("Out of memory!");
+            context.deadline = config.deadline;
             search_exact_(query, metric, predicate, wanted, context);
+            context.deadline = {};
         } else {
             next_candidates_t& next = context.next_candidates;
             std::size_t expansion = (std::max)(config.expansion, wanted);
@@ -2519,7 +2564,10 @@ class index_gt {
             std::size_t closest_slot = search_for_one_(query, metric, prefetch, entry_slot_, max_level_, 0, context);
 
             // For bottom layer we need a more optimized procedure
-            if (!search_to_find_in_base_(query, metric, predicate, prefetch, closest_slot, expansion, context))
+            context.deadline = config.deadline;
+            bool found = search_to_find_in_base_(query, metric, predicate, prefetch, closest_slot, expansion, context);
+            context.deadline = {};
+            if (!found)
                 return result.failed("Out of memory!");
         }
 
@@ -2529,6 +2577,8 @@ class index_gt {
         // Normalize stats
         result.computed_distances = context.computed_distances_count - result.computed_distances;
         result.visited_members = context.iteration_cycles - result.visited_members;
+        result.hops = context.hops_count - result.hops;
+        result.truncated = context.truncated;
         result.count = top.size();
         return result;
     }
@@ -2611,6 +2661,36 @@ class index_gt {
         return result;
     }
 
+    /// @brief  Slot of the node searches start from, meaningful only in non-empty indexes.
+    std::size_t entry_slot() const noexcept { return entry_slot_; }
+
+    /// @brief  Key of the node in a slot, the free key for removed members.
+    vector_key_t slot_key(std::size_t slot) const noexcept { return node_at_(slot).key(); }
+
+    /// @brief  Highest level of the graph the node in a slot belongs to.
+    std::size_t level_at(std::size_t slot) const noexcept {
+        return static_cast<std::size_t>(node_at_(slot).level());
+    }
+
+    /// @brief  Memory of the node in a slot, with its neighbors on every level it belongs to.
+    span_gt<byte_t const> node_bytes_at(std::size_t slot) const noexcept {
+        node_t node = node_at_(slot);
+        return {node.tape(), node_bytes_(node.level())};
+    }
+
+    /**
+     *  @brief  Calls `callback` with the slot of every neighbor of a node on one of its levels.
+     *          Not thread-safe with respect to concurrent insertions.
+     */
+    template <typename callback_at>
+    void for_each_neighbor(std::size_t slot, std::size_t level, callback_at&& callback) const noexcept {
+        neighbors_ref_t neighbors = neighbors_(node_at_(slot), static_cast<level_t>(level));
+        for (std::size_t i = 0; i != neighbors.size(); ++i) {
+            compressed_slot_t neighbor_slot = neighbors[i];
+            callback(static_cast<std::size_t>(neighbor_slot));
+        }
+    }
+
     stats_t stats(std::size_t level) const noexcept {
         stats_t result{};
 
@@ -3148,7 +3228,9 @@ class index_gt {
   private:
     inline static precomputed_constants_t precompute_(index_config_t const& config) noexcept {
         precomputed_constants_t pre;
-        pre.inverse_log_connectivity = 1.0 / std::log(static_cast<double>(config.connectivity));
+        pre.inverse_log_connectivity = config.level_multiplier > 0
+                                           ? config.level_multiplier
+                                           : 1.0 / std::log(static_cast<double>(config.connectivity));
         pre.neighbors_bytes = config.connectivity * sizeof(compressed_slot_t) + sizeof(neighbors_count_t);
         pre.neighbors_base_bytes = config.connectivity_base * sizeof(compressed_slot_t) + sizeof(neighbors_count_t);
         return pre;
@@ -3416,6 +3498,7 @@ class index_gt {
                     }
                 }
                 context.iteration_cycles++;
+                context.hops_count += changed;
             } while (changed);
         }
         return closest_slot;
@@ -3540,6 +3623,8 @@ class index_gt {
 
             next.pop();
             context.iteration_cycles++;
+            if (context.expired(context.iteration_cycles))
+                break;
 
             neighbors_ref_t candidate_neighbors = neighbors_base_(node_at_(candidate.slot));
 
@@ -3584,6 +3669,8 @@ class index_gt {
         top.clear();
         top.reserve(count);
         for (std::size_t i = 0; i != size(); ++i) {
+            if (context.expired(i))
+                break;
             if (!is_dummy<predicate_at>())
                 if (!predicate(at(i)))
                     continue;
@@ -7,10 +7,11 @@
 #pragma once
 #include <stdlib.h> // `aligned_alloc`
 
-#include <functional> // `std::function`
-#include <numeric>    // `std::iota`
-#include <thread>     // `std::thread`
-#include <vector>     // `std::vector`
+#include <condition_variable> // `std::condition_variable`
+#include <functional>         // `std::function`
+#include <numeric>            // `std::iota`
+#include <thread>             // `std::thread`
+#include <vector>             // `std::vector`
 
 #include <usearch/index.hpp>
 #include <usearch/index_plugins.hpp>
@@ -390,6 +391,9 @@ class index_dense_gt {
     /// @brief Mutex, controlling concurrent access to `available_threads_`.
     mutable std::mutex available_threads_mutex_;
 
+    /// @brief Signaled whenever a thread ID is returned to `available_threads_`.
+    mutable std::condition_variable available_threads_released_;
+
 #if defined(USEARCH_DEFINED_CPP17)
     using shared_mutex_t = std::shared_mutex;
 #else
@@ -434,8 +438,49 @@ class index_dense_gt {
     /// @brief A constant for the reserved key value, used to mark deleted entries.
     vector_key_t free_key_ = default_free_value<vector_key_t>();
 
+    /// @brief Claim on one of the `available_threads_`, returned on destruction, unless moved from.
+    struct thread_lock_t {
+        index_dense_gt const* parent{};
+        std::size_t thread_id{};
+        bool engaged{};
+
+        /// @brief An empty claim, holding no thread context.
+        thread_lock_t() noexcept = default;
+        thread_lock_t(index_dense_gt const& parent, std::size_t thread_id, bool engaged) noexcept
+            : parent(&parent), thread_id(thread_id), engaged(engaged) {}
+        thread_lock_t(thread_lock_t&& other) noexcept
+            : parent(other.parent), thread_id(other.thread_id), engaged(other.engaged) {
+            other.engaged = false;
+        }
+        thread_lock_t(thread_lock_t const&) = delete;
+
+        ~thread_lock_t() {
+            if (engaged)
+                parent->thread_unlock_(thread_id);
+        }
+    };
+
   public:
-    using search_result_t = typename index_t::search_result_t;
+    /**
+     *  @brief Search results, referencing the buffers of the thread context they were found with.
+     *
+     *  Keeps that context claimed until destroyed, so that a concurrent search can't take it
+     *  and overwrite the results before they are exported.
+     */
+    class search_result_t : public index_t::search_result_t {
+        thread_lock_t lock_;
+
+      public:
+        search_result_t() noexcept = default;
+        search_result_t(typename index_t::search_result_t result, thread_lock_t lock) noexcept
+            : index_t::search_result_t(std::move(result)), lock_(std::move(lock)) {}
+
+        search_result_t failed(error_t message) noexcept {
+            this->error = std::move(message);
+            return std::move(*this);
+        }
+    };
+
     using cluster_result_t = typename index_t::cluster_result_t;
     using add_result_t = typename index_t::add_result_t;
     using stats_t = typename index_t::stats_t;
@@ -572,6 +617,15 @@ class index_dense_gt {
 
     stats_t stats() const { return typed_->stats(); }
     stats_t stats(std::size_t level) const { return typed_->stats(level); }
+    std::size_t slots() const noexcept { return typed_->size(); }
+    std::size_t entry_slot() const noexcept { return typed_->entry_slot(); }
+    vector_key_t slot_key(std::size_t slot) const noexcept { return typed_->slot_key(slot); }
+    std::size_t level_at(std::size_t slot) const noexcept { return typed_->level_at(slot); }
+    span_gt<byte_t const> node_bytes_at(std::size_t slot) const noexcept { return typed_->node_bytes_at(slot); }
+    template <typename callback_at>
+    void for_each_neighbor(std::size_t slot, std::size_t level, callback_at&& callback) const noexcept {
+        typed_->for_each_neighbor(slot, level, std::forward<callback_at>(callback));
+    }
     stats_t stats(stats_t* stats_per_level, std::size_t max_level) const {
         return typed_->stats(stats_per_level, max_level);
     }
@@ -593,6 +647,144 @@ class index_dense_gt {
             vectors_tape_allocator_.total_allocated();
     }
 
+    /**
+     *  @brief  Changes where the vectors take their pages from, before any vector is stored.
+     *  @return `false` if vectors are already stored, or the index is a view.
+     */
+    bool change_vector_pages(pages_config_t const& pages) {
+        if (typed_->is_immutable())
+            return false;
+        return vectors_tape_allocator_.change_pages(pages);
+    }
+
+    /**
+     *  @brief  Reads the nodes and vectors searches visit first, until `budget_bytes` are covered,
+     *          so the first searches of a viewed index don't wait for the pages to be faulted in.
+     *
+     *  Walks the graph breadth-first from the entry point, covering every level from the top,
+     *  where all searches start, down to the neighborhood of the entry point on the base level.
+     *  @return The number of bytes of nodes and vectors covered.
+     */
+    std::size_t warmup(std::size_t budget_bytes) const {
+        if (!typed_->size() || !budget_bytes)
+            return 0;
+
+        std::size_t covered = 0;
+        std::size_t vector_bytes = metric_.bytes_per_vector();
+        auto touch = [&](byte_t const* bytes, std::size_t length) {
+            if (!bytes || !length)
+                return;
+            volatile byte_t sink = 0;
+            for (std::size_t offset = 0; offset < length; offset += page_allocator_t::page_size())
+                sink = sink ^ bytes[offset];
+            sink = sink ^ bytes[length - 1];
+            covered += length;
+        };
+        auto visit = [&](std::size_t slot) {
+            span_gt<byte_t const> node = typed_->node_bytes_at(slot);
+            touch(node.data(), node.size());
+            touch(vectors_lookup_[slot], vector_bytes);
+        };
+
+        std::vector<bool> visited(typed_->size());
+        std::vector<std::size_t> frontier{typed_->entry_slot()};
+        visited[frontier.front()] = true;
+        visit(frontier.front());
+        for (std::size_t level = typed_->max_level() + 1; level-- != 0 && covered < budget_bytes;) {
+            // Every node reached on the upper levels is also on this one, and expands it again.
+            for (std::size_t i = 0; i != frontier.size() && covered < budget_bytes; ++i)
+                typed_->for_each_neighbor(frontier[i], level, [&](std::size_t neighbor) {
+                    if (visited[neighbor] || covered >= budget_bytes)
+                        return;
+                    visited[neighbor] = true;
+                    visit(neighbor);
+                    frontier.push_back(neighbor);
+                });
+        }
+        return covered;
+    }
+
+    struct memory_lock_result_t {
+        std::size_t bytes{};
+        int error{};
+    };
+
+    /**
+     *  @brief  Locks the memory of the index in RAM, so it is never paged out: the mapping of a view,
+     *          and the arenas of nodes and vectors, including those allocated later.
+     *  @return The number of bytes to lock, and the `errno` of the failure, after which nothing stays locked.
+     */
+    memory_lock_result_t lock_memory() {
+        memory_lock_result_t result;
+        span_gt<byte_t const> viewed = typed_->viewed_bytes();
+        result.bytes = viewed.size() + typed_->tape_allocator().total_allocated() +
+                       vectors_tape_allocator_.total_allocated();
+        if (viewed.size())
+            result.error = lock_pages(viewed.data(), viewed.size());
+        if (!result.error) {
+            result.error = typed_->tape_allocator().lock();
+            if (result.error && viewed.size())
+                unlock_pages(viewed.data(), viewed.size());
+        }
+        if (!result.error) {
+            result.error = vectors_tape_allocator_.lock();
+            if (result.error) {
+                typed_->tape_allocator().unlock();
+                if (viewed.size())
+                    unlock_pages(viewed.data(), viewed.size());
+            }
+        }
+        return result;
+    }
+
+    /**
+     *  @brief  Lets the memory locked with `lock_memory` be paged out again.
+     */
+    void unlock_memory() {
+        span_gt<byte_t const> viewed = typed_->viewed_bytes();
+        if (viewed.size() && memory_locked())
+            unlock_pages(viewed.data(), viewed.size());
+        typed_->tape_allocator().unlock();
+        vectors_tape_allocator_.unlock();
+    }
+
+    bool memory_locked() const noexcept { return vectors_tape_allocator_.locked(); }
+
+    struct memory_breakdown_t {
+        std::size_t graph_bytes{};
+        std::size_t vectors_bytes{};
+        std::size_t tombstones_bytes{};
+        std::size_t slack_bytes{};
+    };
+
+    /**
+     *  @brief  Splits `memory_usage` into the graph, the vectors, the removed members whose
+     *          nodes and vectors haven't been reused yet, and the arena space not handed out.
+     */
+    memory_breakdown_t memory_breakdown() const {
+        std::size_t removed_nodes_bytes = 0;
+        std::size_t removed_vectors_bytes = 0;
+        if (!typed_->is_immutable() && free_keys_.size()) {
+            for (std::size_t slot = 0; slot != typed_->size(); ++slot) {
+                if (typed_->slot_key(slot) != free_key_)
+                    continue;
+                removed_nodes_bytes += typed_->memory_usage_per_node(typed_->level_at(slot));
+                removed_vectors_bytes += metric_.bytes_per_vector();
+            }
+        }
+
+        std::size_t vectors_slack = vectors_tape_allocator_.total_wasted() + vectors_tape_allocator_.total_reserved();
+        std::size_t vectors_bytes = vectors_tape_allocator_.total_allocated();
+        vectors_bytes -= (std::min)(vectors_bytes, vectors_slack);
+
+        memory_breakdown_t result;
+        result.graph_bytes = typed_->memory_usage(0) - removed_nodes_bytes;
+        result.vectors_bytes = vectors_bytes - (std::min)(vectors_bytes, removed_vectors_bytes);
+        result.tombstones_bytes = removed_nodes_bytes + (std::min)(vectors_bytes, removed_vectors_bytes);
+        result.slack_bytes = memory_usage() - result.graph_bytes - result.vectors_bytes - result.tombstones_bytes;
+        return result;
+    }
+
     static constexpr std::size_t any_thread() { return std::numeric_limits<std::size_t>::max(); }
     static constexpr distance_t infinite_distance() { return std::numeric_limits<distance_t>::max(); }
 
@@ -622,6 +814,13 @@ class index_dense_gt {
     template <typename predicate_at> search_result_t filtered_search(f32_t const* vector, std::size_t wanted, predicate_at&& predicate, std::size_t thread = any_thread(), bool exact = false) const { return search_(vector, wanted, std::forward<predicate_at>(predicate), thread, exact, casts_.from_f32); }
     template <typename predicate_at> search_result_t filtered_search(f64_t const* vector, std::size_t wanted, predicate_at&& predicate, std::size_t thread = any_thread(), bool exact = false) const { return search_(vector, wanted, std::forward<predicate_at>(predicate), thread, exact, casts_.from_f64); }
 
+    using deadline_t = std::chrono::steady_clock::time_point;
+    template <typename predicate_at> search_result_t search_until(b1x8_t const* vector, std::size_t wanted, deadline_t deadline, predicate_at&& predicate, std::size_t thread = any_thread()) const { return search_(vector, wanted, std::forward<predicate_at>(predicate), thread, false, casts_.from_b1x8, deadline); }
+    template <typename predicate_at> search_result_t search_until(i8_t const* vector, std::size_t wanted, deadline_t deadline, predicate_at&& predicate, std::size_t thread = any_thread()) const { return search_(vector, wanted, std::forward<predicate_at>(predicate), thread, false, casts_.from_i8, deadline); }
+    template <typename predicate_at> search_result_t search_until(f16_t const* vector, std::size_t wanted, deadline_t deadline, predicate_at&& predicate, std::size_t thread = any_thread()) const { return search_(vector, wanted, std::forward<predicate_at>(predicate), thread, false, casts_.from_f16, deadline); }
+    template <typename predicate_at> search_result_t search_until(f32_t const* vector, std::size_t wanted, deadline_t deadline, predicate_at&& predicate, std::size_t thread = any_thread()) const { return search_(vector, wanted, std::forward<predicate_at>(predicate), thread, false, casts_.from_f32, deadline); }
+    template <typename predicate_at> search_result_t search_until(f64_t const* vector, std::size_t wanted, deadline_t deadline, predicate_at&& predicate, std::size_t thread = any_thread()) const { return search_(vector, wanted, std::forward<predicate_at>(predicate), thread, false, casts_.from_f64, deadline); }
+
     std::size_t get(vector_key_t key, b1x8_t* vector, std::size_t vectors_count = 1) const { return get_(key, vector, vectors_count, casts_.to_b1x8); }
     std::size_t get(vector_key_t key, i8_t* vector, std::size_t vectors_count = 1) const { return get_(key, vector, vectors_count, casts_.to_i8); }
     std::size_t get(vector_key_t key, f16_t* vector, std::size_t vectors_count = 1) const { return get_(key, vector, vectors_count, casts_.to_f16); }
@@ -754,6 +953,7 @@ class index_dense_gt {
             available_threads_.resize(limits.threads());
             std::iota(available_threads_.begin(), available_threads_.end(), 0ul);
         }
+        available_threads_released_.notify_all();
         return typed_->reserve(limits);
     }
 
@@ -1199,6 +1399,16 @@ class index_dense_gt {
         return slot_lookup_.count(key_and_slot_t::any_slot(key));
     }
 
+    /**
+     *  @brief  Calls `callback` with the slot of every vector stored under the `key`.
+     */
+    template <typename callback_at> void for_each_slot(vector_key_t key, callback_at&& callback) const {
+        shared_lock_t lock(slot_lookup_mutex_);
+        auto matching_slots = slot_lookup_.equal_range(key_and_slot_t::any_slot(key));
+        for (auto slots_it = matching_slots.first; slots_it != matching_slots.second; ++slots_it)
+            callback(static_cast<std::size_t>((*slots_it).slot));
+    }
+
     struct labeling_result_t {
         error_t error{};
         std::size_t completed{};
@@ -1304,6 +1514,12 @@ class index_dense_gt {
         labeling_result_t result;
         unique_lock_t lookup_lock(slot_lookup_mutex_);
 
+        // Re-inserting the popped entries under the same key would pop them again forever
+        if (from == to) {
+            result.completed = slot_lookup_.count(key_and_slot_t::any_slot(from));
+            return result;
+        }
+
         if (!multi() && slot_lookup_.contains(key_and_slot_t::any_slot(to)))
             return result.failed("Renaming impossible, the key is already in use");
 
@@ -1709,32 +1925,24 @@ class index_dense_gt {
     }
 
   private:
-    struct thread_lock_t {
-        index_dense_gt const& parent;
-        std::size_t thread_id;
-        bool engaged;
-
-        ~thread_lock_t() {
-            if (engaged)
-                parent.thread_unlock_(thread_id);
-        }
-    };
-
     thread_lock_t thread_lock_(std::size_t thread_id) const {
         if (thread_id != any_thread())
             return {*this, thread_id, false};
 
-        available_threads_mutex_.lock();
+        // More concurrent calls than contexts wait for one to be released
+        std::unique_lock<std::mutex> lock(available_threads_mutex_);
+        available_threads_released_.wait(lock, [this] { return !available_threads_.empty(); });
         thread_id = available_threads_.back();
         available_threads_.pop_back();
-        available_threads_mutex_.unlock();
         return {*this, thread_id, true};
     }
 
     void thread_unlock_(std::size_t thread_id) const {
-        available_threads_mutex_.lock();
-        available_threads_.push_back(thread_id);
-        available_threads_mutex_.unlock();
+        {
+            std::unique_lock<std::mutex> lock(available_threads_mutex_);
+            available_threads_.push_back(thread_id);
+        }
+        available_threads_released_.notify_one();
     }
 
     template <typename scalar_at>
@@ -1788,7 +1996,8 @@ class index_dense_gt {
 
     template <typename scalar_at, typename predicate_at>
     search_result_t search_(scalar_at const* vector, std::size_t wanted, predicate_at&& predicate, std::size_t thread,
-                            bool exact, cast_t const& cast) const {
+                            bool exact, cast_t const& cast,
+                            std::chrono::steady_clock::time_point deadline = {}) const {
 
         // Cast the vector, if needed for compatibility with `metric_`
         thread_lock_t lock = thread_lock_(thread);
@@ -1804,17 +2013,18 @@ class index_dense_gt {
         search_config.thread = lock.thread_id;
         search_config.expansion = config_.expansion_search;
         search_config.exact = exact;
+        search_config.deadline = deadline;
 
         if (std::is_same<typename std::decay<predicate_at>::type, dummy_predicate_t>::value) {
             auto allow = [free_key_ = this->free_key_](member_cref_t const& member) noexcept {
                 return member.key != free_key_;
             };
-            return typed_->search(vector_data, wanted, metric_proxy_t{*this}, search_config, allow);
+            return {typed_->search(vector_data, wanted, metric_proxy_t{*this}, search_config, allow), std::move(lock)};
         } else {
             auto allow = [free_key_ = this->free_key_, &predicate](member_cref_t const& member) noexcept {
                 return member.key != free_key_ && predicate(member.key);
             };
-            return typed_->search(vector_data, wanted, metric_proxy_t{*this}, search_config, allow);
+            return {typed_->search(vector_data, wanted, metric_proxy_t{*this}, search_config, allow), std::move(lock)};
         }
     }
 
@@ -3,6 +3,7 @@
 #include <float.h>  // `_Float16`
 #include <stdlib.h> // `aligned_alloc`
 
+#include <cerrno>  // `errno`
 #include <cstring> // `std::strncmp`
 #include <numeric> // `std::iota`
 #include <thread>  // `std::thread`
@@ -628,6 +629,80 @@ class page_allocator_t {
     }
 };
 
+/**
+ *  @brief  Locks pages in RAM, so they are never paged out.
+ *  @return Zero, or the `errno` of the failure, like `ENOMEM` past the `RLIMIT_MEMLOCK` limit.
+ */
+inline int lock_pages(void const* pages, std::size_t length) noexcept {
+#if defined(USEARCH_DEFINED_WINDOWS)
+    return ::VirtualLock(const_cast<void*>(pages), length) ? 0 : ENOMEM;
+#else
+    return ::mlock(pages, length) == 0 ? 0 : errno;
+#endif
+}
+
+/**
+ *  @brief  Lets pages locked with `lock_pages` be paged out again.
+ */
+inline void unlock_pages(void const* pages, std::size_t length) noexcept {
+#if defined(USEARCH_DEFINED_WINDOWS)
+    ::VirtualUnlock(const_cast<void*>(pages), length);
+#else
+    ::munlock(pages, length);
+#endif
+}
+
+/**
+ *  @brief  Where the arenas of a `memory_mapping_allocator_gt` take their pages from: regular
+ *          pages, transparent huge pages, pre-reserved huge pages, or user-provided functions.
+ *
+ *  Huge pages cover 2 MB each, so scattered reads of large arenas miss the TLB less often.
+ *  Without huge pages reserved in `vm.nr_hugepages`, `huge_tlb_k` falls back to transparent ones.
+ */
+struct pages_config_t {
+    enum kind_t { regular_k, transparent_huge_k, huge_tlb_k, custom_k };
+    using allocate_t = byte_t* (*)(std::size_t, std::uintptr_t);
+    using deallocate_t = void (*)(byte_t*, std::size_t, std::uintptr_t);
+
+    kind_t kind = regular_k;
+    allocate_t allocate = nullptr;
+    deallocate_t deallocate = nullptr;
+    std::uintptr_t state = 0;
+
+    static constexpr std::size_t huge_page_size() { return 2 * 1024 * 1024; }
+
+    byte_t* allocate_pages(std::size_t count_bytes) const noexcept {
+        if (kind == custom_k)
+            return allocate(count_bytes, state);
+        if (kind == regular_k)
+            return page_allocator_t{}.allocate(count_bytes);
+
+        count_bytes = divide_round_up(count_bytes, huge_page_size()) * huge_page_size();
+#if defined(USEARCH_DEFINED_LINUX) && defined(MAP_HUGETLB)
+        if (kind == huge_tlb_k) {
+            void* arena = mmap(NULL, count_bytes, PROT_WRITE | PROT_READ, MAP_PRIVATE | MAP_ANONYMOUS | MAP_HUGETLB,
+                               -1, 0);
+            if (arena != MAP_FAILED)
+                return (byte_t*)arena;
+        }
+#endif
+        byte_t* arena = page_allocator_t{}.allocate(count_bytes);
+#if defined(USEARCH_DEFINED_LINUX) && defined(MADV_HUGEPAGE)
+        if (arena && arena != MAP_FAILED)
+            madvise(arena, count_bytes, MADV_HUGEPAGE);
+#endif
+        return arena;
+    }
+
+    void deallocate_pages(byte_t* arena, std::size_t count_bytes) const noexcept {
+        if (kind == custom_k)
+            return deallocate(arena, count_bytes, state);
+        if (kind != regular_k)
+            count_bytes = divide_round_up(count_bytes, huge_page_size()) * huge_page_size();
+        page_allocator_t{}.deallocate(arena, count_bytes);
+    }
+};
+
 /**
  *  @brief  Memory-mapping allocator designed for "alloc many, free at once" usage patterns.
  *          @b Thread-safe, @b except constructors and destructors.
@@ -649,6 +724,19 @@ template <std::size_t alignment_ak = 1> class memory_mapping_allocator_gt {
     std::size_t last_usage_ = head_size();
     std::size_t last_capacity_ = min_capacity();
     std::size_t wasted_space_ = 0;
+    pages_config_t pages_;
+    bool locked_ = false;
+
+    static byte_t* previous_arena_(byte_t* arena) noexcept {
+        byte_t* previous_arena = nullptr;
+        std::memcpy(&previous_arena, arena, sizeof(byte_t*));
+        return previous_arena;
+    }
+    static std::size_t arena_capacity_(byte_t* arena) noexcept {
+        std::size_t capacity = 0;
+        std::memcpy(&capacity, arena + sizeof(byte_t*), sizeof(std::size_t));
+        return capacity;
+    }
 
   public:
     using value_type = byte_t;
@@ -659,13 +747,16 @@ template <std::size_t alignment_ak = 1> class memory_mapping_allocator_gt {
     memory_mapping_allocator_gt() = default;
     memory_mapping_allocator_gt(memory_mapping_allocator_gt&& other) noexcept
         : last_arena_(exchange(other.last_arena_, nullptr)), last_usage_(exchange(other.last_usage_, 0)),
-          last_capacity_(exchange(other.last_capacity_, 0)), wasted_space_(exchange(other.wasted_space_, 0)) {}
+          last_capacity_(exchange(other.last_capacity_, 0)), wasted_space_(exchange(other.wasted_space_, 0)),
+          pages_(other.pages_), locked_(exchange(other.locked_, false)) {}
 
     memory_mapping_allocator_gt& operator=(memory_mapping_allocator_gt&& other) noexcept {
         std::swap(last_arena_, other.last_arena_);
         std::swap(last_usage_, other.last_usage_);
         std::swap(last_capacity_, other.last_capacity_);
         std::swap(wasted_space_, other.wasted_space_);
+        std::swap(pages_, other.pages_);
+        std::swap(locked_, other.locked_);
         return *this;
     }
 
@@ -681,7 +772,9 @@ template <std::size_t alignment_ak = 1> class memory_mapping_allocator_gt {
             std::memcpy(&previous_arena, last_arena, sizeof(byte_t*));
             std::size_t last_cap = 0;
             std::memcpy(&last_cap, last_arena + sizeof(byte_t*), sizeof(std::size_t));
-            page_allocator_t{}.deallocate(last_arena, last_cap);
+            if (locked_)
+                unlock_pages(last_arena, last_cap);
+            pages_.deallocate_pages(last_arena, last_cap);
             last_arena = previous_arena;
         }
 
@@ -692,6 +785,49 @@ template <std::size_t alignment_ak = 1> class memory_mapping_allocator_gt {
         wasted_space_ = 0;
     }
 
+    /**
+     *  @brief Changes where new arenas take their pages from.
+     *  @return `false` if arenas were already allocated, as they must be freed the way they were allocated.
+     */
+    bool change_pages(pages_config_t const& pages) noexcept {
+        std::unique_lock<std::mutex> lock(mutex_);
+        if (last_arena_)
+            return false;
+        pages_ = pages;
+        return true;
+    }
+
+    /**
+     *  @brief Locks the arenas in RAM with `lock_pages`, and the arenas allocated later as well.
+     *  @return Zero, or the `errno` of the first failure, after which no arena stays locked.
+     */
+    int lock() noexcept {
+        std::unique_lock<std::mutex> lock(mutex_);
+        for (byte_t* arena = last_arena_; arena; arena = previous_arena_(arena)) {
+            int error = lock_pages(arena, arena_capacity_(arena));
+            if (!error)
+                continue;
+            for (byte_t* locked = last_arena_; locked != arena; locked = previous_arena_(locked))
+                unlock_pages(locked, arena_capacity_(locked));
+            return error;
+        }
+        locked_ = true;
+        return 0;
+    }
+
+    /**
+     *  @brief Unlocks the arenas locked with `lock`, and stops locking new ones.
+     */
+    void unlock() noexcept {
+        std::unique_lock<std::mutex> lock(mutex_);
+        if (locked_)
+            for (byte_t* arena = last_arena_; arena; arena = previous_arena_(arena))
+                unlock_pages(arena, arena_capacity_(arena));
+        locked_ = false;
+    }
+
+    bool locked() const noexcept { return locked_; }
+
     /**
      *  @brief Copy constructor.
      *  @note This is a no-op copy constructor since the allocator is not copyable.
@@ -718,9 +854,11 @@ template <std::size_t alignment_ak = 1> class memory_mapping_allocator_gt {
         std::unique_lock<std::mutex> lock(mutex_);
         if (!last_arena_ || (last_usage_ + extended_bytes >= last_capacity_)) {
             std::size_t new_cap = (std::max)(last_capacity_, ceil2(extended_bytes)) * capacity_multiplier();
-            byte_t* new_arena = page_allocator_t{}.allocate(new_cap);
+            byte_t* new_arena = pages_.allocate_pages(new_cap);
             if (!new_arena)
                 return nullptr;
+            if (locked_)
+                lock_pages(new_arena, new_cap);
             std::memcpy(new_arena, &last_arena_, sizeof(byte_t*));
             std::memcpy(new_arena + sizeof(byte_t*), &new_cap, sizeof(std::size_t));
 
@@ -1454,16 +1592,18 @@ class metric_punned_t {
      *  @param  metric_state    The state to pass to the metric function.
      *  @param  metric_kind     The kind of metric to use.
      *  @param  scalar_kind     The kind of scalar to use.
+     *  @param  dimensions      The number of dimensions in the vectors, to size their storage.
      *  @return                 A metric object that can be used to compute distances between vectors.
      */
     inline static metric_punned_t statefull(std::uintptr_t metric_uintptr, std::uintptr_t metric_state,
                                             metric_kind_t metric_kind = metric_kind_t::unknown_k,
-                                            scalar_kind_t scalar_kind = scalar_kind_t::unknown_k) noexcept {
+                                            scalar_kind_t scalar_kind = scalar_kind_t::unknown_k,
+                                            std::size_t dimensions = 0) noexcept {
         metric_punned_t metric;
         metric.metric_routed_ = &metric_punned_t::invoke_array_array_third;
         metric.metric_ptr_ = metric_uintptr;
         metric.metric_third_arg_ = metric_state;
-        metric.dimensions_ = 0;
+        metric.dimensions_ = dimensions;
         metric.metric_kind_ = metric_kind;
         metric.scalar_kind_ = scalar_kind;
         return metric;
@@ -2029,10 +2169,15 @@ class flat_hash_multi_set_gt {
 
         // Pre-increment
         equal_iterator_gt& operator++() {
-            do {
+            // Deleted slots keep their elements, so they must be skipped even when those are equal
+            while (true) {
                 index_ = (index_ + 1) & (parent_->capacity_slots_ - 1);
-            } while (!equals_(parent_->slot_ref(index_).element, query_) &&
-                     (parent_->slot_ref(index_).header.populated & parent_->slot_ref(index_).mask));
+                slot_ref_t slot = parent_->slot_ref(index_);
+                if (~slot.header.populated & slot.mask)
+                    break;
+                if ((~slot.header.deleted & slot.mask) && equals_(slot.element, query_))
+                    break;
+            }
             return *this;
         }
 
@@ -0,0 +1,112 @@
+//! Aggregates over the stored vectors, computed natively without exporting them one by one.
+//!
+//! Clustering, drift monitoring and query expansion often need the average of many stored
+//! vectors. [`Index::centroid`] and [`Index::mean_all`] convert the vectors to `f32` in batches
+//! inside the native index, and sum them into a single buffer, so only the result crosses into
+//! Rust.
+
+use std::sync::PoisonError;
+
+use crate::{Index, Key};
+
+impl Index {
+    /// Averages the vectors stored under the given keys.
+    ///
+    /// Keys with several vectors contribute all of them, repeated keys are counted as many
+    /// times as they're repeated, and missing keys are skipped. Quantized vectors are averaged
+    /// after conversion to `f32`.
+    ///
+    /// # Returns
+    ///
+    /// The centroid, or an empty vector if none of the keys is present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[1.0_f32, 0.0]).unwrap();
+    /// index.add(2, &[3.0_f32, 2.0]).unwrap();
+    /// index.add(3, &[8.0_f32, 8.0]).unwrap();
+    ///
+    /// assert_eq!(index.centroid(&[1, 2]).unwrap(), vec![2.0, 1.0]);
+    /// assert!(index.centroid(&[42]).unwrap().is_empty());
+    /// ```
+    pub fn centroid(&self, keys: &[Key]) -> Result<Vec<f32>, cxx::Exception> {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        let mut centroid = vec![0.0; self.dimensions()];
+        if self.inner.centroid(keys, &mut centroid)? == 0 {
+            centroid.clear();
+        }
+        Ok(centroid)
+    }
+
+    /// Averages all vectors stored in the index.
+    ///
+    /// # Returns
+    ///
+    /// The centroid, or an empty vector if the index is empty.
+    pub fn mean_all(&self) -> Result<Vec<f32>, cxx::Exception> {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        let mut centroid = vec![0.0; self.dimensions()];
+        if self.inner.mean_all(&mut centroid)? == 0 {
+            centroid.clear();
+        }
+        Ok(centroid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_centroid() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        assert!(index.mean_all().unwrap().is_empty());
+        for key in 0..10u64 {
+            index.add(key, &[key as f32, 1.0, -(key as f32)]).unwrap();
+        }
+        assert_eq!(index.mean_all().unwrap(), vec![4.5, 1.0, -4.5]);
+        assert_eq!(index.centroid(&[2, 4, 99]).unwrap(), vec![3.0, 1.0, -3.0]);
+        assert_eq!(index.centroid(&[2, 2, 5]).unwrap(), vec![3.0, 1.0, -3.0]);
+        index.remove(9).unwrap();
+        assert_eq!(index.mean_all().unwrap(), vec![4.0, 1.0, -4.0]);
+
+        // Every vector of a key counts once, however many times the key is exported.
+        let multi = Index::new(&IndexOptions {
+            multi: true,
+            ..options.clone()
+        })
+        .unwrap();
+        multi.reserve(4).unwrap();
+        multi.add(1, &[1.0_f32, 0.0, 0.0]).unwrap();
+        multi.add(1, &[3.0_f32, 0.0, 0.0]).unwrap();
+        multi.add(2, &[8.0_f32, 0.0, 0.0]).unwrap();
+        assert_eq!(multi.centroid(&[1]).unwrap(), vec![2.0, 0.0, 0.0]);
+        assert_eq!(multi.mean_all().unwrap(), vec![4.0, 0.0, 0.0]);
+
+        // Quantized vectors are converted before averaging.
+        let quantized = Index::new(&IndexOptions {
+            quantization: ScalarKind::F16,
+            ..options
+        })
+        .unwrap();
+        quantized.add(1, &[0.5_f32, 1.0, 2.0]).unwrap();
+        quantized.add(2, &[1.5_f32, 3.0, 4.0]).unwrap();
+        assert_eq!(quantized.mean_all().unwrap(), vec![1.0, 2.0, 3.0]);
+    }
+}
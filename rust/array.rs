@@ -0,0 +1,123 @@
+//! Adding, searching and reading vectors as fixed-size arrays.
+//!
+//! Embedding models produce vectors of a known length, which callers often keep as `[T; D]`.
+//! [`Index::add_array`], [`Index::search_array`] and [`Index::get_array`] take and return such
+//! arrays directly, so a vector of the wrong length doesn't type-check against the rest of the
+//! program, and only `D` itself is compared with the dimensions of the index.
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, Key, VectorType};
+
+impl Index {
+    /// Adds a vector stored as an array, like [`Index::add`].
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if `D` isn't the number of dimensions of the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 3,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add_array(1, &[1.0_f32, 0.0, 0.0]).unwrap();
+    /// index.add_array(2, &[0.0_f32, 1.0, 0.0]).unwrap();
+    ///
+    /// let matches = index.search_array(&[0.9_f32, 0.1, 0.0], 1).unwrap();
+    /// assert_eq!(matches.keys, vec![1]);
+    /// assert_eq!(index.get_array::<f32, 3>(2).unwrap(), Some([0.0, 1.0, 0.0]));
+    /// assert_eq!(index.get_array::<f32, 3>(3).unwrap(), None);
+    /// ```
+    pub fn add_array<T: VectorType, const D: usize>(
+        &self,
+        key: Key,
+        vector: &[T; D],
+    ) -> Result<(), IndexError> {
+        self.add(key, vector)
+    }
+
+    /// Performs kANN Search for a query stored as an array, like [`Index::search`].
+    pub fn search_array<T: VectorType, const D: usize>(
+        &self,
+        query: &[T; D],
+        count: usize,
+    ) -> Result<Matches, IndexError> {
+        self.search(query, count)
+    }
+
+    /// Reads the vector stored under a key into an array.
+    ///
+    /// # Returns
+    ///
+    /// The vector, the first one for keys of `multi` indexes holding several, `None` if the key
+    /// is missing, or `IndexError::DimensionMismatch` if `D` isn't the number of dimensions.
+    pub fn get_array<T, const D: usize>(&self, key: Key) -> Result<Option<[T; D]>, IndexError>
+    where
+        T: VectorType + Default + Copy,
+    {
+        self.check_dimensions::<T>(D)?;
+        let mut vector = [T::default(); D];
+        match self.get(key, &mut vector)? {
+            0 => Ok(None),
+            _ => Ok(Some(vector)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{f16, Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_arrays() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            multi: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(3).unwrap();
+        index.add_array(1, &[1.0_f32, 0.0, 0.0, 0.0]).unwrap();
+        index.add_array(1, &[0.0_f32, 1.0, 0.0, 0.0]).unwrap();
+        index.add_array(2, &[0.0_f64, 0.0, 1.0, 0.0]).unwrap();
+        assert_eq!(index.size(), 3);
+
+        let matches = index.search_array(&[0.0_f32, 0.0, 0.9, 0.1], 1).unwrap();
+        assert_eq!(matches.keys, vec![2]);
+        let first = index.get_array::<f16, 4>(1).unwrap().unwrap();
+        assert!(
+            first == [1.0, 0.0, 0.0, 0.0].map(f16::from_f32)
+                || first == [0.0, 1.0, 0.0, 0.0].map(f16::from_f32)
+        );
+        assert_eq!(
+            index.get_array::<f32, 4>(2).unwrap(),
+            Some([0.0, 0.0, 1.0, 0.0])
+        );
+        assert_eq!(index.get_array::<f32, 4>(42).unwrap(), None);
+
+        assert!(matches!(
+            index.add_array(3, &[1.0_f32; 3]),
+            Err(IndexError::DimensionMismatch {
+                expected: 4,
+                got: 3
+            })
+        ));
+        assert!(matches!(
+            index.search_array(&[1.0_f32; 5], 1),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            index.get_array::<f32, 2>(1),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,170 @@
+//! Zero-copy ingestion of Arrow arrays, as produced by embedding pipelines and dataframe libraries.
+//!
+//! Enabled with the `arrow` feature. Embeddings usually arrive as a `FixedSizeList` column,
+//! whose values live in one contiguous buffer. [`Index::add_arrow`] hands slices of that
+//! buffer straight to the native layer, row by row, without copying any vector into a `Vec`.
+//!
+//! `Float32`, `Float64`, and `Int8` values are supported, and converted by the native layer
+//! into the scalar kind of the index, exactly like slices of those types passed to `add`.
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int8Type};
+use arrow_array::{Array, FixedSizeListArray, UInt64Array};
+use arrow_schema::DataType;
+
+use crate::{Index, IndexError, Key, VectorType};
+
+fn invalid_data(message: &str) -> IndexError {
+    IndexError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message,
+    ))
+}
+
+impl Index {
+    /// Adds the rows of a `FixedSizeList` array under the matching keys, growing the capacity
+    /// as needed. Rows with a null vector are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A non-nullable array of keys, as long as `vectors`.
+    /// * `vectors` - A `FixedSizeList` array of `Float32`, `Float64`, or `Int8` values.
+    ///
+    /// # Returns
+    ///
+    /// The number of added vectors, or `IndexError::DimensionMismatch` if the list size
+    /// doesn't match the dimensions of the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{FixedSizeListArray, Float32Array, UInt64Array};
+    /// use arrow_schema::{DataType, Field};
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let keys = UInt64Array::from(vec![1, 2]);
+    /// let values = Float32Array::from(vec![0.1, 0.2, 0.3, 0.4]);
+    /// let item = Arc::new(Field::new("item", DataType::Float32, true));
+    /// let vectors = FixedSizeListArray::try_new(item, 2, Arc::new(values), None).unwrap();
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// assert_eq!(index.add_arrow(&keys, &vectors).unwrap(), 2);
+    /// ```
+    pub fn add_arrow(
+        &self,
+        keys: &UInt64Array,
+        vectors: &FixedSizeListArray,
+    ) -> Result<usize, IndexError> {
+        let dimensions = self.dimensions();
+        if vectors.value_length() as usize != dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: dimensions,
+                got: vectors.value_length() as usize,
+            });
+        }
+        if keys.len() != vectors.len() {
+            return Err(invalid_data("Keys and vectors must have the same length"));
+        }
+        if keys.null_count() > 0 {
+            return Err(invalid_data("Keys must not be null"));
+        }
+
+        let values = vectors.values();
+        match values.data_type() {
+            DataType::Float32 => self.add_rows(
+                keys.values(),
+                vectors,
+                values.as_primitive::<Float32Type>().values(),
+            ),
+            DataType::Float64 => self.add_rows(
+                keys.values(),
+                vectors,
+                values.as_primitive::<Float64Type>().values(),
+            ),
+            DataType::Int8 => self.add_rows(
+                keys.values(),
+                vectors,
+                values.as_primitive::<Int8Type>().values(),
+            ),
+            other => Err(IndexError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Unsupported vector scalar type: {}", other),
+            ))),
+        }
+    }
+
+    /// Adds every non-null row, slicing the contiguous values buffer in place.
+    fn add_rows<T: VectorType>(
+        &self,
+        keys: &[Key],
+        vectors: &FixedSizeListArray,
+        values: &[T],
+    ) -> Result<usize, IndexError> {
+        let dimensions = self.dimensions();
+        let needed = self.size() + vectors.len() - vectors.null_count();
+        if needed > self.capacity() {
+            self.reserve(needed.max(self.capacity() * 2))?;
+        }
+
+        let mut added = 0;
+        for (row, &key) in keys.iter().enumerate() {
+            if vectors.is_null(row) {
+                continue;
+            }
+            let start = vectors.value_offset(row) as usize;
+            T::add(self, key, &values[start..start + dimensions])?;
+            added += 1;
+        }
+        Ok(added)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{FixedSizeListArray, Float64Array, Int8Array, UInt64Array};
+    use arrow_buffer::NullBuffer;
+    use arrow_schema::{DataType, Field};
+
+    use crate::{Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_add_arrow() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+
+        // Slicing the arrays shifts the value offsets, which must be respected.
+        let keys = UInt64Array::from(vec![0, 1, 2, 3]).slice(1, 3);
+        let values = Float64Array::from(vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+        let item = Arc::new(Field::new("item", DataType::Float64, true));
+        let nulls = NullBuffer::from(vec![true, true, false, true]);
+        let vectors = FixedSizeListArray::try_new(item, 2, Arc::new(values), Some(nulls))
+            .unwrap()
+            .slice(1, 3);
+
+        assert_eq!(index.add_arrow(&keys, &vectors).unwrap(), 2);
+        let mut vector: Vec<f32> = Vec::new();
+        index.export(3, &mut vector).unwrap();
+        assert_eq!(vector, vec![3.0, 3.0]);
+        assert!(!index.contains(2));
+
+        let values = Int8Array::from(vec![1, 2, 3]);
+        let item = Arc::new(Field::new("item", DataType::Int8, true));
+        let narrow = FixedSizeListArray::try_new(item, 3, Arc::new(values), None).unwrap();
+        assert!(matches!(
+            index.add_arrow(&UInt64Array::from(vec![9]), &narrow),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,539 @@
+//! Chunked bulk ingestion with progress reporting and cooperative cancellation.
+//!
+//! Multi-hour index builds benefit from periodic feedback and a clean way to stop.
+//! The [`BatchInsert`] job returned by [`Index::batch_insert_chunked`] inserts vectors
+//! chunk by chunk, growing the index capacity as it goes, and hands out a
+//! [`CancellationHandle`] that can be triggered from any thread.
+//!
+//! With the `tokio` feature, [`Index::batch_insert_async`] does the same from async code,
+//! yielding to the runtime between chunks and streaming [`Progress`] snapshots, and
+//! [`Index::export_async`] reads vectors back in equally bounded chunks.
+
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "tokio")]
+use tokio::sync::mpsc;
+
+use crate::{Index, IndexError, Key, VectorType};
+
+/// A snapshot of the ingestion state, passed to the progress callback after every chunk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Number of vectors inserted so far.
+    pub processed: usize,
+    /// Total number of vectors in the batch.
+    pub total: usize,
+    /// Wall-clock time elapsed since the job started.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Average insertion throughput since the start of the job.
+    pub fn items_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.processed as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Estimated time until the whole batch is inserted, extrapolated from the average throughput.
+    /// Returns `None` until the throughput can be measured.
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.items_per_second();
+        if rate <= 0.0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.processed);
+        Some(Duration::from_secs_f64(remaining as f64 / rate))
+    }
+
+    /// Fraction of the batch inserted so far, in the `[0, 1]` range.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.processed as f64 / self.total as f64
+        }
+    }
+}
+
+/// A cheap, cloneable handle used to stop a running job between chunks.
+///
+/// The handle is `Send + Sync`, so it can be moved to a signal handler, a watchdog
+/// thread, or a UI callback, while the job itself runs on the current thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationHandle {
+    /// Creates a new handle in the non-cancelled state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the job to stop. The chunk currently being inserted is completed first.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Checks if cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+/// The outcome of a chunked batch insertion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchSummary {
+    /// Number of vectors inserted before the job finished or was cancelled.
+    pub inserted: usize,
+    /// Total number of vectors in the batch.
+    pub total: usize,
+    /// Wall-clock duration of the job.
+    pub elapsed: Duration,
+    /// `true` if the job was stopped through a [`CancellationHandle`] before completion.
+    pub cancelled: bool,
+}
+
+/// A pending chunked insertion job, created by [`Index::batch_insert_chunked`].
+///
+/// Nothing is inserted until [`BatchInsert::run`] is called, so the cancellation
+/// handle can be obtained and shared beforehand.
+pub struct BatchInsert<'a, T, V, P>
+where
+    T: VectorType,
+    V: AsRef<[T]>,
+    P: Fn(Progress),
+{
+    index: &'a Index,
+    batch: &'a [(Key, V)],
+    chunk_size: usize,
+    progress: P,
+    handle: CancellationHandle,
+    _scalar: std::marker::PhantomData<T>,
+}
+
+impl<'a, T, V, P> BatchInsert<'a, T, V, P>
+where
+    T: VectorType,
+    V: AsRef<[T]>,
+    P: Fn(Progress),
+{
+    /// Returns a handle that can stop this job from another thread.
+    pub fn handle(&self) -> CancellationHandle {
+        self.handle.clone()
+    }
+
+    /// Replaces the job's cancellation handle with an existing one, letting a single
+    /// handle control several jobs.
+    pub fn with_handle(mut self, handle: CancellationHandle) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Executes the job on the current thread.
+    ///
+    /// Before every chunk the index capacity is grown to fit it, then the chunk is
+    /// inserted and the progress callback is invoked. Cancellation is checked between
+    /// chunks, so at most one chunk is inserted after [`CancellationHandle::cancel`].
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchSummary`] on success, or the first error of [`Index::add`], like
+    /// `IndexError::DimensionMismatch` for a vector without the dimensions of the index.
+    pub fn run(self) -> Result<BatchSummary, IndexError> {
+        let start = Instant::now();
+        let total = self.batch.len();
+        let mut inserted = 0;
+
+        for chunk in self.batch.chunks(self.chunk_size.max(1)) {
+            if self.handle.is_cancelled() {
+                break;
+            }
+
+            let required = self.index.size() + chunk.len();
+            if required > self.index.capacity() {
+                self.index.reserve(required)?;
+            }
+            for (key, vector) in chunk {
+                self.index.add(*key, vector.as_ref())?;
+            }
+
+            inserted += chunk.len();
+            (self.progress)(Progress {
+                processed: inserted,
+                total,
+                elapsed: start.elapsed(),
+            });
+        }
+
+        Ok(BatchSummary {
+            inserted,
+            total,
+            elapsed: start.elapsed(),
+            cancelled: inserted < total,
+        })
+    }
+}
+
+/// A stream of [`Progress`] snapshots of an [`AsyncBatchInsert`], ending with the job.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct ProgressStream {
+    receiver: mpsc::UnboundedReceiver<Progress>,
+}
+
+#[cfg(feature = "tokio")]
+impl futures_core::Stream for ProgressStream {
+    type Item = Progress;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Progress>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// A pending chunked insertion job for async contexts, created by [`Index::batch_insert_async`].
+///
+/// Like [`BatchInsert`], nothing is inserted until [`AsyncBatchInsert::run`] is awaited,
+/// so the progress stream and the cancellation handle can be obtained beforehand.
+#[cfg(feature = "tokio")]
+pub struct AsyncBatchInsert<'a, T, V>
+where
+    T: VectorType,
+    V: AsRef<[T]>,
+{
+    index: &'a Index,
+    batch: &'a [(Key, V)],
+    chunk_size: usize,
+    yield_every: usize,
+    progress: Option<mpsc::UnboundedSender<Progress>>,
+    handle: CancellationHandle,
+    _scalar: std::marker::PhantomData<T>,
+}
+
+#[cfg(feature = "tokio")]
+impl<'a, T, V> AsyncBatchInsert<'a, T, V>
+where
+    T: VectorType,
+    V: AsRef<[T]>,
+{
+    /// Returns a handle that can stop this job from another thread or task.
+    pub fn handle(&self) -> CancellationHandle {
+        self.handle.clone()
+    }
+
+    /// Replaces the job's cancellation handle with an existing one.
+    pub fn with_handle(mut self, handle: CancellationHandle) -> Self {
+        self.handle = handle;
+        self
+    }
+
+    /// Returns a stream receiving a [`Progress`] snapshot after every chunk.
+    /// Calling it again replaces the previous stream, which then ends.
+    pub fn progress(&mut self) -> ProgressStream {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.progress = Some(sender);
+        ProgressStream { receiver }
+    }
+
+    /// Executes the job on the current task, yielding to the runtime after every
+    /// `yield_every` chunks, so other tasks on the same worker keep making progress.
+    ///
+    /// # Returns
+    ///
+    /// A [`BatchSummary`] on success, or the first error of [`Index::add`], like
+    /// `IndexError::DimensionMismatch` for a vector without the dimensions of the index.
+    pub async fn run(self) -> Result<BatchSummary, IndexError> {
+        let start = Instant::now();
+        let total = self.batch.len();
+        let mut inserted = 0;
+
+        for (position, chunk) in self.batch.chunks(self.chunk_size.max(1)).enumerate() {
+            if position > 0 && position % self.yield_every.max(1) == 0 {
+                tokio::task::yield_now().await;
+            }
+            if self.handle.is_cancelled() {
+                break;
+            }
+
+            let required = self.index.size() + chunk.len();
+            if required > self.index.capacity() {
+                self.index.reserve(required)?;
+            }
+            for (key, vector) in chunk {
+                self.index.add(*key, vector.as_ref())?;
+            }
+
+            inserted += chunk.len();
+            if let Some(progress) = &self.progress {
+                // The stream may have been dropped, in which case nobody is listening.
+                let _ = progress.send(Progress {
+                    processed: inserted,
+                    total,
+                    elapsed: start.elapsed(),
+                });
+            }
+        }
+
+        Ok(BatchSummary {
+            inserted,
+            total,
+            elapsed: start.elapsed(),
+            cancelled: inserted < total,
+        })
+    }
+}
+
+impl Index {
+    /// Prepares a chunked insertion of a large batch of `(key, vector)` pairs.
+    ///
+    /// The capacity is reserved incrementally, one chunk at a time, and `progress`
+    /// is called after each chunk with the throughput and ETA estimates.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - The `(key, vector)` pairs to insert.
+    /// * `chunk_size` - The number of vectors inserted between progress reports and cancellation checks.
+    /// * `progress` - A callback receiving a [`Progress`] snapshot after every chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let options = IndexOptions { dimensions: 2, ..Default::default() };
+    /// let index = Index::new(&options).unwrap();
+    /// let batch: Vec<(u64, Vec<f32>)> = (0..100).map(|i| (i, vec![i as f32, 1.0])).collect();
+    ///
+    /// let job = index.batch_insert_chunked(&batch, 10, |p| println!("{:.0}%", p.fraction() * 100.0));
+    /// let handle = job.handle(); // Can be moved to another thread to stop the job.
+    /// let summary = job.run().unwrap();
+    /// assert_eq!(summary.inserted, 100);
+    /// assert!(!handle.is_cancelled());
+    /// ```
+    pub fn batch_insert_chunked<'a, T, V, P>(
+        &'a self,
+        batch: &'a [(Key, V)],
+        chunk_size: usize,
+        progress: P,
+    ) -> BatchInsert<'a, T, V, P>
+    where
+        T: VectorType,
+        V: AsRef<[T]>,
+        P: Fn(Progress),
+    {
+        BatchInsert {
+            index: self,
+            batch,
+            chunk_size,
+            progress,
+            handle: CancellationHandle::new(),
+            _scalar: std::marker::PhantomData,
+        }
+    }
+
+    /// Prepares a chunked insertion of a large batch for async contexts.
+    ///
+    /// Behaves like [`Index::batch_insert_chunked`], but yields to the runtime between chunks,
+    /// instead of monopolizing a worker thread, and reports progress through a stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - The `(key, vector)` pairs to insert.
+    /// * `chunk_size` - The number of vectors inserted between progress reports and cancellation checks.
+    /// * `yield_every` - The number of chunks inserted between yields to the runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let options = IndexOptions { dimensions: 2, ..Default::default() };
+    /// let index = Index::new(&options).unwrap();
+    /// let batch: Vec<(u64, Vec<f32>)> = (0..100).map(|i| (i, vec![i as f32, 1.0])).collect();
+    ///
+    /// let mut job = index.batch_insert_async(&batch, 10, 2);
+    /// let _progress = job.progress(); // Can be polled by another task.
+    /// let summary = job.run().await.unwrap();
+    /// assert_eq!(summary.inserted, 100);
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn batch_insert_async<'a, T, V>(
+        &'a self,
+        batch: &'a [(Key, V)],
+        chunk_size: usize,
+        yield_every: usize,
+    ) -> AsyncBatchInsert<'a, T, V>
+    where
+        T: VectorType,
+        V: AsRef<[T]>,
+    {
+        AsyncBatchInsert {
+            index: self,
+            batch,
+            chunk_size,
+            yield_every,
+            progress: None,
+            handle: CancellationHandle::new(),
+            _scalar: std::marker::PhantomData,
+        }
+    }
+
+    /// Exports the vectors stored under many keys, yielding to the runtime after every chunk.
+    /// Keys with several vectors produce one entry per vector, and missing keys none.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys to export.
+    /// * `chunk_size` - The number of keys exported between yields to the runtime.
+    #[cfg(feature = "tokio")]
+    pub async fn export_async<T: VectorType + Default + Clone>(
+        &self,
+        keys: &[Key],
+        chunk_size: usize,
+    ) -> Result<Vec<(Key, Vec<T>)>, cxx::Exception> {
+        let dimensions = self.dimensions();
+        let mut exported = Vec::with_capacity(keys.len());
+        let mut buffer = Vec::new();
+        for (position, chunk) in keys.chunks(chunk_size.max(1)).enumerate() {
+            if position > 0 {
+                tokio::task::yield_now().await;
+            }
+            for &key in chunk {
+                self.export(key, &mut buffer)?;
+                exported.extend(
+                    buffer
+                        .chunks_exact(dimensions)
+                        .map(|vector| (key, vector.to_vec())),
+                );
+            }
+        }
+        Ok(exported)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexOptions;
+    use std::cell::RefCell;
+
+    fn make_batch(count: u64) -> Vec<(Key, Vec<f32>)> {
+        (0..count).map(|i| (i, vec![i as f32, 1.0, 0.5])).collect()
+    }
+
+    #[test]
+    fn test_chunked_insert_reports_progress() {
+        let options = IndexOptions {
+            dimensions: 3,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let batch = make_batch(25);
+
+        let reports = RefCell::new(Vec::new());
+        let summary = index
+            .batch_insert_chunked(&batch, 10, |p| reports.borrow_mut().push(p))
+            .run()
+            .unwrap();
+
+        assert_eq!(summary.inserted, 25);
+        assert!(!summary.cancelled);
+        assert_eq!(index.size(), 25);
+
+        let reports = reports.into_inner();
+        let processed: Vec<usize> = reports.iter().map(|p| p.processed).collect();
+        assert_eq!(processed, vec![10, 20, 25]);
+        assert!(reports.iter().all(|p| p.total == 25));
+    }
+
+    #[test]
+    fn test_chunked_insert_cancellation() {
+        let options = IndexOptions {
+            dimensions: 3,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let batch = make_batch(50);
+
+        let handle = CancellationHandle::new();
+        let trigger = handle.clone();
+        let summary = index
+            .batch_insert_chunked(&batch, 10, move |p| {
+                if p.processed >= 20 {
+                    trigger.cancel();
+                }
+            })
+            .with_handle(handle)
+            .run()
+            .unwrap();
+
+        assert!(summary.cancelled);
+        assert_eq!(summary.inserted, 20);
+        assert_eq!(index.size(), 20);
+    }
+
+    #[test]
+    fn test_chunked_insert_checks_dimensions() {
+        let options = IndexOptions {
+            dimensions: 3,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let mut batch = make_batch(5);
+        batch[3].1.pop();
+
+        let result = index.batch_insert_chunked(&batch, 2, |_| {}).run();
+        assert!(matches!(
+            result,
+            Err(IndexError::DimensionMismatch {
+                expected: 3,
+                got: 2
+            })
+        ));
+        assert_eq!(index.size(), 3);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_insert_and_export() {
+        use std::future::poll_fn;
+        use std::pin::pin;
+
+        use futures_core::Stream;
+
+        let options = IndexOptions {
+            dimensions: 3,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let batch = make_batch(25);
+
+        let mut job = index.batch_insert_async(&batch, 10, 1);
+        let mut progress = pin!(job.progress());
+        let summary = job.run().await.unwrap();
+        assert_eq!(summary.inserted, 25);
+
+        let mut processed = Vec::new();
+        while let Some(p) = poll_fn(|cx| progress.as_mut().poll_next(cx)).await {
+            processed.push(p.processed);
+        }
+        assert_eq!(processed, vec![10, 20, 25]);
+
+        let exported = index.export_async::<f32>(&[3, 7, 99], 2).await.unwrap();
+        assert_eq!(exported.len(), 2);
+        assert_eq!(exported[1], (7, vec![7.0, 1.0, 0.5]));
+    }
+}
@@ -0,0 +1,201 @@
+//! Coalescing of concurrent searches into parallel batches.
+//!
+//! Servers answering many small requests pay for every search on its own: a hop to the blocking
+//! pool, and a native context claimed and released. [`QueryBatcher`] collects the searches
+//! arriving within a short window instead, runs them as one batch spread across the hardware
+//! threads, and resolves every caller with its own matches. A lone request waits for the window
+//! to close, so the window trades a bounded latency for throughput under load.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::Instant;
+
+use crate::ffi::Matches;
+use crate::parallel::in_parallel;
+use crate::{Index, IndexError, VectorType};
+
+/// A pending search, with the channel its matches are sent back through.
+struct Request<T> {
+    query: Vec<T>,
+    count: usize,
+    reply: oneshot::Sender<Result<Matches, IndexError>>,
+}
+
+/// A cloneable handle submitting searches to a background task, which runs them in batches.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use usearch::{Index, IndexOptions, MetricKind, QueryBatcher, ScalarKind};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let index = Arc::new(Index::new(&options).unwrap());
+/// index.add(1, &[0.0_f32, 1.0]).unwrap();
+/// index.add(2, &[1.0_f32, 0.0]).unwrap();
+///
+/// let batcher = QueryBatcher::new(index, Duration::from_millis(1), 64);
+/// let (first, second) = tokio::join!(
+///     batcher.search(vec![0.1_f32, 0.9], 1),
+///     batcher.search(vec![0.9_f32, 0.1], 1),
+/// );
+/// assert_eq!(first.unwrap().keys, vec![1]);
+/// assert_eq!(second.unwrap().keys, vec![2]);
+/// # }
+/// ```
+pub struct QueryBatcher<T> {
+    requests: mpsc::Sender<Request<T>>,
+}
+
+impl<T> Clone for QueryBatcher<T> {
+    fn clone(&self) -> Self {
+        Self {
+            requests: self.requests.clone(),
+        }
+    }
+}
+
+impl<T: VectorType + Send + Sync + 'static> QueryBatcher<T> {
+    /// Spawns the batching task onto the current tokio runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index to search.
+    /// * `max_delay` - How long the first search of a batch waits for others to join it.
+    /// * `max_batch` - The number of searches starting a batch right away. Submissions beyond
+    ///   twice as many pending searches wait for room.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn new(index: Arc<Index>, max_delay: Duration, max_batch: usize) -> Self {
+        let max_batch = max_batch.max(1);
+        let (requests, receiver) = mpsc::channel(max_batch * 2);
+        tokio::spawn(run_batches(index, receiver, max_delay, max_batch));
+        Self { requests }
+    }
+
+    /// Searches for the `count` nearest neighbors of the query, as part of the next batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the batching task stopped, when the runtime shuts down, or after a search of
+    /// its batch panicked.
+    pub async fn search(&self, query: Vec<T>, count: usize) -> Result<Matches, IndexError> {
+        let (reply, matches) = oneshot::channel();
+        let request = Request {
+            query,
+            count,
+            reply,
+        };
+        if self.requests.send(request).await.is_err() {
+            panic!("The query batching task stopped");
+        }
+        matches.await.expect("The query batching task stopped")
+    }
+}
+
+/// Collects the requests into batches, until every handle is dropped.
+async fn run_batches<T: VectorType + Send + Sync + 'static>(
+    index: Arc<Index>,
+    mut receiver: mpsc::Receiver<Request<T>>,
+    max_delay: Duration,
+    max_batch: usize,
+) {
+    while let Some(first) = receiver.recv().await {
+        let closing = Instant::now() + max_delay;
+        let mut batch = vec![first];
+        while batch.len() < max_batch {
+            match tokio::time::timeout_at(closing, receiver.recv()).await {
+                Ok(Some(request)) => batch.push(request),
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        let index = Arc::clone(&index);
+        let searched = tokio::task::spawn_blocking(move || {
+            let results = in_parallel::<_, _, Infallible, _>(&index, &batch, |index, request| {
+                Ok(index.search(&request.query, request.count))
+            });
+            for (request, result) in batch
+                .into_iter()
+                .zip(results.unwrap_or_else(|never| match never {}))
+            {
+                // The caller may have given up on its search meanwhile.
+                let _ = request.reply.send(result);
+            }
+        })
+        .await;
+        if searched.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_batched_searches() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Arc::new(Index::new(&options).unwrap());
+        for key in 0..1000u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+
+        // Concurrent callers each get their own matches, whatever batch they landed in.
+        let batcher = QueryBatcher::new(Arc::clone(&index), Duration::from_millis(5), 16);
+        let tasks: Vec<_> = (0..100u64)
+            .map(|key| {
+                let batcher = batcher.clone();
+                tokio::spawn(async move {
+                    let count = 1 + key as usize % 3;
+                    let matches = batcher.search(vec![key as f32 * 10.0, 0.0], count).await;
+                    (key, count, matches.unwrap().keys)
+                })
+            })
+            .collect();
+        for task in tasks {
+            let (key, count, keys) = task.await.unwrap();
+            assert_eq!(keys.len(), count);
+            assert_eq!(keys[0], key * 10);
+        }
+
+        // Callers giving up don't affect the rest of their batch.
+        let abandoned = tokio::spawn({
+            let batcher = batcher.clone();
+            async move { batcher.search(vec![1.0_f32, 0.0], 1).await }
+        });
+        abandoned.abort();
+        let matches = batcher.search(vec![2.0_f32, 0.0], 1).await.unwrap();
+        assert_eq!(matches.keys, vec![2]);
+
+        // The task stops once the last handle drops, releasing the index.
+        drop(batcher);
+        for _ in 0..100 {
+            if Arc::strong_count(&index) == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        assert_eq!(Arc::strong_count(&index), 1);
+    }
+}
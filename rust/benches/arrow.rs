@@ -0,0 +1,70 @@
+//! Compares adding an Arrow `FixedSizeList` column through per-row copies against
+//! `Index::add_arrow`, which slices the values buffer in place.
+//!
+//! Run with `cargo bench --bench arrow --features arrow`.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arrow_array::{Array, FixedSizeListArray, Float32Array, UInt64Array};
+use arrow_schema::{DataType, Field};
+use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+
+const ROWS: usize = 20_000;
+const DIMENSIONS: usize = 256;
+
+fn fresh_index() -> Index {
+    let options = IndexOptions {
+        dimensions: DIMENSIONS,
+        metric: MetricKind::IP,
+        quantization: ScalarKind::F32,
+        ..Default::default()
+    };
+    let index = Index::new(&options).unwrap();
+    index.reserve(ROWS).unwrap();
+    index
+}
+
+fn time(label: &str, run: impl FnOnce() -> usize) -> Duration {
+    let start = Instant::now();
+    let added = run();
+    let elapsed = start.elapsed();
+    println!(
+        "{label:>10}: {added} vectors in {elapsed:?} ({:.0} vectors/s)",
+        added as f64 / elapsed.as_secs_f64()
+    );
+    elapsed
+}
+
+fn main() {
+    let keys = UInt64Array::from_iter_values(0..ROWS as u64);
+    let values = Float32Array::from_iter_values(
+        (0..ROWS * DIMENSIONS).map(|i| ((i * 7919) % 1000) as f32 / 1000.0),
+    );
+    let item = Arc::new(Field::new("item", DataType::Float32, true));
+    let vectors =
+        FixedSizeListArray::try_new(item, DIMENSIONS as i32, Arc::new(values), None).unwrap();
+
+    let index = fresh_index();
+    let copied = time("to_vec", || {
+        for row in 0..vectors.len() {
+            let list = vectors.value(row);
+            let vector = list
+                .as_any()
+                .downcast_ref::<Float32Array>()
+                .unwrap()
+                .values()
+                .to_vec();
+            index.add(keys.value(row), &vector).unwrap();
+        }
+        index.size()
+    });
+
+    let index = fresh_index();
+    let borrowed = time("zero-copy", || index.add_arrow(&keys, &vectors).unwrap());
+
+    println!(
+        "   savings: {:.1}%",
+        100.0 * (1.0 - borrowed.as_secs_f64() / copied.as_secs_f64())
+    );
+}
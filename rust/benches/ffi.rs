@@ -0,0 +1,171 @@
+//! Tracks the cost of the calls crossing the FFI layer: insertions, one by one and in batches,
+//! searches across scalar kinds, the overhead of filter callbacks, and serialization.
+//!
+//! Run with `cargo bench --bench ffi`, and compare against a saved baseline with
+//! `cargo bench --bench ffi -- --save-baseline main` and `--baseline main`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use usearch::{Index, IndexOptions, Key, MetricKind, ScalarKind};
+
+const ROWS: usize = 10_000;
+const DIMENSIONS: usize = 128;
+const COUNT: usize = 10;
+
+/// Deterministic vectors with components in `[-1, 1)`, generated with a xorshift sequence.
+fn synthetic_vectors(count: usize) -> Vec<Vec<f32>> {
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    (0..count)
+        .map(|_| {
+            (0..DIMENSIONS)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn empty_index(quantization: ScalarKind) -> Index {
+    let options = IndexOptions {
+        dimensions: DIMENSIONS,
+        metric: MetricKind::Cos,
+        quantization,
+        ..Default::default()
+    };
+    Index::new(&options).unwrap()
+}
+
+fn filled_index(quantization: ScalarKind, vectors: &[Vec<f32>]) -> Index {
+    let index = empty_index(quantization);
+    index.reserve(vectors.len()).unwrap();
+    for (key, vector) in vectors.iter().enumerate() {
+        index.add(key as Key, vector).unwrap();
+    }
+    index
+}
+
+fn add(c: &mut Criterion) {
+    let vectors = synthetic_vectors(ROWS / 10);
+    let batch: Vec<(Key, Vec<f32>)> = vectors
+        .iter()
+        .enumerate()
+        .map(|(key, vector)| (key as Key, vector.clone()))
+        .collect();
+
+    let mut group = c.benchmark_group("add");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(vectors.len() as u64));
+    group.bench_function("single", |b| {
+        b.iter_batched(
+            || empty_index(ScalarKind::F32),
+            |index| {
+                for (key, vector) in vectors.iter().enumerate() {
+                    index.add(key as Key, vector).unwrap();
+                }
+                index
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("batch_insert", |b| {
+        b.iter_batched(
+            || empty_index(ScalarKind::F32),
+            |index| {
+                index
+                    .batch_insert_chunked::<f32, _, _>(&batch, 256, |_| {})
+                    .run()
+                    .unwrap();
+                index
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn search(c: &mut Criterion) {
+    let vectors = synthetic_vectors(ROWS);
+    let queries = synthetic_vectors(100);
+
+    let mut group = c.benchmark_group("search");
+    group.throughput(Throughput::Elements(queries.len() as u64));
+    for quantization in [
+        ScalarKind::F64,
+        ScalarKind::F32,
+        ScalarKind::F16,
+        ScalarKind::I8,
+    ] {
+        let index = filled_index(quantization, &vectors);
+        let id = BenchmarkId::from_parameter(format!("{:?}", quantization));
+        group.bench_function(id, |b| {
+            b.iter(|| {
+                for query in &queries {
+                    index.search(query, COUNT).unwrap();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn filtered_search(c: &mut Criterion) {
+    let vectors = synthetic_vectors(ROWS);
+    let queries = synthetic_vectors(100);
+    let index = filled_index(ScalarKind::F32, &vectors);
+
+    let mut group = c.benchmark_group("filtered_search");
+    group.throughput(Throughput::Elements(queries.len() as u64));
+    group.bench_function("unfiltered", |b| {
+        b.iter(|| {
+            for query in &queries {
+                index.search(query, COUNT).unwrap();
+            }
+        })
+    });
+    group.bench_function("accept_all", |b| {
+        b.iter(|| {
+            for query in &queries {
+                index.filtered_search(query, COUNT, |_| true).unwrap();
+            }
+        })
+    });
+    group.bench_function("accept_half", |b| {
+        b.iter(|| {
+            for query in &queries {
+                index
+                    .filtered_search(query, COUNT, |key| key % 2 == 0)
+                    .unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+fn serialization(c: &mut Criterion) {
+    let index = filled_index(ScalarKind::F32, &synthetic_vectors(ROWS));
+    let mut buffer = vec![0; index.serialized_length()];
+    index.save_to_buffer(&mut buffer).unwrap();
+
+    let mut group = c.benchmark_group("serialization");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(buffer.len() as u64));
+    group.bench_function("save_to_buffer", |b| {
+        let mut output = vec![0; buffer.len()];
+        b.iter(|| index.save_to_buffer(&mut output).unwrap())
+    });
+    group.bench_function("load_from_buffer", |b| {
+        let mut loaded = empty_index(ScalarKind::F32);
+        b.iter(|| loaded.load_from_buffer(&buffer).unwrap())
+    });
+    group.bench_function("view_from_buffer", |b| {
+        let mut viewed = empty_index(ScalarKind::F32);
+        b.iter(|| viewed.view_from_buffer(&buffer).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, add, search, filtered_search, serialization);
+criterion_main!(benches);
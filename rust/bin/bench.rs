@@ -0,0 +1,228 @@
+//! Sweeps index options over a dataset, printing recall, throughput, and memory per combination.
+//!
+//! ```sh
+//! cargo run --release --features bench --bin usearch-bench -- \
+//!     --base sift_base.fvecs --queries sift_query.fvecs --truth sift_groundtruth.ivecs \
+//!     --metric l2sq --connectivity 8,16,32 --expansion-add 64,128 --expansion-search 16,64,256
+//! ```
+//!
+//! Vectors are read from `.fvecs` or `.bvecs` files, and ground truth from `.ivecs` files, in
+//! the layout of the TEXMEX corpora: every row is a little-endian `i32` length followed by its
+//! components. Keys are row numbers. Without `--truth`, exact neighbors are found by brute force.
+//! HDF5 datasets, like those of ann-benchmarks, have to be converted to these formats first.
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::time::Instant;
+
+use usearch::metrics::distance;
+use usearch::{Index, IndexOptions, Key, MetricKind, ScalarKind};
+
+const USAGE: &str = "Usage: usearch-bench --base PATH --queries PATH [--truth PATH] \
+    [--metric ip|l2sq|cos] [--scalar f64|f32|f16|i8] [--count N] [--limit N] \
+    [--connectivity N,..] [--expansion-add N,..] [--expansion-search N,..]";
+
+/// Finds the kind whose lowercase name matches the argument.
+fn parse_kind<K: std::fmt::Debug + Copy>(kinds: &[K], name: &str) -> Result<K, String> {
+    kinds
+        .iter()
+        .copied()
+        .find(|kind| format!("{:?}", kind).eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown kind: {}\n{}", name, USAGE))
+}
+
+fn parse_number(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Expected a number, got: {}\n{}", value, USAGE))
+}
+
+fn parse_grid(value: &str) -> Result<Vec<usize>, String> {
+    value.split(',').map(parse_number).collect()
+}
+
+/// Reads up to `limit` rows of a TEXMEX file, converting every component with `convert`.
+fn read_rows<T, const WIDTH: usize>(
+    path: &str,
+    limit: usize,
+    convert: impl Fn([u8; WIDTH]) -> T,
+) -> Result<Vec<Vec<T>>, String> {
+    let file = File::open(path).map_err(|error| format!("Can't open {}: {}", path, error))?;
+    let mut reader = BufReader::new(file);
+    let mut rows = Vec::new();
+    let mut length = [0; 4];
+    while rows.len() < limit && reader.read_exact(&mut length).is_ok() {
+        let length = i32::from_le_bytes(length) as usize;
+        let mut row = vec![0; length * WIDTH];
+        reader
+            .read_exact(&mut row)
+            .map_err(|_| format!("Truncated row {} in {}", rows.len(), path))?;
+        let row = row
+            .chunks_exact(WIDTH)
+            .map(|bytes| convert(bytes.try_into().unwrap()))
+            .collect();
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn read_vectors(path: &str, limit: usize) -> Result<Vec<Vec<f32>>, String> {
+    match path.rsplit('.').next() {
+        Some("fvecs") => read_rows(path, limit, f32::from_le_bytes),
+        Some("bvecs") => read_rows(path, limit, |[byte]: [u8; 1]| byte as f32),
+        Some("hdf5" | "h5") => Err(format!(
+            "HDF5 isn't supported, convert {} to .fvecs first",
+            path
+        )),
+        _ => Err(format!("Expected an .fvecs or .bvecs file, got: {}", path)),
+    }
+}
+
+/// The keys of the `count` closest base vectors of every query, by brute force.
+fn exact_neighbors(
+    metric: MetricKind,
+    base: &[Vec<f32>],
+    queries: &[Vec<f32>],
+    count: usize,
+) -> Vec<Vec<Key>> {
+    queries
+        .iter()
+        .map(|query| {
+            let mut ranked: Vec<(f32, Key)> = base
+                .iter()
+                .enumerate()
+                .map(|(key, vector)| (distance(metric, query, vector), key as Key))
+                .collect();
+            ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+            ranked.iter().take(count).map(|&(_, key)| key).collect()
+        })
+        .collect()
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (mut base, mut queries, mut truth) = (None, None, None);
+    let mut metric = MetricKind::L2sq;
+    let mut quantization = ScalarKind::F32;
+    let mut count = 10;
+    let mut limit = usize::MAX;
+    let mut connectivities = vec![16];
+    let mut expansions_add = vec![128];
+    let mut expansions_search = vec![16, 64, 256];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}\n{}", flag, USAGE))?;
+        match flag.as_str() {
+            "--base" => base = Some(value),
+            "--queries" => queries = Some(value),
+            "--truth" => truth = Some(value),
+            "--count" => count = parse_number(&value)?,
+            "--limit" => limit = parse_number(&value)?,
+            "--connectivity" => connectivities = parse_grid(&value)?,
+            "--expansion-add" => expansions_add = parse_grid(&value)?,
+            "--expansion-search" => expansions_search = parse_grid(&value)?,
+            "--metric" => {
+                metric = parse_kind(&[MetricKind::IP, MetricKind::L2sq, MetricKind::Cos], &value)?
+            }
+            "--scalar" => {
+                quantization = parse_kind(
+                    &[
+                        ScalarKind::F64,
+                        ScalarKind::F32,
+                        ScalarKind::F16,
+                        ScalarKind::I8,
+                    ],
+                    &value,
+                )?
+            }
+            _ => return Err(format!("Unknown flag: {}\n{}", flag, USAGE).into()),
+        }
+    }
+    let base = read_vectors(&base.ok_or(USAGE)?, limit)?;
+    let queries = read_vectors(&queries.ok_or(USAGE)?, usize::MAX)?;
+    let dimensions = base.first().ok_or("The base set is empty")?.len();
+
+    let truth: Vec<Vec<Key>> = match truth {
+        // Truncated base sets invalidate the shipped ground truth.
+        Some(path) if limit == usize::MAX => read_rows(&path, queries.len(), |bytes: [u8; 4]| {
+            i32::from_le_bytes(bytes) as Key
+        })?,
+        _ => {
+            eprintln!("Computing exact neighbors of {} queries", queries.len());
+            exact_neighbors(metric, &base, &queries, count)
+        }
+    };
+    eprintln!(
+        "Loaded {} base vectors and {} queries of {} dimensions",
+        base.len(),
+        queries.len(),
+        dimensions
+    );
+
+    println!(
+        "{:>12} {:>13} {:>16} {:>9} {:>10} {:>10} {:>10} {:>10}",
+        "connectivity",
+        "expansion_add",
+        "expansion_search",
+        "build_s",
+        "adds/s",
+        format!("recall@{}", count),
+        "qps",
+        "memory_mb"
+    );
+    for &connectivity in &connectivities {
+        for &expansion_add in &expansions_add {
+            let options = IndexOptions {
+                dimensions,
+                metric,
+                quantization,
+                connectivity,
+                expansion_add,
+                ..Default::default()
+            };
+            let index = Index::new(&options)?;
+            index.reserve(base.len())?;
+            let start = Instant::now();
+            for (key, vector) in base.iter().enumerate() {
+                index.add(key as Key, vector)?;
+            }
+            let build = start.elapsed().as_secs_f64();
+            let memory = index.memory_usage() as f64 / (1 << 20) as f64;
+
+            for &expansion_search in &expansions_search {
+                index.change_expansion_search(expansion_search);
+                let start = Instant::now();
+                let mut found = 0;
+                for (query, expected) in queries.iter().zip(&truth) {
+                    let matches = index.search(query, count)?;
+                    let expected = &expected[..count.min(expected.len())];
+                    found += matches
+                        .keys
+                        .iter()
+                        .filter(|key| expected.contains(key))
+                        .count();
+                }
+                let elapsed = start.elapsed().as_secs_f64();
+                let recall = found as f64 / (queries.len() * count).max(1) as f64;
+                println!(
+                    "{:>12} {:>13} {:>16} {:>9.2} {:>10.0} {:>10.4} {:>10.0} {:>10.1}",
+                    connectivity,
+                    expansion_add,
+                    expansion_search,
+                    build,
+                    base.len() as f64 / build,
+                    recall,
+                    queries.len() as f64 / elapsed,
+                    memory
+                );
+            }
+        }
+    }
+    Ok(())
+}
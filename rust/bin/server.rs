@@ -0,0 +1,154 @@
+//! Serves a single USearch index over gRPC, as defined in `rust/proto/usearch.proto`.
+//!
+//! ```sh
+//! cargo run --release --features server --bin usearch-server -- \
+//!     --listen 0.0.0.0:50051 --dimensions 256 --metric cos --scalar f16 --index vectors.usearch
+//! ```
+//!
+//! An existing index file is loaded on start, and the index is saved back to it on `Ctrl+C`.
+//! With the `http` feature, `--http ADDRESS` also serves the JSON routes of `usearch::http`.
+//! With the `telemetry` feature, calls and index sizes are exported over OTLP to the collector
+//! named by `OTEL_EXPORTER_OTLP_ENDPOINT`, when it is set.
+
+use std::path::Path;
+
+use usearch::server::IndexService;
+#[cfg(feature = "telemetry")]
+use usearch::telemetry::{IndexMetrics, Telemetry, TelemetryConfig};
+use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+
+const USAGE: &str = "Usage: usearch-server [--listen ADDRESS] [--dimensions N] \
+    [--metric ip|l2sq|cos|pearson|haversine|divergence|hamming|tanimoto|sorensen] \
+    [--scalar f64|f32|f16|i8|b1] [--connectivity N] [--index PATH] [--http ADDRESS]";
+
+/// Finds the kind whose lowercase name matches the argument.
+fn parse_kind<K: std::fmt::Debug + Copy>(kinds: &[K], name: &str) -> Result<K, String> {
+    kinds
+        .iter()
+        .copied()
+        .find(|kind| format!("{:?}", kind).eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown kind: {}\n{}", name, USAGE))
+}
+
+fn parse_number(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Expected a number, got: {}\n{}", value, USAGE))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut listen = "127.0.0.1:50051".to_owned();
+    let mut path = None;
+    let mut http: Option<String> = None;
+    let mut options = IndexOptions {
+        dimensions: 256,
+        metric: MetricKind::Cos,
+        quantization: ScalarKind::F32,
+        ..Default::default()
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}\n{}", flag, USAGE))?;
+        match flag.as_str() {
+            "--listen" => listen = value,
+            "--dimensions" => options.dimensions = parse_number(&value)?,
+            "--connectivity" => options.connectivity = parse_number(&value)?,
+            "--metric" => {
+                options.metric = parse_kind(
+                    &[
+                        MetricKind::IP,
+                        MetricKind::L2sq,
+                        MetricKind::Cos,
+                        MetricKind::Pearson,
+                        MetricKind::Haversine,
+                        MetricKind::Divergence,
+                        MetricKind::Hamming,
+                        MetricKind::Tanimoto,
+                        MetricKind::Sorensen,
+                    ],
+                    &value,
+                )?
+            }
+            "--scalar" => {
+                options.quantization = parse_kind(
+                    &[
+                        ScalarKind::F64,
+                        ScalarKind::F32,
+                        ScalarKind::F16,
+                        ScalarKind::I8,
+                        ScalarKind::B1,
+                    ],
+                    &value,
+                )?
+            }
+            "--index" => path = Some(value),
+            "--http" => http = Some(value),
+            _ => return Err(format!("Unknown flag: {}\n{}", flag, USAGE).into()),
+        }
+    }
+
+    let index = Index::new(&options)?;
+    let mut service = IndexService::new(index);
+    if let Some(path) = path {
+        if Path::new(&path).exists() {
+            let mut loaded = Index::new(&options)?;
+            loaded.load(&path)?;
+            eprintln!("Loaded {} vectors from {}", loaded.size(), path);
+            service = IndexService::new(loaded);
+        }
+        service = service.with_path(path);
+    }
+
+    #[cfg(feature = "telemetry")]
+    let telemetry = match std::env::var_os("OTEL_EXPORTER_OTLP_ENDPOINT") {
+        Some(_) => Some(Telemetry::init(&TelemetryConfig::from_env())?),
+        None => None,
+    };
+    #[cfg(feature = "telemetry")]
+    {
+        service = service.with_metrics(IndexMetrics::default());
+    }
+
+    eprintln!("Serving on {}", listen);
+    let shutdown = service.clone();
+    let grpc = tonic::transport::Server::builder()
+        .add_service(service.clone().into_server())
+        .serve_with_shutdown(listen.parse()?, async {
+            tokio::signal::ctrl_c().await.ok();
+        });
+
+    match http {
+        #[cfg(feature = "http")]
+        Some(address) => {
+            eprintln!("Serving JSON routes on {}", address);
+            let listener = tokio::net::TcpListener::bind(&address).await?;
+            let router = service.into_router();
+            let json = axum::serve(listener, router).with_graceful_shutdown(async {
+                tokio::signal::ctrl_c().await.ok();
+            });
+            let (grpc, json) = tokio::join!(grpc, json);
+            grpc?;
+            json?;
+        }
+        #[cfg(not(feature = "http"))]
+        Some(_) => return Err("Rebuild with the `http` feature to serve JSON routes".into()),
+        None => grpc.await?,
+    }
+
+    if let Some(path) = shutdown.save_index()? {
+        eprintln!("Saved the index to {}", path.display());
+    }
+    #[cfg(feature = "telemetry")]
+    if let Some(telemetry) = telemetry {
+        telemetry.shutdown()?;
+    }
+    Ok(())
+}
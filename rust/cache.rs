@@ -0,0 +1,300 @@
+//! Caching of search results, for workloads repeating the same queries over and over.
+//!
+//! Autocomplete and recommendation backends tend to see a few popular queries most of the time.
+//! A [`CachedIndex`] keeps the matches of the most recently used queries, keyed by the query
+//! rounded to a configurable resolution and the number of requested neighbors, and evicts the
+//! least recently used ones beyond its capacity. Every write through the wrapper empties the
+//! cache, so searches never return matches older than the last write.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use crate::convert::Scalar;
+use crate::ffi::{Matches, MetricKind};
+use crate::{Index, IndexError, IndexOptions, Key, VectorType};
+
+/// The default resolution queries are rounded to before looking them up.
+pub const DEFAULT_RESOLUTION: f32 = 1e-6;
+
+/// A query rounded to the cache resolution, along with the number of requested neighbors.
+type QueryKey = (Box<[i64]>, usize);
+
+/// The cached matches, ordered by their last use.
+struct Entries {
+    capacity: usize,
+    /// Incremented on every write, so searches started before it don't cache stale matches.
+    generation: u64,
+    tick: u64,
+    matches: HashMap<QueryKey, (u64, Matches)>,
+    recency: BTreeMap<u64, QueryKey>,
+}
+
+impl Entries {
+    fn get(&mut self, key: &QueryKey) -> Option<Matches> {
+        let (last_used, matches) = self.matches.get_mut(key)?;
+        let recent = self
+            .recency
+            .remove(last_used)
+            .expect("every entry has a tick");
+        self.tick += 1;
+        *last_used = self.tick;
+        self.recency.insert(self.tick, recent);
+        Some(matches.clone())
+    }
+
+    fn insert(&mut self, key: QueryKey, matches: Matches, generation: u64) {
+        if self.capacity == 0 || generation != self.generation {
+            return;
+        }
+        if let Some((last_used, _)) = self.matches.remove(&key) {
+            self.recency.remove(&last_used);
+        }
+        while self.matches.len() >= self.capacity {
+            let (_, evicted) = self.recency.pop_first().expect("every entry has a tick");
+            self.matches.remove(&evicted);
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.matches.insert(key, (self.tick, matches));
+    }
+
+    fn clear(&mut self) {
+        self.generation += 1;
+        self.matches.clear();
+        self.recency.clear();
+    }
+}
+
+/// An index remembering the matches of its most recent queries.
+///
+/// Writes made directly to the underlying [`CachedIndex::index`] bypass the cache, and have to
+/// be followed by [`CachedIndex::invalidate`].
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{CachedIndex, IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let index = CachedIndex::new(&options, 1024).unwrap();
+/// index.add(1, &[0.0_f32, 1.0]).unwrap();
+///
+/// assert_eq!(index.search(&[0.1_f32, 0.9], 1).unwrap().keys, vec![1]);
+/// assert_eq!(index.search(&[0.1_f32, 0.9], 1).unwrap().keys, vec![1]);
+/// assert_eq!((index.hits(), index.misses()), (1, 1));
+///
+/// // Writes empty the cache, so the next search sees the new vector.
+/// index.add(2, &[0.1_f32, 0.9]).unwrap();
+/// assert_eq!(index.search(&[0.1_f32, 0.9], 1).unwrap().keys, vec![2]);
+/// ```
+pub struct CachedIndex {
+    index: Index,
+    resolution: f32,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CachedIndex {
+    /// Creates an empty index, caching the matches of up to `capacity` queries.
+    pub fn new(options: &IndexOptions, capacity: usize) -> Result<Self, IndexError> {
+        Ok(Self::from_index(Index::new(options)?, capacity))
+    }
+
+    /// Wraps an existing index, caching the matches of up to `capacity` queries.
+    /// A capacity of zero disables caching.
+    pub fn from_index(index: Index, capacity: usize) -> Self {
+        Self {
+            index,
+            resolution: DEFAULT_RESOLUTION,
+            entries: Mutex::new(Entries {
+                capacity,
+                generation: 0,
+                tick: 0,
+                matches: HashMap::new(),
+                recency: BTreeMap::new(),
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Rounds the components of queries to multiples of `resolution` before looking them up,
+    /// so queries closer than that share their matches. Defaults to [`DEFAULT_RESOLUTION`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resolution isn't a positive number.
+    pub fn with_resolution(mut self, resolution: f32) -> Self {
+        assert!(resolution > 0.0, "The cache resolution must be positive");
+        self.resolution = resolution;
+        self.invalidate();
+        self
+    }
+
+    /// The underlying index.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Unwraps the underlying index, dropping the cached matches.
+    pub fn into_index(self) -> Index {
+        self.index
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The maximum number of queries whose matches are cached.
+    pub fn capacity(&self) -> usize {
+        self.entries().capacity
+    }
+
+    /// Number of queries whose matches are currently cached.
+    pub fn cached(&self) -> usize {
+        self.entries().matches.len()
+    }
+
+    /// Number of searches answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of searches that had to query the index.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Drops all cached matches, as needed after writing to the underlying index directly.
+    pub fn invalidate(&self) {
+        self.entries().clear();
+    }
+
+    /// Rounds the query to the cache resolution, as the key of its matches.
+    fn query_key<T: Scalar>(&self, query: &[T], count: usize) -> QueryKey {
+        let mut values = Vec::with_capacity(query.len());
+        T::decode(query, self.index.dimensions(), &mut values);
+        let resolution = f64::from(self.resolution);
+        let rounded = values
+            .iter()
+            .map(|&value| (f64::from(value) / resolution).round() as i64)
+            .collect();
+        (rounded, count)
+    }
+
+    /// Searches for the nearest neighbors of the query, or returns the cached matches of an
+    /// equal query, once rounded to the cache resolution.
+    pub fn search<T: VectorType + Scalar>(
+        &self,
+        query: &[T],
+        count: usize,
+    ) -> Result<Matches, IndexError> {
+        let key = self.query_key(query, count);
+        let generation = {
+            let mut entries = self.entries();
+            if let Some(matches) = entries.get(&key) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(matches);
+            }
+            entries.generation
+        };
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let matches = self.index.search(query, count)?;
+        self.entries().insert(key, matches.clone(), generation);
+        Ok(matches)
+    }
+
+    /// Adds a vector, emptying the cache.
+    pub fn add<T: VectorType>(&self, key: Key, vector: &[T]) -> Result<(), IndexError> {
+        let added = self.index.add(key, vector);
+        self.invalidate();
+        added
+    }
+
+    /// Removes the vectors stored under a key, emptying the cache.
+    pub fn remove(&self, key: Key) -> Result<usize, IndexError> {
+        let removed = self.index.remove(key);
+        self.invalidate();
+        Ok(removed?)
+    }
+
+    /// Renames the vectors stored under a key, emptying the cache.
+    pub fn rename(&self, from: Key, to: Key) -> Result<usize, IndexError> {
+        let renamed = self.index.rename(from, to);
+        self.invalidate();
+        Ok(renamed?)
+    }
+
+    /// Changes the expansion factor used during search, emptying the cache.
+    pub fn change_expansion_search(&self, n: usize) {
+        self.index.change_expansion_search(n);
+        self.invalidate();
+    }
+
+    /// Changes the metric kind, emptying the cache.
+    pub fn change_metric_kind(&self, metric: MetricKind) {
+        self.index.change_metric_kind(metric);
+        self.invalidate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScalarKind;
+
+    #[test]
+    fn test_cached_index() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = CachedIndex::new(&options, 2).unwrap().with_resolution(0.5);
+        for key in 0..10u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+
+        // Queries rounding to the same values, with the same count, share their matches.
+        assert_eq!(index.search(&[3.0_f32, 0.0], 1).unwrap().keys, vec![3]);
+        assert_eq!(index.search(&[3.1_f32, 0.1], 1).unwrap().keys, vec![3]);
+        assert_eq!(index.search(&[3.0_f64, 0.0], 1).unwrap().keys, vec![3]);
+        assert_eq!(index.search(&[3.0_f32, 0.0], 2).unwrap().keys.len(), 2);
+        assert_eq!((index.hits(), index.misses()), (2, 2));
+
+        // The least recently used query is evicted first.
+        index.search(&[3.0_f32, 0.0], 1).unwrap();
+        index.search(&[7.0_f32, 0.0], 1).unwrap();
+        assert_eq!(index.cached(), 2);
+        index.search(&[3.0_f32, 0.0], 1).unwrap();
+        assert_eq!((index.hits(), index.misses()), (4, 3));
+        index.search(&[3.0_f32, 0.0], 2).unwrap();
+        assert_eq!((index.hits(), index.misses()), (4, 4));
+
+        // Writes of any kind drop the stale matches.
+        index.remove(3).unwrap();
+        assert_eq!(index.cached(), 0);
+        assert_ne!(index.search(&[3.0_f32, 0.0], 1).unwrap().keys, vec![3]);
+        assert_eq!(index.search(&[2.0_f32, 0.0], 1).unwrap().keys, vec![2]);
+        index.rename(2, 20).unwrap();
+        assert_eq!(index.search(&[2.0_f32, 0.0], 1).unwrap().keys, vec![20]);
+        index.index().add(30, &[3.0_f32, 0.0]).unwrap();
+        index.invalidate();
+        let matches = index.search(&[3.0_f32, 0.0], 2).unwrap();
+        assert!(matches.keys.contains(&30));
+
+        // Without capacity, every search goes to the index.
+        let uncached = CachedIndex::from_index(index.into_index(), 0);
+        uncached.search(&[3.0_f32, 0.0], 1).unwrap();
+        uncached.search(&[3.0_f32, 0.0], 1).unwrap();
+        assert_eq!((uncached.hits(), uncached.cached()), (0, 0));
+    }
+}
@@ -0,0 +1,141 @@
+//! Canary-safe loading, validating a freshly loaded index with known query-answer pairs.
+//!
+//! Corrupted or mismatched index files often load without errors, but return garbage.
+//! Running a handful of sanity queries with known answers right after loading catches
+//! those before the index starts serving traffic.
+
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::{Index, IndexError, Key, VectorType};
+
+impl Index {
+    /// Runs sanity queries against the index, checking that every expected key
+    /// appears among the `count` closest matches of its query.
+    ///
+    /// # Arguments
+    ///
+    /// * `sanity` - Pairs of query vectors and the keys expected among their matches.
+    /// * `count` - The number of top matches to inspect for every query.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if all expected keys were found, or `IndexError::SanityCheckFailed`
+    /// pointing to the first failed query.
+    pub fn verify_sanity<T, Q>(&self, sanity: &[(Q, Key)], count: usize) -> Result<(), IndexError>
+    where
+        T: VectorType,
+        Q: AsRef<[T]>,
+    {
+        for (position, (query, expected)) in sanity.iter().enumerate() {
+            let matches = self.search(query.as_ref(), count)?;
+            if !matches.keys.contains(expected) {
+                return Err(IndexError::SanityCheckFailed {
+                    query: position,
+                    expected: *expected,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the index from a specified file and validates it with sanity queries.
+    ///
+    /// If any of the expected keys is missing from the top `count` matches of its query,
+    /// the index is reset, so a corrupted file never serves traffic.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path from where the index will be loaded.
+    /// * `sanity` - Pairs of query vectors and the keys expected among their matches.
+    /// * `count` - The number of top matches to inspect for every query.
+    #[cfg(not(no_fs))]
+    pub fn load_verified<T, Q, P>(
+        &mut self,
+        path: P,
+        sanity: &[(Q, Key)],
+        count: usize,
+    ) -> Result<(), IndexError>
+    where
+        T: VectorType,
+        Q: AsRef<[T]>,
+        P: AsRef<Path>,
+    {
+        self.load(path)?;
+        self.verify_or_reset(sanity, count)
+    }
+
+    /// Loads the index from an in-memory buffer and validates it with sanity queries.
+    /// Behaves like [`Index::load_verified`].
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - The buffer from where the index will be loaded.
+    /// * `sanity` - Pairs of query vectors and the keys expected among their matches.
+    /// * `count` - The number of top matches to inspect for every query.
+    pub fn load_from_buffer_verified<T, Q>(
+        &mut self,
+        buffer: &[u8],
+        sanity: &[(Q, Key)],
+        count: usize,
+    ) -> Result<(), IndexError>
+    where
+        T: VectorType,
+        Q: AsRef<[T]>,
+    {
+        self.load_from_buffer(buffer)?;
+        self.verify_or_reset(sanity, count)
+    }
+
+    fn verify_or_reset<T, Q>(&mut self, sanity: &[(Q, Key)], count: usize) -> Result<(), IndexError>
+    where
+        T: VectorType,
+        Q: AsRef<[T]>,
+    {
+        let verdict = self.verify_sanity(sanity, count);
+        if verdict.is_err() {
+            self.reset()?;
+        }
+        verdict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexError, IndexOptions};
+
+    #[test]
+    fn test_load_verified() {
+        let options = IndexOptions {
+            dimensions: 3,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(10).unwrap();
+        index.add(1, &[1.0_f32, 0.0, 0.0]).unwrap();
+        index.add(2, &[0.0_f32, 1.0, 0.0]).unwrap();
+        index.add(3, &[0.0_f32, 0.0, 1.0]).unwrap();
+
+        let mut buffer = vec![0; index.serialized_length()];
+        index.save_to_buffer(&mut buffer).unwrap();
+
+        let mut loaded = Index::new(&options).unwrap();
+        let good = [([1.0_f32, 0.1, 0.0], 1), ([0.0_f32, 0.1, 1.0], 3)];
+        assert!(loaded.load_from_buffer_verified(&buffer, &good, 1).is_ok());
+        assert_eq!(loaded.size(), 3);
+
+        let bad = [([1.0_f32, 0.1, 0.0], 1), ([0.0_f32, 0.1, 1.0], 2)];
+        match loaded.load_from_buffer_verified(&buffer, &bad, 1) {
+            Err(IndexError::SanityCheckFailed { query, expected }) => {
+                assert_eq!(query, 1);
+                assert_eq!(expected, 2);
+            }
+            _ => panic!("Expected a sanity check failure"),
+        }
+        assert_eq!(loaded.size(), 0);
+        assert!(matches!(
+            index.verify_sanity(&[([1.0_f32, 0.0], 1)], 1),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,412 @@
+//! A stable C ABI over the Rust layer, for embedding into other languages.
+//!
+//! Enabled with the `capi` feature, and declared in `rust/usearch_rs.h`. Build a shared
+//! library with `cargo rustc --release --features capi --crate-type cdylib`.
+//!
+//! Every fallible function takes a trailing `usearch_rs_error_t*`. On failure, it is set to a
+//! NUL-terminated message owned by the library, valid until the next call on the same thread.
+//! On success, it is set to `NULL`. Vectors always cross the boundary as `float` arrays and are
+//! converted to the scalar kind of the index by the native layer.
+
+#![allow(non_camel_case_types)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use crate::{Index, IndexError, IndexOptions, Key, MetricKind, ScalarKind};
+
+// Error message storage, keeping the last message of every thread alive for the caller.
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+/// Options passed to [`usearch_rs_create`], mirroring [`IndexOptions`].
+///
+/// Metric and scalar kinds use the numeric values of [`MetricKind`] and [`ScalarKind`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct usearch_rs_options_t {
+    /// The number of dimensions in the vectors to be indexed.
+    pub dimensions: usize,
+    /// The numeric value of the [`MetricKind`].
+    pub metric: i32,
+    /// The numeric value of the [`ScalarKind`].
+    pub quantization: i32,
+    /// The connectivity of the graph, or `0` for the default.
+    pub connectivity: usize,
+    /// The expansion factor used for index construction, or `0` for the default.
+    pub expansion_add: usize,
+    /// The expansion factor used for search operations, or `0` for the default.
+    pub expansion_search: usize,
+    /// Whether multiple vectors can share a key.
+    pub multi: bool,
+}
+
+/// Opaque handle to an [`Index`].
+pub type usearch_rs_index_t = *mut Index;
+
+/// Pointer to an error message, or `NULL`.
+pub type usearch_rs_error_t = *const c_char;
+
+/// Stores the outcome of a call into the error out-parameter, returning the value or a fallback.
+unsafe fn report<T>(
+    result: Result<T, IndexError>,
+    fallback: T,
+    error: *mut usearch_rs_error_t,
+) -> T {
+    match result {
+        Ok(value) => {
+            if !error.is_null() {
+                *error = ptr::null();
+            }
+            value
+        }
+        Err(err) => {
+            if !error.is_null() {
+                let message = CString::new(err.to_string().replace('\0', " ")).unwrap_or_default();
+                *error = LAST_ERROR.with(|last| {
+                    *last.borrow_mut() = message;
+                    last.borrow().as_ptr()
+                });
+            }
+            fallback
+        }
+    }
+}
+
+unsafe fn vector<'a>(data: *const f32, length: usize) -> &'a [f32] {
+    if length == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(data, length)
+    }
+}
+
+unsafe fn vector_mut<'a>(data: *mut f32, length: usize) -> &'a mut [f32] {
+    if length == 0 {
+        &mut []
+    } else {
+        std::slice::from_raw_parts_mut(data, length)
+    }
+}
+
+/// Returns the version of the crate as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn usearch_rs_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Creates a new index, returning `NULL` on failure.
+///
+/// # Safety
+///
+/// `options` must point to a valid `usearch_rs_options_t`, and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_create(
+    options: *const usearch_rs_options_t,
+    error: *mut usearch_rs_error_t,
+) -> usearch_rs_index_t {
+    let options = &*options;
+    let options = IndexOptions {
+        dimensions: options.dimensions,
+        metric: MetricKind {
+            repr: options.metric,
+        },
+        quantization: ScalarKind {
+            repr: options.quantization,
+        },
+        connectivity: options.connectivity,
+        expansion_add: options.expansion_add,
+        expansion_search: options.expansion_search,
+        multi: options.multi,
+        ..Default::default()
+    };
+    let created = Index::new(&options).map(|index| Box::into_raw(Box::new(index)));
+    report(created, ptr::null_mut(), error)
+}
+
+/// Releases an index created by [`usearch_rs_create`]. Passing `NULL` is a no-op.
+///
+/// # Safety
+///
+/// `index` must be `NULL` or a handle that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_free(index: usearch_rs_index_t) {
+    if !index.is_null() {
+        drop(Box::from_raw(index));
+    }
+}
+
+/// Returns the number of vectors in the index.
+///
+/// # Safety
+///
+/// `index` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_size(index: usearch_rs_index_t) -> usize {
+    (*index).size()
+}
+
+/// Returns the capacity of the index, including reserved space.
+///
+/// # Safety
+///
+/// `index` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_capacity(index: usearch_rs_index_t) -> usize {
+    (*index).capacity()
+}
+
+/// Returns the number of dimensions of the indexed vectors.
+///
+/// # Safety
+///
+/// `index` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_dimensions(index: usearch_rs_index_t) -> usize {
+    (*index).dimensions()
+}
+
+/// Reserves memory for a specified number of incoming vectors.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_reserve(
+    index: usearch_rs_index_t,
+    capacity: usize,
+    error: *mut usearch_rs_error_t,
+) {
+    report((*index).reserve(capacity).map_err(Into::into), (), error)
+}
+
+/// Adds a vector of `dimensions` floats under the given key.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, `vector_data` must point to `dimensions` floats,
+/// and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_add(
+    index: usearch_rs_index_t,
+    key: Key,
+    vector_data: *const f32,
+    dimensions: usize,
+    error: *mut usearch_rs_error_t,
+) {
+    let added = (*index).add(key, vector(vector_data, dimensions));
+    report(added, (), error)
+}
+
+/// Searches for up to `count` nearest neighbors, returning the number of matches written.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, `query` must point to `dimensions` floats, `keys` and
+/// `distances` must have room for `count` elements, and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_search(
+    index: usearch_rs_index_t,
+    query: *const f32,
+    dimensions: usize,
+    count: usize,
+    keys: *mut Key,
+    distances: *mut f32,
+    error: *mut usearch_rs_error_t,
+) -> usize {
+    let found = (*index)
+        .search(vector(query, dimensions), count)
+        .map(|matches| {
+            let found = matches.keys.len().min(count);
+            ptr::copy_nonoverlapping(matches.keys.as_ptr(), keys, found);
+            ptr::copy_nonoverlapping(matches.distances.as_ptr(), distances, found);
+            found
+        });
+    report(found, 0, error)
+}
+
+/// Copies up to `length / dimensions` vectors stored under the key, returning their number.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, `vector_data` must have room for `length` floats,
+/// and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_get(
+    index: usearch_rs_index_t,
+    key: Key,
+    vector_data: *mut f32,
+    length: usize,
+    error: *mut usearch_rs_error_t,
+) -> usize {
+    let found = (*index).get(key, vector_mut(vector_data, length));
+    report(found.map_err(Into::into), 0, error)
+}
+
+/// Removes the vectors stored under the key, returning their number.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_remove(
+    index: usearch_rs_index_t,
+    key: Key,
+    error: *mut usearch_rs_error_t,
+) -> usize {
+    report((*index).remove(key).map_err(Into::into), 0, error)
+}
+
+/// Checks if the index contains the key.
+///
+/// # Safety
+///
+/// `index` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_contains(index: usearch_rs_index_t, key: Key) -> bool {
+    (*index).contains(key)
+}
+
+/// Returns the number of bytes [`usearch_rs_save_buffer`] needs.
+///
+/// # Safety
+///
+/// `index` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_serialized_length(index: usearch_rs_index_t) -> usize {
+    (*index).serialized_length()
+}
+
+/// Serializes the index into a buffer of at least [`usearch_rs_serialized_length`] bytes.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, `buffer` must have room for `length` bytes,
+/// and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_save_buffer(
+    index: usearch_rs_index_t,
+    buffer: *mut u8,
+    length: usize,
+    error: *mut usearch_rs_error_t,
+) {
+    let buffer = std::slice::from_raw_parts_mut(buffer, length);
+    report((*index).save_to_buffer(buffer), (), error)
+}
+
+/// Loads the index from a buffer produced by [`usearch_rs_save_buffer`].
+///
+/// # Safety
+///
+/// `index` must be a valid handle, `buffer` must hold `length` bytes,
+/// and `error` must be `NULL` or writable.
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_load_buffer(
+    index: usearch_rs_index_t,
+    buffer: *const u8,
+    length: usize,
+    error: *mut usearch_rs_error_t,
+) {
+    let buffer = std::slice::from_raw_parts(buffer, length);
+    report((*index).load_from_buffer(buffer), (), error)
+}
+
+/// Saves the index to a file.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, `path` must be a NUL-terminated UTF-8 string,
+/// and `error` must be `NULL` or writable.
+#[cfg(not(no_fs))]
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_save(
+    index: usearch_rs_index_t,
+    path: *const c_char,
+    error: *mut usearch_rs_error_t,
+) {
+    let saved = path_str(path).and_then(|path| (*index).save(path));
+    report(saved, (), error)
+}
+
+/// Loads the index from a file.
+///
+/// # Safety
+///
+/// `index` must be a valid handle, `path` must be a NUL-terminated UTF-8 string,
+/// and `error` must be `NULL` or writable.
+#[cfg(not(no_fs))]
+#[no_mangle]
+pub unsafe extern "C" fn usearch_rs_load(
+    index: usearch_rs_index_t,
+    path: *const c_char,
+    error: *mut usearch_rs_error_t,
+) {
+    let loaded = path_str(path).and_then(|path| (*index).load(path));
+    report(loaded, (), error)
+}
+
+#[cfg(not(no_fs))]
+unsafe fn path_str<'a>(path: *const c_char) -> Result<&'a str, IndexError> {
+    CStr::from_ptr(path)
+        .to_str()
+        .map_err(|err| IndexError::Io(std::io::Error::new(std::io::ErrorKind::InvalidInput, err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c_abi_round_trip() {
+        unsafe {
+            let mut error: usearch_rs_error_t = ptr::null();
+            let options = usearch_rs_options_t {
+                dimensions: 3,
+                metric: MetricKind::L2sq.repr,
+                quantization: ScalarKind::F32.repr,
+                connectivity: 0,
+                expansion_add: 0,
+                expansion_search: 0,
+                multi: false,
+            };
+            let index = usearch_rs_create(&options, &mut error);
+            assert!(error.is_null());
+            usearch_rs_reserve(index, 10, &mut error);
+            usearch_rs_add(index, 42, [0.1_f32, 0.2, 0.3].as_ptr(), 3, &mut error);
+            assert!(error.is_null());
+            assert_eq!(usearch_rs_size(index), 1);
+
+            let (mut keys, mut distances) = ([0 as Key; 4], [0.0_f32; 4]);
+            let query = [0.1_f32, 0.2, 0.3];
+            let found = usearch_rs_search(
+                index,
+                query.as_ptr(),
+                3,
+                4,
+                keys.as_mut_ptr(),
+                distances.as_mut_ptr(),
+                &mut error,
+            );
+            assert_eq!((found, keys[0]), (1, 42));
+
+            let mut buffer = vec![0u8; usearch_rs_serialized_length(index)];
+            usearch_rs_save_buffer(index, buffer.as_mut_ptr(), buffer.len(), &mut error);
+            assert!(error.is_null());
+            usearch_rs_load_buffer(index, buffer.as_ptr(), 3, &mut error);
+            assert!(!error.is_null());
+            assert!(!CStr::from_ptr(error).to_bytes().is_empty());
+            usearch_rs_free(index);
+
+            let invalid = usearch_rs_options_t {
+                dimensions: 0,
+                ..options
+            };
+            assert!(usearch_rs_create(&invalid, &mut error).is_null());
+            assert!(CStr::from_ptr(error)
+                .to_string_lossy()
+                .contains("dimensions"));
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Fault injection for testing the retry and degradation logic of services built on the index.
+//!
+//! Enabled with the `chaos` feature. [`Index::enable_chaos`] makes a configurable fraction of
+//! `add`, `search`, and `save` calls fail before reaching the index. Injected failures are raised
+//! by the native layer, so they arrive as the same `cxx::Exception`, or `IndexError::Native`,
+//! as genuine ones, and [`is_injected`] tells them apart in assertions.
+//!
+//! Never enable the feature in production builds: the dice are rolled on every call.
+
+use std::sync::{Mutex, PoisonError};
+
+use crate::{ffi, Index};
+
+/// Prefix of the messages of injected failures.
+const MESSAGE_PREFIX: &str = "Injected failure in ";
+
+/// Failure rates of the operations subject to fault injection, each in the `[0, 1]` range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    /// Fraction of insertions that fail.
+    pub add: f64,
+    /// Fraction of searches, filtered or not, that fail.
+    pub search: f64,
+    /// Fraction of saves, to files or buffers, that fail.
+    pub save: f64,
+    /// Seed of the generator deciding which calls fail, for reproducible runs.
+    pub seed: u64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            add: 0.0,
+            search: 0.0,
+            save: 0.0,
+            seed: 0x2545_F491_4F6C_DD1D,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Fails every operation with the same probability.
+    pub fn uniform(rate: f64) -> Self {
+        Self {
+            add: rate,
+            search: rate,
+            save: rate,
+            ..Default::default()
+        }
+    }
+
+    fn rate(&self, operation: &str) -> f64 {
+        match operation {
+            "add" => self.add,
+            "search" => self.search,
+            "save" => self.save,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Per-index fault injection state, disabled by default.
+#[derive(Debug, Default)]
+pub(crate) struct FaultInjector {
+    state: Mutex<Option<(ChaosConfig, u64)>>,
+}
+
+/// Checks if an error was injected by [`Index::enable_chaos`], rather than raised by the index.
+pub fn is_injected<E: std::fmt::Display>(err: &E) -> bool {
+    err.to_string().starts_with(MESSAGE_PREFIX)
+}
+
+impl Index {
+    /// Starts failing a fraction of the operations of this index, as configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{chaos, ChaosConfig, Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.enable_chaos(ChaosConfig { add: 1.0, ..Default::default() });
+    /// let err = index.add(1, &[0.6_f32, 0.8]).unwrap_err();
+    /// assert!(chaos::is_injected(&err));
+    ///
+    /// index.disable_chaos();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    /// ```
+    pub fn enable_chaos(&self, config: ChaosConfig) {
+        let mut state = self
+            .faults
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Xorshift generators never leave the all-zero state.
+        *state = Some((config, config.seed | 1));
+    }
+
+    /// Stops injecting failures.
+    pub fn disable_chaos(&self) {
+        let mut state = self
+            .faults
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *state = None;
+    }
+
+    /// Rolls the dice for an operation, failing it with the configured probability.
+    pub(crate) fn inject_fault(&self, operation: &str) -> Result<(), cxx::Exception> {
+        let mut state = self
+            .faults
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let Some((config, random)) = state.as_mut() else {
+            return Ok(());
+        };
+        *random ^= *random << 13;
+        *random ^= *random >> 7;
+        *random ^= *random << 17;
+        let draw = (*random >> 11) as f64 / (1u64 << 53) as f64;
+        if draw < config.rate(operation) {
+            return ffi::raise_injected_failure(operation);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_fault_injection() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.enable_chaos(ChaosConfig {
+            add: 0.5,
+            search: 1.0,
+            ..Default::default()
+        });
+
+        let mut failed = 0;
+        for key in 0..1000u64 {
+            if let Err(err) = index.add(key, &[key as f32, 0.0]) {
+                assert!(is_injected(&err));
+                failed += 1;
+            }
+        }
+        assert!((400..600).contains(&failed), "{} failed insertions", failed);
+        assert_eq!(index.size(), 1000 - failed);
+
+        let err: IndexError = index.search(&[0.0_f32, 0.0], 5).unwrap_err();
+        assert!(matches!(err, IndexError::Native(_)));
+        assert!(is_injected(&err));
+        let mut buffer = vec![0; index.serialized_length()];
+        assert!(index.save_to_buffer(&mut buffer).is_ok());
+
+        index.disable_chaos();
+        assert!(index.search(&[0.0_f32, 0.0], 5).is_ok());
+        // Genuine failures, like duplicate keys, aren't reported as injected.
+        let present = index.keys()[0];
+        assert!(!is_injected(
+            &index.add(present, &[0.0_f32, 0.0]).unwrap_err()
+        ));
+    }
+}
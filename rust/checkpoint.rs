@@ -0,0 +1,261 @@
+//! Resumable index construction with periodic checkpoints.
+//!
+//! Long builds that crash would otherwise restart from zero. The [`CheckpointedBuilder`]
+//! serializes the partially built index every `N` insertions or `T` seconds, together
+//! with the offset into the input stream, and picks up from the latest checkpoint on restart.
+//!
+//! Every checkpoint is written to its own `checkpoint-<offset>.usearch` file, and only then
+//! the `checkpoint.latest` manifest is atomically replaced to point at it. Both are flushed to
+//! the disk before the previous checkpoint is removed, so a crash at any moment leaves the
+//! directory with a consistent index and offset pair.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::snapshot::{rename_synced, sync_parent, temporary_path, write_synced};
+use crate::{Index, IndexError, IndexOptions, Key, VectorType};
+
+const MANIFEST_NAME: &str = "checkpoint.latest";
+const FILE_PREFIX: &str = "checkpoint-";
+const FILE_SUFFIX: &str = ".usearch";
+
+/// Builds an index from a stream of `(key, vector)` pairs, checkpointing the progress to a directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use usearch::{CheckpointedBuilder, IndexOptions};
+/// use std::time::Duration;
+///
+/// let options = IndexOptions { dimensions: 128, ..Default::default() };
+/// let mut builder = CheckpointedBuilder::open(&options, "build-checkpoints")
+///     .unwrap()
+///     .every_inserts(1_000_000)
+///     .every_duration(Duration::from_secs(600));
+///
+/// // Items consumed before the last checkpoint are skipped automatically.
+/// let stream = (0..10_000_000u64).map(|i| (i, vec![i as f32; 128]));
+/// builder.ingest(stream).unwrap();
+/// let index = builder.finish().unwrap();
+/// ```
+pub struct CheckpointedBuilder {
+    index: Index,
+    directory: PathBuf,
+    every_inserts: Option<usize>,
+    every_duration: Option<Duration>,
+    offset: usize,
+    checkpointed_offset: usize,
+    last_checkpoint: Instant,
+}
+
+impl CheckpointedBuilder {
+    /// Opens a builder over a checkpoint directory, creating it if needed.
+    ///
+    /// If the directory contains a checkpoint, the index is restored from it and
+    /// [`CheckpointedBuilder::offset`] reports how many input items it already covers.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options used to create the index, must match the ones of existing checkpoints.
+    /// * `directory` - The directory where checkpoints are stored.
+    pub fn open<P: AsRef<Path>>(options: &IndexOptions, directory: P) -> Result<Self, IndexError> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+
+        let mut index = Index::new(options)?;
+        let mut offset = 0;
+        if let Some((latest, latest_offset)) = Self::latest_checkpoint(&directory)? {
+            index.load(&latest)?;
+            offset = latest_offset;
+        }
+
+        Ok(Self {
+            index,
+            directory,
+            every_inserts: None,
+            every_duration: None,
+            offset,
+            checkpointed_offset: offset,
+            last_checkpoint: Instant::now(),
+        })
+    }
+
+    /// Checkpoints after every `n` consumed input items.
+    pub fn every_inserts(mut self, n: usize) -> Self {
+        self.every_inserts = Some(n.max(1));
+        self
+    }
+
+    /// Checkpoints when at least `interval` has passed since the last checkpoint.
+    pub fn every_duration(mut self, interval: Duration) -> Self {
+        self.every_duration = Some(interval);
+        self
+    }
+
+    /// Number of input items consumed so far, including the ones restored from a checkpoint.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The partially built index.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Adds the next input item, growing the capacity as needed and checkpointing if due.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the vector.
+    /// * `vector` - A slice containing the vector data.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the vector doesn't have the dimensions of the index,
+    /// in which case the item isn't consumed.
+    pub fn add<T: VectorType>(&mut self, key: Key, vector: &[T]) -> Result<(), IndexError> {
+        self.index.add(key, vector)?;
+        self.offset += 1;
+
+        if self.checkpoint_due() {
+            self.checkpoint()?;
+        }
+        Ok(())
+    }
+
+    /// Consumes the whole input stream, skipping the items already covered by the restored checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The complete input stream, starting from its very first item.
+    pub fn ingest<T, V, I>(&mut self, items: I) -> Result<(), IndexError>
+    where
+        T: VectorType,
+        V: AsRef<[T]>,
+        I: IntoIterator<Item = (Key, V)>,
+    {
+        for (key, vector) in items.into_iter().skip(self.offset) {
+            self.add(key, vector.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Writes a checkpoint immediately, regardless of the configured policy.
+    ///
+    /// The checkpoint and the manifest are flushed to the disk before the previous checkpoint
+    /// is removed, so the manifest never points at a checkpoint that didn't reach it.
+    pub fn checkpoint(&mut self) -> Result<(), IndexError> {
+        let path = self.checkpoint_path(self.offset);
+        let temporary = temporary_path(&path);
+        if let Err(err) = self.index.save(&temporary) {
+            let _ = fs::remove_file(&temporary);
+            return Err(err);
+        }
+        rename_synced(&temporary, &path)?;
+
+        // Atomically switch the manifest to the new checkpoint.
+        let manifest = self.directory.join(MANIFEST_NAME);
+        let manifest_temp = temporary_path(&manifest);
+        let renamed = write_synced(&manifest_temp, self.offset.to_string().as_bytes())
+            .and_then(|()| fs::rename(&manifest_temp, &manifest));
+        if let Err(err) = renamed {
+            let _ = fs::remove_file(&manifest_temp);
+            return Err(err.into());
+        }
+        sync_parent(&manifest)?;
+
+        // Older checkpoints are no longer needed.
+        if self.checkpointed_offset != self.offset {
+            let previous = self.checkpoint_path(self.checkpointed_offset);
+            if previous.exists() {
+                fs::remove_file(previous)?;
+            }
+        }
+
+        self.checkpointed_offset = self.offset;
+        self.last_checkpoint = Instant::now();
+        Ok(())
+    }
+
+    /// Completes the build, removing the checkpoint files, and returns the index.
+    pub fn finish(self) -> Result<Index, IndexError> {
+        let manifest = self.directory.join(MANIFEST_NAME);
+        if manifest.exists() {
+            fs::remove_file(manifest)?;
+        }
+        let last = self.checkpoint_path(self.checkpointed_offset);
+        if last.exists() {
+            fs::remove_file(last)?;
+        }
+        Ok(self.index)
+    }
+
+    fn checkpoint_due(&self) -> bool {
+        let pending = self.offset - self.checkpointed_offset;
+        if pending == 0 {
+            return false;
+        }
+        let by_count = self.every_inserts.is_some_and(|n| pending >= n);
+        let by_time = self
+            .every_duration
+            .is_some_and(|interval| self.last_checkpoint.elapsed() >= interval);
+        by_count || by_time
+    }
+
+    fn checkpoint_path(&self, offset: usize) -> PathBuf {
+        self.directory
+            .join(format!("{}{:020}{}", FILE_PREFIX, offset, FILE_SUFFIX))
+    }
+
+    fn latest_checkpoint(directory: &Path) -> Result<Option<(PathBuf, usize)>, IndexError> {
+        let manifest = directory.join(MANIFEST_NAME);
+        if !manifest.exists() {
+            return Ok(None);
+        }
+        let offset: usize = fs::read_to_string(&manifest)?.trim().parse().map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Malformed checkpoint manifest",
+            )
+        })?;
+        let path = directory.join(format!("{}{:020}{}", FILE_PREFIX, offset, FILE_SUFFIX));
+        Ok(Some((path, offset)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_from_checkpoint() {
+        let directory = std::env::temp_dir().join("usearch.rust.checkpoints");
+        let _ = fs::remove_dir_all(&directory);
+        let options = IndexOptions {
+            dimensions: 2,
+            ..Default::default()
+        };
+        let stream = || (0..50u64).map(|i| (i, vec![i as f32, 1.0]));
+
+        // Simulate a crash after 35 items, with checkpoints every 10.
+        {
+            let mut builder = CheckpointedBuilder::open(&options, &directory)
+                .unwrap()
+                .every_inserts(10);
+            builder.ingest(stream().take(35)).unwrap();
+            assert_eq!(builder.offset(), 35);
+        }
+
+        let mut builder = CheckpointedBuilder::open(&options, &directory).unwrap();
+        assert_eq!(builder.offset(), 30);
+        assert_eq!(builder.index().size(), 30);
+
+        builder.ingest(stream()).unwrap();
+        let index = builder.finish().unwrap();
+        assert_eq!(index.size(), 50);
+        assert!(index.contains(49));
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 0);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,384 @@
+//! Minimal checksumming utilities shared by the persistence layers.
+//!
+//! Snapshots written by [`Index::save`](crate::Index::save) and its buffer siblings are sealed
+//! with the CRC32 of the whole snapshot and its length, stored in the last 12 bytes of the 64-byte
+//! native header, which the native layer leaves zeroed and ignores. The checksum covers the
+//! snapshot with those 12 bytes zeroed. Loads verify the seal, and reject truncated or corrupted
+//! snapshots with [`IndexError::CorruptSnapshot`]. Views of files only check the sealed length,
+//! to stay lazy, unless created with [`Index::view_verified`](crate::Index::view_verified).
+//! Snapshots of older versions carry no seal, and are loaded unverified.
+
+#[cfg(not(no_fs))]
+use std::fs::{File, OpenOptions};
+#[cfg(not(no_fs))]
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+#[cfg(not(no_fs))]
+use crate::metadata::locate_in_file;
+use crate::metadata::{locate_in_buffer, HEADER_LENGTH};
+use crate::IndexError;
+
+/// Offset of the seal within the native header: the CRC32, then the length of the snapshot.
+const SEAL_OFFSET: usize = 52;
+const SEAL_LENGTH: usize = 12;
+
+/// Bytes read at once when checksumming files.
+#[cfg(not(no_fs))]
+const CHUNK_LENGTH: usize = 1 << 20;
+
+/// Lookup table for the reflected IEEE 802.3 CRC32 polynomial.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Incremental CRC32 hasher, compatible with `zlib`, `gzip`, and `crc32fast`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Crc32 {
+    state: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self { state: 0xFFFF_FFFF }
+    }
+}
+
+impl Crc32 {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        let mut crc = self.state;
+        for &byte in bytes {
+            crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+        }
+        self.state = crc;
+    }
+
+    pub(crate) fn finalize(self) -> u32 {
+        self.state ^ 0xFFFF_FFFF
+    }
+}
+
+/// Computes the CRC32 checksum of a buffer in one pass.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut hasher = Crc32::new();
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+fn corrupt(reason: String) -> IndexError {
+    IndexError::CorruptSnapshot { reason }
+}
+
+/// Explains a missing header by a truncation, if the snapshot starts with the shape of a matrix
+/// of `vector_bytes` wide vectors, that the header should follow past the end.
+fn truncated_matrix(prefix: &[u8], length: u64, vector_bytes: usize) -> Option<IndexError> {
+    let mut bytes = [0u8; 16];
+    bytes[..prefix.len().min(16)].copy_from_slice(&prefix[..prefix.len().min(16)]);
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as u64;
+    let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+    let shapes = [(u32_at(0), u32_at(4), 8), (u64_at(0), u64_at(8), 16)];
+    shapes.into_iter().find_map(|(rows, columns, shape)| {
+        let header = rows.checked_mul(columns)?.checked_add(shape)?;
+        let expected = header.checked_add(HEADER_LENGTH as u64)?;
+        (rows > 0 && columns == vector_bytes as u64 && length < expected).then(|| {
+            corrupt(format!(
+                "truncated to {} bytes, before the header expected at byte {}",
+                length, header
+            ))
+        })
+    })
+}
+
+/// Reads the seal of a header, as the checksum and the sealed length, zero if unsealed.
+fn read_seal(seal: &[u8]) -> (u32, u64) {
+    let checksum = u32::from_le_bytes(seal[..4].try_into().unwrap());
+    let length = u64::from_le_bytes(seal[4..SEAL_LENGTH].try_into().unwrap());
+    (checksum, length)
+}
+
+fn write_seal(checksum: u32, length: u64) -> [u8; SEAL_LENGTH] {
+    let mut seal = [0u8; SEAL_LENGTH];
+    seal[..4].copy_from_slice(&checksum.to_le_bytes());
+    seal[4..].copy_from_slice(&length.to_le_bytes());
+    seal
+}
+
+/// Compares the checksum of a sealed snapshot with the one recorded in its seal.
+fn check_seal(expected: u32, found: u32) -> Result<(), IndexError> {
+    if expected != found {
+        return Err(corrupt(format!(
+            "checksum mismatch, expected {:08x} and found {:08x}",
+            expected, found
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the sealed length against the available bytes, and returns it.
+fn sealed_length(length: u64, available: u64, seal_end: u64) -> Result<u64, IndexError> {
+    if length > available {
+        return Err(corrupt(format!(
+            "truncated to {} of {} bytes",
+            available, length
+        )));
+    }
+    if length < seal_end {
+        return Err(corrupt(format!(
+            "sealed length of {} bytes ends before the header",
+            length
+        )));
+    }
+    Ok(length)
+}
+
+/// Seals a snapshot freshly serialized into a buffer, with its seal still zeroed.
+pub(crate) fn seal_buffer(buffer: &mut [u8]) -> Result<(), IndexError> {
+    let (header, _) = locate_in_buffer(buffer)?;
+    let seal = header + SEAL_OFFSET;
+    let sealed = write_seal(crc32(buffer), buffer.len() as u64);
+    buffer[seal..seal + SEAL_LENGTH].copy_from_slice(&sealed);
+    Ok(())
+}
+
+/// Checks that a buffer holds a compatible snapshot, complete and intact if sealed.
+///
+/// Bytes past the sealed length are ignored, like those of a larger buffer.
+pub(crate) fn verify_buffer(buffer: &[u8], vector_bytes: usize) -> Result<(), IndexError> {
+    let (header, metadata) = locate_in_buffer(buffer).map_err(|err| {
+        truncated_matrix(buffer, buffer.len() as u64, vector_bytes).unwrap_or(err)
+    })?;
+    metadata.check_compatible()?;
+    let seal = header + SEAL_OFFSET;
+    let (checksum, length) = read_seal(&buffer[seal..seal + SEAL_LENGTH]);
+    if length == 0 {
+        return Ok(());
+    }
+    let end = (seal + SEAL_LENGTH) as u64;
+    let length = sealed_length(length, buffer.len() as u64, end)? as usize;
+    let mut hasher = Crc32::new();
+    hasher.update(&buffer[..seal]);
+    hasher.update(&[0; SEAL_LENGTH]);
+    hasher.update(&buffer[seal + SEAL_LENGTH..length]);
+    check_seal(checksum, hasher.finalize())
+}
+
+/// Computes the CRC32 of the first `length` bytes of a file, reading the seal as zeroes.
+#[cfg(not(no_fs))]
+fn crc32_of_file(file: &mut File, length: u64, seal: u64) -> std::io::Result<u32> {
+    file.seek(SeekFrom::Start(0))?;
+    let mut hasher = Crc32::new();
+    let mut chunk = vec![0u8; CHUNK_LENGTH];
+    let mut offset = 0u64;
+    while offset < length {
+        let size = (length - offset).min(CHUNK_LENGTH as u64) as usize;
+        file.read_exact(&mut chunk[..size])?;
+        let end = offset + size as u64;
+        let zeroed_start = seal.clamp(offset, end);
+        let zeroed_end = (seal + SEAL_LENGTH as u64).clamp(offset, end);
+        chunk[(zeroed_start - offset) as usize..(zeroed_end - offset) as usize].fill(0);
+        hasher.update(&chunk[..size]);
+        offset = end;
+    }
+    Ok(hasher.finalize())
+}
+
+/// Seals a snapshot freshly saved to a file, re-reading it once.
+#[cfg(not(no_fs))]
+pub(crate) fn seal_file(path: &Path) -> Result<(), IndexError> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let length = file.metadata()?.len();
+    let (header, _) = locate_in_file(&mut file, length)?;
+    let seal = header + SEAL_OFFSET as u64;
+    let checksum = crc32_of_file(&mut file, length, seal)?;
+    file.seek(SeekFrom::Start(seal))?;
+    file.write_all(&write_seal(checksum, length))?;
+    Ok(())
+}
+
+/// Checks that a file holds a compatible snapshot, at least as long as its sealed length, and
+/// returns the seal, reading only the header.
+#[cfg(not(no_fs))]
+fn verify_file_seal(
+    file: &mut File,
+    vector_bytes: usize,
+) -> Result<Option<(u32, u64, u64)>, IndexError> {
+    let available = file.metadata()?.len();
+    let (header, metadata) = match locate_in_file(file, available) {
+        Ok(found) => found,
+        Err(err) => {
+            let mut prefix = [0u8; 16];
+            file.seek(SeekFrom::Start(0))?;
+            let read = file.read(&mut prefix)?;
+            return Err(truncated_matrix(&prefix[..read], available, vector_bytes).unwrap_or(err));
+        }
+    };
+    metadata.check_compatible()?;
+    let seal = header + SEAL_OFFSET as u64;
+    let mut bytes = [0u8; SEAL_LENGTH];
+    file.seek(SeekFrom::Start(seal))?;
+    file.read_exact(&mut bytes)?;
+    let (checksum, length) = read_seal(&bytes);
+    if length == 0 {
+        return Ok(None);
+    }
+    let length = sealed_length(length, available, seal + SEAL_LENGTH as u64)?;
+    Ok(Some((checksum, length, seal)))
+}
+
+/// Checks that a file holds a compatible snapshot, not truncated if sealed, without reading
+/// past its header.
+#[cfg(not(no_fs))]
+pub(crate) fn verify_file_header(path: &Path, vector_bytes: usize) -> Result<(), IndexError> {
+    verify_file_seal(&mut File::open(path)?, vector_bytes)?;
+    Ok(())
+}
+
+/// Checks that a file holds a compatible snapshot, complete and intact if sealed.
+///
+/// Bytes past the sealed length, like the records appended by [`crate::Index::sync`], are left
+/// to their own checks.
+#[cfg(not(no_fs))]
+pub(crate) fn verify_file(path: &Path, vector_bytes: usize) -> Result<(), IndexError> {
+    let mut file = File::open(path)?;
+    match verify_file_seal(&mut file, vector_bytes)? {
+        Some((checksum, length, seal)) => {
+            check_seal(checksum, crc32_of_file(&mut file, length, seal)?)
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_crc32_reference_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let mut hasher = Crc32::new();
+        hasher.update(b"1234");
+        hasher.update(b"56789");
+        assert_eq!(hasher.finalize(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_snapshot_seal() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(100).unwrap();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32; 4]).unwrap();
+        }
+        let mut buffer = vec![0; index.serialized_length()];
+        index.save_to_buffer(&mut buffer).unwrap();
+        let (header, _) = locate_in_buffer(&buffer).unwrap();
+        let corrupt_on_load = |buffer: &[u8]| {
+            let mut loaded = Index::new(&options).unwrap();
+            matches!(
+                loaded.load_from_buffer(buffer),
+                Err(IndexError::CorruptSnapshot { .. })
+            )
+        };
+
+        let mut loaded = Index::new(&options).unwrap();
+        loaded.load_from_buffer(&buffer).unwrap();
+        assert_eq!(loaded.size(), 100);
+        // Larger buffers keep loading, as the seal records the length.
+        let mut padded = buffer.clone();
+        padded.extend_from_slice(&[0; 32]);
+        loaded.view_from_buffer(&padded).unwrap();
+
+        let mut flipped = buffer.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        assert!(corrupt_on_load(&flipped));
+        assert!(corrupt_on_load(&buffer[..buffer.len() - 1]));
+        assert!(corrupt_on_load(&buffer[..header - 100]));
+        let mut loaded = Index::new(&options).unwrap();
+        match loaded.view_from_buffer(&flipped) {
+            Err(err) => assert!(err.to_string().starts_with("Corrupt snapshot: checksum")),
+            Ok(()) => panic!("Viewed a corrupted snapshot"),
+        }
+
+        // Snapshots of older versions carry no seal, and load unverified.
+        let mut unsealed = buffer.clone();
+        unsealed[header + SEAL_OFFSET..header + HEADER_LENGTH].fill(0);
+        assert_eq!(
+            crc32(&unsealed),
+            read_seal(&buffer[header + SEAL_OFFSET..]).0
+        );
+        loaded.load_from_buffer(&unsealed).unwrap();
+
+        #[cfg(not(no_fs))]
+        {
+            let path = std::env::temp_dir().join("usearch-checksum-test.usearch");
+            index.save(&path).unwrap();
+            assert_eq!(std::fs::read(&path).unwrap(), buffer);
+            let mut from_file = Index::new(&options).unwrap();
+            from_file.load(&path).unwrap();
+            from_file.view(&path).unwrap();
+            assert_eq!(from_file.size(), 100);
+
+            let file = OpenOptions::new().write(true).open(&path).unwrap();
+            file.set_len(buffer.len() as u64 - 10).unwrap();
+            let mut from_file = Index::new(&options).unwrap();
+            assert!(matches!(
+                from_file.load(&path),
+                Err(IndexError::CorruptSnapshot { .. })
+            ));
+            file.set_len(header as u64 / 2).unwrap();
+            assert!(matches!(
+                from_file.view(&path),
+                Err(IndexError::CorruptSnapshot { .. })
+            ));
+            std::fs::write(&path, &flipped).unwrap();
+            assert!(matches!(
+                from_file.load(&path),
+                Err(IndexError::CorruptSnapshot { .. })
+            ));
+
+            // Views only check the header, unless asked to verify the whole file.
+            let mut flipped_vector = buffer.clone();
+            flipped_vector[16] ^= 1;
+            std::fs::write(&path, &flipped_vector).unwrap();
+            from_file.view(&path).unwrap();
+            assert!(matches!(
+                from_file.view_verified(&path),
+                Err(IndexError::CorruptSnapshot { .. })
+            ));
+            from_file.reset().unwrap();
+            std::fs::write(&path, &buffer).unwrap();
+            from_file.view_verified(&path).unwrap();
+            assert_eq!(from_file.size(), 100);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Several named vector spaces, like "text" and "image" embeddings, managed as one unit.
+//!
+//! Multi-modal applications keep one index per embedding model, each with its own dimensions
+//! and metric. A [`Collection`] holds them under names, shares one key namespace across them,
+//! so removing a key removes it everywhere, and persists all of them to a single directory,
+//! as one `<name>.usearch` file per space.
+
+use std::collections::BTreeMap;
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::ffi::Matches;
+#[cfg(not(no_fs))]
+use crate::file_metadata;
+use crate::{Index, IndexError, IndexOptions, Key, VectorType};
+
+/// File extension of the spaces persisted by [`Collection::save`].
+#[cfg(not(no_fs))]
+const EXTENSION: &str = "usearch";
+
+fn check_name(name: &str) -> Result<(), IndexError> {
+    let valid = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if name.is_empty() || !valid {
+        return Err(IndexError::InvalidOptions {
+            reason: format!(
+                "Space name {:?} must be non-empty and only contain ASCII letters, digits, `_`, and `-`",
+                name
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn missing(name: &str) -> IndexError {
+    IndexError::InvalidOptions {
+        reason: format!("No space named {:?}", name),
+    }
+}
+
+/// Named indexes with possibly different dimensions and metrics, sharing one key namespace.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{Collection, IndexOptions, MetricKind, ScalarKind};
+///
+/// let mut collection = Collection::new();
+/// let text = IndexOptions { dimensions: 3, metric: MetricKind::Cos, quantization: ScalarKind::F32, ..Default::default() };
+/// let image = IndexOptions { dimensions: 2, metric: MetricKind::L2sq, quantization: ScalarKind::F32, ..Default::default() };
+/// collection.create_space("text", &text).unwrap();
+/// collection.create_space("image", &image).unwrap();
+///
+/// collection.add("text", 42, &[0.1_f32, 0.2, 0.3]).unwrap();
+/// collection.add("image", 42, &[0.5_f32, 0.5]).unwrap();
+///
+/// let matches = collection.search("image", &[0.4_f32, 0.5], 1).unwrap();
+/// assert_eq!(matches.keys, vec![42]);
+/// assert_eq!(collection.remove(42).unwrap(), 2);
+/// ```
+#[derive(Default)]
+pub struct Collection {
+    spaces: BTreeMap<String, Index>,
+}
+
+impl Collection {
+    /// Creates a collection without spaces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty space with the given options.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if the name is already taken, or can't be used as a file name.
+    pub fn create_space(
+        &mut self,
+        name: &str,
+        options: &IndexOptions,
+    ) -> Result<&mut Index, IndexError> {
+        check_name(name)?;
+        if self.spaces.contains_key(name) {
+            return Err(IndexError::InvalidOptions {
+                reason: format!("Space {:?} already exists", name),
+            });
+        }
+        Ok(self
+            .spaces
+            .entry(name.to_owned())
+            .or_insert(Index::new(options)?))
+    }
+
+    /// Removes a space with all of its vectors, returning its index.
+    pub fn drop_space(&mut self, name: &str) -> Option<Index> {
+        self.spaces.remove(name)
+    }
+
+    /// The index of a space, if it exists.
+    pub fn space(&self, name: &str) -> Option<&Index> {
+        self.spaces.get(name)
+    }
+
+    /// The names of all spaces, in lexicographic order.
+    pub fn space_names(&self) -> impl Iterator<Item = &str> {
+        self.spaces.keys().map(String::as_str)
+    }
+
+    /// The number of spaces.
+    pub fn len(&self) -> usize {
+        self.spaces.len()
+    }
+
+    /// Checks if the collection has no spaces.
+    pub fn is_empty(&self) -> bool {
+        self.spaces.is_empty()
+    }
+
+    /// Adds a vector to a space under the specified key.
+    pub fn add<T: VectorType>(
+        &self,
+        space: &str,
+        key: Key,
+        vector: &[T],
+    ) -> Result<(), IndexError> {
+        let index = self.space(space).ok_or_else(|| missing(space))?;
+        index.add(key, vector)
+    }
+
+    /// Searches one space for the nearest neighbors of the query.
+    pub fn search<T: VectorType>(
+        &self,
+        space: &str,
+        query: &[T],
+        count: usize,
+    ) -> Result<Matches, IndexError> {
+        let index = self.space(space).ok_or_else(|| missing(space))?;
+        index.search(query, count)
+    }
+
+    /// Checks if any space has a vector under the key.
+    pub fn contains(&self, key: Key) -> bool {
+        self.spaces.values().any(|index| index.contains(key))
+    }
+
+    /// The names of the spaces having a vector under the key.
+    pub fn spaces_of(&self, key: Key) -> Vec<&str> {
+        self.spaces
+            .iter()
+            .filter(|(_, index)| index.contains(key))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Removes the vectors stored under a key from every space.
+    ///
+    /// # Returns
+    ///
+    /// The number of removed vectors, across all spaces.
+    pub fn remove(&self, key: Key) -> Result<usize, IndexError> {
+        let mut removed = 0;
+        for index in self.spaces.values() {
+            removed += index.remove(key)?;
+        }
+        Ok(removed)
+    }
+
+    /// Saves every space to `<name>.usearch` in the directory, creating it if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory where the spaces will be saved.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, directory: P) -> Result<(), IndexError> {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory)?;
+        for (name, index) in &self.spaces {
+            let path = directory.join(name).with_extension(EXTENSION);
+            index.save(&path)?;
+        }
+        Ok(())
+    }
+
+    /// Loads every `<name>.usearch` file of the directory as a space, configured from its header.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory from where the spaces will be loaded.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(directory: P) -> Result<Self, IndexError> {
+        let mut collection = Self::new();
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let metadata = file_metadata(&path)?;
+            let options = IndexOptions {
+                dimensions: metadata.dimensions,
+                metric: metadata.metric,
+                quantization: metadata.quantization,
+                multi: metadata.multi,
+                ..Default::default()
+            };
+            collection.create_space(name, &options)?.load(&path)?;
+        }
+        Ok(collection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetricKind, ScalarKind};
+
+    #[test]
+    fn test_collection_round_trip() {
+        let text = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::Cos,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let image = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            ..Default::default()
+        };
+        let mut collection = Collection::new();
+        collection.create_space("text", &text).unwrap();
+        collection.create_space("image", &image).unwrap();
+        assert!(collection.create_space("text", &text).is_err());
+        assert!(collection.create_space("../escape", &text).is_err());
+
+        for key in 0..10u64 {
+            collection
+                .add("text", key, &[key as f32, 1.0, 0.0])
+                .unwrap();
+            if key % 2 == 0 {
+                collection.add("image", key, &[key as f32, 0.0]).unwrap();
+            }
+        }
+        assert!(collection.add("audio", 1, &[0.0_f32]).is_err());
+        assert_eq!(collection.spaces_of(4), vec!["image", "text"]);
+        assert_eq!(collection.remove(4).unwrap(), 2);
+        assert!(!collection.contains(4));
+
+        let directory = std::env::temp_dir().join("usearch-collection-round-trip");
+        collection.save(&directory).unwrap();
+        let loaded = Collection::load(&directory).unwrap();
+        assert_eq!(
+            loaded.space_names().collect::<Vec<_>>(),
+            vec!["image", "text"]
+        );
+        assert_eq!(loaded.space("text").unwrap().size(), 9);
+        assert_eq!(loaded.space("image").unwrap().dimensions(), 2);
+        let matches = loaded.search("image", &[6.1_f32, 0.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![6]);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+//! Reclaiming the slots of removed vectors.
+//!
+//! [`Index::remove`] only marks vectors as deleted: their slots stay in the graph as
+//! tombstones, still traversed by searches, until new insertions recycle them. Workloads that
+//! mostly remove accumulate them and slowly degrade. [`Index::compact`] re-inserts the live
+//! vectors into a fresh graph, in parallel, and reports the memory it reclaimed.
+
+use std::sync::PoisonError;
+
+use crate::{b1x8, f16, Index, IndexError, Key, ScalarKind, VectorType};
+
+/// Outcome of an [`Index::compact`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionReport {
+    /// Number of deleted slots dropped from the graph.
+    pub removed_slots: usize,
+    /// Memory usage of the index in bytes, before compaction.
+    pub memory_before: usize,
+    /// Memory usage of the index in bytes, after compaction.
+    pub memory_after: usize,
+}
+
+impl CompactionReport {
+    /// Number of bytes returned by the compaction, zero if the index grew.
+    pub fn reclaimed_bytes(&self) -> usize {
+        self.memory_before.saturating_sub(self.memory_after)
+    }
+}
+
+/// Number of vectors every thread copies between two calls of the transfer callback.
+const REPORT_EVERY: usize = 1024;
+
+/// The pair of indexes shared by the threads of a compaction.
+pub(crate) struct Transfer<'a> {
+    pub(crate) source: &'a Index,
+    pub(crate) target: &'a Index,
+    /// Called from the copying threads with the number of vectors they copied since last time.
+    pub(crate) copied: Option<&'a (dyn Fn(usize) + Sync)>,
+}
+
+impl Transfer<'_> {
+    /// Copies every vector of the keys, exported as `T`.
+    fn copy<T: VectorType + Default + Clone>(&self, keys: &[Key]) -> Result<(), IndexError> {
+        let dimensions = self.source.dimensions();
+        let mut vectors: Vec<T> = Vec::new();
+        let mut unreported = 0;
+        for &key in keys {
+            self.source.export(key, &mut vectors)?;
+            for vector in vectors.chunks_exact(dimensions) {
+                self.target.add(key, vector)?;
+                unreported += 1;
+            }
+            if let Some(copied) = self.copied.filter(|_| unreported >= REPORT_EVERY) {
+                copied(std::mem::take(&mut unreported));
+            }
+        }
+        if let Some(copied) = self.copied.filter(|_| unreported > 0) {
+            copied(unreported);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn copy_all<T: VectorType + Default + Clone>(
+        &self,
+        keys: &[Key],
+        threads: usize,
+    ) -> Result<(), IndexError> {
+        let chunk_size = keys.len().div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = keys
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || self.copy::<T>(chunk)))
+                .collect();
+            handles.into_iter().try_for_each(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+        })
+    }
+}
+
+impl Index {
+    /// Number of removed vectors whose slots haven't been reclaimed yet.
+    pub fn deleted(&self) -> usize {
+        self.inner.deleted()
+    }
+
+    /// Rebuilds the graph from the live vectors, dropping the slots of removed ones.
+    ///
+    /// Keys, vectors, the metric, including custom ones, and all options are preserved. Vectors
+    /// are copied in the scalar kind they are stored in, so quantized values don't drift. The
+    /// index is only replaced once every vector was copied, and is left untouched on failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `threads` - The number of threads re-inserting vectors, zero for all available ones.
+    ///   It is capped by the available parallelism, as the native layer holds one insertion
+    ///   context per hardware thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let mut index = Index::new(&options).unwrap();
+    /// for key in 0..100u64 {
+    ///     index.add(key, &[key as f32, 0.0]).unwrap();
+    /// }
+    /// for key in 0..50u64 {
+    ///     index.remove(key).unwrap();
+    /// }
+    /// assert_eq!(index.deleted(), 50);
+    ///
+    /// let report = index.compact(0).unwrap();
+    /// assert_eq!(report.removed_slots, 50);
+    /// assert_eq!(index.deleted(), 0);
+    /// assert_eq!(index.size(), 50);
+    /// ```
+    pub fn compact(&mut self, threads: usize) -> Result<CompactionReport, IndexError> {
+        let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let threads = match threads {
+            0 => available,
+            threads => threads.min(available),
+        };
+        let removed_slots = self.deleted();
+        let memory_before = self.memory_usage();
+
+        let mut keys = self.keys();
+        keys.sort_unstable();
+        keys.dedup();
+        let mut fresh = Index::new(&self.options())?;
+        fresh.reserve(self.size())?;
+        if let Some(metric) = self
+            .metric_fn
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .last()
+        {
+            fresh.register_metric_state(metric);
+        }
+
+        let transfer = Transfer {
+            source: self,
+            target: &fresh,
+            copied: None,
+        };
+        let copied = match self.inner.scalar_kind() {
+            ScalarKind::B1 => transfer.copy_all::<b1x8>(&keys, threads),
+            ScalarKind::I8 => transfer.copy_all::<i8>(&keys, threads),
+            ScalarKind::F16 => transfer.copy_all::<f16>(&keys, threads),
+            ScalarKind::F64 => transfer.copy_all::<f64>(&keys, threads),
+            _ => transfer.copy_all::<f32>(&keys, threads),
+        };
+        // Both native indexes call the same pinned metric, which stays owned by `self`.
+        if copied.is_ok() {
+            std::mem::swap(&mut self.inner, &mut fresh.inner);
+        }
+        copied?;
+
+        Ok(CompactionReport {
+            removed_slots,
+            memory_before,
+            memory_after: self.memory_usage(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind};
+
+    #[test]
+    fn test_compaction() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            connectivity: 8,
+            multi: true,
+            ..Default::default()
+        };
+        let mut index = Index::new(&options).unwrap();
+        for key in 0..500u64 {
+            index.add(key, &[key as f32, 1.0]).unwrap();
+        }
+        index.add(499, &[499.0_f32, 2.0]).unwrap();
+        for key in 0..400u64 {
+            index.remove(key).unwrap();
+        }
+        assert_eq!(index.deleted(), 400);
+
+        let report = index.compact(2).unwrap();
+        assert_eq!(report.removed_slots, 400);
+        assert!(report.reclaimed_bytes() > 0);
+        assert_eq!(report.memory_after, index.memory_usage());
+        assert_eq!(index.deleted(), 0);
+        assert_eq!(index.size(), 101);
+        assert_eq!(index.connectivity(), 8);
+        assert_eq!(index.options().quantization, ScalarKind::F16);
+        assert!(!index.contains(10));
+        assert_eq!(index.count(499), 2);
+
+        let matches = index.search(&[450.0_f32, 1.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![450]);
+    }
+
+    #[test]
+    fn test_compaction_keeps_custom_metric() {
+        let options = IndexOptions {
+            dimensions: 2,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let mut index = Index::new(&options).unwrap();
+        index.change_metric::<f32>(Box::new(|a, b| unsafe {
+            let a = std::slice::from_raw_parts(a, 2);
+            let b = std::slice::from_raw_parts(b, 2);
+            (a[0] - b[0]).abs() * 2.0 + (a[1] - b[1]).abs() * 0.7
+        }));
+        index.add(1, &[1.0_f32, 0.0]).unwrap();
+        index.add(2, &[0.0_f32, 1.0]).unwrap();
+        index.remove(1).unwrap();
+
+        index.compact(1).unwrap();
+        index.add(3, &[3.0_f32, 0.0]).unwrap();
+        let matches = index.search(&[0.0_f32, 0.0], 2).unwrap();
+        assert_eq!(matches.keys, vec![2, 3]);
+        assert_eq!(matches.distances, vec![0.7, 6.0]);
+    }
+}
@@ -0,0 +1,249 @@
+//! Golden-file fixtures for checking that persisted indexes survive upgrades of this crate.
+//!
+//! [`write_golden`] saves a small reference index for every valid metric and scalar kind
+//! combination into a directory. Commit that directory next to your own fixtures, and run
+//! [`check_golden`] in CI: after an upgrade, it reloads every file, compares the header,
+//! the stored vectors, and the reachability of every member with the expected values.
+
+use std::path::Path;
+
+use crate::{b1x8, file_metadata, Index, IndexError, IndexOptions, Key, MetricKind, ScalarKind};
+
+const DIMENSIONS: usize = 8;
+const COUNT: Key = 16;
+
+const METRICS: [MetricKind; 9] = [
+    MetricKind::IP,
+    MetricKind::L2sq,
+    MetricKind::Cos,
+    MetricKind::Pearson,
+    MetricKind::Haversine,
+    MetricKind::Divergence,
+    MetricKind::Hamming,
+    MetricKind::Tanimoto,
+    MetricKind::Sorensen,
+];
+
+const SCALARS: [ScalarKind; 5] = [
+    ScalarKind::F64,
+    ScalarKind::F32,
+    ScalarKind::F16,
+    ScalarKind::I8,
+    ScalarKind::B1,
+];
+
+/// All metric and scalar kind combinations accepted by `IndexOptions::validate`.
+fn combinations() -> Vec<IndexOptions> {
+    let mut combinations = Vec::new();
+    for metric in METRICS {
+        for quantization in SCALARS {
+            let options = IndexOptions {
+                dimensions: if metric == MetricKind::Haversine {
+                    2
+                } else {
+                    DIMENSIONS
+                },
+                metric,
+                quantization,
+                ..Default::default()
+            };
+            if options.validate().is_ok() {
+                combinations.push(options);
+            }
+        }
+    }
+    combinations
+}
+
+fn file_name(options: &IndexOptions) -> String {
+    format!(
+        "golden-{:?}-{:?}.usearch",
+        options.metric, options.quantization
+    )
+    .to_lowercase()
+}
+
+/// Deterministic vector of the key, with latitude and longitude in degrees for `Haversine`.
+fn golden_vector(key: Key, options: &IndexOptions) -> Vec<f32> {
+    let mut state = (key + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    let mut values: Vec<f32> = (0..options.dimensions)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            // Quarters survive the round trip through every scalar kind, including `i8` and `f16`.
+            (state % 5) as f32 / 4.0
+        })
+        .collect();
+    if options.metric == MetricKind::Haversine {
+        values[0] = values[0] * 160.0 - 80.0;
+        values[1] = values[1] * 340.0 - 170.0;
+    }
+    values
+}
+
+fn golden_bits(key: Key) -> b1x8 {
+    b1x8((key * 37 + 11) as u8)
+}
+
+fn mismatch(file: &str, found: String, expected: String) -> IndexError {
+    IndexError::IncompatibleFormat {
+        found: format!("{} in {}", found, file),
+        expected,
+    }
+}
+
+/// Writes a small reference index for every valid metric and scalar kind combination.
+///
+/// # Arguments
+///
+/// * `directory` - The directory to write the files to, created if missing.
+///
+/// # Returns
+///
+/// The number of written files.
+pub fn write_golden<P: AsRef<Path>>(directory: P) -> Result<usize, IndexError> {
+    let directory = directory.as_ref();
+    std::fs::create_dir_all(directory)?;
+    let combinations = combinations();
+    for options in &combinations {
+        let index = Index::new(options)?;
+        index.reserve(COUNT as usize)?;
+        for key in 0..COUNT {
+            if options.quantization == ScalarKind::B1 {
+                index.add(key, &[golden_bits(key)])?;
+            } else {
+                index.add(key, &golden_vector(key, options))?;
+            }
+        }
+        index.save(directory.join(file_name(options)))?;
+    }
+    Ok(combinations.len())
+}
+
+/// Checks that the files produced by [`write_golden`], possibly with an older version of this
+/// crate, are still read correctly.
+///
+/// Combinations missing from the directory, like ones added in newer versions, are skipped.
+///
+/// # Arguments
+///
+/// * `directory` - The directory the files were written to.
+///
+/// # Returns
+///
+/// The number of checked files, or `IndexError::IncompatibleFormat` describing the first mismatch.
+///
+/// # Examples
+///
+/// ```no_run
+/// // Once, with the version of the crate that produced your persisted indexes:
+/// usearch::compat::write_golden("tests/golden").unwrap();
+///
+/// // In CI, after every upgrade:
+/// usearch::compat::check_golden("tests/golden").unwrap();
+/// ```
+pub fn check_golden<P: AsRef<Path>>(directory: P) -> Result<usize, IndexError> {
+    let directory = directory.as_ref();
+    let mut checked = 0;
+    for options in combinations() {
+        let name = file_name(&options);
+        let path = directory.join(&name);
+        if !path.exists() {
+            continue;
+        }
+
+        let metadata = file_metadata(&path)?;
+        let expected_header = (
+            options.metric,
+            options.quantization,
+            options.dimensions,
+            COUNT as usize,
+        );
+        let found_header = (
+            metadata.metric,
+            metadata.quantization,
+            metadata.dimensions,
+            metadata.size,
+        );
+        if found_header != expected_header {
+            return Err(mismatch(
+                &name,
+                format!("header {:?}", found_header),
+                format!("header {:?}", expected_header),
+            ));
+        }
+
+        let mut index = Index::new(&options)?;
+        index.load(&path)?;
+        for key in 0..COUNT {
+            let found = if options.quantization == ScalarKind::B1 {
+                let mut bits = vec![b1x8(0); options.dimensions];
+                index.get(key, &mut bits)?;
+                if bits[0] != golden_bits(key) {
+                    return Err(mismatch(
+                        &name,
+                        format!("key {} stored as {:?}", key, bits[0]),
+                        format!("{:?}", golden_bits(key)),
+                    ));
+                }
+                index.filtered_search(&[golden_bits(key)], 1, |other| other == key)?
+            } else {
+                let expected = golden_vector(key, &options);
+                let mut vector = vec![0.0_f32; options.dimensions];
+                index.get(key, &mut vector)?;
+                if vector != expected {
+                    return Err(mismatch(
+                        &name,
+                        format!("key {} stored as {:?}", key, vector),
+                        format!("{:?}", expected),
+                    ));
+                }
+                index.filtered_search(&expected, 1, |other| other == key)?
+            };
+            if found.keys != [key] {
+                return Err(mismatch(
+                    &name,
+                    format!("key {} unreachable", key),
+                    "every key reachable".to_owned(),
+                ));
+            }
+        }
+        checked += 1;
+    }
+
+    if checked == 0 {
+        return Err(IndexError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No golden files in {}", directory.display()),
+        )));
+    }
+    Ok(checked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_golden_round_trip() {
+        let directory = std::env::temp_dir().join("usearch.rust.golden");
+        let written = write_golden(&directory).unwrap();
+        assert!(written >= 15);
+        assert_eq!(check_golden(&directory).unwrap(), written);
+
+        // Tamper with one of the files, flipping the metric in its header.
+        let path = directory.join("golden-l2sq-f32.usearch");
+        let mut bytes = std::fs::read(&path).unwrap();
+        let header = bytes.windows(7).position(|w| w == b"usearch").unwrap();
+        bytes[header + 13] = b'c';
+        std::fs::write(&path, bytes).unwrap();
+        assert!(matches!(
+            check_golden(&directory),
+            Err(IndexError::IncompatibleFormat { .. })
+        ));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert!(check_golden(&directory).is_err());
+    }
+}
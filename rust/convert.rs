@@ -0,0 +1,520 @@
+//! Conversions between the scalar kinds supported by the index, usable without any index.
+//!
+//! Every integration (Arrow, NumPy `.npy`, Candle, ...) needs the same conversions between
+//! `f64`, `f32`, [`f16`](struct@f16), `i8`, and packed [`b1x8`] bits. The helpers below follow the same
+//! semantics as the native C++ casts, so vectors converted here and vectors converted
+//! implicitly by the index produce identical results:
+//!
+//! - Floats are mapped to `i8` by scaling by `100`, truncating, and clamping to `[-100, 100]`.
+//!   The native layer truncates to `i8` before clamping, so the results only match for floats
+//!   within `(-1.28, 1.28)`, past which the native cast overflows and these helpers saturate.
+//! - Bits are packed most-significant-bit first, with positive values mapped to `1`.
+//!
+//! Slices of `f32` are converted to and from [`f16`](struct@f16) and `i8` by branch-free kernels over
+//! fixed-size chunks, which the compiler vectorizes for the target. Other pairs convert one value
+//! at a time.
+
+use crate::{b1x8, f16};
+
+/// Divisor used by the native layer to map `[-1, 1]` floats into `i8` integers.
+pub const I8_DIVISOR: f32 = 100.0;
+
+/// Largest magnitude of the `i8` integers produced by the native layer.
+const I8_LIMIT: f32 = 100.0;
+
+/// Number of values converted per chunk by the vectorized kernels.
+const LANES: usize = 16;
+
+/// A potentially lossy conversion between two scalar kinds.
+pub trait LossyCast<To>: Copy {
+    /// Converts the value, rounding, saturating, or truncating as needed.
+    fn lossy_cast(self) -> To;
+
+    /// Converts every element of `input` into the matching element of `output`, stopping at
+    /// the end of the shorter one. Overridden with vectorized kernels for the common pairs.
+    fn lossy_cast_slice(input: &[Self], output: &mut [To]) {
+        for (to, from) in output.iter_mut().zip(input) {
+            *to = from.lossy_cast();
+        }
+    }
+}
+
+/// Applies a branch-free `cast` over chunks of [`LANES`] values, and then over the remainder.
+#[inline(always)]
+fn cast_chunks<From: Copy, To>(input: &[From], output: &mut [To], cast: impl Fn(From) -> To) {
+    let length = input.len().min(output.len());
+    let (input, output) = (&input[..length], &mut output[..length]);
+    let mut inputs = input.chunks_exact(LANES);
+    let mut outputs = output.chunks_exact_mut(LANES);
+    for (from, to) in (&mut inputs).zip(&mut outputs) {
+        for (to, &from) in to.iter_mut().zip(from) {
+            *to = cast(from);
+        }
+    }
+    for (to, &from) in outputs.into_remainder().iter_mut().zip(inputs.remainder()) {
+        *to = cast(from);
+    }
+}
+
+/// Converts `f32` bits into the closest `f16` bits, like [`f16::from_f32`], with selects in place
+/// of branches. Subnormal results are rounded by the floating-point adder, aligning the value
+/// against a magic constant.
+#[inline(always)]
+fn f32_to_f16_bits(bits: u32) -> u16 {
+    const F16_OVERFLOW: u32 = (127 + 16) << 23;
+    const F16_NORMAL: u32 = (127 - 14) << 23;
+    const SUBNORMAL_MAGIC: u32 = ((127 - 15) + (23 - 10) + 1) << 23;
+
+    let sign = (bits >> 16) & 0x8000;
+    let magnitude = bits & 0x7FFF_FFFF;
+    let special = if magnitude > 0x7F80_0000 {
+        0x7E00 | ((magnitude >> 13) & 0x03FF)
+    } else {
+        0x7C00
+    };
+    let subnormal = (f32::from_bits(magnitude) + f32::from_bits(SUBNORMAL_MAGIC))
+        .to_bits()
+        .wrapping_sub(SUBNORMAL_MAGIC);
+    let odd = (magnitude >> 13) & 1;
+    let normal = magnitude
+        .wrapping_sub((127 - 15) << 23)
+        .wrapping_add(0x0FFF + odd)
+        >> 13;
+    let half = if magnitude >= F16_OVERFLOW {
+        special
+    } else if magnitude < F16_NORMAL {
+        subnormal
+    } else {
+        normal
+    };
+    (sign | half) as u16
+}
+
+/// Converts `f16` bits into `f32` bits, like [`f16::to_f32`], with selects in place of branches.
+/// Subnormal inputs are normalized by the floating-point subtractor.
+#[inline(always)]
+fn f16_to_f32_bits(bits: u16) -> u32 {
+    const SHIFTED_EXPONENT: u32 = 0x7C00 << 13;
+    const SUBNORMAL_MAGIC: u32 = 113 << 23;
+
+    let bits = bits as u32;
+    let shifted = (bits & 0x7FFF) << 13;
+    let exponent = shifted & SHIFTED_EXPONENT;
+    let rebased = shifted + ((127 - 15) << 23);
+    let special = (rebased + ((128 - 16) << 23))
+        | if shifted > SHIFTED_EXPONENT {
+            0x0040_0000
+        } else {
+            0
+        };
+    let subnormal =
+        (f32::from_bits(rebased + (1 << 23)) - f32::from_bits(SUBNORMAL_MAGIC)).to_bits();
+    let magnitude = if exponent == SHIFTED_EXPONENT {
+        special
+    } else if exponent == 0 {
+        subnormal
+    } else {
+        rebased
+    };
+    magnitude | ((bits & 0x8000) << 16)
+}
+
+impl LossyCast<f16> for f32 {
+    #[inline]
+    fn lossy_cast(self) -> f16 {
+        f16::from_f32(self)
+    }
+
+    fn lossy_cast_slice(input: &[f32], output: &mut [f16]) {
+        cast_chunks(input, output, |value| {
+            f16::from_bits(f32_to_f16_bits(value.to_bits()))
+        });
+    }
+}
+
+impl LossyCast<f32> for f16 {
+    #[inline]
+    fn lossy_cast(self) -> f32 {
+        self.to_f32()
+    }
+
+    fn lossy_cast_slice(input: &[f16], output: &mut [f32]) {
+        cast_chunks(input, output, |value| {
+            f32::from_bits(f16_to_f32_bits(value.to_bits()))
+        });
+    }
+}
+
+impl LossyCast<i8> for f32 {
+    #[inline]
+    fn lossy_cast(self) -> i8 {
+        (self * I8_DIVISOR).clamp(-I8_LIMIT, I8_LIMIT) as i8
+    }
+
+    fn lossy_cast_slice(input: &[f32], output: &mut [i8]) {
+        cast_chunks(input, output, |value| value.lossy_cast());
+    }
+}
+
+impl LossyCast<f32> for i8 {
+    #[inline]
+    fn lossy_cast(self) -> f32 {
+        self as f32 / I8_DIVISOR
+    }
+
+    fn lossy_cast_slice(input: &[i8], output: &mut [f32]) {
+        cast_chunks(input, output, |value| value.lossy_cast());
+    }
+}
+
+macro_rules! impl_lossy_cast {
+    ($from:ty => $to:ty, |$value:ident| $body:expr) => {
+        impl LossyCast<$to> for $from {
+            #[inline]
+            fn lossy_cast(self) -> $to {
+                let $value = self;
+                $body
+            }
+        }
+    };
+}
+
+impl_lossy_cast!(f64 => f64, |v| v);
+impl_lossy_cast!(f32 => f32, |v| v);
+impl_lossy_cast!(f16 => f16, |v| v);
+impl_lossy_cast!(i8 => i8, |v| v);
+
+impl_lossy_cast!(f64 => f32, |v| v as f32);
+impl_lossy_cast!(f32 => f64, |v| v as f64);
+impl_lossy_cast!(f64 => f16, |v| f16::from_f32(v as f32));
+impl_lossy_cast!(f16 => f64, |v| v.to_f32() as f64);
+
+impl_lossy_cast!(f64 => i8, |v| (v as f32).lossy_cast());
+impl_lossy_cast!(f16 => i8, |v| v.to_f32().lossy_cast());
+impl_lossy_cast!(i8 => f64, |v| v as f64 / I8_DIVISOR as f64);
+impl_lossy_cast!(i8 => f16, |v| f16::from_f32(v as f32 / I8_DIVISOR));
+
+/// Converts every element of `input` into the matching element of `output`.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{convert, f16};
+///
+/// let input = [0.5_f32, -1.0, 2.0];
+/// let mut output = [f16::from_bits(0); 3];
+/// convert::cast_slice_lossy::<f32, f16>(&input, &mut output);
+/// assert_eq!(output[1].to_f32(), -1.0);
+/// ```
+pub fn cast_slice_lossy<From, To>(input: &[From], output: &mut [To])
+where
+    From: LossyCast<To>,
+{
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "Input and output slices must have the same length"
+    );
+    From::lossy_cast_slice(input, output);
+}
+
+/// Converts a slice into a newly allocated vector of another scalar kind.
+pub fn cast_vec_lossy<From, To>(input: &[From]) -> Vec<To>
+where
+    From: LossyCast<To>,
+{
+    input.iter().map(|from| from.lossy_cast()).collect()
+}
+
+/// Maps floating-point values into `i8` integers and back, optionally per dimension.
+pub trait Quantizer {
+    /// Quantizes the `value` found at the `dimension` position of a vector.
+    fn quantize(&self, value: f32, dimension: usize) -> i8;
+
+    /// Approximately reconstructs the original value from its quantized form.
+    fn dequantize(&self, value: i8, dimension: usize) -> f32;
+}
+
+/// The simplest quantizer, multiplying all values by the same `scale`, truncating, and clamping
+/// to `[-100, 100]`, like the native layer.
+///
+/// The default scale matches the native layer, expecting inputs in the `[-1, 1]` range, and
+/// quantizes like [`LossyCast`] into `i8`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleQuantizer {
+    /// The multiplier applied before truncation.
+    pub scale: f32,
+}
+
+impl Default for ScaleQuantizer {
+    fn default() -> Self {
+        Self { scale: I8_DIVISOR }
+    }
+}
+
+impl Quantizer for ScaleQuantizer {
+    #[inline]
+    fn quantize(&self, value: f32, _dimension: usize) -> i8 {
+        (value * self.scale).clamp(-I8_LIMIT, I8_LIMIT) as i8
+    }
+
+    #[inline]
+    fn dequantize(&self, value: i8, _dimension: usize) -> f32 {
+        value as f32 / self.scale
+    }
+}
+
+/// Quantizes a vector into `i8` integers with the provided quantizer.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+pub fn quantize_slice<Q: Quantizer + ?Sized>(input: &[f32], quantizer: &Q, output: &mut [i8]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "Input and output slices must have the same length"
+    );
+    for (dimension, (to, from)) in output.iter_mut().zip(input.iter()).enumerate() {
+        *to = quantizer.quantize(*from, dimension);
+    }
+}
+
+/// Reconstructs a vector of floats from its quantized form with the provided quantizer.
+///
+/// # Panics
+///
+/// Panics if `input` and `output` have different lengths.
+pub fn dequantize_slice<Q: Quantizer + ?Sized>(input: &[i8], quantizer: &Q, output: &mut [f32]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "Input and output slices must have the same length"
+    );
+    for (dimension, (to, from)) in output.iter_mut().zip(input.iter()).enumerate() {
+        *to = quantizer.dequantize(*from, dimension);
+    }
+}
+
+/// Number of bytes needed to pack `bits` binary dimensions.
+pub fn packed_length(bits: usize) -> usize {
+    bits.div_ceil(8)
+}
+
+/// Packs booleans into bytes, most-significant-bit first, as expected by `ScalarKind::B1` indexes.
+pub fn pack_bits(bits: &[bool]) -> Vec<b1x8> {
+    let mut packed = vec![b1x8(0); packed_length(bits.len())];
+    for (i, &bit) in bits.iter().enumerate() {
+        if bit {
+            packed[i / 8].0 |= 128 >> (i % 8);
+        }
+    }
+    packed
+}
+
+/// Packs the signs of a vector into bytes, mapping positive values to `1`, and all others to `0`.
+/// Matches the implicit conversion applied when adding floats to a `ScalarKind::B1` index.
+pub fn pack_signs<T: Copy + PartialOrd + Default>(values: &[T]) -> Vec<b1x8> {
+    let zero = T::default();
+    let mut packed = vec![b1x8(0); packed_length(values.len())];
+    for (i, value) in values.iter().enumerate() {
+        if *value > zero {
+            packed[i / 8].0 |= 128 >> (i % 8);
+        }
+    }
+    packed
+}
+
+/// Unpacks the first `bits` bits of a packed vector into booleans.
+///
+/// # Panics
+///
+/// Panics if `packed` is shorter than `packed_length(bits)`.
+pub fn unpack_bits(packed: &[b1x8], bits: usize) -> Vec<bool> {
+    assert!(
+        packed.len() >= packed_length(bits),
+        "Packed slice is too short"
+    );
+    (0..bits)
+        .map(|i| packed[i / 8].0 & (128 >> (i % 8)) != 0)
+        .collect()
+}
+
+/// Scalar types that can be decoded into `f32` vectors and encoded back, like the native layer does.
+pub trait Scalar: Copy {
+    /// Number of scalars needed to encode a vector of the given dimensions.
+    fn encoded_length(dimensions: usize) -> usize {
+        dimensions
+    }
+
+    /// Appends the vector to `output` as `dimensions` floats.
+    fn decode(vector: &[Self], dimensions: usize, output: &mut Vec<f32>);
+
+    /// Encodes `dimensions` floats into the output slice.
+    fn encode(values: &[f32], output: &mut [Self]);
+}
+
+macro_rules! impl_lossy_scalar {
+    ($type:ty) => {
+        impl Scalar for $type {
+            fn decode(vector: &[Self], _dimensions: usize, output: &mut Vec<f32>) {
+                let start = output.len();
+                output.resize(start + vector.len(), 0.0);
+                Self::lossy_cast_slice(vector, &mut output[start..]);
+            }
+
+            fn encode(values: &[f32], output: &mut [Self]) {
+                f32::lossy_cast_slice(values, output);
+            }
+        }
+    };
+}
+
+impl_lossy_scalar!(f32);
+impl_lossy_scalar!(f64);
+impl_lossy_scalar!(f16);
+impl_lossy_scalar!(i8);
+
+impl Scalar for b1x8 {
+    fn encoded_length(dimensions: usize) -> usize {
+        dimensions.div_ceil(8)
+    }
+
+    fn decode(vector: &[Self], dimensions: usize, output: &mut Vec<f32>) {
+        output.extend(
+            unpack_bits(vector, dimensions)
+                .into_iter()
+                .map(|bit| if bit { 1.0 } else { 0.0 }),
+        );
+    }
+
+    fn encode(values: &[f32], output: &mut [Self]) {
+        for (to, from) in output.iter_mut().zip(pack_signs(values)) {
+            *to = from;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_half_precision_round_trip() {
+        for value in [0.0_f32, -0.0, 1.0, -2.5, 0.333, 65504.0, 6.1e-5, 5.96e-8] {
+            let half: f16 = value.lossy_cast();
+            let restored: f32 = half.lossy_cast();
+            assert!((restored - value).abs() <= value.abs() * 1e-3 + 1e-7);
+        }
+        assert!(f16::from_f32(1e6).to_f32().is_infinite());
+        assert!(f16::from_f32(f32::NAN).to_f32().is_nan());
+        assert_eq!(f16::from_f32(1.0).to_bits(), 0x3C00);
+    }
+
+    #[test]
+    fn test_i8_and_bit_conversions() {
+        let input = [0.5_f32, -1.0, 2.0, 0.0];
+        let converted: Vec<i8> = cast_vec_lossy(&input);
+        assert_eq!(converted, vec![50, -100, 100, 0]);
+
+        let mut quantized = [0_i8; 4];
+        quantize_slice(&input, &ScaleQuantizer { scale: 10.0 }, &mut quantized);
+        assert_eq!(quantized, [5, -10, 20, 0]);
+
+        let packed = pack_signs(&[1.0_f32, -1.0, 0.0, 3.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(packed, vec![b1x8(0b1001_0000), b1x8(0b1000_0000)]);
+        assert_eq!(
+            unpack_bits(&packed, 9),
+            vec![true, false, false, true, false, false, false, false, true]
+        );
+        assert_eq!(pack_bits(&unpack_bits(&packed, 9)), packed);
+    }
+
+    #[test]
+    fn test_vectorized_kernels_match_scalar_casts() {
+        let halves: Vec<f16> = (0..=u16::MAX).map(f16::from_bits).collect();
+        let mut widened = vec![0.0_f32; halves.len()];
+        cast_slice_lossy(&halves, &mut widened);
+        for (half, wide) in halves.iter().zip(&widened) {
+            assert_eq!(wide.to_bits(), half.to_f32().to_bits(), "{:?}", half);
+        }
+
+        // Every 257th bit pattern covers all exponents and many mantissas, with the edge cases
+        // of rounding, overflow, and underflow listed explicitly.
+        let mut floats: Vec<f32> = (0..=u32::MAX).step_by(257).map(f32::from_bits).collect();
+        floats.extend([
+            65504.0,
+            65519.99,
+            65520.0,
+            2.0_f32.powi(-25),
+            2.0_f32.powi(-24),
+            1.0,
+        ]);
+        let mut narrowed = vec![f16::default(); floats.len()];
+        cast_slice_lossy(&floats, &mut narrowed);
+        for (float, half) in floats.iter().zip(&narrowed) {
+            assert_eq!(
+                half.to_bits(),
+                f16::from_f32(*float).to_bits(),
+                "{:e}",
+                float
+            );
+        }
+
+        let quantized: Vec<i8> = cast_vec_lossy(&floats[..1000]);
+        let mut chunked = vec![0_i8; 1000];
+        cast_slice_lossy(&floats[..1000], &mut chunked);
+        assert_eq!(chunked, quantized);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_f16_matches_native_cast() {
+        use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+        let input: Vec<f32> = (0..37).map(|i| (i as f32 - 18.0) * 0.3137).collect();
+        let options = IndexOptions {
+            dimensions: input.len(),
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add(1, &input).unwrap();
+        let mut native = vec![f16::default(); input.len()];
+        index.get(1, &mut native).unwrap();
+
+        let mut converted = vec![f16::default(); input.len()];
+        cast_slice_lossy(&input, &mut converted);
+        assert_eq!(f16::to_i16s(&converted), f16::to_i16s(&native));
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_i8_matches_native_cast() {
+        use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+        let input = [0.5_f32, -1.0, 1.2, -1.27, 0.0, 0.123, -0.999, 0.01];
+        let options = IndexOptions {
+            dimensions: input.len(),
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add(1, &input).unwrap();
+        let mut native = [0_i8; 8];
+        index.get(1, &mut native).unwrap();
+
+        let converted: Vec<i8> = cast_vec_lossy(&input);
+        assert_eq!(converted, native);
+        let mut quantized = [0_i8; 8];
+        quantize_slice(&input, &ScaleQuantizer::default(), &mut quantized);
+        assert_eq!(quantized, native);
+        assert_eq!(native[2], 100);
+    }
+}
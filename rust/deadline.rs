@@ -0,0 +1,157 @@
+//! Searches bounded in time, for services with latency objectives.
+//!
+//! The cost of a graph traversal varies with the query, and a few outliers dominate the tail
+//! latency. [`Index::search_with_deadline`] hands a time budget to the native search, which
+//! checks the clock every 64 visited nodes and, once the budget runs out, stops traversing and
+//! returns the closest matches found so far, flagged as `truncated`.
+
+use std::time::Duration;
+
+use crate::ffi::DeadlineMatches;
+use crate::unwind::with_filter;
+use crate::{Index, Key, VectorType};
+
+fn budget_ns(budget: Duration) -> u64 {
+    budget.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl Index {
+    /// Performs kANN Search, stopping the traversal once the time budget runs out.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to search for.
+    /// * `budget` - The time the native search may take, measured from its start.
+    ///
+    /// # Returns
+    ///
+    /// The closest matches found in time, and whether the budget cut the search short,
+    /// in which case they may be fewer or farther than those of [`Index::search`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[0.0_f32, 1.0]).unwrap();
+    ///
+    /// let results = index
+    ///     .search_with_deadline(&[0.0_f32, 0.9], 1, Duration::from_millis(5))
+    ///     .unwrap();
+    /// if !results.truncated {
+    ///     assert_eq!(results.matches.keys, vec![1]);
+    /// }
+    /// ```
+    pub fn search_with_deadline<T: VectorType>(
+        &self,
+        query: &[T],
+        count: usize,
+        budget: Duration,
+    ) -> Result<DeadlineMatches, cxx::Exception> {
+        self.inject_fault("search")?;
+        // SAFETY: A zero filter is never called.
+        self.guard_metric(|| unsafe {
+            T::search_until(self, query, count, budget_ns(budget), 0, 0)
+        })?
+    }
+
+    /// Performs filtered kANN Search, stopping the traversal once the time budget runs out.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to search for.
+    /// * `budget` - The time the native search may take, measured from its start.
+    /// * `filter` - A closure returning `true` for the keys that may be included in the results.
+    pub fn filtered_search_with_deadline<T: VectorType, F>(
+        &self,
+        query: &[T],
+        count: usize,
+        budget: Duration,
+        filter: F,
+    ) -> Result<DeadlineMatches, cxx::Exception>
+    where
+        F: Fn(Key) -> bool,
+    {
+        self.inject_fault("search")?;
+        self.guard_metric(|| {
+            with_filter(filter, |trampoline_fn, closure_address| {
+                // SAFETY: The trampoline matches the closure, which outlives the search.
+                unsafe {
+                    T::search_until(
+                        self,
+                        query,
+                        count,
+                        budget_ns(budget),
+                        trampoline_fn,
+                        closure_address,
+                    )
+                }
+            })
+        })?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_search_with_deadline() {
+        let options = IndexOptions {
+            dimensions: 8,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            expansion_search: 512,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..5000u64 {
+            let vector: Vec<f32> = (0..8).map(|i| ((key * 31 + i) % 97) as f32).collect();
+            index.add(key, &vector).unwrap();
+        }
+        let query = [48.0_f32; 8];
+
+        let relaxed = index
+            .search_with_deadline(&query, 10, Duration::from_secs(3600))
+            .unwrap();
+        assert!(!relaxed.truncated);
+        let plain = index.search(&query, 10).unwrap();
+        assert_eq!(relaxed.matches.keys, plain.keys);
+
+        let rushed = index
+            .search_with_deadline(&query, 10, Duration::ZERO)
+            .unwrap();
+        assert!(rushed.truncated);
+        assert!(rushed.matches.keys.len() <= 10);
+
+        // The deadline doesn't leak into later searches or insertions sharing the context.
+        assert!(
+            !index
+                .search_with_deadline(&query, 10, Duration::MAX)
+                .unwrap()
+                .truncated
+        );
+        index.add(5000, &query).unwrap();
+        assert_eq!(index.search(&query, 1).unwrap().keys, vec![5000]);
+
+        let filtered = index
+            .filtered_search_with_deadline(&query, 10, Duration::from_secs(3600), |key| {
+                key % 2 == 1
+            })
+            .unwrap();
+        assert!(!filtered.truncated);
+        assert!(!filtered.matches.keys.is_empty());
+        assert!(filtered.matches.keys.iter().all(|key| key % 2 == 1));
+    }
+}
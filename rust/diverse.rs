@@ -0,0 +1,151 @@
+//! Diversified searches, trading some relevance for results unlike each other.
+//!
+//! Recommendations built from the plain nearest neighbors often repeat the same item in slight
+//! variations. [`Index::search_diverse`] over-fetches candidates and picks them one at a time
+//! with maximal marginal relevance (MMR): every pick is the candidate closest to the query,
+//! penalized by its distance to the closest candidate picked before it.
+
+use crate::convert::Scalar;
+use crate::ffi::Matches;
+use crate::metrics::distance;
+use crate::{Distance, Index, IndexError, VectorType};
+
+/// Number of nearest neighbors considered by [`Index::search_diverse`] per returned result.
+const POOL_FACTOR: usize = 4;
+
+impl Index {
+    /// Retrieves `4 * count` nearest neighbors of the query, and picks `count` of them with
+    /// maximal marginal relevance.
+    ///
+    /// Every pick minimizes `lambda * d(query, c) - (1 - lambda) * min(d(c, s))` over the
+    /// remaining candidates `c`, with `s` going over the candidates picked before. Distances
+    /// between candidates are measured on their stored vectors with the metric kind of the
+    /// index, ignoring custom metrics.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of results to return.
+    /// * `lambda` - From `0.0`, favoring diversity alone, to `1.0`, the plain nearest neighbors.
+    ///
+    /// # Returns
+    ///
+    /// The picked keys in the order they were picked, with their distances to the query, or
+    /// `IndexError::InvalidOptions` if `lambda` is outside of `[0, 1]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 1,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[1.0_f32]).unwrap();
+    /// index.add(2, &[1.1_f32]).unwrap();
+    /// index.add(3, &[3.0_f32]).unwrap();
+    ///
+    /// // The near-duplicate of the first result gives way to a different one.
+    /// assert_eq!(index.search(&[1.0_f32], 2).unwrap().keys, vec![1, 2]);
+    /// assert_eq!(index.search_diverse(&[1.0_f32], 2, 0.3).unwrap().keys, vec![1, 3]);
+    /// ```
+    pub fn search_diverse<T: VectorType + Scalar + Default + Clone>(
+        &self,
+        query: &[T],
+        count: usize,
+        lambda: f32,
+    ) -> Result<Matches, IndexError> {
+        if !(0.0..=1.0).contains(&lambda) {
+            return Err(IndexError::InvalidOptions {
+                reason: format!("The MMR lambda must be between 0 and 1, got {lambda}"),
+            });
+        }
+        let candidates = self.search(query, count * POOL_FACTOR)?;
+        let dimensions = self.dimensions();
+        let vectors: Vec<Vec<f32>> = self
+            .stored_vectors::<T>(&candidates.keys)?
+            .iter()
+            .map(|vector| {
+                let mut decoded = Vec::with_capacity(dimensions);
+                T::decode(vector, dimensions, &mut decoded);
+                decoded
+            })
+            .collect();
+        let metric = self.inner.metric_kind();
+
+        // Distance of every remaining candidate to its closest picked one, if any was picked.
+        let mut redundancy: Vec<Option<Distance>> = vec![None; vectors.len()];
+        let mut remaining: Vec<usize> = (0..vectors.len()).collect();
+        let mut picked = Matches {
+            keys: Vec::with_capacity(count),
+            distances: Vec::with_capacity(count),
+        };
+        while picked.keys.len() < count && !remaining.is_empty() {
+            let score = |candidate: usize| {
+                let relevance = lambda * candidates.distances[candidate];
+                relevance - (1.0 - lambda) * redundancy[candidate].unwrap_or(0.0)
+            };
+            let (position, &best) = remaining
+                .iter()
+                .enumerate()
+                .min_by(|(_, &a), (_, &b)| score(a).total_cmp(&score(b)))
+                .expect("candidates remain");
+            remaining.swap_remove(position);
+            picked.keys.push(candidates.keys[best]);
+            picked.distances.push(candidates.distances[best]);
+            for &candidate in &remaining {
+                let apart = distance(metric, &vectors[candidate], &vectors[best]);
+                let closest = redundancy[candidate].get_or_insert(apart);
+                *closest = closest.min(apart);
+            }
+        }
+        Ok(picked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_search_diverse() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        // A tight cluster right by the query, and three more, further away in other directions.
+        for key in 0..5u64 {
+            index.add(key, &[1.0 + key as f32 * 0.01, 0.0]).unwrap();
+        }
+        index.add(10, &[0.0_f32, 2.0]).unwrap();
+        index.add(11, &[-2.0_f32, 0.0]).unwrap();
+        index.add(12, &[0.0_f32, -2.0]).unwrap();
+        let query = [1.0_f32, 0.5];
+
+        let plain = index.search(&query, 3).unwrap();
+        let relevant = index.search_diverse(&query, 3, 1.0).unwrap();
+        assert_eq!(relevant.keys, plain.keys);
+        assert_eq!(relevant.distances, plain.distances);
+
+        let diverse = index.search_diverse(&query, 3, 0.3).unwrap();
+        assert_eq!(diverse.keys, vec![0, 11, 10]);
+        assert_eq!(diverse.distances, vec![0.25, 9.25, 3.25]);
+
+        // The distances still refer to the query, whatever the order of the picks.
+        let spread = index.search_diverse(&query, 8, 0.0).unwrap();
+        assert_eq!(spread.keys.len(), 8);
+        let plain = index.search(&query, 8).unwrap();
+        for (key, distance) in spread.keys.iter().zip(&spread.distances) {
+            let position = plain.keys.iter().position(|k| k == key).unwrap();
+            assert_eq!(plain.distances[position], *distance);
+        }
+        assert!(index.search_diverse(&query, 3, 1.5).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Snapshots encrypted at rest with AES-256-GCM.
+//!
+//! Indexes of embeddings can leak the documents they were computed from, so snapshots kept on
+//! shared object storage should be encrypted. [`Index::save_encrypted`] and its buffer sibling
+//! encrypt the serialized index with a caller-provided [`EncryptionKey`] and a random nonce, and
+//! [`Index::load_encrypted`] rejects snapshots whose header or contents were tampered with, or
+//! that were encrypted with another key. The layout is a header of the magic, the nonce and the
+//! length of the serialized index, authenticated but not encrypted, followed by the encrypted
+//! index and the 16-byte authentication tag.
+//!
+//! Encrypted snapshots can't be viewed, as searches would need the decrypted bytes in memory.
+
+#[cfg(not(no_fs))]
+use std::path::Path;
+use std::sync::PoisonError;
+
+use aes_gcm::aead::{AeadCore, AeadInPlace, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce, Tag};
+
+use crate::{checksum, Index, IndexError};
+
+/// Identifies encrypted snapshots, and the version of their layout.
+const MAGIC: &[u8; 8] = b"USENCR01";
+
+/// Bytes of the nonce drawn for every snapshot.
+const NONCE_LENGTH: usize = 12;
+
+/// Bytes of the authenticated header: the magic, the nonce, and the serialized length.
+const HEADER_LENGTH: usize = MAGIC.len() + NONCE_LENGTH + 8;
+
+/// Bytes of the authentication tag closing the snapshot.
+const TAG_LENGTH: usize = 16;
+
+/// A 256-bit key encrypting and authenticating snapshots.
+///
+/// The key bytes are left out of the `Debug` output.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    /// Wraps 32 bytes of key material, like those of a key from a secrets manager.
+    pub fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Draws a random key from the operating system.
+    pub fn generate() -> Self {
+        Self(Aes256Gcm::generate_key(OsRng).into())
+    }
+
+    /// The key material, to be stored away from the snapshots it protects.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(&self.0.into())
+    }
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
+
+fn invalid(message: String) -> IndexError {
+    IndexError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message,
+    ))
+}
+
+/// Decrypts a snapshot written by [`Index::save_encrypted_to_buffer`], after authenticating it.
+fn decrypt(key: &EncryptionKey, buffer: &[u8]) -> Result<Vec<u8>, IndexError> {
+    if buffer.len() < HEADER_LENGTH + TAG_LENGTH || !buffer.starts_with(MAGIC) {
+        let found = buffer.get(..MAGIC.len()).unwrap_or(buffer);
+        return Err(IndexError::IncompatibleFormat {
+            found: String::from_utf8_lossy(found).into_owned(),
+            expected: String::from_utf8_lossy(MAGIC).into_owned(),
+        });
+    }
+    let (header, rest) = buffer.split_at(HEADER_LENGTH);
+    let mut length = [0; 8];
+    length.copy_from_slice(&header[MAGIC.len() + NONCE_LENGTH..]);
+    let length = u64::from_le_bytes(length);
+    if length != (rest.len() - TAG_LENGTH) as u64 {
+        return Err(invalid(format!(
+            "Encrypted index holds {} bytes, but its header announces {}",
+            rest.len() - TAG_LENGTH,
+            length
+        )));
+    }
+    let (encrypted, tag) = rest.split_at(rest.len() - TAG_LENGTH);
+    let nonce = Nonce::from_slice(&header[MAGIC.len()..MAGIC.len() + NONCE_LENGTH]);
+    let mut decrypted = encrypted.to_vec();
+    key.cipher()
+        .decrypt_in_place_detached(nonce, header, &mut decrypted, Tag::from_slice(tag))
+        .map_err(|_| {
+            invalid("Encrypted index failed authentication: wrong key or tampered data".into())
+        })?;
+    Ok(decrypted)
+}
+
+impl Index {
+    /// Reports the size of the snapshot written by [`Index::save_encrypted_to_buffer`].
+    pub fn encrypted_serialized_length(&self) -> usize {
+        HEADER_LENGTH + self.serialized_length() + TAG_LENGTH
+    }
+
+    /// Serializes the index and encrypts it with the key, into the start of the buffer.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written, or `IndexError::Io` if the buffer is shorter than
+    /// [`Index::encrypted_serialized_length`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{EncryptionKey, Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.reserve(10).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    ///
+    /// let key = EncryptionKey::generate();
+    /// let mut buffer = vec![0; index.encrypted_serialized_length()];
+    /// index.save_encrypted_to_buffer(&key, &mut buffer).unwrap();
+    ///
+    /// let mut loaded = Index::new(&index.options()).unwrap();
+    /// loaded.load_encrypted_from_buffer(&key, &buffer).unwrap();
+    /// assert!(loaded.contains(1));
+    /// assert!(loaded.load_encrypted_from_buffer(&EncryptionKey::generate(), &buffer).is_err());
+    /// ```
+    pub fn save_encrypted_to_buffer(
+        &self,
+        key: &EncryptionKey,
+        buffer: &mut [u8],
+    ) -> Result<usize, IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let length = self.inner.serialized_length();
+        let total = HEADER_LENGTH + length + TAG_LENGTH;
+        if buffer.len() < total {
+            return Err(IndexError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Buffer of {} bytes can't hold the {} bytes of the encrypted index",
+                    buffer.len(),
+                    total
+                ),
+            )));
+        }
+        self.inject_fault("save")?;
+        let (header, rest) = buffer[..total].split_at_mut(HEADER_LENGTH);
+        let (serialized, tag) = rest.split_at_mut(length);
+        self.inner.save_to_buffer(serialized)?;
+        checksum::seal_buffer(serialized)?;
+
+        let nonce = Aes256Gcm::generate_nonce(OsRng);
+        header[..MAGIC.len()].copy_from_slice(MAGIC);
+        header[MAGIC.len()..MAGIC.len() + NONCE_LENGTH].copy_from_slice(&nonce);
+        header[MAGIC.len() + NONCE_LENGTH..].copy_from_slice(&(length as u64).to_le_bytes());
+        let sealed = key
+            .cipher()
+            .encrypt_in_place_detached(&nonce, header, serialized)
+            .map_err(|_| invalid(format!("Index of {} bytes is too large to encrypt", length)))?;
+        tag.copy_from_slice(&sealed);
+        Ok(total)
+    }
+
+    /// Authenticates and decrypts a snapshot written by [`Index::save_encrypted_to_buffer`],
+    /// and loads the index from it.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::IncompatibleFormat` if the buffer isn't an encrypted snapshot, and
+    /// `IndexError::Io` if it was encrypted with another key, truncated, or tampered with.
+    pub fn load_encrypted_from_buffer(
+        &mut self,
+        key: &EncryptionKey,
+        buffer: &[u8],
+    ) -> Result<(), IndexError> {
+        let decrypted = decrypt(key, buffer)?;
+        self.load_from_buffer(&decrypted)
+    }
+
+    /// Serializes the index, encrypted with the key, to a specified file.
+    #[cfg(not(no_fs))]
+    pub fn save_encrypted<P: AsRef<Path>>(
+        &self,
+        key: &EncryptionKey,
+        path: P,
+    ) -> Result<(), IndexError> {
+        let mut buffer = Vec::new();
+        loop {
+            buffer.resize(self.encrypted_serialized_length(), 0);
+            // Insertions between measuring and saving may outgrow the buffer.
+            match self.save_encrypted_to_buffer(key, &mut buffer) {
+                Ok(written) => {
+                    buffer.truncate(written);
+                    break;
+                }
+                Err(IndexError::Io(err)) if err.kind() == std::io::ErrorKind::InvalidInput => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(std::fs::write(path, buffer)?)
+    }
+
+    /// Authenticates and decrypts a file written by [`Index::save_encrypted`], and loads the
+    /// index from it.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::IncompatibleFormat` if the file isn't an encrypted snapshot, and
+    /// `IndexError::Io` if it can't be read, was encrypted with another key, or was tampered with.
+    #[cfg(not(no_fs))]
+    pub fn load_encrypted<P: AsRef<Path>>(
+        &mut self,
+        key: &EncryptionKey,
+        path: P,
+    ) -> Result<(), IndexError> {
+        let buffer = std::fs::read(path)?;
+        self.load_encrypted_from_buffer(key, &buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_encryption() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(100).unwrap();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32; 4]).unwrap();
+        }
+        let key = EncryptionKey::new([7; 32]);
+        assert_eq!(format!("{:?}", key), "EncryptionKey(..)");
+
+        let mut buffer = vec![0; index.encrypted_serialized_length() + 5];
+        let written = index.save_encrypted_to_buffer(&key, &mut buffer).unwrap();
+        assert_eq!(written, index.encrypted_serialized_length());
+        buffer.truncate(written);
+        // Neither the native header nor the vectors are readable.
+        assert!(!buffer.windows(7).any(|window| window == b"usearch"));
+        assert!(index.save_encrypted_to_buffer(&key, &mut [0; 16]).is_err());
+
+        let mut loaded = Index::new(&options).unwrap();
+        loaded.load_encrypted_from_buffer(&key, &buffer).unwrap();
+        assert_eq!(loaded.size(), 100);
+        assert_eq!(loaded.search(&[42.2_f32; 4], 1).unwrap().keys, vec![42]);
+
+        // Every snapshot draws its own nonce.
+        let mut again = vec![0; written];
+        index.save_encrypted_to_buffer(&key, &mut again).unwrap();
+        assert_ne!(again, buffer);
+
+        let wrong = EncryptionKey::new([8; 32]);
+        assert!(matches!(
+            loaded.load_encrypted_from_buffer(&wrong, &buffer),
+            Err(IndexError::Io(_))
+        ));
+        for position in [MAGIC.len(), HEADER_LENGTH + 100, written - 1] {
+            let mut tampered = buffer.clone();
+            tampered[position] ^= 1;
+            assert!(matches!(
+                loaded.load_encrypted_from_buffer(&key, &tampered),
+                Err(IndexError::Io(_))
+            ));
+        }
+        assert!(loaded
+            .load_encrypted_from_buffer(&key, &buffer[..written - 1])
+            .is_err());
+        let mut plain = vec![0; index.serialized_length()];
+        index.save_to_buffer(&mut plain).unwrap();
+        assert!(matches!(
+            loaded.load_encrypted_from_buffer(&key, &plain),
+            Err(IndexError::IncompatibleFormat { .. })
+        ));
+
+        #[cfg(not(no_fs))]
+        {
+            let path = std::env::temp_dir().join("usearch-encryption-test.usearch");
+            index.save_encrypted(&key, &path).unwrap();
+            let mut from_file = Index::new(&options).unwrap();
+            from_file.load_encrypted(&key, &path).unwrap();
+            assert_eq!(from_file.size(), 100);
+            assert!(from_file.load(&path).is_err());
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Exact nearest neighbors, by comparing queries with every vector.
+//!
+//! Measuring the recall of an approximate index needs the true neighbors of every query, which
+//! only a brute-force scan finds. [`ExactSearch`] holds vectors as plain `f32` rows and scans all
+//! of them, spreading queries across all hardware threads. With the `wgpu` feature, large scans
+//! with the `IP`, `L2sq` and `Cos` metrics run on the GPU instead, and fall back to the CPU when
+//! no adapter is available. The re-ranking stage of [`Index::search_with`] and
+//! [`Index::recall`] compute their distances the same way.
+
+use crate::ffi::{BatchMatches, Matches};
+use crate::{metrics, Distance, Index, IndexError, Key, MetricKind};
+
+/// Number of distances computed before the rows are handed to the caller, bounding the memory
+/// of a scan to 64 MiB, however many queries and vectors it covers.
+const BLOCK_DISTANCES: usize = 1 << 24;
+
+/// Number of multiply-adds below which a scan stays on the CPU, as uploading the vectors to the
+/// GPU would take longer than comparing them.
+#[cfg(feature = "wgpu")]
+const GPU_MIN_WORK: usize = 1 << 24;
+
+/// Whether large scans run on the GPU, which needs the `wgpu` feature and a GPU adapter.
+///
+/// The adapter is looked up on the first call, and kept for the lifetime of the process.
+pub fn gpu_available() -> bool {
+    #[cfg(feature = "wgpu")]
+    return crate::gpu::available();
+    #[cfg(not(feature = "wgpu"))]
+    false
+}
+
+/// Computes the distances of every query to every vector, both packed row-major, into a
+/// row-major matrix with a row per query.
+pub(crate) fn distances(
+    metric: MetricKind,
+    dimensions: usize,
+    queries: &[f32],
+    vectors: &[f32],
+) -> Vec<Distance> {
+    #[cfg(feature = "wgpu")]
+    if queries.len() * vectors.len() / dimensions.max(1) >= GPU_MIN_WORK {
+        if let Some(matrix) = crate::gpu::distances(metric, dimensions, queries, vectors) {
+            return matrix;
+        }
+    }
+    let vectors_count = vectors.len() / dimensions.max(1);
+    let mut matrix = vec![0.0; queries.len() / dimensions.max(1) * vectors_count];
+    if matrix.is_empty() {
+        return matrix;
+    }
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let rows_per_thread = (matrix.len() / vectors_count).div_ceil(threads);
+    std::thread::scope(|scope| {
+        let rows = matrix.chunks_mut(rows_per_thread * vectors_count);
+        for (rows, queries) in rows.zip(queries.chunks(rows_per_thread * dimensions)) {
+            scope.spawn(move || {
+                let rows = rows.chunks_exact_mut(vectors_count);
+                for (row, query) in rows.zip(queries.chunks_exact(dimensions)) {
+                    for (distance, vector) in row.iter_mut().zip(vectors.chunks_exact(dimensions)) {
+                        *distance = metrics::distance(metric, query, vector);
+                    }
+                }
+            });
+        }
+    });
+    matrix
+}
+
+/// Computes the distances of every query to every vector, block by block, and calls `visit`
+/// with the position of every query and its row of distances.
+pub(crate) fn scan<F>(
+    metric: MetricKind,
+    dimensions: usize,
+    queries: &[f32],
+    vectors: &[f32],
+    mut visit: F,
+) where
+    F: FnMut(usize, &[Distance]),
+{
+    let vectors_count = vectors.len() / dimensions.max(1);
+    if vectors_count == 0 {
+        for query in 0..queries.len() / dimensions.max(1) {
+            visit(query, &[]);
+        }
+        return;
+    }
+    let block = (BLOCK_DISTANCES / vectors_count).max(1);
+    for (b, queries) in queries.chunks(block * dimensions).enumerate() {
+        let matrix = distances(metric, dimensions, queries, vectors);
+        for (i, row) in matrix.chunks_exact(vectors_count).enumerate() {
+            visit(b * block + i, row);
+        }
+    }
+}
+
+/// Picks the `count` smallest distances of a row, closest first, and by key between ties.
+fn closest(row: &[Distance], keys: &[Key], count: usize) -> Vec<(Distance, Key)> {
+    let mut ranked: Vec<(Distance, Key)> = row.iter().copied().zip(keys.iter().copied()).collect();
+    let order = |a: &(Distance, Key), b: &(Distance, Key)| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1));
+    if count < ranked.len() {
+        ranked.select_nth_unstable_by(count, order);
+        ranked.truncate(count);
+    }
+    ranked.sort_unstable_by(order);
+    ranked
+}
+
+/// Vectors compared by brute force, for ground truth and small collections.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{ExactSearch, MetricKind};
+///
+/// let mut exact = ExactSearch::new(2, MetricKind::L2sq);
+/// for key in 0..100u64 {
+///     exact.add(key, &[key as f32, 0.0]).unwrap();
+/// }
+///
+/// let matches = exact.search(&[41.8, 0.0], 2).unwrap();
+/// assert_eq!(matches.keys, vec![42, 41]);
+/// let results = exact.search_many(&[0.0, 0.0, 99.0, 1.0], 1).unwrap();
+/// assert_eq!(results.keys_of(1), &[99]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExactSearch {
+    dimensions: usize,
+    metric: MetricKind,
+    keys: Vec<Key>,
+    vectors: Vec<f32>,
+}
+
+impl ExactSearch {
+    /// Creates an empty collection of vectors with the given dimensions, compared with `metric`.
+    pub fn new(dimensions: usize, metric: MetricKind) -> Self {
+        Self {
+            dimensions,
+            metric,
+            keys: Vec::new(),
+            vectors: Vec::new(),
+        }
+    }
+
+    /// Copies every vector of an index, converted to `f32`, to compare them with the metric
+    /// of the index.
+    pub fn from_index(index: &Index) -> Result<Self, IndexError> {
+        let mut exact = Self::new(index.dimensions(), index.options().metric);
+        let mut keys = index.keys();
+        keys.sort_unstable();
+        keys.dedup();
+        let mut vectors = Vec::new();
+        for key in keys {
+            let found = index.export(key, &mut vectors)?;
+            exact.keys.extend(std::iter::repeat_n(key, found));
+            exact.vectors.extend_from_slice(&vectors);
+        }
+        Ok(exact)
+    }
+
+    /// Adds a vector under a key. Keys may repeat, and are returned once per vector.
+    pub fn add(&mut self, key: Key, vector: &[f32]) -> Result<(), IndexError> {
+        if vector.len() != self.dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.dimensions,
+                got: vector.len(),
+            });
+        }
+        self.keys.push(key);
+        self.vectors.extend_from_slice(vector);
+        Ok(())
+    }
+
+    /// The number of vectors.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether there are no vectors.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The number of dimensions of the vectors.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// The metric the vectors are compared with.
+    pub fn metric(&self) -> MetricKind {
+        self.metric
+    }
+
+    /// Finds the `count` vectors closest to the query.
+    ///
+    /// # Returns
+    ///
+    /// The exact matches, closest first, or `IndexError::DimensionMismatch` if the query
+    /// doesn't have the dimensions of the vectors.
+    pub fn search(&self, query: &[f32], count: usize) -> Result<Matches, IndexError> {
+        if query.len() != self.dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.dimensions,
+                got: query.len(),
+            });
+        }
+        let row = distances(self.metric, self.dimensions, query, &self.vectors);
+        let (distances, keys) = closest(&row, &self.keys, count).into_iter().unzip();
+        Ok(Matches { keys, distances })
+    }
+
+    /// Finds the `count` vectors closest to every query packed row-major in `queries`.
+    ///
+    /// # Returns
+    ///
+    /// The exact matches of every query, in query order, or `IndexError::DimensionMismatch`
+    /// if `queries` doesn't hold a whole number of vectors.
+    pub fn search_many(&self, queries: &[f32], count: usize) -> Result<BatchMatches, IndexError> {
+        let dimensions = self.dimensions;
+        if dimensions == 0 || !queries.len().is_multiple_of(dimensions) {
+            return Err(IndexError::DimensionMismatch {
+                expected: queries.len().div_ceil(dimensions.max(1)) * dimensions,
+                got: queries.len(),
+            });
+        }
+        let queries_count = queries.len() / dimensions;
+        let mut results = BatchMatches {
+            keys: vec![0; queries_count * count],
+            distances: vec![0.0; queries_count * count],
+            counts: vec![0; queries_count],
+            count,
+        };
+        scan(
+            self.metric,
+            dimensions,
+            queries,
+            &self.vectors,
+            |query, row| {
+                let found = closest(row, &self.keys, count);
+                let start = query * count;
+                for (slot, (distance, key)) in found.iter().enumerate() {
+                    results.keys[start + slot] = *key;
+                    results.distances[start + slot] = *distance;
+                }
+                results.counts[query] = found.len();
+            },
+        );
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, ScalarKind};
+
+    #[test]
+    fn test_exact_search() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::Cos,
+            quantization: ScalarKind::F32,
+            multi: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(300).unwrap();
+        for key in 0..299u64 {
+            let angle = key as f32 / 100.0;
+            index.add(key, &[angle.cos(), angle.sin(), 0.0]).unwrap();
+        }
+        index.add(7, &[0.0_f32, 0.0, 1.0]).unwrap();
+
+        let exact = ExactSearch::from_index(&index).unwrap();
+        assert_eq!(exact.len(), 300);
+        assert_eq!(exact.metric(), MetricKind::Cos);
+        let query = [1.5_f32.cos(), 1.5_f32.sin(), 0.0];
+        let matches = exact.search(&query, 3).unwrap();
+        assert_eq!(matches.keys, vec![150, 149, 151]);
+        assert!(matches.distances[0] < 1e-6);
+        // Both vectors of a repeated key are compared.
+        let matches = exact.search(&[0.0, 0.0, 2.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![7]);
+
+        let queries: Vec<f32> = (0..50u64)
+            .flat_map(|key| {
+                let angle = (key * 6) as f32 / 100.0;
+                [angle.cos(), angle.sin(), 0.0]
+            })
+            .collect();
+        let results = exact.search_many(&queries, 2).unwrap();
+        assert_eq!(results.len(), 50);
+        for (i, query) in queries.chunks(3).enumerate() {
+            assert_eq!(results.keys_of(i)[0], i as u64 * 6);
+            assert_eq!(
+                results.matches_of(i).keys,
+                exact.search(query, 2).unwrap().keys
+            );
+        }
+
+        // Scans larger than a block hand their rows over in several batches.
+        let mut wide = ExactSearch::new(1, MetricKind::L2sq);
+        for key in 0..(BLOCK_DISTANCES / 64) as u64 {
+            wide.add(key, &[key as f32]).unwrap();
+        }
+        let queries: Vec<f32> = (0..100).map(|i| (i * 1000) as f32).collect();
+        let results = wide.search_many(&queries, 1).unwrap();
+        assert!((0..100).all(|i| results.keys_of(i) == [i as u64 * 1000]));
+
+        let empty = ExactSearch::new(3, MetricKind::IP);
+        assert!(empty.search(&[1.0, 0.0, 0.0], 5).unwrap().keys.is_empty());
+        let results = empty.search_many(&[1.0, 0.0, 0.0], 5).unwrap();
+        assert!(results.keys_of(0).is_empty());
+        assert!(matches!(
+            exact.search(&[1.0], 1),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+        assert!(exact.search_many(&[1.0, 0.0], 1).is_err());
+        let mut exact = exact;
+        assert!(exact.add(1, &[1.0, 0.0]).is_err());
+        assert!(!gpu_available() || cfg!(feature = "wgpu"));
+    }
+}
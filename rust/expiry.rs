@@ -0,0 +1,209 @@
+//! Time-to-live for the vectors of an index, as in session and embedding caches.
+//!
+//! An [`ExpiringIndex`] records a deadline for every key added with
+//! [`ExpiringIndex::add_with_ttl`]. Expired keys are filtered out of search results right away,
+//! and [`ExpiringIndex::compact_expired`] removes them from the index, so nothing has to be
+//! rebuilt. Deadlines are kept in memory, ordered by time, so finding the expired keys only
+//! visits those keys.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, IndexOptions, Key, VectorType};
+
+/// An index whose keys can expire after a time-to-live.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use usearch::expiry::ExpiringIndex;
+/// use usearch::{IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let mut cache = ExpiringIndex::new(&options).unwrap();
+/// cache.add_with_ttl(1, &[0.0_f32, 0.0], Duration::ZERO).unwrap();
+/// cache.add(2, &[1.0_f32, 1.0]).unwrap();
+///
+/// // The first key expired immediately, so it is no longer returned, and can be purged.
+/// assert_eq!(cache.search(&[0.0_f32, 0.0], 2).unwrap().keys, vec![2]);
+/// assert_eq!(cache.compact_expired().unwrap(), 1);
+/// ```
+pub struct ExpiringIndex {
+    index: Index,
+    deadlines: HashMap<Key, Instant>,
+    queue: BTreeSet<(Instant, Key)>,
+}
+
+impl ExpiringIndex {
+    /// Creates an empty index.
+    pub fn new(options: &IndexOptions) -> Result<Self, IndexError> {
+        Ok(Self::from_index(Index::new(options)?))
+    }
+
+    /// Wraps an existing index, whose present keys never expire.
+    pub fn from_index(index: Index) -> Self {
+        Self {
+            index,
+            deadlines: HashMap::new(),
+            queue: BTreeSet::new(),
+        }
+    }
+
+    /// The underlying index, which still contains expired keys until they are compacted.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Unwraps the underlying index, dropping the deadlines.
+    pub fn into_index(self) -> Index {
+        self.index
+    }
+
+    /// The deadline of a key, if it has one.
+    pub fn expires_at(&self, key: Key) -> Option<Instant> {
+        self.deadlines.get(&key).copied()
+    }
+
+    /// Checks if a key has expired. Keys without a deadline never expire.
+    pub fn is_expired(&self, key: Key) -> bool {
+        self.expires_at(key)
+            .is_some_and(|deadline| deadline <= Instant::now())
+    }
+
+    /// Number of keys with a deadline, expired or not.
+    pub fn expiring(&self) -> usize {
+        self.deadlines.len()
+    }
+
+    fn clear_deadline(&mut self, key: Key) {
+        if let Some(deadline) = self.deadlines.remove(&key) {
+            self.queue.remove(&(deadline, key));
+        }
+    }
+
+    /// Adds a vector that never expires, clearing any previous deadline of the key.
+    pub fn add<T: VectorType>(&mut self, key: Key, vector: &[T]) -> Result<(), IndexError> {
+        self.index.add(key, vector)?;
+        self.clear_deadline(key);
+        Ok(())
+    }
+
+    /// Adds a vector that expires after `ttl`, replacing any previous deadline of the key.
+    ///
+    /// A `ttl` too long to be represented as an [`Instant`], like [`Duration::MAX`], never expires.
+    pub fn add_with_ttl<T: VectorType>(
+        &mut self,
+        key: Key,
+        vector: &[T],
+        ttl: Duration,
+    ) -> Result<(), IndexError> {
+        self.index.add(key, vector)?;
+        self.clear_deadline(key);
+        if let Some(deadline) = Instant::now().checked_add(ttl) {
+            self.deadlines.insert(key, deadline);
+            self.queue.insert((deadline, key));
+        }
+        Ok(())
+    }
+
+    /// Removes the vectors stored under a key, along with its deadline.
+    pub fn remove(&mut self, key: Key) -> Result<usize, IndexError> {
+        self.clear_deadline(key);
+        Ok(self.index.remove(key)?)
+    }
+
+    /// Searches for the nearest neighbors of the query, skipping expired keys.
+    pub fn search<T: VectorType>(&self, query: &[T], count: usize) -> Result<Matches, IndexError> {
+        let now = Instant::now();
+        let any_expired = self
+            .queue
+            .first()
+            .is_some_and(|&(deadline, _)| deadline <= now);
+        let matches = if any_expired {
+            self.index.filtered_search(query, count, |key| {
+                self.deadlines
+                    .get(&key)
+                    .is_none_or(|&deadline| deadline > now)
+            })?
+        } else {
+            self.index.search(query, count)?
+        };
+        Ok(matches)
+    }
+
+    /// Removes all expired keys from the index.
+    ///
+    /// # Returns
+    ///
+    /// The number of removed keys.
+    pub fn compact_expired(&mut self) -> Result<usize, IndexError> {
+        let now = Instant::now();
+        let mut removed = 0;
+        while let Some(&(deadline, key)) = self.queue.first() {
+            if deadline > now {
+                break;
+            }
+            self.index.remove(key)?;
+            self.queue.pop_first();
+            self.deadlines.remove(&key);
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetricKind, ScalarKind};
+
+    #[test]
+    fn test_expiring_index() {
+        let options = IndexOptions {
+            dimensions: 1,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let mut cache = ExpiringIndex::new(&options).unwrap();
+        for key in 0..10u64 {
+            let ttl = if key % 2 == 0 {
+                Duration::ZERO
+            } else {
+                Duration::from_secs(3600)
+            };
+            cache.add_with_ttl(key, &[key as f32], ttl).unwrap();
+        }
+        cache.add(10, &[10.0_f32]).unwrap();
+        // Refreshing a key without a TTL makes it permanent.
+        cache.add_with_ttl(11, &[11.0_f32], Duration::ZERO).unwrap();
+        cache.remove(11).unwrap();
+        cache.add(11, &[11.0_f32]).unwrap();
+
+        assert!(cache.is_expired(4));
+        assert!(!cache.is_expired(5));
+        assert!(cache.expires_at(10).is_none());
+        let matches = cache.search(&[4.0_f32], 3).unwrap();
+        assert!(!matches.keys.is_empty());
+        assert!(matches.keys.iter().all(|key| key % 2 == 1 || *key >= 10));
+
+        assert_eq!(cache.compact_expired().unwrap(), 5);
+        assert_eq!(cache.index().size(), 7);
+        assert_eq!(cache.expiring(), 5);
+        assert_eq!(cache.compact_expired().unwrap(), 0);
+        assert_eq!(cache.search(&[4.0_f32], 2).unwrap().keys.len(), 2);
+
+        // Overflowing deadlines never expire.
+        cache.add_with_ttl(12, &[12.0_f32], Duration::MAX).unwrap();
+        assert!(cache.expires_at(12).is_none());
+        assert!(!cache.is_expired(12));
+        assert_eq!(cache.expiring(), 5);
+    }
+}
@@ -0,0 +1,225 @@
+//! Handing indexes over to serving infrastructure built on hnswlib.
+//!
+//! Both libraries build the same kind of hierarchical graph, so [`Index::export_hnswlib`]
+//! writes the native graph as is, level by level, in the layout of hnswlib's `saveIndex`:
+//! every slot becomes an element with the same neighbors and `f32` copies of its vector,
+//! and removed slots become elements marked as deleted.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::PoisonError;
+
+use crate::import::HNSWLIB_DELETE_MARK;
+use crate::{Index, IndexError, Key, MetricKind};
+
+/// hnswlib stores neighbor lists as a 32-bit header, followed by 32-bit element identifiers.
+const HNSWLIB_LIST_HEADER: usize = 4;
+
+impl Index {
+    /// Writes the index as an hnswlib file, loadable with `loadIndex` in the space matching the
+    /// metric: `l2` for `L2sq`, `ip` for `IP`, and `cosine` for `Cos`, whose vectors are
+    /// normalized, as hnswlib expects. Removed vectors are exported as deleted elements with
+    /// zeroed data, so compacting the index first keeps the file smaller.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` for other metrics and for multi-vector indexes, as hnswlib
+    /// supports neither.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use usearch::{Index, IndexOptions, MetricKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(42, &[0.2_f32, 0.1]).unwrap();
+    /// index.export_hnswlib("index.bin").unwrap();
+    /// ```
+    pub fn export_hnswlib<P: AsRef<Path>>(&self, path: P) -> Result<(), IndexError> {
+        let metric = self.inner.metric_kind();
+        if !matches!(metric, MetricKind::L2sq | MetricKind::IP | MetricKind::Cos) {
+            return Err(IndexError::InvalidOptions {
+                reason: format!("hnswlib has no space for the {metric:?} metric"),
+            });
+        }
+        if self.inner.multi() {
+            return Err(IndexError::InvalidOptions {
+                reason: "hnswlib stores a single vector per label".to_owned(),
+            });
+        }
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let slots = self.inner.slots();
+        if u32::try_from(slots).is_err() {
+            return Err(IndexError::InvalidOptions {
+                reason: format!("hnswlib addresses at most 2^32 elements, not {slots}"),
+            });
+        }
+
+        // hnswlib lists have a fixed capacity, which must fit the longest native ones.
+        let mut levels = Vec::with_capacity(slots);
+        let connectivity = self.connectivity();
+        let (mut base_neighbors, mut upper_neighbors) = (connectivity * 2, connectivity);
+        for slot in 0..slots {
+            let level = self.inner.slot_level(slot)?;
+            for l in 0..=level {
+                let neighbors = self.inner.slot_neighbors(slot, l)?.len();
+                match l {
+                    0 => base_neighbors = base_neighbors.max(neighbors),
+                    _ => upper_neighbors = upper_neighbors.max(neighbors),
+                }
+            }
+            levels.push(level);
+        }
+
+        let dimensions = self.dimensions();
+        let base_links = HNSWLIB_LIST_HEADER + 4 * base_neighbors;
+        let upper_links = HNSWLIB_LIST_HEADER + 4 * upper_neighbors;
+        let data_size = 4 * dimensions;
+        let element_size = base_links + data_size + std::mem::size_of::<Key>();
+        let level_factor = 1.0 / (connectivity.max(2) as f64).ln();
+
+        let mut writer = BufWriter::new(File::create(path)?);
+        for value in [
+            0,
+            slots,
+            slots,
+            element_size,
+            base_links + data_size,
+            base_links,
+        ] {
+            writer.write_all(&(value as u64).to_le_bytes())?;
+        }
+        writer.write_all(&(self.inner.max_level() as i32).to_le_bytes())?;
+        writer.write_all(&(self.inner.entry_slot() as u32).to_le_bytes())?;
+        for value in [upper_neighbors, base_neighbors, connectivity] {
+            writer.write_all(&(value as u64).to_le_bytes())?;
+        }
+        writer.write_all(&level_factor.to_le_bytes())?;
+        writer.write_all(&(self.expansion_add() as u64).to_le_bytes())?;
+
+        let free_key = self.inner.free_key();
+        let mut vector = vec![0.0_f32; dimensions];
+        let mut element = Vec::with_capacity(element_size);
+        for slot in 0..slots {
+            let key = self.inner.slot_key(slot)?;
+            let removed = key == free_key;
+            element.clear();
+            let flag = if removed { HNSWLIB_DELETE_MARK } else { 0 };
+            write_list(
+                &mut element,
+                &self.inner.slot_neighbors(slot, 0)?,
+                base_links,
+                flag,
+            );
+
+            vector.fill(0.0);
+            if !removed {
+                self.inner.get_f32(key, &mut vector)?;
+            }
+            if metric == MetricKind::Cos {
+                let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+                if norm > 0.0 {
+                    vector.iter_mut().for_each(|x| *x /= norm);
+                }
+            }
+            for value in &vector {
+                element.extend_from_slice(&value.to_le_bytes());
+            }
+            element.extend_from_slice(&key.to_le_bytes());
+            writer.write_all(&element)?;
+        }
+
+        // The upper levels follow the base one, prefixed by their length in bytes.
+        for (slot, &level) in levels.iter().enumerate() {
+            writer.write_all(&((upper_links * level) as u32).to_le_bytes())?;
+            element.clear();
+            for l in 1..=level {
+                write_list(
+                    &mut element,
+                    &self.inner.slot_neighbors(slot, l)?,
+                    upper_links,
+                    0,
+                );
+            }
+            writer.write_all(&element)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Appends a neighbor list, padded to the fixed length of the level.
+fn write_list(element: &mut Vec<u8>, neighbors: &[u64], length: usize, flag: u8) {
+    let start = element.len();
+    element.extend_from_slice(&(neighbors.len() as u16).to_le_bytes());
+    element.extend_from_slice(&[flag, 0]);
+    for &neighbor in neighbors {
+        element.extend_from_slice(&(neighbor as u32).to_le_bytes());
+    }
+    element.resize(start + length, 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, ScalarKind};
+
+    #[test]
+    fn test_export_hnswlib() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 4,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..200u64 {
+            index.add(key, &[key as f32, (key % 7) as f32]).unwrap();
+        }
+        index.remove(10).unwrap();
+        let path = std::env::temp_dir().join("usearch-export.hnswlib");
+        index.export_hnswlib(&path).unwrap();
+
+        // Every element keeps its neighbors, and the entry point reaches the top level.
+        let file = std::fs::read(&path).unwrap();
+        let read_u64 = |at: usize| u64::from_le_bytes(file[at..at + 8].try_into().unwrap());
+        let (element_size, base_links) = (read_u64(24) as usize, read_u64(40) as usize);
+        assert_eq!((read_u64(8), read_u64(16)), (200, 200));
+        assert_eq!(read_u64(64), 8);
+        let entry = u32::from_le_bytes(file[52..56].try_into().unwrap()) as usize;
+        assert_eq!(
+            index.inner.slot_level(entry).unwrap(),
+            index.inner.max_level()
+        );
+        for slot in [0, 57, 199] {
+            let element = &file[96 + slot * element_size..];
+            let count = u16::from_le_bytes(element[..2].try_into().unwrap()) as usize;
+            let neighbors: Vec<u64> = element[4..4 + 4 * count]
+                .chunks_exact(4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()) as u64)
+                .collect();
+            assert_eq!(neighbors, index.inner.slot_neighbors(slot, 0).unwrap());
+            assert!(4 + 4 * count <= base_links);
+        }
+
+        let imported = Index::import_hnswlib(&path, MetricKind::L2sq).unwrap();
+        assert_eq!(imported.size(), 199);
+        assert!(!imported.contains(10));
+        assert_eq!(imported.search(&[57.0_f32, 1.0], 1).unwrap().keys, vec![57]);
+        std::fs::remove_file(&path).unwrap();
+
+        let multi = Index::new(&IndexOptions {
+            multi: true,
+            ..options.clone()
+        })
+        .unwrap();
+        assert!(multi.export_hnswlib(&path).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Building indexes from iterators of embeddings.
+//!
+//! Embeddings usually come out of a pipeline as `(key, vector)` pairs. [`Index::from_iter_with`]
+//! and the [`Extend`] implementation of [`Index`] consume such iterators in chunks, reserving
+//! capacity from their size hints, and insert every chunk in parallel, one slice of it per
+//! hardware thread.
+
+use crate::parallel::in_parallel;
+use crate::{Index, IndexError, IndexOptions, Key, VectorType};
+
+/// Number of pairs buffered from the iterator before inserting them in parallel.
+const CHUNK_SIZE: usize = 4096;
+
+impl Index {
+    /// Creates an index and inserts every `(key, vector)` pair of the iterator.
+    ///
+    /// # Returns
+    ///
+    /// The new index, or the first error, like a vector not matching the dimensions of the
+    /// options, in which case the pairs of the failing chunk may be partially inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let embeddings = (0..1000u64).map(|key| (key, vec![key as f32, 0.0]));
+    /// let mut index = Index::from_iter_with(&options, embeddings).unwrap();
+    /// assert_eq!(index.size(), 1000);
+    ///
+    /// index.extend([(1000, vec![-1.0_f32, 0.0])]);
+    /// assert_eq!(index.search(&[-2.0_f32, 0.0], 1).unwrap().keys, vec![1000]);
+    /// ```
+    pub fn from_iter_with<T, I>(options: &IndexOptions, iter: I) -> Result<Index, IndexError>
+    where
+        T: VectorType + Sync,
+        I: IntoIterator<Item = (Key, Vec<T>)>,
+    {
+        let index = Index::new(options)?;
+        index.add_iter(iter)?;
+        Ok(index)
+    }
+
+    /// Inserts the pairs of the iterator chunk by chunk, reserving capacity ahead of every chunk.
+    fn add_iter<T, I>(&self, iter: I) -> Result<(), IndexError>
+    where
+        T: VectorType + Sync,
+        I: IntoIterator<Item = (Key, Vec<T>)>,
+    {
+        let mut iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        if lower > 0 {
+            self.reserve(self.size() + lower)?;
+        }
+        let dimensions = self.dimensions();
+        let mut chunk: Vec<(Key, Vec<T>)> = Vec::with_capacity(CHUNK_SIZE.min(lower.max(1)));
+        loop {
+            chunk.clear();
+            chunk.extend(iter.by_ref().take(CHUNK_SIZE));
+            if chunk.is_empty() {
+                return Ok(());
+            }
+            if let Some((_, vector)) = chunk.iter().find(|(_, v)| v.len() != dimensions) {
+                return Err(IndexError::DimensionMismatch {
+                    expected: dimensions,
+                    got: vector.len(),
+                });
+            }
+            if self.size() + chunk.len() > self.capacity() {
+                self.reserve((self.size() + chunk.len()).max(self.capacity() * 2))?;
+            }
+            in_parallel(self, &chunk, |index, (key, vector)| index.add(*key, vector))?;
+        }
+    }
+}
+
+/// Inserts every `(key, vector)` pair, in parallel chunks, like [`Index::from_iter_with`].
+///
+/// # Panics
+///
+/// If a pair can't be inserted, like a vector not matching the dimensions of the index, as
+/// [`Extend`] can't report errors. Use [`Index::add`] to handle them.
+impl<T: VectorType + Sync> Extend<(Key, Vec<T>)> for Index {
+    fn extend<I: IntoIterator<Item = (Key, Vec<T>)>>(&mut self, iter: I) {
+        if let Err(err) = self.add_iter(iter) {
+            panic!("Failed to extend the index: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{f16, Index, IndexOptions, Key, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_from_iter_with() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            ..Default::default()
+        };
+        // Filtering hides the length from the size hint, so capacity grows along the way.
+        let embeddings = (0..10_000u64)
+            .filter(|key| key % 2 == 0)
+            .map(|key| (key, vec![key as f32, 1.0, -1.0]));
+        let mut index = Index::from_iter_with(&options, embeddings).unwrap();
+        assert_eq!(index.size(), 5000);
+        let mut vector = [f16::default(); 3];
+        index.get(4242, &mut vector).unwrap();
+        assert_eq!(vector, [4242.0, 1.0, -1.0].map(f16::from_f32));
+
+        let more: Vec<(Key, Vec<f32>)> = (1..100u64)
+            .step_by(2)
+            .map(|key| (key, vec![key as f32, 1.0, -1.0]))
+            .collect();
+        index.extend(more);
+        assert_eq!(index.size(), 5050);
+        assert!(index.capacity() >= 5050);
+        let matches = index.search(&[41.0_f32, 1.0, -1.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![41]);
+
+        let invalid = [(1, vec![0.0_f32; 3]), (2, vec![0.0_f32; 2])];
+        assert!(Index::from_iter_with(&options, invalid).is_err());
+        let extended = std::panic::catch_unwind(move || {
+            index.extend([(20_000, vec![0.0_f32; 4])]);
+        });
+        assert!(extended.is_err());
+    }
+}
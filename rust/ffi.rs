@@ -0,0 +1,123 @@
+//! Plain-Rust definitions of the structs and enums shared with the native layer.
+//!
+//! Compiled instead of the `cxx` bridge when the `native` feature is disabled. The shapes match
+//! the code generated by `cxx`, where enums are transparent structs with one associated constant
+//! per variant, so [`crate::pure`] and the rest of the crate compile unchanged against either.
+
+#![allow(non_upper_case_globals)]
+
+/// The metric kind used to differentiate built-in distance functions.
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct MetricKind {
+    pub repr: i32,
+}
+
+impl MetricKind {
+    pub const Unknown: Self = MetricKind { repr: 0 };
+    /// The Inner Product metric, defined as `IP = 1 - sum(a[i] * b[i])`.
+    pub const IP: Self = MetricKind { repr: 1 };
+    /// The squared Euclidean Distance metric, defined as `L2 = sum((a[i] - b[i])^2)`.
+    pub const L2sq: Self = MetricKind { repr: 2 };
+    /// The Cosine Similarity metric, defined as `Cos = 1 - sum(a[i] * b[i]) / (sqrt(sum(a[i]^2) * sqrt(sum(b[i]^2)))`.
+    pub const Cos: Self = MetricKind { repr: 3 };
+    /// The Pearson Correlation metric.
+    pub const Pearson: Self = MetricKind { repr: 4 };
+    /// The Haversine (Great Circle) Distance metric.
+    pub const Haversine: Self = MetricKind { repr: 5 };
+    /// The Jensen Shannon Divergence metric.
+    pub const Divergence: Self = MetricKind { repr: 6 };
+    /// The bit-level Hamming Distance metric, defined as the number of differing bits.
+    pub const Hamming: Self = MetricKind { repr: 7 };
+    /// The bit-level Tanimoto (Jaccard) metric, defined as the number of intersecting bits divided by the number of union bits.
+    pub const Tanimoto: Self = MetricKind { repr: 8 };
+    /// The bit-level Sorensen metric.
+    pub const Sorensen: Self = MetricKind { repr: 9 };
+}
+
+impl std::fmt::Debug for MetricKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            MetricKind::Unknown => "Unknown",
+            MetricKind::IP => "IP",
+            MetricKind::L2sq => "L2sq",
+            MetricKind::Cos => "Cos",
+            MetricKind::Pearson => "Pearson",
+            MetricKind::Haversine => "Haversine",
+            MetricKind::Divergence => "Divergence",
+            MetricKind::Hamming => "Hamming",
+            MetricKind::Tanimoto => "Tanimoto",
+            MetricKind::Sorensen => "Sorensen",
+            _ => return write!(f, "{}", self.repr),
+        };
+        f.write_str(name)
+    }
+}
+
+/// The scalar kind used to differentiate built-in vector element types.
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct ScalarKind {
+    pub repr: i32,
+}
+
+impl ScalarKind {
+    pub const Unknown: Self = ScalarKind { repr: 0 };
+    /// 64-bit double-precision IEEE 754 floating-point number.
+    pub const F64: Self = ScalarKind { repr: 1 };
+    /// 32-bit single-precision IEEE 754 floating-point number.
+    pub const F32: Self = ScalarKind { repr: 2 };
+    /// 16-bit half-precision IEEE 754 floating-point number (different from `bf16`).
+    pub const F16: Self = ScalarKind { repr: 3 };
+    /// 8-bit signed integer.
+    pub const I8: Self = ScalarKind { repr: 4 };
+    /// 1-bit binary value, packed 8 per byte.
+    pub const B1: Self = ScalarKind { repr: 5 };
+}
+
+impl std::fmt::Debug for ScalarKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match *self {
+            ScalarKind::Unknown => "Unknown",
+            ScalarKind::F64 => "F64",
+            ScalarKind::F32 => "F32",
+            ScalarKind::F16 => "F16",
+            ScalarKind::I8 => "I8",
+            ScalarKind::B1 => "B1",
+            _ => return write!(f, "{}", self.repr),
+        };
+        f.write_str(name)
+    }
+}
+
+/// The resulting matches from a search operation.
+/// It contains the keys and distances of the closest vectors.
+#[derive(Debug, Clone)]
+pub struct Matches {
+    pub keys: Vec<u64>,
+    pub distances: Vec<f32>,
+}
+
+/// The index options used to configure the dense index during creation.
+/// It contains the number of dimensions, the metric kind, the scalar kind,
+/// the connectivity, the expansion values, the multi-flag, and the parameters
+/// drawing the levels of new nodes.
+#[derive(Debug, PartialEq)]
+pub struct IndexOptions {
+    pub dimensions: usize,
+    pub metric: MetricKind,
+    pub quantization: ScalarKind,
+    pub connectivity: usize,
+    pub expansion_add: usize,
+    pub expansion_search: usize,
+    pub multi: bool,
+    /// Multiplier of the random levels of new nodes, `mL` in the paper.
+    /// Zero stands for `1 / ln(connectivity)`, and larger values make taller graphs.
+    pub level_multiplier: f64,
+    /// Seed of the generators drawing the levels of new nodes, one per thread.
+    pub level_seed: u64,
+    /// Whether vectors are scaled to a unit L2 norm when added, and queries when searched.
+    pub auto_normalize: bool,
+    /// Whether the memory of the index is locked in RAM.
+    pub lock_memory: bool,
+}
@@ -0,0 +1,232 @@
+//! A serve-only index type, shareable across threads.
+//!
+//! Most deployments build an index offline and only query it in production. [`FrozenIndex`]
+//! encodes that mode in the type system: it has no `add`, `remove`, `rename`, or `reset`,
+//! and is `Send` and `Sync`, so it can be put in an `Arc` and queried from any thread.
+//! Concurrent queries beyond the search contexts of the native index wait for one to be
+//! released, like they do on [`Index`].
+
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, Key, VectorType};
+
+/// Read-only delegators to the wrapped `index`, shared by [`FrozenIndex`] and
+/// [`crate::reader::IndexReader`].
+macro_rules! read_accessors {
+    () => {
+        /// Performs k-Approximate Nearest Neighbors (kANN) Search, like [`Index::search`].
+        pub fn search<T: VectorType>(
+            &self,
+            query: &[T],
+            count: usize,
+        ) -> Result<Matches, IndexError> {
+            self.index.search(query, count)
+        }
+
+        /// Performs filtered kANN Search, like [`Index::filtered_search`].
+        pub fn filtered_search<T: VectorType, F>(
+            &self,
+            query: &[T],
+            count: usize,
+            filter: F,
+        ) -> Result<Matches, IndexError>
+        where
+            F: Fn(Key) -> bool,
+        {
+            self.index.filtered_search(query, count, filter)
+        }
+
+        /// Extracts the vectors of a key into a slice, like [`Index::get`].
+        pub fn get<T: VectorType>(
+            &self,
+            key: Key,
+            vector: &mut [T],
+        ) -> Result<usize, cxx::Exception> {
+            self.index.get(key, vector)
+        }
+
+        /// Extracts the vectors of a key into a resizable vector, like [`Index::export`].
+        pub fn export<T: VectorType + Default + Clone>(
+            &self,
+            key: Key,
+            vector: &mut Vec<T>,
+        ) -> Result<usize, cxx::Exception> {
+            self.index.export(key, vector)
+        }
+
+        /// Checks if the index contains a vector with the specified key.
+        pub fn contains(&self, key: Key) -> bool {
+            self.index.contains(key)
+        }
+
+        /// Number of vectors stored under the specified key.
+        pub fn count(&self, key: Key) -> usize {
+            self.index.count(key)
+        }
+
+        /// Collects the keys of all vectors, in no particular order.
+        pub fn keys(&self) -> Vec<Key> {
+            self.index.keys()
+        }
+
+        /// Number of vectors in the index.
+        pub fn size(&self) -> usize {
+            self.index.size()
+        }
+
+        /// Number of dimensions of every vector.
+        pub fn dimensions(&self) -> usize {
+            self.index.dimensions()
+        }
+
+        /// The connectivity of the graph.
+        pub fn connectivity(&self) -> usize {
+            self.index.connectivity()
+        }
+
+        /// The expansion factor used during search.
+        pub fn expansion_search(&self) -> usize {
+            self.index.expansion_search()
+        }
+
+        /// A lower bound on the memory consumed by the index, in bytes.
+        pub fn memory_usage(&self) -> usize {
+            self.index.memory_usage()
+        }
+    };
+}
+
+pub(crate) use read_accessors;
+
+/// An immutable index, safe to share between threads.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let index = Index::new(&options).unwrap();
+/// index.add(1, &[0.0_f32, 1.0]).unwrap();
+/// index.add(2, &[1.0_f32, 0.0]).unwrap();
+///
+/// let frozen = Arc::new(index.freeze());
+/// let handle = {
+///     let frozen = Arc::clone(&frozen);
+///     std::thread::spawn(move || frozen.search(&[0.9_f32, 0.0], 1).unwrap().keys)
+/// };
+/// assert_eq!(handle.join().unwrap(), vec![2]);
+/// ```
+pub struct FrozenIndex {
+    index: Index,
+}
+
+impl Index {
+    /// Converts the index into a read-only one, which can be shared between threads.
+    pub fn freeze(self) -> FrozenIndex {
+        FrozenIndex { index: self }
+    }
+}
+
+impl FrozenIndex {
+    /// Loads an index from a file, configured from its header, like [`Index::open`].
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, IndexError> {
+        Ok(Index::open(path)?.freeze())
+    }
+
+    /// Memory-maps an index from a file, configured from its header, like [`Index::open_view`].
+    #[cfg(not(no_fs))]
+    pub fn view<P: AsRef<Path>>(path: P) -> Result<Self, IndexError> {
+        Ok(Index::open_view(path)?.freeze())
+    }
+
+    /// Converts back into a mutable index.
+    pub fn thaw(self) -> Index {
+        self.index
+    }
+
+    read_accessors!();
+
+    /// The name of the SIMD capability used for distance computations.
+    pub fn hardware_acceleration(&self) -> String {
+        self.index.hardware_acceleration()
+    }
+
+    /// Expected size of the serialized index, in bytes.
+    pub fn serialized_length(&self) -> usize {
+        self.index.serialized_length()
+    }
+
+    /// Saves the index to a file.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), IndexError> {
+        self.index.save(path)
+    }
+
+    /// Saves the index into a buffer of at least [`FrozenIndex::serialized_length`] bytes.
+    pub fn save_to_buffer(&self, buffer: &mut [u8]) -> Result<(), IndexError> {
+        self.index.save_to_buffer(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_frozen_index_across_threads() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..200u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+        let path = std::env::temp_dir().join("usearch-frozen.usearch");
+        index.save(&path).unwrap();
+
+        // More threads than contexts, which must wait for each other.
+        let frozen = Arc::new(index.freeze());
+        let handles: Vec<_> = (0..8u64)
+            .map(|thread| {
+                let frozen = Arc::clone(&frozen);
+                std::thread::spawn(move || {
+                    for key in (thread..200).step_by(8) {
+                        let matches = frozen.search(&[key as f32, 0.0], 1).unwrap();
+                        assert_eq!(matches.keys, vec![key]);
+                        let mut vector: Vec<f32> = Vec::new();
+                        frozen.export(key, &mut vector).unwrap();
+                        assert_eq!(vector, vec![key as f32, 0.0]);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let viewed = FrozenIndex::view(&path).unwrap();
+        assert_eq!(viewed.size(), 200);
+        assert_eq!(viewed.search(&[7.2_f32, 0.0], 1).unwrap().keys, vec![7]);
+        let loaded = FrozenIndex::load(&path).unwrap();
+        let index = loaded.thaw();
+        index.add(200, &[200.0_f32, 0.0]).unwrap();
+        assert_eq!(index.size(), 201);
+        drop(viewed);
+        std::fs::remove_file(path).unwrap();
+    }
+}
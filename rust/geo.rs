@@ -0,0 +1,190 @@
+//! Geographic searches over the `Haversine` metric, with distances in familiar units.
+//!
+//! The native `Haversine` metric returns central angles in radians as `f32`, which rounds
+//! distances on Earth to about a meter and leaves the conversion to every caller.
+//! [`Index::search_geo`] takes latitude and longitude in degrees as `f64`, re-measures the
+//! candidates from their stored coordinates in `f64`, and reports them in a [`DistanceUnit`].
+//! Storing coordinates with `ScalarKind::F64` keeps them exact to the nanodegree.
+
+use crate::{Index, IndexError, Key, MetricKind};
+
+/// The mean radius of the Earth, in kilometers, as defined by the IUGG.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Units of distances along the surface of the Earth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceUnit {
+    /// The central angle, as returned by the native metric.
+    Radians,
+    /// Kilometers, on a sphere of mean Earth radius.
+    #[default]
+    Kilometers,
+    /// Meters, on a sphere of mean Earth radius.
+    Meters,
+    /// International miles, on a sphere of mean Earth radius.
+    Miles,
+    /// Nautical miles, on a sphere of mean Earth radius.
+    NauticalMiles,
+}
+
+impl DistanceUnit {
+    /// The length of one radian of central angle, in this unit.
+    pub fn per_radian(self) -> f64 {
+        match self {
+            DistanceUnit::Radians => 1.0,
+            DistanceUnit::Kilometers => EARTH_RADIUS_KM,
+            DistanceUnit::Meters => EARTH_RADIUS_KM * 1000.0,
+            DistanceUnit::Miles => EARTH_RADIUS_KM / 1.609344,
+            DistanceUnit::NauticalMiles => EARTH_RADIUS_KM / 1.852,
+        }
+    }
+
+    /// Converts a central angle in radians into this unit.
+    pub fn from_radians(self, radians: f64) -> f64 {
+        radians * self.per_radian()
+    }
+
+    /// Converts a distance in this unit into a central angle in radians.
+    pub fn to_radians(self, distance: f64) -> f64 {
+        distance / self.per_radian()
+    }
+}
+
+/// The great-circle distance between two points, given as latitude and longitude in degrees,
+/// as a central angle in radians. Matches the native `Haversine` metric, in `f64`.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::geo::{haversine, DistanceUnit};
+///
+/// let paris = [48.8566, 2.3522];
+/// let london = [51.5074, -0.1278];
+/// let kilometers = DistanceUnit::Kilometers.from_radians(haversine(paris, london));
+/// assert!((kilometers - 343.6).abs() < 0.5);
+/// ```
+pub fn haversine(a: [f64; 2], b: [f64; 2]) -> f64 {
+    let (lat_a, lon_a, lat_b, lon_b) = (a[0], a[1], b[0], b[1]);
+    let lat_delta = (lat_b - lat_a).to_radians() / 2.0;
+    let lon_delta = (lon_b - lon_a).to_radians() / 2.0;
+    let x = lat_delta.sin().powi(2)
+        + lat_a.to_radians().cos() * lat_b.to_radians().cos() * lon_delta.sin().powi(2);
+    2.0 * x.sqrt().asin()
+}
+
+impl Index {
+    /// Searches for the places closest to a point, in an index using the `Haversine` metric.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The latitude and longitude of the query, in degrees.
+    /// * `count` - The maximum number of places to return.
+    /// * `unit` - The unit of the returned distances.
+    ///
+    /// # Returns
+    ///
+    /// The keys and distances of the closest places, nearest first, with distances computed in
+    /// `f64` from the stored coordinates. For keys with several vectors, the closest one counts.
+    /// `IndexError::InvalidOptions` if the index uses another metric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::geo::DistanceUnit;
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::Haversine,
+    ///     quantization: ScalarKind::F64,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.reserve(2).unwrap();
+    /// index.add(1, &[48.8566_f64, 2.3522]).unwrap(); // Paris
+    /// index.add(2, &[52.5200_f64, 13.4050]).unwrap(); // Berlin
+    ///
+    /// let london = [51.5074, -0.1278];
+    /// let nearest = index.search_geo(london, 1, DistanceUnit::Miles).unwrap();
+    /// assert_eq!(nearest[0].0, 1);
+    /// assert!((nearest[0].1 - 213.5).abs() < 0.5);
+    /// ```
+    pub fn search_geo(
+        &self,
+        point: [f64; 2],
+        count: usize,
+        unit: DistanceUnit,
+    ) -> Result<Vec<(Key, f64)>, IndexError> {
+        let metric = self.inner.metric_kind();
+        if metric != MetricKind::Haversine {
+            return Err(IndexError::InvalidOptions {
+                reason: format!("Geographic searches require the Haversine metric, not {metric:?}"),
+            });
+        }
+
+        let matches = self.search(&point, count)?;
+        let mut keys = matches.keys;
+        keys.dedup();
+        let mut coordinates: Vec<f64> = Vec::new();
+        let mut places = Vec::with_capacity(keys.len());
+        for key in keys {
+            self.export(key, &mut coordinates)?;
+            let radians = coordinates
+                .chunks_exact(2)
+                .map(|place| haversine(point, [place[0], place[1]]))
+                .fold(f64::INFINITY, f64::min);
+            places.push((key, unit.from_radians(radians)));
+        }
+        places.sort_by(|a, b| a.1.total_cmp(&b.1));
+        Ok(places)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, ScalarKind};
+
+    #[test]
+    fn test_search_geo() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::Haversine,
+            quantization: ScalarKind::F64,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(100).unwrap();
+        // A grid of points a thousandth of a degree apart, about 111 meters along a meridian.
+        for key in 0..100u64 {
+            let latitude = 45.0 + (key / 10) as f64 * 1e-3;
+            let longitude = 7.0 + (key % 10) as f64 * 1e-3;
+            index.add(key, &[latitude, longitude]).unwrap();
+        }
+
+        let point = [45.0031, 7.0042];
+        let places = index.search_geo(point, 4, DistanceUnit::Meters).unwrap();
+        assert_eq!(places[0].0, 34);
+        assert!(places.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        let exact = DistanceUnit::Meters.from_radians(haversine(point, [45.003, 7.004]));
+        assert_eq!(places[0].1, exact);
+        assert!((exact - 19.26).abs() < 0.01, "{exact}");
+
+        // Same central angle, whatever the unit.
+        let kilometers = index
+            .search_geo(point, 1, DistanceUnit::Kilometers)
+            .unwrap();
+        assert!((kilometers[0].1 * 1000.0 - exact).abs() < 1e-9);
+        let radians = index.search_geo(point, 1, DistanceUnit::Radians).unwrap();
+        let meters = DistanceUnit::Meters.from_radians(radians[0].1);
+        assert!((meters - exact).abs() < 1e-9);
+        assert!((DistanceUnit::Miles.to_radians(1.0) * EARTH_RADIUS_KM - 1.609344).abs() < 1e-12);
+
+        let planar = Index::new(&IndexOptions {
+            metric: MetricKind::L2sq,
+            ..options
+        })
+        .unwrap();
+        assert!(planar.search_geo(point, 1, DistanceUnit::Meters).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Brute-force distance matrices computed on the GPU through `wgpu`.
+//!
+//! A compute shader evaluates one query-vector pair per invocation, over tiles of queries and
+//! vectors sized to the limits of the device. The device is picked once, on first use, among
+//! the adapters of any backend `wgpu` supports on the platform: Vulkan, Metal, DirectX 12 or
+//! OpenGL. Every function here returns `None` when the GPU can't serve the request, so the
+//! callers in [`crate::exact`] fall back to the CPU.
+
+use std::sync::OnceLock;
+
+use wgpu::util::DeviceExt;
+
+use crate::{Distance, MetricKind};
+
+/// Number of invocations per workgroup, along the vectors.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Maximum number of workgroups per dispatch dimension, guaranteed by every device.
+const MAX_WORKGROUPS: u32 = 65535;
+
+const SHADER: &str = r#"
+struct Params {
+    dimensions: u32,
+    queries: u32,
+    vectors: u32,
+    metric: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> queries: array<f32>;
+@group(0) @binding(2) var<storage, read> vectors: array<f32>;
+@group(0) @binding(3) var<storage, read_write> distances: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let vector = id.x;
+    let query = id.y;
+    if (vector >= params.vectors || query >= params.queries) {
+        return;
+    }
+    let a = query * params.dimensions;
+    let b = vector * params.dimensions;
+    var dot = 0.0;
+    var a2 = 0.0;
+    var b2 = 0.0;
+    var l2 = 0.0;
+    for (var i = 0u; i < params.dimensions; i = i + 1u) {
+        let x = queries[a + i];
+        let y = vectors[b + i];
+        dot = dot + x * y;
+        a2 = a2 + x * x;
+        b2 = b2 + y * y;
+        l2 = l2 + (x - y) * (x - y);
+    }
+    var distance = 0.0;
+    if (params.metric == 0u) {
+        distance = 1.0 - dot;
+    } else if (params.metric == 1u) {
+        distance = l2;
+    } else if (a2 == 0.0 && b2 == 0.0) {
+        distance = 0.0;
+    } else if (a2 == 0.0 || b2 == 0.0) {
+        distance = 1.0;
+    } else {
+        distance = 1.0 - dot / (sqrt(a2) * sqrt(b2));
+    }
+    distances[query * params.vectors + vector] = distance;
+}
+"#;
+
+/// The device and the compiled distance kernel, shared by all searches.
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    /// The largest storage buffer a single binding may cover, in bytes.
+    max_binding: u64,
+}
+
+/// The GPU, if one was found when first asked for.
+fn gpu() -> Option<&'static Gpu> {
+    static GPU: OnceLock<Option<Gpu>> = OnceLock::new();
+    GPU.get_or_init(|| pollster::block_on(Gpu::new())).as_ref()
+}
+
+/// Whether a GPU adapter was found, and distances of supported metrics are computed on it.
+pub(crate) fn available() -> bool {
+    gpu().is_some()
+}
+
+impl Gpu {
+    async fn new() -> Option<Gpu> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await
+            .ok()?;
+        let limits = adapter.limits();
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("usearch-exact"),
+                required_limits: limits.clone(),
+                ..Default::default()
+            })
+            .await
+            .ok()?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("usearch-distances"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("usearch-distances"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let max_binding =
+            u64::from(limits.max_storage_buffer_binding_size).min(limits.max_buffer_size);
+        Some(Gpu {
+            device,
+            queue,
+            pipeline,
+            max_binding,
+        })
+    }
+
+    /// Computes the distances of one tile of queries to one tile of vectors, row-major.
+    fn tile(
+        &self,
+        metric: u32,
+        dimensions: usize,
+        queries: &[f32],
+        vectors: &[f32],
+    ) -> Option<Vec<f32>> {
+        let queries_count = queries.len() / dimensions;
+        let vectors_count = vectors.len() / dimensions;
+        let params = [
+            dimensions as u32,
+            queries_count as u32,
+            vectors_count as u32,
+            metric,
+        ];
+        let storage = |label: &str, data: &[f32]| {
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some(label),
+                    contents: &bytes(data),
+                    usage: wgpu::BufferUsages::STORAGE,
+                })
+        };
+        let params = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("usearch-params"),
+                contents: &params
+                    .iter()
+                    .flat_map(|x| x.to_ne_bytes())
+                    .collect::<Vec<u8>>(),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let queries = storage("usearch-queries", queries);
+        let vectors = storage("usearch-vectors", vectors);
+        let size = (queries_count * vectors_count * std::mem::size_of::<f32>()) as u64;
+        let distances = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("usearch-distances"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("usearch-staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bindings = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("usearch-bindings"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                entry(0, &params),
+                entry(1, &queries),
+                entry(2, &vectors),
+                entry(3, &distances),
+            ],
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bindings, &[]);
+            pass.dispatch_workgroups(
+                (vectors_count as u32).div_ceil(WORKGROUP_SIZE),
+                queries_count as u32,
+                1,
+            );
+        }
+        encoder.copy_buffer_to_buffer(&distances, 0, &staging, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        staging.map_async(wgpu::MapMode::Read, .., move |mapped| {
+            let _ = sender.send(mapped);
+        });
+        self.device.poll(wgpu::PollType::Wait).ok()?;
+        receiver.recv().ok()?.ok()?;
+        let mapped = staging.slice(..).get_mapped_range();
+        let tile = mapped
+            .chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect();
+        drop(mapped);
+        staging.unmap();
+        Some(tile)
+    }
+}
+
+fn entry(binding: u32, buffer: &wgpu::Buffer) -> wgpu::BindGroupEntry<'_> {
+    wgpu::BindGroupEntry {
+        binding,
+        resource: buffer.as_entire_binding(),
+    }
+}
+
+fn bytes(data: &[f32]) -> Vec<u8> {
+    data.iter().flat_map(|x| x.to_ne_bytes()).collect()
+}
+
+/// Computes the distances of every query to every vector, both packed row-major, into a
+/// row-major matrix with a row per query.
+///
+/// # Returns
+///
+/// `None` if there is no GPU, the metric isn't one of `IP`, `L2sq` and `Cos`, or a single
+/// vector exceeds the limits of the device.
+pub(crate) fn distances(
+    metric: MetricKind,
+    dimensions: usize,
+    queries: &[f32],
+    vectors: &[f32],
+) -> Option<Vec<Distance>> {
+    let metric = match metric {
+        MetricKind::IP => 0,
+        MetricKind::L2sq => 1,
+        MetricKind::Cos => 2,
+        _ => return None,
+    };
+    let gpu = gpu()?;
+    let row = (dimensions * std::mem::size_of::<f32>()) as u64;
+    let fits = |bytes: u64| (gpu.max_binding / bytes.max(1)) as usize;
+    let queries_count = queries.len() / dimensions;
+    let vectors_count = vectors.len() / dimensions;
+    let vectors_tile = vectors_count
+        .min(fits(row))
+        .min((MAX_WORKGROUPS * WORKGROUP_SIZE) as usize);
+    let queries_tile = queries_count
+        .min(fits(row))
+        .min(fits(vectors_tile as u64 * 4))
+        .min(MAX_WORKGROUPS as usize);
+    if vectors_tile == 0 || queries_tile == 0 {
+        return None;
+    }
+
+    let mut matrix = vec![0.0; queries_count * vectors_count];
+    for (q, queries) in queries.chunks(queries_tile * dimensions).enumerate() {
+        for (v, vectors) in vectors.chunks(vectors_tile * dimensions).enumerate() {
+            let tile = gpu.tile(metric, dimensions, queries, vectors)?;
+            let width = vectors.len() / dimensions;
+            for (i, distances) in tile.chunks_exact(width).enumerate() {
+                let start = (q * queries_tile + i) * vectors_count + v * vectors_tile;
+                matrix[start..start + width].copy_from_slice(distances);
+            }
+        }
+    }
+    Some(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics;
+
+    #[test]
+    fn test_distances_match_cpu() {
+        if !available() {
+            eprintln!("No GPU adapter, skipping");
+            return;
+        }
+        let dimensions = 7;
+        let mut state = 0x2545_f491_u32;
+        let mut random = || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 24) as f32 - 0.5
+        };
+        let mut queries: Vec<f32> = (0..5 * dimensions).map(|_| random()).collect();
+        let mut vectors: Vec<f32> = (0..300 * dimensions).map(|_| random()).collect();
+        // Zero vectors take the special cases of the cosine distance.
+        queries[..dimensions].fill(0.0);
+        vectors[..dimensions].fill(0.0);
+
+        for metric in [MetricKind::IP, MetricKind::L2sq, MetricKind::Cos] {
+            let matrix = distances(metric, dimensions, &queries, &vectors).unwrap();
+            let expected = queries.chunks_exact(dimensions).flat_map(|query| {
+                vectors
+                    .chunks_exact(dimensions)
+                    .map(move |vector| metrics::distance(metric, query, vector))
+            });
+            assert_eq!(matrix.len(), 5 * 300);
+            for (gpu, cpu) in matrix.iter().zip(expected) {
+                assert!(
+                    (gpu - cpu).abs() <= 1e-4 * cpu.abs().max(1.0),
+                    "{:?}",
+                    metric
+                );
+            }
+        }
+        assert!(distances(MetricKind::Hamming, dimensions, &queries, &vectors).is_none());
+    }
+}
@@ -0,0 +1,228 @@
+//! Dumping the proximity graph, to visualize it or debug its connectivity.
+//!
+//! [`Index::export_graph`] writes every node of the graph, identified by its slot, with its key,
+//! its highest level, and its outgoing edges on every level. Graphviz and Gephi read the
+//! [`GraphFormat::Dot`] output, while [`GraphFormat::Jsonl`] suits ad-hoc scripts. Removed vectors
+//! keep their slots, and their edges, until they are recycled, so they are exported as well.
+//!
+//! [`Index::neighbors`] reads the edges of a single node by key instead, for analytics walking
+//! the graph itself, like clustering near-duplicates or detecting communities.
+
+use std::io::Write;
+use std::sync::PoisonError;
+
+use crate::{Index, IndexError, Key};
+
+/// The layout of an exported graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// A Graphviz `digraph`, with a `level` attribute on nodes and edges, and removed nodes dashed.
+    Dot,
+    /// One JSON object per node, like
+    /// `{"slot":0,"key":42,"level":1,"neighbors":[[3,5],[7]]}`, listing the slots of its
+    /// neighbors from the base level up, with a `null` key for removed nodes.
+    Jsonl,
+}
+
+impl Index {
+    /// Writes the nodes, levels, and edges of the graph in the given format.
+    ///
+    /// Insertions wait for the export to finish, so that it captures a consistent graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{GraphFormat, Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    /// index.add(2, &[0.8_f32, 0.6]).unwrap();
+    ///
+    /// let mut dot = Vec::new();
+    /// index.export_graph(&mut dot, GraphFormat::Dot).unwrap();
+    /// assert!(String::from_utf8(dot).unwrap().contains("s0 -> s1 [level=0];"));
+    /// ```
+    pub fn export_graph<W: Write>(
+        &self,
+        mut writer: W,
+        format: GraphFormat,
+    ) -> Result<(), IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let free_key = self.inner.free_key();
+        if format == GraphFormat::Dot {
+            writeln!(writer, "digraph usearch {{")?;
+        }
+        for slot in 0..self.inner.slots() {
+            let key = self.inner.slot_key(slot)?;
+            let removed = key == free_key;
+            let level = self.inner.slot_level(slot)?;
+            match format {
+                GraphFormat::Dot => {
+                    let style = if removed { ", style=dashed" } else { "" };
+                    let label = if removed {
+                        "removed".to_owned()
+                    } else {
+                        key.to_string()
+                    };
+                    writeln!(
+                        writer,
+                        "  s{slot} [label=\"{label}\", level={level}{style}];"
+                    )?;
+                    for l in 0..=level {
+                        for neighbor in self.inner.slot_neighbors(slot, l)? {
+                            writeln!(writer, "  s{slot} -> s{neighbor} [level={l}];")?;
+                        }
+                    }
+                }
+                GraphFormat::Jsonl => {
+                    let key = if removed {
+                        "null".to_owned()
+                    } else {
+                        key.to_string()
+                    };
+                    write!(
+                        writer,
+                        "{{\"slot\":{slot},\"key\":{key},\"level\":{level},\"neighbors\":["
+                    )?;
+                    for l in 0..=level {
+                        let neighbors = self.inner.slot_neighbors(slot, l)?;
+                        let neighbors: Vec<String> = neighbors.iter().map(u64::to_string).collect();
+                        let separator = if l == 0 { "" } else { "," };
+                        write!(writer, "{separator}[{}]", neighbors.join(","))?;
+                    }
+                    writeln!(writer, "]}}")?;
+                }
+            }
+        }
+        if format == GraphFormat::Dot {
+            writeln!(writer, "}}")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Lists the keys the vectors of `key` link to on the given level of the graph, skipping
+    /// removed neighbors. In multi-vector indexes, the edges of all vectors of the key are
+    /// merged, without duplicates.
+    ///
+    /// # Returns
+    ///
+    /// An empty list if the key is missing, or if none of its vectors reach that level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    /// index.add(2, &[0.8_f32, 0.6]).unwrap();
+    /// assert_eq!(index.neighbors(1, 0).unwrap(), vec![2]);
+    /// assert!(index.neighbors(3, 0).unwrap().is_empty());
+    /// ```
+    pub fn neighbors(&self, key: Key, level: usize) -> Result<Vec<Key>, IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let free_key = self.inner.free_key();
+        let mut neighbors = Vec::new();
+        for slot in self.inner.key_slots(key) {
+            let slot = slot as usize;
+            if self.inner.slot_level(slot)? < level {
+                continue;
+            }
+            for neighbor in self.inner.slot_neighbors(slot, level)? {
+                let neighbor = self.inner.slot_key(neighbor as usize)?;
+                if neighbor != free_key && !neighbors.contains(&neighbor) {
+                    neighbors.push(neighbor);
+                }
+            }
+        }
+        Ok(neighbors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_export_graph() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 4,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..100u64 {
+            index.add(key * 10, &[key as f32, 0.0]).unwrap();
+        }
+        index.remove(30).unwrap();
+        let stats = index.stats();
+        let edges: usize = stats.levels.iter().map(|level| level.edges).sum();
+
+        let mut dot = Vec::new();
+        index.export_graph(&mut dot, GraphFormat::Dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("digraph usearch {\n") && dot.ends_with("}\n"));
+        assert_eq!(dot.matches(" -> ").count(), edges);
+        assert!(dot.contains("  s3 [label=\"removed\", level="));
+        assert_eq!(dot.matches("style=dashed").count(), 1);
+        assert!(dot.contains("  s7 [label=\"70\", level="));
+
+        let mut jsonl = Vec::new();
+        index.export_graph(&mut jsonl, GraphFormat::Jsonl).unwrap();
+        let jsonl = String::from_utf8(jsonl).unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 100);
+        assert!(lines[3].starts_with("{\"slot\":3,\"key\":null,\"level\":"));
+        assert!(lines[7].starts_with("{\"slot\":7,\"key\":70,\"level\":"));
+        let top = stats.levels.len() - 1;
+        let tops = lines
+            .iter()
+            .filter(|line| line.contains(&format!("\"level\":{top},")))
+            .count();
+        assert_eq!(tops, stats.levels[top].nodes);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 4,
+            multi: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+        index.add(50, &[500.0, 0.0]).unwrap();
+
+        // Edges match the stored lists of the slots, and removed neighbors disappear.
+        let slot = index.inner.key_slots(20)[0] as usize;
+        let stored: Vec<Key> = index
+            .inner
+            .slot_neighbors(slot, 0)
+            .unwrap()
+            .iter()
+            .map(|&neighbor| index.inner.slot_key(neighbor as usize).unwrap())
+            .collect();
+        assert_eq!(index.neighbors(20, 0).unwrap(), stored);
+        assert!(stored.contains(&21));
+        index.remove(21).unwrap();
+        assert!(!index.neighbors(20, 0).unwrap().contains(&21));
+
+        // Both vectors of a key contribute their edges.
+        let merged = index.neighbors(50, 0).unwrap();
+        assert!(merged.contains(&49) || merged.contains(&51));
+        assert!(merged.contains(&99));
+
+        let top = index.inner.max_level();
+        assert!(index.neighbors(20, top + 1).unwrap().is_empty());
+        assert!(index.neighbors(1000, 0).unwrap().is_empty());
+    }
+}
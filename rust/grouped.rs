@@ -0,0 +1,191 @@
+//! Searches returning the best matches per group, like the best chunks of every document.
+//!
+//! Documents split into chunks are usually indexed one vector per chunk, while results are
+//! shown per document. [`Index::search_grouped`] maps every match to its group with a caller
+//! provided function, and keeps the best few matches of the best groups, probing the
+//! neighborhood of the query with searches of growing size until those groups are complete.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Distance, Index, IndexError, Key, VectorType};
+
+/// The number of neighbors fetched by the first probe, per requested match.
+const POOL_FACTOR: usize = 2;
+
+/// The largest number of neighbors fetched by a single probe, unless the request needs more.
+const LAST_PROBE: usize = 4096;
+
+/// The best matches of a group, as returned by [`Index::search_grouped`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupMatches<G> {
+    /// The group the matches belong to.
+    pub group: G,
+    /// The keys of the matches, from the closest.
+    pub keys: Vec<Key>,
+    /// The distances from the query to the matches.
+    pub distances: Vec<Distance>,
+}
+
+impl Index {
+    /// Searches for the `k_groups` groups with the closest matches, and the `k_per_group`
+    /// closest matches of each.
+    ///
+    /// The neighborhood is probed with searches of doubling size, until every returned group
+    /// has `k_per_group` matches, up to 4096 neighbors or the whole index. Groups with fewer
+    /// vectors than that are returned with the ones found.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `k_groups` - The maximum number of groups to return.
+    /// * `k_per_group` - The maximum number of matches to return per group.
+    /// * `group_of` - Maps the key of a match to its group, like a chunk to its document.
+    ///
+    /// # Returns
+    ///
+    /// The groups ordered by their closest match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 1,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// // Three chunks per document, the document being the key divided by 10.
+    /// for key in [10, 11, 12, 20, 21, 22, 30, 31, 32] {
+    ///     index.add(key, &[key as f32]).unwrap();
+    /// }
+    ///
+    /// let groups = index.search_grouped(&[19.0_f32], 2, 2, |key| key / 10).unwrap();
+    /// assert_eq!(groups[0].group, 2);
+    /// assert_eq!(groups[0].keys, vec![20, 21]);
+    /// assert_eq!(groups[1].group, 1);
+    /// assert_eq!(groups[1].keys, vec![12, 11]);
+    /// ```
+    pub fn search_grouped<T, G, F>(
+        &self,
+        query: &[T],
+        k_groups: usize,
+        k_per_group: usize,
+        group_of: F,
+    ) -> Result<Vec<GroupMatches<G>>, IndexError>
+    where
+        T: VectorType,
+        G: Eq + Hash + Clone,
+        F: Fn(Key) -> G,
+    {
+        let size = self.size();
+        let wanted = k_groups.saturating_mul(k_per_group);
+        if wanted == 0 || size == 0 {
+            return Ok(Vec::new());
+        }
+        let last_probe = LAST_PROBE.max(wanted).min(size);
+        let mut count = wanted.saturating_mul(POOL_FACTOR).min(last_probe);
+        loop {
+            let matches = self.search(query, count)?;
+            let mut positions: HashMap<G, usize> = HashMap::new();
+            let mut groups: Vec<GroupMatches<G>> = Vec::with_capacity(k_groups);
+            for element in matches.elements() {
+                let group = group_of(element.key);
+                let position = match positions.get(&group) {
+                    Some(&position) => position,
+                    None if groups.len() < k_groups => {
+                        positions.insert(group.clone(), groups.len());
+                        groups.push(GroupMatches {
+                            group,
+                            keys: Vec::with_capacity(k_per_group),
+                            distances: Vec::with_capacity(k_per_group),
+                        });
+                        groups.len() - 1
+                    }
+                    None => continue,
+                };
+                let matches = &mut groups[position];
+                if matches.keys.len() < k_per_group {
+                    matches.keys.push(element.key);
+                    matches.distances.push(element.distance);
+                }
+            }
+
+            let complete = groups.len() == k_groups
+                && groups.iter().all(|group| group.keys.len() == k_per_group);
+            if complete || count >= last_probe {
+                return Ok(groups);
+            }
+            count = (count * 2).min(last_probe);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexError, IndexOptions, Key, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_search_grouped() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        assert!(index
+            .search_grouped(&[0.0_f32, 0.0], 3, 2, |key| key)
+            .unwrap()
+            .is_empty());
+
+        // Document 0 has a hundred chunks close to the origin, crowding out the other
+        // documents of the plain search, each having three chunks further away.
+        for chunk in 0..100u64 {
+            index.add(chunk, &[chunk as f32 * 0.001, 0.0]).unwrap();
+        }
+        for document in 1..20u64 {
+            for chunk in 0..3u64 {
+                let key = document * 1000 + chunk;
+                index
+                    .add(key, &[document as f32, chunk as f32 * 0.1])
+                    .unwrap();
+            }
+        }
+        let document = |key: Key| key / 1000;
+        let plain = index.search(&[0.0_f32, 0.0], 20).unwrap();
+        assert!(plain.keys.iter().all(|&key| document(key) == 0));
+
+        let groups = index
+            .search_grouped(&[0.0_f32, 0.0], 3, 2, document)
+            .unwrap();
+        let summary: Vec<(u64, Vec<Key>)> = groups
+            .iter()
+            .map(|group| (group.group, group.keys.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, vec![0, 1]),
+                (1, vec![1000, 1001]),
+                (2, vec![2000, 2001])
+            ]
+        );
+        assert_eq!(groups[1].distances[0], 1.0);
+
+        // Groups smaller than requested are returned with what they have.
+        let groups = index
+            .search_grouped(&[5.0_f32, 0.0], 2, 5, document)
+            .unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].group, 5);
+        assert_eq!(groups[0].keys, vec![5000, 5001, 5002]);
+        assert!(matches!(
+            index.search_grouped(&[5.0_f32], 2, 5, document),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+    }
+}
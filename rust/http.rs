@@ -0,0 +1,243 @@
+//! A JSON-over-HTTP interface to the [`IndexService`], for prototyping from non-Rust clients.
+//!
+//! Enabled with the `http` feature, which builds on the gRPC service of the `server` feature,
+//! so both protocols can serve the same index at once. The routes are:
+//!
+//! - `POST /vectors` with `{ "vectors": [{ "key": 42, "vector": [0.1, 0.2] }] }` adds vectors.
+//! - `DELETE /vectors` with `{ "keys": [42] }` removes vectors.
+//! - `POST /search` with `{ "vector": [0.1, 0.2], "count": 10 }` returns keys and distances.
+//! - `POST /index/save` saves the index to the path the service was configured with.
+//! - `GET /index/stats` reports the size and configuration of the index.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tonic::{Code, Status};
+
+use crate::server::IndexService;
+use crate::{Distance, Key};
+
+/// A vector stored under a key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorEntry {
+    /// The key of the vector.
+    pub key: Key,
+    /// The vector scalars.
+    pub vector: Vec<f32>,
+}
+
+/// Body of `POST /vectors`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddBody {
+    /// The vectors to add.
+    pub vectors: Vec<VectorEntry>,
+}
+
+/// Body of `DELETE /vectors`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoveBody {
+    /// The keys to remove.
+    pub keys: Vec<Key>,
+}
+
+/// Body of `POST /search`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchBody {
+    /// The query vector.
+    pub vector: Vec<f32>,
+    /// The maximum number of neighbors to return, 10 by default.
+    #[serde(default = "default_count")]
+    pub count: usize,
+}
+
+fn default_count() -> usize {
+    10
+}
+
+/// Response of `POST /search`, ordered from the closest match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchReply {
+    /// Keys of the matched vectors.
+    pub keys: Vec<Key>,
+    /// Distances from the query to the matched vectors.
+    pub distances: Vec<Distance>,
+}
+
+/// Response of `POST /vectors` and `DELETE /vectors`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CountReply {
+    /// The number of added or removed vectors.
+    pub count: u64,
+}
+
+/// Response of `POST /index/save`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveReply {
+    /// The path the index was saved to.
+    pub path: String,
+}
+
+/// Response of `GET /index/stats`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsReply {
+    /// The number of vectors in the index.
+    pub size: u64,
+    /// The number of vectors the index can hold before growing.
+    pub capacity: u64,
+    /// The number of dimensions of every vector.
+    pub dimensions: u64,
+    /// The maximum number of edges per node in the graph.
+    pub connectivity: u64,
+    /// The expansion factor used during insertions.
+    pub expansion_add: u64,
+    /// The expansion factor used during searches.
+    pub expansion_search: u64,
+    /// The approximate memory usage in bytes.
+    pub memory_usage: u64,
+    /// The SIMD capability used by the distance kernels.
+    pub hardware_acceleration: String,
+}
+
+/// A service error, reported as a JSON object with an `error` message.
+#[derive(Debug)]
+pub struct HttpError(Status);
+
+impl IntoResponse for HttpError {
+    fn into_response(self) -> Response {
+        let code = match self.0.code() {
+            Code::InvalidArgument => StatusCode::BAD_REQUEST,
+            Code::AlreadyExists => StatusCode::CONFLICT,
+            Code::FailedPrecondition => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        let body = serde_json::json!({ "error": self.0.message() });
+        (code, Json(body)).into_response()
+    }
+}
+
+impl From<Status> for HttpError {
+    fn from(status: Status) -> Self {
+        HttpError(status)
+    }
+}
+
+async fn add(
+    State(service): State<IndexService>,
+    Json(body): Json<AddBody>,
+) -> Result<Json<CountReply>, HttpError> {
+    let vectors = body
+        .vectors
+        .into_iter()
+        .map(|entry| (entry.key, entry.vector))
+        .collect();
+    let count = service.add_vectors(vectors).await?;
+    Ok(Json(CountReply { count }))
+}
+
+async fn remove(
+    State(service): State<IndexService>,
+    Json(body): Json<RemoveBody>,
+) -> Result<Json<CountReply>, HttpError> {
+    let count = service.remove_vectors(body.keys).await?;
+    Ok(Json(CountReply { count }))
+}
+
+async fn search(
+    State(service): State<IndexService>,
+    Json(body): Json<SearchBody>,
+) -> Result<Json<SearchReply>, HttpError> {
+    let (keys, distances) = service.search_vectors(body.vector, body.count).await?;
+    Ok(Json(SearchReply { keys, distances }))
+}
+
+async fn save(State(service): State<IndexService>) -> Result<Json<SaveReply>, HttpError> {
+    let path = service.save_vectors().await?;
+    Ok(Json(SaveReply {
+        path: path.to_string_lossy().into_owned(),
+    }))
+}
+
+async fn stats(State(service): State<IndexService>) -> Json<StatsReply> {
+    let stats = service.stats_response();
+    Json(StatsReply {
+        size: stats.size,
+        capacity: stats.capacity,
+        dimensions: stats.dimensions,
+        connectivity: stats.connectivity,
+        expansion_add: stats.expansion_add,
+        expansion_search: stats.expansion_search,
+        memory_usage: stats.memory_usage,
+        hardware_acceleration: stats.hardware_acceleration,
+    })
+}
+
+impl IndexService {
+    /// Wraps the service into an axum router, serving the JSON routes listed in the module docs.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use usearch::server::IndexService;
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// # async fn serve() -> Result<(), Box<dyn std::error::Error>> {
+    /// let index = Index::new(&IndexOptions { dimensions: 256, ..Default::default() })?;
+    /// let router = IndexService::new(index).with_path("index.usearch").into_router();
+    /// let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+    /// axum::serve(listener, router).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_router(self) -> Router {
+        Router::new()
+            .route("/vectors", post(add).delete(remove))
+            .route("/search", post(search))
+            .route("/index/save", post(save))
+            .route("/index/stats", get(stats))
+            .with_state(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_routes() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let service = IndexService::new(Index::new(&options).unwrap());
+
+        let body: AddBody = serde_json::from_str(
+            r#"{ "vectors": [{ "key": 1, "vector": [0.0, 1.0] }, { "key": 2, "vector": [1.0, 0.0] }] }"#,
+        )
+        .unwrap();
+        let added = add(State(service.clone()), Json(body)).await.unwrap();
+        assert_eq!(added.0.count, 2);
+
+        let body: SearchBody = serde_json::from_str(r#"{ "vector": [0.9, 0.1] }"#).unwrap();
+        assert_eq!(body.count, 10);
+        let found = search(State(service.clone()), Json(body)).await.unwrap();
+        assert_eq!(found.0.keys, vec![2, 1]);
+
+        let removed = remove(
+            State(service.clone()),
+            Json(RemoveBody { keys: vec![2, 3] }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(removed.0.count, 1);
+        assert_eq!(stats(State(service.clone())).await.0.size, 1);
+
+        let response = save(State(service)).await.unwrap_err().into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -0,0 +1,161 @@
+//! Blending vector search results with scores computed elsewhere, like BM25 keyword relevance.
+//!
+//! [`Index::hybrid_search`] re-scores a pool of nearest neighbors with a caller-provided function
+//! of the key and the distance, and [`reciprocal_rank_fusion`] merges independent rankings, like
+//! the keys of a vector search and of a full-text search, by their ranks alone. Both return a
+//! unified ranking of [`ResultElement`]s, where a lower `distance` is better.
+
+use std::collections::HashMap;
+
+use crate::search::ResultElement;
+use crate::{Index, IndexError, Key, VectorType};
+
+/// The usual smoothing constant of reciprocal rank fusion, dampening the weight of top ranks.
+pub const RRF_K: f32 = 60.0;
+
+/// Number of nearest neighbors re-scored by [`Index::hybrid_search`] per returned result.
+const POOL_FACTOR: usize = 4;
+
+impl Index {
+    /// Retrieves the nearest neighbors of the query and re-ranks them by a blended score.
+    ///
+    /// `4 * count` candidates are fetched, re-scored with `scorer(key, distance)`, and the
+    /// `count` lowest scores are returned, with the score in place of the distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of results to return.
+    /// * `scorer` - Combines a key and its vector distance into a score, where lower is better.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 1,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[0.0_f32]).unwrap();
+    /// index.add(2, &[0.5_f32]).unwrap();
+    ///
+    /// // Keyword relevance computed elsewhere, boosting the second document.
+    /// let bm25 = HashMap::from([(2, 1.0_f32)]);
+    /// let results = index
+    ///     .hybrid_search(&[0.0_f32], 2, |key, distance| {
+    ///         distance - 0.5 * bm25.get(&key).copied().unwrap_or(0.0)
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(results[0].key, 2);
+    /// ```
+    pub fn hybrid_search<T, F>(
+        &self,
+        query: &[T],
+        count: usize,
+        scorer: F,
+    ) -> Result<Vec<ResultElement>, IndexError>
+    where
+        T: VectorType,
+        F: Fn(Key, f32) -> f32,
+    {
+        let candidates = self.search(query, count * POOL_FACTOR)?;
+        let mut results: Vec<ResultElement> = candidates
+            .elements()
+            .map(|element| ResultElement {
+                key: element.key,
+                distance: scorer(element.key, element.distance),
+            })
+            .collect();
+        results.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        results.truncate(count);
+        Ok(results)
+    }
+}
+
+/// Merges rankings of keys, each ordered from the best, with reciprocal rank fusion.
+///
+/// Every key scores `1 / (k + rank)` in every ranking it appears in, with ranks starting at one,
+/// and the scores are summed. The fused ranking is returned from the best key, carrying the
+/// negated score as its distance, so that lower stays better. Ties are broken by key.
+///
+/// # Arguments
+///
+/// * `rankings` - The rankings to merge, like vector and keyword search results.
+/// * `k` - The smoothing constant, usually [`RRF_K`].
+///
+/// # Examples
+///
+/// ```
+/// use usearch::hybrid::{reciprocal_rank_fusion, RRF_K};
+///
+/// let vector_hits = [1, 2, 3];
+/// let keyword_hits = [3, 4];
+/// let fused = reciprocal_rank_fusion(&[&vector_hits, &keyword_hits], RRF_K);
+/// assert_eq!(fused[0].key, 3);
+/// assert_eq!(fused.len(), 4);
+/// ```
+pub fn reciprocal_rank_fusion(rankings: &[&[Key]], k: f32) -> Vec<ResultElement> {
+    let mut scores: HashMap<Key, f32> = HashMap::new();
+    for ranking in rankings {
+        for (rank, &key) in ranking.iter().enumerate() {
+            *scores.entry(key).or_default() += 1.0 / (k + rank as f32 + 1.0);
+        }
+    }
+    let mut fused: Vec<ResultElement> = scores
+        .into_iter()
+        .map(|(key, score)| ResultElement {
+            key,
+            distance: -score,
+        })
+        .collect();
+    fused.sort_by(|a, b| a.distance.total_cmp(&b.distance).then(a.key.cmp(&b.key)));
+    fused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_hybrid_search_and_fusion() {
+        let options = IndexOptions {
+            dimensions: 1,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..20u64 {
+            index.add(key, &[key as f32]).unwrap();
+        }
+
+        // Without external scores, the ranking is the plain vector ranking.
+        let plain = index
+            .hybrid_search(&[0.0_f32], 3, |_, distance| distance)
+            .unwrap();
+        let keys: Vec<Key> = plain.iter().map(|element| element.key).collect();
+        assert_eq!(keys, vec![0, 1, 2]);
+
+        // Penalizing even keys pushes them behind the odd ones within the candidate pool.
+        let blended = index
+            .hybrid_search(&[0.0_f32], 3, |key, distance| {
+                distance + if key % 2 == 0 { 100.0 } else { 0.0 }
+            })
+            .unwrap();
+        let keys: Vec<Key> = blended.iter().map(|element| element.key).collect();
+        assert_eq!(keys, vec![1, 3, 5]);
+        assert_eq!(blended[0].distance, 1.0);
+
+        let vector_hits: Vec<Key> = plain.iter().map(|element| element.key).collect();
+        let fused = reciprocal_rank_fusion(&[&vector_hits, &[2, 7]], RRF_K);
+        let keys: Vec<Key> = fused.iter().map(|element| element.key).collect();
+        assert_eq!(keys, vec![2, 0, 1, 7]);
+        assert!((fused[0].distance + 1.0 / 61.0 + 1.0 / 63.0).abs() < 1e-6);
+    }
+}
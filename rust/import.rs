@@ -0,0 +1,406 @@
+//! Migrating indexes built with FAISS or hnswlib.
+//!
+//! Both libraries store the full-precision vectors next to their graphs, so
+//! [`Index::import_faiss`] and [`Index::import_hnswlib`] read the vectors and their labels,
+//! skip the foreign graph, and insert everything into a new `f32` index, whose graph is built
+//! with the connectivity and expansion factors found in the file, when it records them.
+//!
+//! FAISS files may hold a flat index (`IndexFlatL2` or `IndexFlatIP`), an `IndexHNSWFlat` over
+//! one, or either of them wrapped in an `IndexIDMap`, which provides the keys. Otherwise, like
+//! in hnswlib files, the keys are the positions of the vectors.
+
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use crate::{Index, IndexError, IndexOptions, Key, MetricKind, ScalarKind};
+
+/// The FAISS metric types with a native counterpart.
+const FAISS_METRIC_INNER_PRODUCT: i32 = 0;
+const FAISS_METRIC_L2: i32 = 1;
+
+/// Bit of the level-0 neighbor list header marking hnswlib elements as deleted.
+pub(crate) const HNSWLIB_DELETE_MARK: u8 = 0x01;
+
+/// Vectors, keys, and construction parameters read from a foreign file.
+struct Imported {
+    options: IndexOptions,
+    keys: Option<Vec<Key>>,
+    vectors: Vec<f32>,
+}
+
+impl Imported {
+    fn into_index(self) -> Result<Index, IndexError> {
+        let dimensions = self.options.dimensions;
+        let count = self.vectors.len() / dimensions.max(1);
+        let index = Index::new(&self.options)?;
+        index.reserve(count)?;
+        for (position, vector) in self.vectors.chunks_exact(dimensions).enumerate() {
+            let key = match &self.keys {
+                Some(keys) => keys[position],
+                None => position as Key,
+            };
+            index.add(key, vector)?;
+        }
+        Ok(index)
+    }
+}
+
+/// Little-endian reads of the plain values foreign formats are made of.
+struct Source<R> {
+    reader: R,
+}
+
+impl<R: Read> Source<R> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], IndexError> {
+        let mut bytes = [0; N];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, IndexError> {
+        Ok(self.bytes::<1>()?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32, IndexError> {
+        Ok(i32::from_le_bytes(self.bytes()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, IndexError> {
+        Ok(u64::from_le_bytes(self.bytes()?))
+    }
+
+    fn f32s(&mut self, count: usize) -> Result<Vec<f32>, IndexError> {
+        let mut bytes = vec![0; count * 4];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect())
+    }
+
+    fn skip(&mut self, length: u64) -> Result<(), IndexError> {
+        let skipped = std::io::copy(&mut (&mut self.reader).take(length), &mut std::io::sink())?;
+        if skipped != length {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(())
+    }
+
+    /// Reads the number of elements prefixing a FAISS vector.
+    fn faiss_length(&mut self) -> Result<usize, IndexError> {
+        usize::try_from(self.u64()?).map_err(|_| malformed("FAISS", "oversized vector"))
+    }
+
+    fn skip_faiss_vector(&mut self, element: u64) -> Result<(), IndexError> {
+        let length = self.faiss_length()? as u64;
+        self.skip(length * element)
+    }
+}
+
+fn malformed(format: &str, detail: &str) -> IndexError {
+    IndexError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Malformed {format} index: {detail}"),
+    ))
+}
+
+/// The common header of FAISS indexes, and the metric it maps to.
+fn read_faiss_header<R: Read>(
+    source: &mut Source<R>,
+) -> Result<(usize, usize, MetricKind), IndexError> {
+    let dimensions =
+        usize::try_from(source.i32()?).map_err(|_| malformed("FAISS", "dimensions"))?;
+    let count = usize::try_from(source.u64()?).map_err(|_| malformed("FAISS", "vector count"))?;
+    source.skip(16)?; // Two unused 64-bit fields.
+    source.u8()?; // Whether the index is trained.
+    let metric = match source.i32()? {
+        FAISS_METRIC_INNER_PRODUCT => MetricKind::IP,
+        FAISS_METRIC_L2 => MetricKind::L2sq,
+        other => {
+            return Err(IndexError::IncompatibleFormat {
+                found: format!("FAISS metric type {other}"),
+                expected: "METRIC_INNER_PRODUCT or METRIC_L2".to_owned(),
+            })
+        }
+    };
+    Ok((dimensions, count, metric))
+}
+
+fn read_faiss<R: Read>(source: &mut Source<R>) -> Result<Imported, IndexError> {
+    let fourcc = source.bytes::<4>()?;
+    match &fourcc {
+        b"IxF2" | b"IxFI" | b"IxFl" => {
+            let (dimensions, count, metric) = read_faiss_header(source)?;
+            let length = source.faiss_length()?;
+            if length != dimensions * count {
+                return Err(malformed("FAISS", "flat codes don't match the header"));
+            }
+            Ok(Imported {
+                options: IndexOptions {
+                    dimensions,
+                    metric,
+                    quantization: ScalarKind::F32,
+                    ..Default::default()
+                },
+                keys: None,
+                vectors: source.f32s(length)?,
+            })
+        }
+        b"IHNf" => {
+            let (_, _, metric) = read_faiss_header(source)?;
+            source.skip_faiss_vector(8)?; // Level assignment probabilities.
+            let neighbors_per_level = source.faiss_length()?;
+            let mut cumulative = Vec::with_capacity(neighbors_per_level.min(64));
+            for _ in 0..neighbors_per_level {
+                cumulative.push(source.i32()?);
+            }
+            source.skip_faiss_vector(4)?; // Levels of the nodes.
+            source.skip_faiss_vector(8)?; // Offsets of the neighbor lists.
+            source.skip_faiss_vector(4)?; // Neighbor lists.
+            source.i32()?; // Entry point.
+            source.i32()?; // Maximum level.
+            let expansion_add = source.i32()?;
+            let expansion_search = source.i32()?;
+            source.i32()?; // Unused beam width.
+
+            let mut imported = read_faiss(source)?;
+            imported.options.metric = metric;
+            // The base level holds twice as many neighbors as the upper ones.
+            imported.options.connectivity = cumulative
+                .get(1)
+                .map_or(0, |&neighbors| (neighbors.max(0) / 2) as usize);
+            imported.options.expansion_add = expansion_add.max(0) as usize;
+            imported.options.expansion_search = expansion_search.max(0) as usize;
+            Ok(imported)
+        }
+        b"IxMp" | b"IxM2" => {
+            read_faiss_header(source)?;
+            let mut imported = read_faiss(source)?;
+            let length = source.faiss_length()?;
+            let mut keys = Vec::with_capacity(length.min(1 << 20));
+            for _ in 0..length {
+                keys.push(source.u64()? as Key);
+            }
+            if keys.len() * imported.options.dimensions != imported.vectors.len() {
+                return Err(malformed("FAISS", "identifiers don't match the vectors"));
+            }
+            imported.keys = Some(keys);
+            Ok(imported)
+        }
+        _ => Err(IndexError::IncompatibleFormat {
+            found: format!("FAISS index type {:?}", String::from_utf8_lossy(&fourcc)),
+            expected: "IndexFlat, IndexHNSWFlat, or IndexIDMap".to_owned(),
+        }),
+    }
+}
+
+fn read_hnswlib<R: Read>(
+    source: &mut Source<R>,
+    metric: MetricKind,
+) -> Result<Imported, IndexError> {
+    let level0_offset = source.u64()?;
+    source.u64()?; // Maximum number of elements.
+    let count = source.u64()?;
+    let element_size = source.u64()?;
+    let label_offset = source.u64()?;
+    let data_offset = source.u64()?;
+    source.i32()?; // Maximum level.
+    source.i32()?; // Entry point.
+    source.u64()?; // Maximum neighbors on the upper levels.
+    source.u64()?; // Maximum neighbors on the base level.
+    let connectivity = source.u64()?;
+    source.u64()?; // Level generation factor.
+    let expansion_add = source.u64()?;
+
+    let data_size = label_offset.checked_sub(data_offset);
+    let layout_fits = data_size.is_some_and(|size| size % 4 == 0)
+        && level0_offset + 4 <= element_size
+        && label_offset + 8 <= element_size;
+    if !layout_fits {
+        return Err(malformed("hnswlib", "inconsistent element layout"));
+    }
+    let dimensions = (data_size.unwrap() / 4) as usize;
+
+    let mut element = vec![0; element_size as usize];
+    let mut keys = Vec::new();
+    let mut vectors = Vec::new();
+    for _ in 0..count {
+        source.reader.read_exact(&mut element)?;
+        if element[level0_offset as usize + 2] & HNSWLIB_DELETE_MARK != 0 {
+            continue;
+        }
+        let label = &element[label_offset as usize..label_offset as usize + 8];
+        keys.push(Key::from_le_bytes(label.try_into().unwrap()));
+        let data = &element[data_offset as usize..label_offset as usize];
+        vectors.extend(
+            data.chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())),
+        );
+    }
+    // The upper levels of the graph follow, and aren't needed.
+
+    Ok(Imported {
+        options: IndexOptions {
+            dimensions,
+            metric,
+            quantization: ScalarKind::F32,
+            connectivity: connectivity as usize,
+            expansion_add: expansion_add as usize,
+            ..Default::default()
+        },
+        keys: Some(keys),
+        vectors,
+    })
+}
+
+impl Index {
+    /// Builds an index from a FAISS file written by `faiss::write_index`.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::IncompatibleFormat` for index types or metrics without a counterpart, and
+    /// `IndexError::Io` for truncated or inconsistent files.
+    pub fn import_faiss<P: AsRef<Path>>(path: P) -> Result<Index, IndexError> {
+        let mut source = Source {
+            reader: BufReader::new(File::open(path)?),
+        };
+        read_faiss(&mut source)?.into_index()
+    }
+
+    /// Builds an index from an hnswlib file written by `saveIndex`, which doesn't record the
+    /// metric. Indexes of the `cosine` space store normalized vectors, and can be imported
+    /// with either `MetricKind::Cos` or `MetricKind::IP`. Deleted elements are skipped.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::Io` for truncated or inconsistent files.
+    pub fn import_hnswlib<P: AsRef<Path>>(
+        path: P,
+        metric: MetricKind,
+    ) -> Result<Index, IndexError> {
+        let mut source = Source {
+            reader: BufReader::new(File::open(path)?),
+        };
+        read_hnswlib(&mut source, metric)?.into_index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn faiss_header(
+        file: &mut Vec<u8>,
+        fourcc: &[u8; 4],
+        dimensions: i32,
+        count: u64,
+        metric: i32,
+    ) {
+        file.extend_from_slice(fourcc);
+        file.extend_from_slice(&dimensions.to_le_bytes());
+        file.extend_from_slice(&count.to_le_bytes());
+        file.extend_from_slice(&[0; 16]);
+        file.push(1);
+        file.extend_from_slice(&metric.to_le_bytes());
+    }
+
+    fn faiss_flat(file: &mut Vec<u8>, metric: i32, vectors: &[[f32; 2]]) {
+        let fourcc = if metric == FAISS_METRIC_L2 {
+            b"IxF2"
+        } else {
+            b"IxFI"
+        };
+        faiss_header(file, fourcc, 2, vectors.len() as u64, metric);
+        file.extend_from_slice(&(vectors.len() as u64 * 2).to_le_bytes());
+        for value in vectors.iter().flatten() {
+            file.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn test_import() {
+        let vectors: Vec<[f32; 2]> = (0..50).map(|i| [i as f32, 1.0]).collect();
+        let directory = std::env::temp_dir();
+
+        let mut flat = Vec::new();
+        faiss_flat(&mut flat, FAISS_METRIC_L2, &vectors);
+        let path = directory.join("usearch-import-flat.faiss");
+        std::fs::write(&path, &flat).unwrap();
+        let index = Index::import_faiss(&path).unwrap();
+        assert_eq!((index.size(), index.dimensions()), (50, 2));
+        assert_eq!(index.search(&[7.2_f32, 1.0], 1).unwrap().keys, vec![7]);
+
+        // An HNSW index with 16 neighbors per node, wrapped with identifiers.
+        let mut mapped = Vec::new();
+        faiss_header(&mut mapped, b"IxMp", 2, 50, FAISS_METRIC_INNER_PRODUCT);
+        faiss_header(&mut mapped, b"IHNf", 2, 50, FAISS_METRIC_INNER_PRODUCT);
+        for (length, element) in [(1u64, 8), (3, 4), (50, 4), (51, 8), (0, 4)] {
+            mapped.extend_from_slice(&length.to_le_bytes());
+            if element == 4 && length == 3 {
+                for cumulative in [0i32, 32, 48] {
+                    mapped.extend_from_slice(&cumulative.to_le_bytes());
+                }
+            } else {
+                mapped.extend(std::iter::repeat_n(0, (length * element) as usize));
+            }
+        }
+        for value in [0i32, 0, 40, 24, 1] {
+            mapped.extend_from_slice(&value.to_le_bytes());
+        }
+        faiss_flat(&mut mapped, FAISS_METRIC_INNER_PRODUCT, &vectors);
+        mapped.extend_from_slice(&50u64.to_le_bytes());
+        for key in 0..50u64 {
+            mapped.extend_from_slice(&(1000 + key).to_le_bytes());
+        }
+        std::fs::write(&path, &mapped).unwrap();
+        let index = Index::import_faiss(&path).unwrap();
+        assert_eq!(index.connectivity(), 16);
+        assert_eq!(index.expansion_add(), 40);
+        assert!(index.contains(1049) && !index.contains(49));
+        assert_eq!(index.search(&[1.0_f32, 0.0], 1).unwrap().keys, vec![1049]);
+
+        std::fs::write(&path, &mapped[..mapped.len() - 4]).unwrap();
+        assert!(Index::import_faiss(&path).is_err());
+        mapped[..4].copy_from_slice(b"IwPQ");
+        std::fs::write(&path, &mapped).unwrap();
+        assert!(matches!(
+            Index::import_faiss(&path),
+            Err(IndexError::IncompatibleFormat { .. })
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        // hnswlib elements hold their neighbors, the vector, and the label.
+        let mut hnswlib = Vec::new();
+        let links = 4 + 2 * 16 * 4;
+        let element_size = links + 8 + 8;
+        for value in [0u64, 100, 50, element_size, links + 8, links] {
+            hnswlib.extend_from_slice(&value.to_le_bytes());
+        }
+        hnswlib.extend_from_slice(&[0; 8]);
+        for value in [16u64, 32, 16, 0, 100] {
+            hnswlib.extend_from_slice(&value.to_le_bytes());
+        }
+        for (position, vector) in vectors.iter().enumerate() {
+            let deleted = if position == 3 {
+                HNSWLIB_DELETE_MARK
+            } else {
+                0
+            };
+            hnswlib.extend_from_slice(&[0, 0, deleted, 0]);
+            hnswlib.extend(std::iter::repeat_n(0, links as usize - 4));
+            for value in vector {
+                hnswlib.extend_from_slice(&value.to_le_bytes());
+            }
+            hnswlib.extend_from_slice(&(500 + position as u64).to_le_bytes());
+        }
+        let path = directory.join("usearch-import.hnswlib");
+        std::fs::write(&path, &hnswlib).unwrap();
+        let index = Index::import_hnswlib(&path, MetricKind::L2sq).unwrap();
+        assert_eq!(index.size(), 49);
+        assert!(!index.contains(503));
+        assert_eq!(index.expansion_add(), 100);
+        assert_eq!(index.search(&[9.1_f32, 1.0], 1).unwrap().keys, vec![509]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+//! Ingesting full-precision embeddings into indexes of narrower scalars.
+//!
+//! Models emit `f32` or `f64` embeddings, while large indexes store `f16` or `i8` scalars.
+//! [`Index::ingest_as`] checks once that the chosen scalar type is the one the index stores,
+//! and the returned [`IngestAs`] adapter converts every inserted vector on the fly, with the
+//! same semantics as [`convert::LossyCast`](crate::convert::LossyCast), reusing one buffer
+//! across a batch.
+
+use std::marker::PhantomData;
+
+use crate::convert::LossyCast;
+use crate::{Index, IndexError, Key, VectorType};
+
+/// Inserts vectors into an index, converting them into its scalar type `T` first.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{f16, Index, IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F16,
+///     ..Default::default()
+/// };
+/// let index = Index::new(&options).unwrap();
+/// index.reserve(3).unwrap();
+///
+/// let ingest = index.ingest_as::<f16>().unwrap();
+/// ingest.add(1, &[0.5_f32, 0.25]).unwrap();
+/// ingest.add_batch(&[2, 3], &[[1.0_f64, 0.0], [0.0, 1.0]]).unwrap();
+///
+/// let mut vector = [f16::from_bits(0); 2];
+/// index.get(1, &mut vector).unwrap();
+/// assert_eq!(vector.map(f16::to_f32), [0.5, 0.25]);
+/// assert_eq!(index.size(), 3);
+/// ```
+pub struct IngestAs<'a, T> {
+    index: &'a Index,
+    scalar: PhantomData<T>,
+}
+
+impl Index {
+    /// Creates an adapter converting inserted vectors into `T`, the scalar type of the index.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if the index stores another scalar kind.
+    pub fn ingest_as<T: VectorType>(&self) -> Result<IngestAs<'_, T>, IndexError> {
+        let stored = self.inner.scalar_kind();
+        if stored != T::SCALAR_KIND {
+            return Err(IndexError::InvalidOptions {
+                reason: format!(
+                    "The index stores {:?} scalars, not {:?}",
+                    stored,
+                    T::SCALAR_KIND
+                ),
+            });
+        }
+        Ok(IngestAs {
+            index: self,
+            scalar: PhantomData,
+        })
+    }
+}
+
+impl<T: VectorType + Copy + Default> IngestAs<'_, T> {
+    /// The index the vectors are inserted into.
+    pub fn index(&self) -> &Index {
+        self.index
+    }
+
+    fn add_with_buffer<S: LossyCast<T>>(
+        &self,
+        key: Key,
+        vector: &[S],
+        buffer: &mut Vec<T>,
+    ) -> Result<(), IndexError> {
+        let dimensions = self.index.dimensions();
+        if vector.len() != dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: dimensions,
+                got: vector.len(),
+            });
+        }
+        buffer.clear();
+        buffer.extend(vector.iter().map(|&value| value.lossy_cast()));
+        self.index.add(key, buffer)
+    }
+
+    /// Converts a vector and adds it under the specified key.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the vector length differs from the index dimensions.
+    pub fn add<S: LossyCast<T>>(&self, key: Key, vector: &[S]) -> Result<(), IndexError> {
+        self.add_with_buffer(key, vector, &mut Vec::new())
+    }
+
+    /// Converts and adds vectors under the matching keys, stopping at the first error.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the numbers of keys and vectors differ, or if a vector
+    /// length differs from the index dimensions.
+    pub fn add_batch<S, V>(&self, keys: &[Key], vectors: &[V]) -> Result<(), IndexError>
+    where
+        S: LossyCast<T>,
+        V: AsRef<[S]>,
+    {
+        if keys.len() != vectors.len() {
+            return Err(IndexError::DimensionMismatch {
+                expected: keys.len(),
+                got: vectors.len(),
+            });
+        }
+        let mut buffer = Vec::with_capacity(self.index.dimensions());
+        for (&key, vector) in keys.iter().zip(vectors) {
+            self.add_with_buffer(key, vector.as_ref(), &mut buffer)?;
+        }
+        Ok(())
+    }
+
+    /// Converts and adds vectors stored contiguously, one per key, stopping at the first error.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the length of `flat` isn't the number of keys
+    /// times the index dimensions.
+    pub fn add_flat<S: LossyCast<T>>(&self, keys: &[Key], flat: &[S]) -> Result<(), IndexError> {
+        let dimensions = self.index.dimensions();
+        if flat.len() != keys.len() * dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: keys.len() * dimensions,
+                got: flat.len(),
+            });
+        }
+        let mut buffer = Vec::with_capacity(dimensions);
+        for (&key, vector) in keys.iter().zip(flat.chunks_exact(dimensions.max(1))) {
+            self.add_with_buffer(key, vector, &mut buffer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{f16, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_ingest_as() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(10).unwrap();
+        assert!(index.ingest_as::<f16>().is_err());
+
+        let ingest = index.ingest_as::<i8>().unwrap();
+        ingest.add(1, &[0.5_f32, -0.25, 2.0]).unwrap();
+        let mut vector = [0_i8; 3];
+        index.get(1, &mut vector).unwrap();
+        assert_eq!(vector, [50, -25, 100]);
+
+        let vectors = vec![vec![0.1_f64, 0.2, 0.3], vec![-0.1, -0.2, -0.3]];
+        ingest.add_batch(&[2, 3], &vectors).unwrap();
+        ingest
+            .add_flat(&[4, 5], &[0.0_f32, 0.0, 0.1, 0.0, 0.1, 0.0])
+            .unwrap();
+        index.get(3, &mut vector).unwrap();
+        assert_eq!(vector, [-10, -20, -30]);
+        index.get(5, &mut vector).unwrap();
+        assert_eq!(vector, [0, 10, 0]);
+        assert_eq!(index.size(), 5);
+
+        assert!(ingest.add(6, &[0.0_f32; 2]).is_err());
+        assert!(ingest.add_batch(&[6, 7], &vectors[..1]).is_err());
+        assert!(ingest.add_flat(&[6], &[0.0_f32; 4]).is_err());
+        assert_eq!(index.size(), 5);
+    }
+}
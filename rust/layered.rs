@@ -0,0 +1,377 @@
+//! Composite "view-plus-delta" serving on top of an immutable base index.
+//!
+//! A common deployment memory-maps a large, read-only base index with [`Index::view`],
+//! and accumulates fresh vectors in a small in-memory delta index. Removing entries from
+//! a viewed index is impossible, so [`LayeredIndex`] tracks them in a persisted
+//! [`DeletionSet`], which is consulted automatically on every base-index search.
+
+#[cfg(not(no_fs))]
+use std::fs::File;
+#[cfg(not(no_fs))]
+use std::io::{BufReader, BufWriter};
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use roaring::RoaringTreemap;
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, Key, VectorType};
+
+/// A compact, persistable set of logically deleted keys, backed by a Roaring bitmap.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeletionSet {
+    keys: RoaringTreemap,
+}
+
+impl DeletionSet {
+    /// Creates an empty deletion set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the key as deleted. Returns `true` if it wasn't marked before.
+    pub fn insert(&mut self, key: Key) -> bool {
+        self.keys.insert(key)
+    }
+
+    /// Unmarks the key. Returns `true` if it was marked before.
+    pub fn remove(&mut self, key: Key) -> bool {
+        self.keys.remove(key)
+    }
+
+    /// Checks if the key is marked as deleted.
+    pub fn contains(&self, key: Key) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Number of keys marked as deleted.
+    pub fn len(&self) -> usize {
+        self.keys.len() as usize
+    }
+
+    /// Checks if no keys are marked as deleted.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Unmarks all keys.
+    pub fn clear(&mut self) {
+        self.keys.clear()
+    }
+
+    /// Iterates over the deleted keys in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Key> + '_ {
+        self.keys.iter()
+    }
+
+    /// Reports the size of the serialized representation in bytes.
+    pub fn serialized_length(&self) -> usize {
+        self.keys.serialized_size()
+    }
+
+    /// Serializes the set into a writer, using the portable Roaring format.
+    pub fn save_to_writer<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
+        self.keys.serialize_into(writer)
+    }
+
+    /// Deserializes the set from a reader, previously populated with [`DeletionSet::save_to_writer`].
+    pub fn load_from_reader<R: std::io::Read>(reader: R) -> std::io::Result<Self> {
+        Ok(Self {
+            keys: RoaringTreemap::deserialize_from(reader)?,
+        })
+    }
+
+    /// Saves the set to a specified file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where the set will be saved.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save_to_writer(&mut writer)?;
+        std::io::Write::flush(&mut writer)
+    }
+
+    /// Loads the set from a specified file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path from where the set will be loaded.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::load_from_reader(BufReader::new(File::open(path)?))
+    }
+}
+
+impl FromIterator<Key> for DeletionSet {
+    fn from_iter<I: IntoIterator<Item = Key>>(iter: I) -> Self {
+        Self {
+            keys: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Merges two sorted result lists into one, keeping at most `count` closest entries.
+pub(crate) fn merge_matches(first: Matches, second: Matches, count: usize) -> Matches {
+    let mut merged = Matches {
+        keys: Vec::with_capacity(count),
+        distances: Vec::with_capacity(count),
+    };
+    let (mut i, mut j) = (0, 0);
+    while merged.keys.len() < count && (i < first.keys.len() || j < second.keys.len()) {
+        let take_first = j >= second.keys.len()
+            || (i < first.keys.len() && first.distances[i] <= second.distances[j]);
+        if take_first {
+            merged.keys.push(first.keys[i]);
+            merged.distances.push(first.distances[i]);
+            i += 1;
+        } else {
+            merged.keys.push(second.keys[j]);
+            merged.distances.push(second.distances[j]);
+            j += 1;
+        }
+    }
+    merged
+}
+
+/// An immutable base index combined with a mutable delta index and a deletion set.
+///
+/// - New vectors are added to the delta index.
+/// - Removals of base entries are recorded in the [`DeletionSet`].
+/// - Re-adding a key present in the base shadows the base entry.
+/// - Searches query both indexes, skip deleted base entries, and merge the results.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{Index, IndexOptions, LayeredIndex};
+///
+/// let options = IndexOptions { dimensions: 2, ..Default::default() };
+/// let base = Index::new(&options).unwrap();
+/// base.reserve(10).unwrap();
+/// base.add(1, &[1.0_f32, 0.0]).unwrap();
+/// base.add(2, &[0.0_f32, 1.0]).unwrap();
+///
+/// let mut layered = LayeredIndex::new(base, Index::new(&options).unwrap());
+/// layered.remove(1).unwrap();
+/// layered.add(3, &[1.0_f32, 0.1]).unwrap();
+///
+/// let results = layered.search(&[1.0_f32, 0.0], 10).unwrap();
+/// assert_eq!(results.keys, vec![3, 2]);
+/// ```
+pub struct LayeredIndex {
+    base: Index,
+    delta: Index,
+    deletions: DeletionSet,
+}
+
+impl LayeredIndex {
+    /// Combines a base and a delta index, with no deletions applied.
+    pub fn new(base: Index, delta: Index) -> Self {
+        Self::with_deletions(base, delta, DeletionSet::new())
+    }
+
+    /// Combines a base and a delta index, applying a previously persisted deletion set.
+    pub fn with_deletions(base: Index, delta: Index, deletions: DeletionSet) -> Self {
+        Self {
+            base,
+            delta,
+            deletions,
+        }
+    }
+
+    /// The immutable base index.
+    pub fn base(&self) -> &Index {
+        &self.base
+    }
+
+    /// The mutable delta index, receiving all new vectors.
+    pub fn delta(&self) -> &Index {
+        &self.delta
+    }
+
+    /// The keys logically deleted from the base index.
+    pub fn deletions(&self) -> &DeletionSet {
+        &self.deletions
+    }
+
+    /// Decomposes the composite view into its parts.
+    pub fn into_parts(self) -> (Index, Index, DeletionSet) {
+        (self.base, self.delta, self.deletions)
+    }
+
+    /// Checks if the key is visible through the composite view.
+    pub fn contains(&self, key: Key) -> bool {
+        self.delta.contains(key) || (self.base.contains(key) && !self.deletions.contains(key))
+    }
+
+    /// Number of vectors visible through the composite view.
+    ///
+    /// Keys present in both layers are only counted once, as the delta shadows the base.
+    pub fn size(&self) -> usize {
+        let hidden: usize = self.deletions.iter().map(|key| self.base.count(key)).sum();
+        self.base.size() - hidden + self.delta.size()
+    }
+
+    /// Adds a vector to the delta index, shadowing any base entry under the same key.
+    /// The delta index capacity is grown automatically.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the vector doesn't have the dimensions of the index.
+    pub fn add<T: VectorType>(&mut self, key: Key, vector: &[T]) -> Result<(), IndexError> {
+        self.delta.add(key, vector)?;
+        if self.base.contains(key) {
+            self.deletions.insert(key);
+        }
+        Ok(())
+    }
+
+    /// Removes a key from both layers. Returns the number of removed vectors.
+    pub fn remove(&mut self, key: Key) -> Result<usize, IndexError> {
+        let mut removed = self.delta.remove(key)?;
+        if self.base.contains(key) && self.deletions.insert(key) {
+            removed += self.base.count(key);
+        }
+        Ok(removed)
+    }
+
+    /// Performs k-Approximate Nearest Neighbors Search over both layers, skipping deleted base entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to search for.
+    ///
+    /// # Returns
+    ///
+    /// The merged matches, or `IndexError::DimensionMismatch` if the query doesn't have the
+    /// dimensions of the index.
+    pub fn search<T: VectorType>(&self, query: &[T], count: usize) -> Result<Matches, IndexError> {
+        self.filtered_search(query, count, |_| true)
+    }
+
+    /// Performs k-Approximate Nearest Neighbors Search over both layers, with a custom predicate
+    /// applied on top of the deletion set.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to search for.
+    /// * `filter` - A closure that takes a `Key` and returns `true` if the corresponding vector should be included.
+    pub fn filtered_search<T: VectorType, F>(
+        &self,
+        query: &[T],
+        count: usize,
+        filter: F,
+    ) -> Result<Matches, IndexError>
+    where
+        F: Fn(Key) -> bool,
+    {
+        let base = if self.deletions.is_empty() {
+            self.base.filtered_search(query, count, &filter)?
+        } else {
+            let deletions = &self.deletions;
+            self.base
+                .filtered_search(query, count, |key| !deletions.contains(key) && filter(key))?
+        };
+        let delta = self.delta.filtered_search(query, count, &filter)?;
+        Ok(merge_matches(base, delta, count))
+    }
+
+    /// Saves the deletion set next to the base index, so it can be re-applied on restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where the deletion set will be saved.
+    #[cfg(not(no_fs))]
+    pub fn save_deletions<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        self.deletions.save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexOptions;
+
+    fn make_index(vectors: &[(Key, [f32; 2])]) -> Index {
+        let options = IndexOptions {
+            dimensions: 2,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(vectors.len().max(1)).unwrap();
+        for (key, vector) in vectors {
+            index.add(*key, vector).unwrap();
+        }
+        index
+    }
+
+    #[test]
+    fn test_deletions_hide_base_entries() {
+        let base = make_index(&[(1, [1.0, 0.0]), (2, [0.0, 1.0]), (3, [1.0, 1.0])]);
+        let mut layered = LayeredIndex::new(base, make_index(&[]));
+
+        assert_eq!(layered.remove(1).unwrap(), 1);
+        layered.add(4, &[1.0_f32, 0.1]).unwrap();
+        assert!(!layered.contains(1));
+        assert!(layered.contains(4));
+        assert_eq!(layered.size(), 3);
+
+        let results = layered.search(&[1.0_f32, 0.0], 10).unwrap();
+        assert_eq!(results.keys.len(), 3);
+        assert_eq!(results.keys[0], 4);
+        assert!(!results.keys.contains(&1));
+
+        assert!(matches!(
+            layered.search(&[1.0_f32, 0.0, 0.0], 10),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 3
+            })
+        ));
+        assert!(matches!(
+            layered.add(5, &[1.0_f32]),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+        assert!(!layered.contains(5));
+    }
+
+    #[test]
+    fn test_size_hides_every_base_vector_of_a_key() {
+        let base = Index::new(&IndexOptions {
+            dimensions: 2,
+            multi: true,
+            ..Default::default()
+        })
+        .unwrap();
+        base.reserve(4).unwrap();
+        base.add(1, &[1.0_f32, 0.0]).unwrap();
+        base.add(1, &[0.9_f32, 0.1]).unwrap();
+        base.add(1, &[0.8_f32, 0.2]).unwrap();
+        base.add(2, &[0.0_f32, 1.0]).unwrap();
+        let mut layered = LayeredIndex::new(base, make_index(&[]));
+        assert_eq!(layered.size(), 4);
+
+        layered.remove(1).unwrap();
+        assert_eq!(layered.size(), 1);
+        layered.add(1, &[1.0_f32, 0.0]).unwrap();
+        assert_eq!(layered.size(), 2);
+    }
+
+    #[test]
+    #[cfg(not(no_fs))]
+    fn test_deletion_set_round_trip() {
+        let deletions: DeletionSet = [5, 1 << 40, 7].into_iter().collect();
+        let path = std::env::temp_dir().join("usearch.rust.deletions.bin");
+        deletions.save(&path).unwrap();
+        let restored = DeletionSet::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored, deletions);
+        assert!(restored.contains(1 << 40));
+        assert_eq!(restored.iter().collect::<Vec<_>>(), vec![5, 7, 1 << 40]);
+    }
+}
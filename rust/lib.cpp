@@ -1,6 +1,10 @@
 #include "lib.hpp"
 #include "usearch/rust/lib.rs.h"
 
+#if !defined(USEARCH_DEFINED_WINDOWS)
+#include <sys/resource.h> // `getrlimit`
+#endif
+
 using namespace unum::usearch;
 using namespace unum;
 
@@ -10,6 +14,14 @@ using search_result_t = typename index_t::search_result_t;
 using labeling_result_t = typename index_t::labeling_result_t;
 using vector_key_t = typename index_dense_t::vector_key_t;
 
+#define USEARCH_RUST_STRINGIFY_(x) #x
+#define USEARCH_RUST_STRINGIFY(x) USEARCH_RUST_STRINGIFY_(x)
+
+/// The version of the bridge, looked up by `build.rs` in pre-built libraries, which must be
+/// compiled from the same version of this file and of the header generated for `rust/lib.rs`.
+extern "C" char const usearch_rust_abi[] = "usearch-rust-abi/" USEARCH_RUST_STRINGIFY(USEARCH_VERSION_MAJOR) "." //
+    USEARCH_RUST_STRINGIFY(USEARCH_VERSION_MINOR) "." USEARCH_RUST_STRINGIFY(USEARCH_VERSION_PATCH);
+
 metric_kind_t rust_to_cpp_metric(MetricKind value) {
     switch (value) {
     case MetricKind::IP: return metric_kind_t::ip_k;
@@ -36,6 +48,74 @@ scalar_kind_t rust_to_cpp_scalar(ScalarKind value) {
     }
 }
 
+MetricKind cpp_to_rust_metric(metric_kind_t value) {
+    switch (value) {
+    case metric_kind_t::ip_k: return MetricKind::IP;
+    case metric_kind_t::l2sq_k: return MetricKind::L2sq;
+    case metric_kind_t::cos_k: return MetricKind::Cos;
+    case metric_kind_t::pearson_k: return MetricKind::Pearson;
+    case metric_kind_t::haversine_k: return MetricKind::Haversine;
+    case metric_kind_t::divergence_k: return MetricKind::Divergence;
+    case metric_kind_t::hamming_k: return MetricKind::Hamming;
+    case metric_kind_t::tanimoto_k: return MetricKind::Tanimoto;
+    case metric_kind_t::sorensen_k: return MetricKind::Sorensen;
+    default: return MetricKind::Unknown;
+    }
+}
+
+ScalarKind cpp_to_rust_scalar(scalar_kind_t value) {
+    switch (value) {
+    case scalar_kind_t::i8_k: return ScalarKind::I8;
+    case scalar_kind_t::f16_k: return ScalarKind::F16;
+    case scalar_kind_t::f32_k: return ScalarKind::F32;
+    case scalar_kind_t::f64_k: return ScalarKind::F64;
+    case scalar_kind_t::b1x8_k: return ScalarKind::B1;
+    default: return ScalarKind::Unknown;
+    }
+}
+
+/**
+ *  @brief  Reinterprets a slice as a single vector, after checking it holds exactly one vector of the
+ *          index dimensions, as the native index reads that many scalars whatever the slice length.
+ */
+template <typename scalar_at> std::size_t vector_length_(index_dense_t const& index) {
+    return std::is_same<scalar_at, b1x8_t>::value ? divide_round_up<8>(index.dimensions()) : index.dimensions();
+}
+
+template <typename scalar_at, typename element_at>
+scalar_at const* vector_(index_dense_t const& index, rust::Slice<element_at const> vec) {
+    if (vec.size() != vector_length_<scalar_at>(index))
+        throw std::invalid_argument("Vector length must match index dimensionality");
+    return reinterpret_cast<scalar_at const*>(vec.data());
+}
+
+/**
+ *  @brief  Inserts vectors packed row-major, one per key, on all hardware threads. Stops at the first
+ *          failure, which is re-thrown on the calling thread, as workers can't propagate exceptions.
+ */
+template <typename scalar_at, typename element_at>
+void add_many_(index_dense_t& index, rust::Slice<vector_key_t const> keys, rust::Slice<element_at const> vectors) {
+    std::size_t stride = vector_length_<scalar_at>(index);
+    if (vectors.size() != keys.size() * stride)
+        throw std::invalid_argument("Vectors length must be the number of keys times the index dimensionality");
+    scalar_at const* data = reinterpret_cast<scalar_at const*>(vectors.data());
+    std::mutex failure_mutex;
+    std::string failure;
+    executor_default_t executor;
+    executor.dynamic(keys.size(), [&](std::size_t, std::size_t task) {
+        add_result_t result = index.add(keys[task], data + task * stride);
+        if (result)
+            return true;
+        std::lock_guard<std::mutex> lock(failure_mutex);
+        char const* message = result.error.release();
+        if (failure.empty())
+            failure = message;
+        return false;
+    });
+    if (!failure.empty())
+        throw std::runtime_error(failure);
+}
+
 template <typename scalar_at, typename predicate_at = dummy_predicate_t>
 Matches search_(index_dense_t& index, scalar_at const* vec, size_t count, predicate_at&& predicate = predicate_at{}) {
     Matches matches;
@@ -51,6 +131,57 @@ Matches search_(index_dense_t& index, scalar_at const* vec, size_t count, predic
     return matches;
 }
 
+template <typename scalar_at>
+size_t search_into_(index_dense_t& index, scalar_at const* vec, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) {
+    if (keys.size() != distances.size())
+        throw std::invalid_argument("Keys and distances buffers must have the same length");
+    search_result_t result = index.search(vec, keys.size());
+    result.error.raise();
+    return result.dump_to(keys.data(), distances.data());
+}
+
+/**
+ *  @brief  Searches for queries packed row-major on all hardware threads, writing the matches of every
+ *          query into its own row of `count` slots. Stops at the first failure, like `add_many_`.
+ */
+template <typename scalar_at, typename element_at>
+BatchMatches search_many_(index_dense_t& index, rust::Slice<element_at const> queries, size_t count) {
+    std::size_t stride = vector_length_<scalar_at>(index);
+    if (!stride || queries.size() % stride)
+        throw std::invalid_argument("Queries length must be a multiple of the index dimensionality");
+    std::size_t queries_count = queries.size() / stride;
+    BatchMatches batch;
+    batch.count = count;
+    batch.keys.reserve(queries_count * count);
+    batch.distances.reserve(queries_count * count);
+    batch.counts.reserve(queries_count);
+    for (size_t i = 0; i != queries_count * count; ++i)
+        batch.keys.push_back(0), batch.distances.push_back(0);
+    for (size_t i = 0; i != queries_count; ++i)
+        batch.counts.push_back(0);
+
+    scalar_at const* data = reinterpret_cast<scalar_at const*>(queries.data());
+    std::mutex failure_mutex;
+    std::string failure;
+    executor_default_t executor;
+    executor.dynamic(queries_count, [&](std::size_t, std::size_t task) {
+        search_result_t result = index.search(data + task * stride, count);
+        if (result) {
+            std::size_t offset = task * count;
+            batch.counts[task] = result.dump_to(batch.keys.data() + offset, batch.distances.data() + offset);
+            return true;
+        }
+        std::lock_guard<std::mutex> lock(failure_mutex);
+        char const* message = result.error.release();
+        if (failure.empty())
+            failure = message;
+        return false;
+    });
+    if (!failure.empty())
+        throw std::runtime_error(failure);
+    return batch;
+}
+
 NativeIndex::NativeIndex(std::unique_ptr<index_t> index) : index_(std::move(index)) {}
 
 auto make_predicate(uptr_t metric, uptr_t metric_state) {
@@ -61,24 +192,97 @@ auto make_predicate(uptr_t metric, uptr_t metric_state) {
     };
 }
 
+template <typename scalar_at>
+DeadlineMatches search_until_(index_dense_t& index, scalar_at const* vec, size_t count, uint64_t budget_ns,
+                              uptr_t filter_function, uptr_t filter_state) {
+    // Clamp the budget, so that the deadline can't overflow the clock
+    auto budget = std::chrono::nanoseconds((std::min<uint64_t>)(budget_ns, uint64_t(1) << 62));
+    auto deadline = std::chrono::steady_clock::now() + budget;
+
+    DeadlineMatches result;
+    Matches& matches = result.matches;
+    matches.keys.reserve(count);
+    matches.distances.reserve(count);
+    for (size_t i = 0; i != count; ++i)
+        matches.keys.push_back(0), matches.distances.push_back(0);
+    search_result_t found = filter_function
+                                ? index.search_until(vec, count, deadline, make_predicate(filter_function, filter_state))
+                                : index.search_until(vec, count, deadline, dummy_predicate_t{});
+    found.error.raise();
+    count = found.dump_to(matches.keys.data(), matches.distances.data());
+    matches.keys.truncate(count);
+    matches.distances.truncate(count);
+    result.truncated = found.truncated;
+    return result;
+}
+
+template <typename scalar_at>
+TracedMatches search_traced_(index_dense_t& index, scalar_at const* vec, size_t count) {
+    TracedMatches result;
+    Matches& matches = result.matches;
+    matches.keys.reserve(count);
+    matches.distances.reserve(count);
+    for (size_t i = 0; i != count; ++i)
+        matches.keys.push_back(0), matches.distances.push_back(0);
+    search_result_t found = index.search(vec, count);
+    found.error.raise();
+    count = found.dump_to(matches.keys.data(), matches.distances.data());
+    matches.keys.truncate(count);
+    matches.distances.truncate(count);
+    result.stats.visited = found.visited_members;
+    result.stats.distance_computations = found.computed_distances;
+    result.stats.hops = found.hops;
+    return result;
+}
+
 // clang-format off
-void NativeIndex::add_b1x8(vector_key_t key, rust::Slice<uint8_t const> vec) const { index_->add(key, (b1x8_t const*)vec.data()).error.raise(); }
-void NativeIndex::add_i8(vector_key_t key, rust::Slice<int8_t const> vec) const { index_->add(key, vec.data()).error.raise(); }
-void NativeIndex::add_f16(vector_key_t key, rust::Slice<int16_t const> vec) const { index_->add(key, (f16_t const*)vec.data()).error.raise(); }
-void NativeIndex::add_f32(vector_key_t key, rust::Slice<float const> vec) const { index_->add(key, vec.data()).error.raise(); }
-void NativeIndex::add_f64(vector_key_t key, rust::Slice<double const> vec) const { index_->add(key, vec.data()).error.raise(); }
-
-Matches NativeIndex::search_b1x8(rust::Slice<uint8_t const> vec, size_t count) const { return search_(*index_, (b1x8_t const*)vec.data(), count); }
-Matches NativeIndex::search_i8(rust::Slice<int8_t const> vec, size_t count) const { return search_(*index_, vec.data(), count); }
-Matches NativeIndex::search_f16(rust::Slice<int16_t const> vec, size_t count) const { return search_(*index_, (f16_t const*)vec.data(), count); }
-Matches NativeIndex::search_f32(rust::Slice<float const> vec, size_t count) const { return search_(*index_, vec.data(), count); }
-Matches NativeIndex::search_f64(rust::Slice<double const> vec, size_t count) const { return search_(*index_, vec.data(), count); }
-
-Matches NativeIndex::filtered_search_b1x8(rust::Slice<uint8_t const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, (b1x8_t const*)vec.data(), count, make_predicate(metric, metric_state)); }
-Matches NativeIndex::filtered_search_i8(rust::Slice<int8_t const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vec.data(), count, make_predicate(metric, metric_state)); }
-Matches NativeIndex::filtered_search_f16(rust::Slice<int16_t const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, (f16_t const*)vec.data(), count, make_predicate(metric, metric_state)); }
-Matches NativeIndex::filtered_search_f32(rust::Slice<float const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vec.data(), count, make_predicate(metric, metric_state)); }
-Matches NativeIndex::filtered_search_f64(rust::Slice<double const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vec.data(), count, make_predicate(metric, metric_state)); }
+void NativeIndex::add_b1x8(vector_key_t key, rust::Slice<uint8_t const> vec) const { index_->add(key, vector_<b1x8_t>(*index_, vec)).error.raise(); }
+void NativeIndex::add_i8(vector_key_t key, rust::Slice<int8_t const> vec) const { index_->add(key, vector_<int8_t>(*index_, vec)).error.raise(); }
+void NativeIndex::add_f16(vector_key_t key, rust::Slice<int16_t const> vec) const { index_->add(key, vector_<f16_t>(*index_, vec)).error.raise(); }
+void NativeIndex::add_f32(vector_key_t key, rust::Slice<float const> vec) const { index_->add(key, vector_<float>(*index_, vec)).error.raise(); }
+void NativeIndex::add_f64(vector_key_t key, rust::Slice<double const> vec) const { index_->add(key, vector_<double>(*index_, vec)).error.raise(); }
+
+void NativeIndex::add_many_b1x8(rust::Slice<vector_key_t const> keys, rust::Slice<uint8_t const> vectors) const { add_many_<b1x8_t>(*index_, keys, vectors); }
+void NativeIndex::add_many_i8(rust::Slice<vector_key_t const> keys, rust::Slice<int8_t const> vectors) const { add_many_<int8_t>(*index_, keys, vectors); }
+void NativeIndex::add_many_f16(rust::Slice<vector_key_t const> keys, rust::Slice<int16_t const> vectors) const { add_many_<f16_t>(*index_, keys, vectors); }
+void NativeIndex::add_many_f32(rust::Slice<vector_key_t const> keys, rust::Slice<float const> vectors) const { add_many_<float>(*index_, keys, vectors); }
+void NativeIndex::add_many_f64(rust::Slice<vector_key_t const> keys, rust::Slice<double const> vectors) const { add_many_<double>(*index_, keys, vectors); }
+
+Matches NativeIndex::search_b1x8(rust::Slice<uint8_t const> vec, size_t count) const { return search_(*index_, vector_<b1x8_t>(*index_, vec), count); }
+Matches NativeIndex::search_i8(rust::Slice<int8_t const> vec, size_t count) const { return search_(*index_, vector_<int8_t>(*index_, vec), count); }
+Matches NativeIndex::search_f16(rust::Slice<int16_t const> vec, size_t count) const { return search_(*index_, vector_<f16_t>(*index_, vec), count); }
+Matches NativeIndex::search_f32(rust::Slice<float const> vec, size_t count) const { return search_(*index_, vector_<float>(*index_, vec), count); }
+Matches NativeIndex::search_f64(rust::Slice<double const> vec, size_t count) const { return search_(*index_, vector_<double>(*index_, vec), count); }
+
+Matches NativeIndex::filtered_search_b1x8(rust::Slice<uint8_t const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vector_<b1x8_t>(*index_, vec), count, make_predicate(metric, metric_state)); }
+Matches NativeIndex::filtered_search_i8(rust::Slice<int8_t const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vector_<int8_t>(*index_, vec), count, make_predicate(metric, metric_state)); }
+Matches NativeIndex::filtered_search_f16(rust::Slice<int16_t const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vector_<f16_t>(*index_, vec), count, make_predicate(metric, metric_state)); }
+Matches NativeIndex::filtered_search_f32(rust::Slice<float const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vector_<float>(*index_, vec), count, make_predicate(metric, metric_state)); }
+Matches NativeIndex::filtered_search_f64(rust::Slice<double const> vec, size_t count, uptr_t metric, uptr_t metric_state) const { return search_(*index_, vector_<double>(*index_, vec), count, make_predicate(metric, metric_state)); }
+
+DeadlineMatches NativeIndex::search_until_b1x8(rust::Slice<uint8_t const> vec, size_t count, uint64_t budget_ns, uptr_t filter, uptr_t filter_state) const { return search_until_(*index_, vector_<b1x8_t>(*index_, vec), count, budget_ns, filter, filter_state); }
+DeadlineMatches NativeIndex::search_until_i8(rust::Slice<int8_t const> vec, size_t count, uint64_t budget_ns, uptr_t filter, uptr_t filter_state) const { return search_until_(*index_, vector_<int8_t>(*index_, vec), count, budget_ns, filter, filter_state); }
+DeadlineMatches NativeIndex::search_until_f16(rust::Slice<int16_t const> vec, size_t count, uint64_t budget_ns, uptr_t filter, uptr_t filter_state) const { return search_until_(*index_, vector_<f16_t>(*index_, vec), count, budget_ns, filter, filter_state); }
+DeadlineMatches NativeIndex::search_until_f32(rust::Slice<float const> vec, size_t count, uint64_t budget_ns, uptr_t filter, uptr_t filter_state) const { return search_until_(*index_, vector_<float>(*index_, vec), count, budget_ns, filter, filter_state); }
+DeadlineMatches NativeIndex::search_until_f64(rust::Slice<double const> vec, size_t count, uint64_t budget_ns, uptr_t filter, uptr_t filter_state) const { return search_until_(*index_, vector_<double>(*index_, vec), count, budget_ns, filter, filter_state); }
+
+TracedMatches NativeIndex::search_traced_b1x8(rust::Slice<uint8_t const> vec, size_t count) const { return search_traced_(*index_, vector_<b1x8_t>(*index_, vec), count); }
+TracedMatches NativeIndex::search_traced_i8(rust::Slice<int8_t const> vec, size_t count) const { return search_traced_(*index_, vector_<int8_t>(*index_, vec), count); }
+TracedMatches NativeIndex::search_traced_f16(rust::Slice<int16_t const> vec, size_t count) const { return search_traced_(*index_, vector_<f16_t>(*index_, vec), count); }
+TracedMatches NativeIndex::search_traced_f32(rust::Slice<float const> vec, size_t count) const { return search_traced_(*index_, vector_<float>(*index_, vec), count); }
+TracedMatches NativeIndex::search_traced_f64(rust::Slice<double const> vec, size_t count) const { return search_traced_(*index_, vector_<double>(*index_, vec), count); }
+
+size_t NativeIndex::search_into_b1x8(rust::Slice<uint8_t const> vec, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const { return search_into_(*index_, vector_<b1x8_t>(*index_, vec), keys, distances); }
+size_t NativeIndex::search_into_i8(rust::Slice<int8_t const> vec, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const { return search_into_(*index_, vector_<int8_t>(*index_, vec), keys, distances); }
+size_t NativeIndex::search_into_f16(rust::Slice<int16_t const> vec, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const { return search_into_(*index_, vector_<f16_t>(*index_, vec), keys, distances); }
+size_t NativeIndex::search_into_f32(rust::Slice<float const> vec, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const { return search_into_(*index_, vector_<float>(*index_, vec), keys, distances); }
+size_t NativeIndex::search_into_f64(rust::Slice<double const> vec, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const { return search_into_(*index_, vector_<double>(*index_, vec), keys, distances); }
+
+BatchMatches NativeIndex::search_many_b1x8(rust::Slice<uint8_t const> queries, size_t count) const { return search_many_<b1x8_t>(*index_, queries, count); }
+BatchMatches NativeIndex::search_many_i8(rust::Slice<int8_t const> queries, size_t count) const { return search_many_<int8_t>(*index_, queries, count); }
+BatchMatches NativeIndex::search_many_f16(rust::Slice<int16_t const> queries, size_t count) const { return search_many_<f16_t>(*index_, queries, count); }
+BatchMatches NativeIndex::search_many_f32(rust::Slice<float const> queries, size_t count) const { return search_many_<float>(*index_, queries, count); }
+BatchMatches NativeIndex::search_many_f64(rust::Slice<double const> queries, size_t count) const { return search_many_<double>(*index_, queries, count); }
 
 size_t NativeIndex::get_b1x8(vector_key_t key, rust::Slice<uint8_t> vec) const { if (vec.size() % dimensions()) throw std::invalid_argument("Vector length must be a multiple of index dimensionality"); return index_->get(key, (b1x8_t*)vec.data(), vec.size() / dimensions()); }
 size_t NativeIndex::get_i8(vector_key_t key, rust::Slice<int8_t> vec) const { if (vec.size() % dimensions()) throw std::invalid_argument("Vector length must be a multiple of index dimensionality"); return index_->get(key, vec.data(), vec.size() / dimensions()); }
@@ -97,7 +301,8 @@ void NativeIndex::change_metric(uptr_t metric, uptr_t state) const {
         reinterpret_cast<std::uintptr_t>(metric),     //
         reinterpret_cast<std::uintptr_t>(state),      //
         index_->metric().metric_kind(),               //
-        index_->scalar_kind()));
+        index_->scalar_kind(),                        //
+        index_->dimensions()));
 }
 
 void NativeIndex::change_metric_kind(MetricKind metric) const {
@@ -122,22 +327,198 @@ size_t NativeIndex::rename(vector_key_t from, vector_key_t to) const {
 size_t NativeIndex::count(vector_key_t key) const { return index_->count(key); }
 bool NativeIndex::contains(vector_key_t key) const { return index_->contains(key); }
 
+size_t NativeIndex::export_keys(rust::Slice<vector_key_t> keys, size_t offset) const {
+    size_t available = index_->size() - (std::min)(offset, index_->size());
+    index_->export_keys(keys.data(), offset, keys.size());
+    return (std::min)(available, keys.size());
+}
+
+/**
+ *  @brief  Averages the vectors stored under the given keys into `centroid`, converting them to
+ *          `f32` in batches and accumulating in `f64`. Keys with several vectors contribute all
+ *          of them, and missing keys none.
+ *  @return The number of vectors averaged, leaving `centroid` untouched if zero.
+ */
+static size_t average_vectors_(index_t const& index, vector_key_t const* keys, size_t keys_count,
+                               rust::Slice<float> centroid) {
+    size_t dimensions = index.dimensions();
+    if (centroid.size() != dimensions)
+        throw std::invalid_argument("Centroid length must match index dimensionality");
+
+    std::vector<double> sums(dimensions, 0.0);
+    std::vector<float> buffer;
+    size_t averaged = 0;
+    for (size_t i = 0; i != keys_count; ++i) {
+        size_t count = index.count(keys[i]);
+        if (!count)
+            continue;
+        buffer.resize(count * dimensions);
+        count = index.get(keys[i], buffer.data(), count);
+        for (float const* vector = buffer.data(); vector != buffer.data() + count * dimensions;
+             vector += dimensions)
+            for (size_t j = 0; j != dimensions; ++j)
+                sums[j] += vector[j];
+        averaged += count;
+    }
+    if (averaged)
+        for (size_t j = 0; j != dimensions; ++j)
+            centroid[j] = static_cast<float>(sums[j] / averaged);
+    return averaged;
+}
+
+size_t NativeIndex::centroid(rust::Slice<vector_key_t const> keys, rust::Slice<float> centroid) const {
+    return average_vectors_(*index_, keys.data(), keys.size(), centroid);
+}
+
+size_t NativeIndex::mean_all(rust::Slice<float> centroid) const {
+    // Keys with several vectors are exported once per vector, but averaged once per key.
+    std::vector<vector_key_t> keys(index_->size());
+    index_->export_keys(keys.data(), 0, keys.size());
+    if (index_->multi()) {
+        std::sort(keys.begin(), keys.end());
+        keys.erase(std::unique(keys.begin(), keys.end()), keys.end());
+    }
+    return average_vectors_(*index_, keys.data(), keys.size(), centroid);
+}
+
 void NativeIndex::reserve(size_t capacity) const { index_->reserve(capacity); }
 
 size_t NativeIndex::dimensions() const { return index_->dimensions(); }
 size_t NativeIndex::connectivity() const { return index_->connectivity(); }
 size_t NativeIndex::size() const { return index_->size(); }
+size_t NativeIndex::deleted() const { return index_->stats().nodes - index_->size(); }
+bool NativeIndex::multi() const { return index_->multi(); }
+MetricKind NativeIndex::metric_kind() const { return cpp_to_rust_metric(index_->metric().metric_kind()); }
+ScalarKind NativeIndex::scalar_kind() const { return cpp_to_rust_scalar(index_->scalar_kind()); }
+size_t NativeIndex::max_level() const { return index_->max_level(); }
+
+LevelStats NativeIndex::level_stats(size_t level) const {
+    auto stats = index_->stats(level);
+    return {stats.nodes, stats.edges, stats.max_edges, stats.allocated_bytes};
+}
+
+size_t NativeIndex::slots() const { return index_->slots(); }
+size_t NativeIndex::entry_slot() const { return index_->entry_slot(); }
+vector_key_t NativeIndex::free_key() const { return index_->free_key(); }
+
+vector_key_t NativeIndex::slot_key(size_t slot) const {
+    if (slot >= index_->slots())
+        throw std::out_of_range("Slot is out of range");
+    return index_->slot_key(slot);
+}
+
+rust::Vec<uint64_t> NativeIndex::key_slots(vector_key_t key) const {
+    rust::Vec<uint64_t> slots;
+    index_->for_each_slot(key, [&](size_t slot) { slots.push_back(slot); });
+    return slots;
+}
+
+size_t NativeIndex::slot_level(size_t slot) const {
+    if (slot >= index_->slots())
+        throw std::out_of_range("Slot is out of range");
+    return index_->level_at(slot);
+}
+
+rust::Vec<uint64_t> NativeIndex::slot_neighbors(size_t slot, size_t level) const {
+    if (slot >= index_->slots())
+        throw std::out_of_range("Slot is out of range");
+    if (level > index_->level_at(slot))
+        throw std::out_of_range("The node doesn't reach that level");
+    rust::Vec<uint64_t> neighbors;
+    index_->for_each_neighbor(slot, level, [&](size_t neighbor) { neighbors.push_back(neighbor); });
+    return neighbors;
+}
+
 size_t NativeIndex::capacity() const { return index_->capacity(); }
+
+double NativeIndex::level_multiplier() const {
+    double multiplier = index_->config().level_multiplier;
+    return multiplier > 0 ? multiplier : 1.0 / std::log(static_cast<double>(index_->connectivity()));
+}
+
+uint64_t NativeIndex::level_seed() const { return index_->config().level_seed; }
 size_t NativeIndex::serialized_length() const { return index_->serialized_length(); }
 
-void NativeIndex::save(rust::Str path) const { index_->save(output_file_t(std::string(path).c_str())).error.raise(); }
-void NativeIndex::load(rust::Str path) const { index_->load(input_file_t(std::string(path).c_str())).error.raise(); }
-void NativeIndex::view(rust::Str path) const {
-    index_->view(memory_mapped_file_t(std::string(path).c_str())).error.raise();
+/**
+ *  @brief  Copies the raw bytes of an OS path into a NUL-terminated string, rejecting paths that
+ *          would be silently truncated at an interior NUL byte and open another file.
+ */
+static std::string native_path_(rust::Slice<uint8_t const> path) {
+    std::string result(reinterpret_cast<char const*>(path.data()), path.size());
+    if (result.empty())
+        throw std::invalid_argument("The path is empty");
+    if (result.find('\0') != std::string::npos)
+        throw std::invalid_argument("The path contains a NUL byte");
+    return result;
+}
+
+void NativeIndex::save(rust::Slice<uint8_t const> path) const {
+    std::string file = native_path_(path);
+    index_->save(output_file_t(file.c_str())).error.raise();
+}
+
+void NativeIndex::load(rust::Slice<uint8_t const> path) const {
+    std::string file = native_path_(path);
+    index_->load(input_file_t(file.c_str())).error.raise();
+}
+
+void NativeIndex::view(rust::Slice<uint8_t const> path) const {
+    std::string file = native_path_(path);
+    index_->view(memory_mapped_file_t(file.c_str())).error.raise();
+    if (index_->memory_locked())
+        index_->lock_memory();
 }
 
 void NativeIndex::reset() const { index_->reset(); }
 size_t NativeIndex::memory_usage() const { return index_->memory_usage(); }
+
+MemoryBreakdown NativeIndex::memory_breakdown() const {
+    auto breakdown = index_->memory_breakdown();
+    return {breakdown.graph_bytes, breakdown.vectors_bytes, breakdown.tombstones_bytes, breakdown.slack_bytes};
+}
+
+size_t NativeIndex::warmup(size_t budget_bytes) const { return index_->warmup(budget_bytes); }
+
+/// The `RLIMIT_MEMLOCK` limit of the process, in bytes, or `SIZE_MAX` if there is none.
+static size_t memory_lock_limit() {
+#if defined(USEARCH_DEFINED_WINDOWS)
+    return SIZE_MAX;
+#else
+    struct rlimit limit;
+    if (getrlimit(RLIMIT_MEMLOCK, &limit) != 0 || limit.rlim_cur == RLIM_INFINITY)
+        return SIZE_MAX;
+    return static_cast<size_t>(limit.rlim_cur);
+#endif
+}
+
+MemoryLock NativeIndex::lock_memory() const {
+    auto result = index_->lock_memory();
+    return {result.bytes, memory_lock_limit(), result.error};
+}
+
+void NativeIndex::unlock_memory() const { index_->unlock_memory(); }
+bool NativeIndex::memory_locked() const { return index_->memory_locked(); }
+
+void NativeIndex::change_vector_pages(VectorPages pages) const {
+    pages_config_t config;
+    switch (pages) {
+    case VectorPages::TransparentHuge: config.kind = pages_config_t::transparent_huge_k; break;
+    case VectorPages::HugeTlb: config.kind = pages_config_t::huge_tlb_k; break;
+    default: config.kind = pages_config_t::regular_k; break;
+    }
+    if (!index_->change_vector_pages(config))
+        throw std::runtime_error("Vector pages can only be changed before vectors are stored");
+}
+
+void NativeIndex::change_vector_allocator(uptr_t allocate, uptr_t deallocate, uptr_t state) const {
+    pages_config_t config;
+    config.kind = pages_config_t::custom_k;
+    config.allocate = reinterpret_cast<pages_config_t::allocate_t>(allocate);
+    config.deallocate = reinterpret_cast<pages_config_t::deallocate_t>(deallocate);
+    config.state = static_cast<std::uintptr_t>(state);
+    if (!index_->change_vector_pages(config))
+        throw std::runtime_error("The vector allocator can only be changed before vectors are stored");
+}
 char const* NativeIndex::hardware_acceleration() const { return index_->metric().isa_name(); }
 
 void NativeIndex::save_to_buffer(rust::Slice<uint8_t> buffer) const {
@@ -150,6 +531,8 @@ void NativeIndex::load_from_buffer(rust::Slice<uint8_t const> buffer) const {
 
 void NativeIndex::view_from_buffer(rust::Slice<uint8_t const> buffer) const {
     index_->view(memory_mapped_file_t((byte_t*)buffer.data(), buffer.size())).error.raise();
+    if (index_->memory_locked())
+        index_->lock_memory();
 }
 
 std::unique_ptr<NativeIndex> wrap(index_t&& index) {
@@ -168,5 +551,15 @@ std::unique_ptr<NativeIndex> new_native_index(IndexOptions const& options) {
         throw std::invalid_argument("Unsupported metric or scalar type");
     index_dense_config_t config(options.connectivity, options.expansion_add, options.expansion_search);
     config.multi = options.multi;
+    config.level_multiplier = options.level_multiplier;
+    config.level_seed = options.level_seed;
     return wrap(index_t::make(metric, config));
 }
+
+void raise_injected_failure(rust::Str operation) {
+    throw std::runtime_error("Injected failure in `" + std::string(operation) + "`");
+}
+
+void raise_callback_panic(rust::Str message) {
+    throw std::runtime_error("Callback panicked: " + std::string(message));
+}
@@ -3,9 +3,16 @@
 
 // We don't have to forward decalre all of those:
 struct Matches;
+struct DeadlineMatches;
+struct TracedMatches;
+struct BatchMatches;
+struct LevelStats;
+struct MemoryBreakdown;
+struct MemoryLock;
 struct IndexOptions;
 enum class MetricKind;
 enum class ScalarKind;
+enum class VectorPages;
 
 #include <usearch/index_dense.hpp> // `unum::usearch::index_dense_t`
 
@@ -32,6 +39,12 @@ class NativeIndex {
     void add_f32(vector_key_t key, rust::Slice<float const> vector) const;
     void add_f64(vector_key_t key, rust::Slice<double const> vector) const;
 
+    void add_many_b1x8(rust::Slice<vector_key_t const> keys, rust::Slice<uint8_t const> vectors) const;
+    void add_many_i8(rust::Slice<vector_key_t const> keys, rust::Slice<int8_t const> vectors) const;
+    void add_many_f16(rust::Slice<vector_key_t const> keys, rust::Slice<int16_t const> vectors) const;
+    void add_many_f32(rust::Slice<vector_key_t const> keys, rust::Slice<float const> vectors) const;
+    void add_many_f64(rust::Slice<vector_key_t const> keys, rust::Slice<double const> vectors) const;
+
     Matches search_b1x8(rust::Slice<uint8_t const> query, size_t count) const;
     Matches search_i8(rust::Slice<int8_t const> query, size_t count) const;
     Matches search_f16(rust::Slice<int16_t const> query, size_t count) const;
@@ -44,6 +57,30 @@ class NativeIndex {
     Matches filtered_search_f16(rust::Slice<int16_t const> query, size_t count, uptr_t filter_function, uptr_t filter_state) const;
     Matches filtered_search_f32(rust::Slice<float const> query, size_t count, uptr_t filter_function, uptr_t filter_state) const;
     Matches filtered_search_f64(rust::Slice<double const> query, size_t count, uptr_t filter_function, uptr_t filter_state) const;
+
+    DeadlineMatches search_until_b1x8(rust::Slice<uint8_t const> query, size_t count, uint64_t budget_ns, uptr_t filter_function, uptr_t filter_state) const;
+    DeadlineMatches search_until_i8(rust::Slice<int8_t const> query, size_t count, uint64_t budget_ns, uptr_t filter_function, uptr_t filter_state) const;
+    DeadlineMatches search_until_f16(rust::Slice<int16_t const> query, size_t count, uint64_t budget_ns, uptr_t filter_function, uptr_t filter_state) const;
+    DeadlineMatches search_until_f32(rust::Slice<float const> query, size_t count, uint64_t budget_ns, uptr_t filter_function, uptr_t filter_state) const;
+    DeadlineMatches search_until_f64(rust::Slice<double const> query, size_t count, uint64_t budget_ns, uptr_t filter_function, uptr_t filter_state) const;
+
+    TracedMatches search_traced_b1x8(rust::Slice<uint8_t const> query, size_t count) const;
+    TracedMatches search_traced_i8(rust::Slice<int8_t const> query, size_t count) const;
+    TracedMatches search_traced_f16(rust::Slice<int16_t const> query, size_t count) const;
+    TracedMatches search_traced_f32(rust::Slice<float const> query, size_t count) const;
+    TracedMatches search_traced_f64(rust::Slice<double const> query, size_t count) const;
+
+    size_t search_into_b1x8(rust::Slice<uint8_t const> query, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const;
+    size_t search_into_i8(rust::Slice<int8_t const> query, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const;
+    size_t search_into_f16(rust::Slice<int16_t const> query, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const;
+    size_t search_into_f32(rust::Slice<float const> query, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const;
+    size_t search_into_f64(rust::Slice<double const> query, rust::Slice<vector_key_t> keys, rust::Slice<float> distances) const;
+
+    BatchMatches search_many_b1x8(rust::Slice<uint8_t const> queries, size_t count) const;
+    BatchMatches search_many_i8(rust::Slice<int8_t const> queries, size_t count) const;
+    BatchMatches search_many_f16(rust::Slice<int16_t const> queries, size_t count) const;
+    BatchMatches search_many_f32(rust::Slice<float const> queries, size_t count) const;
+    BatchMatches search_many_f64(rust::Slice<double const> queries, size_t count) const;
     // clang-format on
 
     size_t get_b1x8(vector_key_t key, rust::Slice<uint8_t> vector) const;
@@ -64,18 +101,44 @@ class NativeIndex {
     size_t remove(vector_key_t key) const;
     size_t rename(vector_key_t from, vector_key_t to) const;
     bool contains(vector_key_t key) const;
+    size_t export_keys(rust::Slice<vector_key_t> keys, size_t offset) const;
+    size_t centroid(rust::Slice<vector_key_t const> keys, rust::Slice<float> centroid) const;
+    size_t mean_all(rust::Slice<float> centroid) const;
 
     size_t dimensions() const;
     size_t connectivity() const;
     size_t size() const;
+    size_t deleted() const;
+    bool multi() const;
+    MetricKind metric_kind() const;
+    ScalarKind scalar_kind() const;
     size_t capacity() const;
     size_t serialized_length() const;
-
-    void save(rust::Str path) const;
-    void load(rust::Str path) const;
-    void view(rust::Str path) const;
+    size_t max_level() const;
+    double level_multiplier() const;
+    uint64_t level_seed() const;
+    LevelStats level_stats(size_t level) const;
+
+    size_t slots() const;
+    size_t entry_slot() const;
+    vector_key_t free_key() const;
+    vector_key_t slot_key(size_t slot) const;
+    rust::Vec<uint64_t> key_slots(vector_key_t key) const;
+    size_t slot_level(size_t slot) const;
+    rust::Vec<uint64_t> slot_neighbors(size_t slot, size_t level) const;
+
+    void save(rust::Slice<uint8_t const> path) const;
+    void load(rust::Slice<uint8_t const> path) const;
+    void view(rust::Slice<uint8_t const> path) const;
     void reset() const;
     size_t memory_usage() const;
+    MemoryBreakdown memory_breakdown() const;
+    size_t warmup(size_t budget_bytes) const;
+    MemoryLock lock_memory() const;
+    void unlock_memory() const;
+    bool memory_locked() const;
+    void change_vector_pages(VectorPages pages) const;
+    void change_vector_allocator(uptr_t allocate, uptr_t deallocate, uptr_t state) const;
     char const* hardware_acceleration() const;
 
     void save_to_buffer(rust::Slice<uint8_t> buffer) const;
@@ -87,3 +150,5 @@ class NativeIndex {
 };
 
 std::unique_ptr<NativeIndex> new_native_index(IndexOptions const& options);
+void raise_injected_failure(rust::Str operation);
+void raise_callback_panic(rust::Str message);
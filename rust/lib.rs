@@ -52,6 +52,123 @@ impl std::fmt::Display for BitAddressableError {
 
 impl std::error::Error for BitAddressableError {}
 
+/// Represents errors that can occur when operating on an index.
+#[derive(Debug)]
+pub enum IndexError {
+    /// Error raised by the native C++ layer.
+    #[cfg(feature = "native")]
+    Native(cxx::Exception),
+    /// Error raised by the file system or another I/O source.
+    Io(std::io::Error),
+    /// Error indicating a sanity query didn't find the expected key among its top matches.
+    SanityCheckFailed {
+        /// Position of the failed query in the provided sanity set.
+        query: usize,
+        /// The key expected among the matches.
+        expected: Key,
+    },
+    /// Error indicating the index options describe an impossible configuration.
+    InvalidOptions {
+        /// Human-readable explanation of the violated rule.
+        reason: String,
+    },
+    /// Error indicating a serialized index can't be loaded by this version of the library.
+    IncompatibleFormat {
+        /// Description of the format found in the file.
+        found: String,
+        /// Description of the format supported by this build.
+        expected: String,
+    },
+    /// Error indicating a vector or buffer length doesn't match the index dimensions.
+    DimensionMismatch {
+        /// Length expected by the index.
+        expected: usize,
+        /// Length of the provided slice.
+        got: usize,
+    },
+    /// Error indicating a key is already present in an index that doesn't allow multiple vectors per key.
+    DuplicateKey {
+        /// The conflicting key.
+        key: Key,
+    },
+    /// Error indicating the memory of an index can't be locked in RAM within the `RLIMIT_MEMLOCK` limit.
+    MemoryLockLimit {
+        /// Bytes the index needed to lock.
+        requested: usize,
+        /// The limit of the process, in bytes, or `usize::MAX` without one.
+        limit: usize,
+    },
+    /// Error indicating a serialized index was truncated or corrupted since it was saved.
+    CorruptSnapshot {
+        /// What the verification found.
+        reason: String,
+    },
+}
+
+impl std::fmt::Display for IndexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            #[cfg(feature = "native")]
+            IndexError::Native(ref err) => write!(f, "{}", err),
+            IndexError::Io(ref err) => write!(f, "{}", err),
+            IndexError::SanityCheckFailed { query, expected } => write!(
+                f,
+                "Sanity query #{} didn't find the expected key {}",
+                query, expected
+            ),
+            IndexError::InvalidOptions { ref reason } => {
+                write!(f, "Invalid index options: {}", reason)
+            }
+            IndexError::IncompatibleFormat {
+                ref found,
+                ref expected,
+            } => write!(
+                f,
+                "Incompatible index format: found {}, expected {}",
+                found, expected
+            ),
+            IndexError::DimensionMismatch { expected, got } => write!(
+                f,
+                "Dimension mismatch: expected {} scalars, got {}",
+                expected, got
+            ),
+            IndexError::DuplicateKey { key } => write!(f, "Key {} is already present", key),
+            IndexError::MemoryLockLimit { requested, limit } => write!(
+                f,
+                "Locking {} bytes in memory exceeds the RLIMIT_MEMLOCK limit of {} bytes",
+                requested, limit
+            ),
+            IndexError::CorruptSnapshot { ref reason } => {
+                write!(f, "Corrupt snapshot: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            #[cfg(feature = "native")]
+            IndexError::Native(ref err) => Some(err),
+            IndexError::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl From<cxx::Exception> for IndexError {
+    fn from(err: cxx::Exception) -> Self {
+        IndexError::Native(err)
+    }
+}
+
+impl From<std::io::Error> for IndexError {
+    fn from(err: std::io::Error) -> Self {
+        IndexError::Io(err)
+    }
+}
+
 /// Trait for types that can be addressed at the bit level.
 /// Provides methods to set and get individual bits within the implementing type.
 pub trait BitAddressable {
@@ -79,7 +196,7 @@ pub trait BitAddressable {
 /// interpretation of individual bits via various utility functions.
 #[repr(transparent)]
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
 pub struct b1x8(pub u8);
 
 impl b1x8 {
@@ -113,7 +230,7 @@ impl b1x8 {
 /// 1 sign bit, 5 exponent bits, and 10 mantissa bits.
 #[repr(transparent)]
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 pub struct f16(i16);
 
 impl f16 {
@@ -143,6 +260,97 @@ impl f16 {
     }
 }
 
+impl f16 {
+    /// Converts a single-precision float into the closest half-precision value,
+    /// rounding to nearest-even, saturating overflows to infinity, and preserving NaNs.
+    pub fn from_f32(value: f32) -> Self {
+        let bits = value.to_bits();
+        let sign = (bits >> 16) & 0x8000;
+        let exponent = ((bits >> 23) & 0xFF) as i32;
+        let mantissa = bits & 0x007F_FFFF;
+
+        // Infinity and NaN, keeping NaNs quiet
+        if exponent == 0xFF {
+            let nan = if mantissa != 0 {
+                0x0200 | (mantissa >> 13)
+            } else {
+                0
+            };
+            return f16((sign | 0x7C00 | nan) as u16 as i16);
+        }
+
+        let half_exponent = exponent - 127 + 15;
+        if half_exponent >= 0x1F {
+            return f16((sign | 0x7C00) as u16 as i16);
+        }
+
+        // Subnormal results, or underflow to signed zero
+        if half_exponent <= 0 {
+            if 14 - half_exponent > 24 {
+                return f16(sign as u16 as i16);
+            }
+            let mantissa = mantissa | 0x0080_0000;
+            let mut half_mantissa = mantissa >> (14 - half_exponent);
+            let round_bit = 1 << (13 - half_exponent);
+            if (mantissa & round_bit) != 0 && (mantissa & (3 * round_bit - 1)) != 0 {
+                half_mantissa += 1;
+            }
+            return f16((sign | half_mantissa) as u16 as i16);
+        }
+
+        let half = sign | ((half_exponent as u32) << 10) | (mantissa >> 13);
+        let round_bit = 0x0000_1000;
+        let rounded = if (mantissa & round_bit) != 0 && (mantissa & (3 * round_bit - 1)) != 0 {
+            half + 1
+        } else {
+            half
+        };
+        f16(rounded as u16 as i16)
+    }
+
+    /// Converts the half-precision value into a single-precision float, losslessly.
+    pub fn to_f32(self) -> f32 {
+        let bits = self.0 as u16 as u32;
+        if bits & 0x7FFF == 0 {
+            return f32::from_bits(bits << 16);
+        }
+
+        let sign = (bits & 0x8000) << 16;
+        let exponent = bits & 0x7C00;
+        let mantissa = bits & 0x03FF;
+
+        // Infinity and NaN
+        if exponent == 0x7C00 {
+            return if mantissa == 0 {
+                f32::from_bits(sign | 0x7F80_0000)
+            } else {
+                f32::from_bits(sign | 0x7FC0_0000 | (mantissa << 13))
+            };
+        }
+
+        // Subnormal values are normalized in the wider type
+        if exponent == 0 {
+            let shift = (mantissa as u16).leading_zeros() - 6;
+            let exponent = (127 - 15 - shift) << 23;
+            let mantissa = (mantissa << (14 + shift)) & 0x007F_FFFF;
+            return f32::from_bits(sign | exponent | mantissa);
+        }
+
+        let exponent = ((exponent >> 10) + 127 - 15) << 23;
+        f32::from_bits(sign | exponent | (mantissa << 13))
+    }
+
+    /// Returns the raw IEEE 754 half-precision bits.
+    pub fn to_bits(self) -> u16 {
+        self.0 as u16
+    }
+
+    /// Constructs a half-precision value from raw IEEE 754 bits.
+    pub fn from_bits(bits: u16) -> Self {
+        f16(bits as i16)
+    }
+}
+
 impl BitAddressable for b1x8 {
     /// Sets a bit at a specific index within the byte.
     ///
@@ -241,6 +449,7 @@ impl std::fmt::Debug for f16 {
     }
 }
 
+#[cfg(feature = "native")]
 #[cxx::bridge]
 pub mod ffi {
 
@@ -286,17 +495,43 @@ pub mod ffi {
         B1,
     }
 
+    /// The pages the vectors of an index are stored in, set with [`Index::change_vector_pages`].
+    #[derive(Debug)]
+    #[repr(i32)]
+    enum VectorPages {
+        /// Regular pages of the operating system, usually 4 KB.
+        Regular,
+        /// Regular pages, which Linux is advised with `madvise` to back with 2 MB huge pages.
+        TransparentHuge,
+        /// Huge pages reserved with `vm.nr_hugepages` and mapped with `MAP_HUGETLB` on Linux,
+        /// or transparent huge pages when none are left.
+        HugeTlb,
+    }
+
     /// The resulting matches from a search operation.
     /// It contains the keys and distances of the closest vectors.
-    #[derive(Debug)]
+    #[derive(Debug, Clone)]
     struct Matches {
         keys: Vec<u64>,
         distances: Vec<f32>,
     }
 
+    /// The resulting matches of many queries, packed into flat arrays, with a row of `count`
+    /// slots per query, filled from the front.
+    #[derive(Debug, Clone)]
+    struct BatchMatches {
+        keys: Vec<u64>,
+        distances: Vec<f32>,
+        /// The number of matches found for every query.
+        counts: Vec<usize>,
+        /// The number of slots per query.
+        count: usize,
+    }
+
     /// The index options used to configure the dense index during creation.
     /// It contains the number of dimensions, the metric kind, the scalar kind,
-    /// the connectivity, the expansion values, and the multi-flag.
+    /// the connectivity, the expansion values, the multi-flag, and the parameters
+    /// drawing the levels of new nodes.
     #[derive(Debug, PartialEq)]
     struct IndexOptions {
         dimensions: usize,
@@ -306,6 +541,82 @@ pub mod ffi {
         expansion_add: usize,
         expansion_search: usize,
         multi: bool,
+        /// Multiplier of the random levels of new nodes, `mL` in the paper.
+        /// Zero stands for `1 / ln(connectivity)`, and larger values make taller graphs.
+        level_multiplier: f64,
+        /// Seed of the generators drawing the levels of new nodes, one per thread.
+        level_seed: u64,
+        /// Whether vectors are scaled to a unit L2 norm when added, and queries when searched.
+        auto_normalize: bool,
+        /// Whether the memory of the index is locked in RAM, like with [`Index::lock_memory`].
+        lock_memory: bool,
+    }
+
+    /// Graph statistics of a single level of the index.
+    #[derive(Debug, Clone, Copy, Default, PartialEq)]
+    struct LevelStats {
+        /// Number of nodes present on the level.
+        nodes: usize,
+        /// Number of edges between the nodes of the level.
+        edges: usize,
+        /// Number of edges the nodes of the level could hold.
+        max_edges: usize,
+        /// Memory allocated for the nodes and their neighbor lists, in bytes.
+        allocated_bytes: usize,
+    }
+
+    /// Memory usage of an index, split by component, as reported by [`Index::memory_breakdown`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    struct MemoryBreakdown {
+        /// Nodes and neighbor lists of present members, with the per-member and per-thread
+        /// bookkeeping of the graph, in bytes.
+        graph_bytes: usize,
+        /// Vectors of present members, in bytes.
+        vectors_bytes: usize,
+        /// Nodes and vectors of removed members, held until their slots are reused, in bytes.
+        tombstones_bytes: usize,
+        /// Arena space lost to alignment or reserved for future members, in bytes.
+        slack_bytes: usize,
+    }
+
+    /// The outcome of locking the memory of an index, for [`Index::lock_memory`].
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    struct MemoryLock {
+        /// Bytes of nodes, vectors and mapped files to lock.
+        bytes: usize,
+        /// The `RLIMIT_MEMLOCK` limit of the process, in bytes, or `usize::MAX` without one.
+        limit_bytes: usize,
+        /// Zero, or the `errno` of the failure, after which nothing stays locked.
+        error: i32,
+    }
+
+    /// The resulting matches from a search bounded by a deadline.
+    #[derive(Debug)]
+    struct DeadlineMatches {
+        /// The keys and distances of the closest vectors found in time.
+        matches: Matches,
+        /// Whether the deadline stopped the search before it converged.
+        truncated: bool,
+    }
+
+    /// The cost of a single search, as counted by the native graph traversal.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    struct SearchStats {
+        /// Number of nodes whose neighbor lists were scanned, on all levels.
+        visited: usize,
+        /// Number of distances computed between the query and the stored vectors.
+        distance_computations: usize,
+        /// Number of moves of the greedy descent through the upper levels.
+        hops: usize,
+    }
+
+    /// The resulting matches from a search, with the cost of the traversal.
+    #[derive(Debug)]
+    struct TracedMatches {
+        /// The keys and distances of the closest vectors found.
+        matches: Matches,
+        /// The cost of the search.
+        stats: SearchStats,
     }
 
     // C++ types and signatures exposed to Rust.
@@ -329,12 +640,32 @@ pub mod ffi {
         pub fn change_metric(self: &NativeIndex, metric: usize, metric_state: usize);
 
         pub fn new_native_index(options: &IndexOptions) -> Result<UniquePtr<NativeIndex>>;
+        /// Throws the native exception reported for failures injected by the `chaos` feature.
+        pub fn raise_injected_failure(operation: &str) -> Result<()>;
+        /// Throws the native exception reported for panics caught in filters and custom metrics.
+        pub fn raise_callback_panic(message: &str) -> Result<()>;
         pub fn reserve(self: &NativeIndex, capacity: usize) -> Result<()>;
         pub fn dimensions(self: &NativeIndex) -> usize;
         pub fn connectivity(self: &NativeIndex) -> usize;
         pub fn size(self: &NativeIndex) -> usize;
+        pub fn deleted(self: &NativeIndex) -> usize;
+        pub fn multi(self: &NativeIndex) -> bool;
+        pub fn metric_kind(self: &NativeIndex) -> MetricKind;
+        pub fn scalar_kind(self: &NativeIndex) -> ScalarKind;
         pub fn capacity(self: &NativeIndex) -> usize;
         pub fn serialized_length(self: &NativeIndex) -> usize;
+        pub fn max_level(self: &NativeIndex) -> usize;
+        pub fn level_multiplier(self: &NativeIndex) -> f64;
+        pub fn level_seed(self: &NativeIndex) -> u64;
+        pub fn level_stats(self: &NativeIndex, level: usize) -> LevelStats;
+
+        pub fn slots(self: &NativeIndex) -> usize;
+        pub fn entry_slot(self: &NativeIndex) -> usize;
+        pub fn free_key(self: &NativeIndex) -> u64;
+        pub fn slot_key(self: &NativeIndex, slot: usize) -> Result<u64>;
+        pub fn key_slots(self: &NativeIndex, key: u64) -> Vec<u64>;
+        pub fn slot_level(self: &NativeIndex, slot: usize) -> Result<usize>;
+        pub fn slot_neighbors(self: &NativeIndex, slot: usize, level: usize) -> Result<Vec<u64>>;
 
         pub fn add_b1x8(self: &NativeIndex, key: u64, vector: &[u8]) -> Result<()>;
         pub fn add_i8(self: &NativeIndex, key: u64, vector: &[i8]) -> Result<()>;
@@ -342,6 +673,12 @@ pub mod ffi {
         pub fn add_f32(self: &NativeIndex, key: u64, vector: &[f32]) -> Result<()>;
         pub fn add_f64(self: &NativeIndex, key: u64, vector: &[f64]) -> Result<()>;
 
+        pub fn add_many_b1x8(self: &NativeIndex, keys: &[u64], vectors: &[u8]) -> Result<()>;
+        pub fn add_many_i8(self: &NativeIndex, keys: &[u64], vectors: &[i8]) -> Result<()>;
+        pub fn add_many_f16(self: &NativeIndex, keys: &[u64], vectors: &[i16]) -> Result<()>;
+        pub fn add_many_f32(self: &NativeIndex, keys: &[u64], vectors: &[f32]) -> Result<()>;
+        pub fn add_many_f64(self: &NativeIndex, keys: &[u64], vectors: &[f64]) -> Result<()>;
+
         pub fn search_b1x8(self: &NativeIndex, query: &[u8], count: usize) -> Result<Matches>;
         pub fn search_i8(self: &NativeIndex, query: &[i8], count: usize) -> Result<Matches>;
         pub fn search_f16(self: &NativeIndex, query: &[i16], count: usize) -> Result<Matches>;
@@ -384,6 +721,130 @@ pub mod ffi {
             filter_state: usize,
         ) -> Result<Matches>;
 
+        pub fn search_until_b1x8(
+            self: &NativeIndex,
+            query: &[u8],
+            count: usize,
+            budget_ns: u64,
+            filter: usize,
+            filter_state: usize,
+        ) -> Result<DeadlineMatches>;
+        pub fn search_until_i8(
+            self: &NativeIndex,
+            query: &[i8],
+            count: usize,
+            budget_ns: u64,
+            filter: usize,
+            filter_state: usize,
+        ) -> Result<DeadlineMatches>;
+        pub fn search_until_f16(
+            self: &NativeIndex,
+            query: &[i16],
+            count: usize,
+            budget_ns: u64,
+            filter: usize,
+            filter_state: usize,
+        ) -> Result<DeadlineMatches>;
+        pub fn search_until_f32(
+            self: &NativeIndex,
+            query: &[f32],
+            count: usize,
+            budget_ns: u64,
+            filter: usize,
+            filter_state: usize,
+        ) -> Result<DeadlineMatches>;
+        pub fn search_until_f64(
+            self: &NativeIndex,
+            query: &[f64],
+            count: usize,
+            budget_ns: u64,
+            filter: usize,
+            filter_state: usize,
+        ) -> Result<DeadlineMatches>;
+
+        pub fn search_traced_b1x8(
+            self: &NativeIndex,
+            query: &[u8],
+            count: usize,
+        ) -> Result<TracedMatches>;
+        pub fn search_traced_i8(
+            self: &NativeIndex,
+            query: &[i8],
+            count: usize,
+        ) -> Result<TracedMatches>;
+        pub fn search_traced_f16(
+            self: &NativeIndex,
+            query: &[i16],
+            count: usize,
+        ) -> Result<TracedMatches>;
+        pub fn search_traced_f32(
+            self: &NativeIndex,
+            query: &[f32],
+            count: usize,
+        ) -> Result<TracedMatches>;
+        pub fn search_traced_f64(
+            self: &NativeIndex,
+            query: &[f64],
+            count: usize,
+        ) -> Result<TracedMatches>;
+
+        pub fn search_into_b1x8(
+            self: &NativeIndex,
+            query: &[u8],
+            keys: &mut [u64],
+            distances: &mut [f32],
+        ) -> Result<usize>;
+        pub fn search_into_i8(
+            self: &NativeIndex,
+            query: &[i8],
+            keys: &mut [u64],
+            distances: &mut [f32],
+        ) -> Result<usize>;
+        pub fn search_into_f16(
+            self: &NativeIndex,
+            query: &[i16],
+            keys: &mut [u64],
+            distances: &mut [f32],
+        ) -> Result<usize>;
+        pub fn search_into_f32(
+            self: &NativeIndex,
+            query: &[f32],
+            keys: &mut [u64],
+            distances: &mut [f32],
+        ) -> Result<usize>;
+        pub fn search_into_f64(
+            self: &NativeIndex,
+            query: &[f64],
+            keys: &mut [u64],
+            distances: &mut [f32],
+        ) -> Result<usize>;
+
+        pub fn search_many_b1x8(
+            self: &NativeIndex,
+            queries: &[u8],
+            count: usize,
+        ) -> Result<BatchMatches>;
+        pub fn search_many_i8(
+            self: &NativeIndex,
+            queries: &[i8],
+            count: usize,
+        ) -> Result<BatchMatches>;
+        pub fn search_many_f16(
+            self: &NativeIndex,
+            queries: &[i16],
+            count: usize,
+        ) -> Result<BatchMatches>;
+        pub fn search_many_f32(
+            self: &NativeIndex,
+            queries: &[f32],
+            count: usize,
+        ) -> Result<BatchMatches>;
+        pub fn search_many_f64(
+            self: &NativeIndex,
+            queries: &[f64],
+            count: usize,
+        ) -> Result<BatchMatches>;
+
         pub fn get_b1x8(self: &NativeIndex, key: u64, buffer: &mut [u8]) -> Result<usize>;
         pub fn get_i8(self: &NativeIndex, key: u64, buffer: &mut [i8]) -> Result<usize>;
         pub fn get_f16(self: &NativeIndex, key: u64, buffer: &mut [i16]) -> Result<usize>;
@@ -394,12 +855,27 @@ pub mod ffi {
         pub fn rename(self: &NativeIndex, from: u64, to: u64) -> Result<usize>;
         pub fn contains(self: &NativeIndex, key: u64) -> bool;
         pub fn count(self: &NativeIndex, key: u64) -> usize;
+        pub fn export_keys(self: &NativeIndex, keys: &mut [u64], offset: usize) -> usize;
+        pub fn centroid(self: &NativeIndex, keys: &[u64], centroid: &mut [f32]) -> Result<usize>;
+        pub fn mean_all(self: &NativeIndex, centroid: &mut [f32]) -> Result<usize>;
 
-        pub fn save(self: &NativeIndex, path: &str) -> Result<()>;
-        pub fn load(self: &NativeIndex, path: &str) -> Result<()>;
-        pub fn view(self: &NativeIndex, path: &str) -> Result<()>;
+        pub fn save(self: &NativeIndex, path: &[u8]) -> Result<()>;
+        pub fn load(self: &NativeIndex, path: &[u8]) -> Result<()>;
+        pub fn view(self: &NativeIndex, path: &[u8]) -> Result<()>;
         pub fn reset(self: &NativeIndex) -> Result<()>;
         pub fn memory_usage(self: &NativeIndex) -> usize;
+        pub fn memory_breakdown(self: &NativeIndex) -> MemoryBreakdown;
+        pub fn warmup(self: &NativeIndex, budget_bytes: usize) -> usize;
+        pub fn lock_memory(self: &NativeIndex) -> MemoryLock;
+        pub fn unlock_memory(self: &NativeIndex);
+        pub fn memory_locked(self: &NativeIndex) -> bool;
+        pub fn change_vector_pages(self: &NativeIndex, pages: VectorPages) -> Result<()>;
+        pub fn change_vector_allocator(
+            self: &NativeIndex,
+            allocate: usize,
+            deallocate: usize,
+            state: usize,
+        ) -> Result<()>;
         pub fn hardware_acceleration(self: &NativeIndex) -> *const c_char;
 
         pub fn save_to_buffer(self: &NativeIndex, buffer: &mut [u8]) -> Result<()>;
@@ -408,9 +884,259 @@ pub mod ffi {
     }
 }
 
+// Without the native layer, the same structs and enums are defined in plain Rust.
+#[cfg(not(feature = "native"))]
+pub mod ffi;
+
+#[cfg(not(any(feature = "native", feature = "pure-rust")))]
+compile_error!("Either the `native` or the `pure-rust` feature must be enabled");
+
 // Re-export the FFI structs and enums at the crate root for easy access
+#[cfg(feature = "native")]
+pub use ffi::{BatchMatches, SearchStats};
 pub use ffi::{IndexOptions, MetricKind, ScalarKind};
 
+#[cfg(feature = "native")]
+pub mod aggregate;
+#[cfg(feature = "native")]
+pub mod array;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "native")]
+pub mod batch;
+#[cfg(feature = "async")]
+pub mod batcher;
+#[cfg(feature = "native")]
+pub mod cache;
+#[cfg(feature = "native")]
+pub mod canary;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "chaos")]
+pub mod chaos;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod checkpoint;
+#[cfg(feature = "native")]
+pub mod collection;
+#[cfg(feature = "native")]
+pub mod compaction;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod compat;
+pub mod convert;
+#[cfg(feature = "native")]
+pub mod deadline;
+#[cfg(feature = "native")]
+pub mod diverse;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+#[cfg(feature = "native")]
+pub mod exact;
+#[cfg(feature = "native")]
+pub mod expiry;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod export;
+#[cfg(feature = "native")]
+pub mod extend;
+#[cfg(feature = "native")]
+pub mod frozen;
+#[cfg(feature = "native")]
+pub mod geo;
+#[cfg(feature = "wgpu")]
+mod gpu;
+#[cfg(feature = "native")]
+pub mod graph;
+#[cfg(feature = "native")]
+pub mod grouped;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "native")]
+pub mod hybrid;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod import;
+#[cfg(feature = "native")]
+pub mod ingest;
+#[cfg(feature = "layered")]
+pub mod layered;
+#[cfg(feature = "maintenance")]
+pub mod maintenance;
+#[cfg(feature = "native")]
+pub mod matryoshka;
+#[cfg(feature = "native")]
+pub mod memlock;
+#[cfg(feature = "native")]
+pub mod metadata;
+pub mod metrics;
+#[cfg(feature = "native")]
+pub mod normalize;
+#[cfg(feature = "numa")]
+pub mod numa;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod open;
+pub mod options;
+#[cfg(feature = "native")]
+pub mod pages;
+#[cfg(feature = "native")]
+pub mod parallel;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "native")]
+pub mod pq;
+#[cfg(all(test, feature = "native"))]
+mod properties;
+#[cfg(feature = "pure-rust")]
+pub mod pure;
+#[cfg(feature = "native")]
+pub mod quantize;
+#[cfg(feature = "query")]
+pub mod query;
+#[cfg(feature = "native")]
+pub mod range;
+#[cfg(feature = "native")]
+pub mod reader;
+#[cfg(feature = "native")]
+pub mod rebuild;
+#[cfg(feature = "native")]
+pub mod recommend;
+#[cfg(feature = "native")]
+pub mod reduce;
+#[cfg(feature = "native")]
+pub mod replication;
+#[cfg(feature = "native")]
+pub mod requantize;
+#[cfg(feature = "native")]
+pub mod search;
+#[cfg(feature = "native")]
+pub mod self_check;
+#[cfg(feature = "native")]
+pub mod semantic;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "native")]
+pub mod snapshot;
+#[cfg(feature = "native")]
+pub mod sparse;
+#[cfg(feature = "async")]
+pub mod spawned;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod spill;
+#[cfg(feature = "native")]
+pub mod stats;
+#[cfg(feature = "object_store")]
+pub mod store;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod sync;
+#[cfg(feature = "native")]
+pub mod tags;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+#[cfg(feature = "native")]
+pub mod tenant;
+#[cfg(feature = "native")]
+pub mod verify;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub mod wal;
+#[cfg(feature = "native")]
+pub mod warmup;
+
+#[cfg(feature = "native")]
+mod checksum;
+#[cfg(feature = "native")]
+mod unwind;
+
+#[cfg(feature = "tokio")]
+pub use batch::{AsyncBatchInsert, ProgressStream};
+#[cfg(feature = "native")]
+pub use batch::{BatchInsert, BatchSummary, CancellationHandle, Progress};
+#[cfg(feature = "async")]
+pub use batcher::QueryBatcher;
+#[cfg(feature = "native")]
+pub use cache::CachedIndex;
+#[cfg(feature = "chaos")]
+pub use chaos::ChaosConfig;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub use checkpoint::CheckpointedBuilder;
+#[cfg(feature = "native")]
+pub use collection::Collection;
+#[cfg(feature = "native")]
+pub use compaction::CompactionReport;
+#[cfg(feature = "encryption")]
+pub use encryption::EncryptionKey;
+#[cfg(feature = "native")]
+pub use exact::ExactSearch;
+#[cfg(feature = "native")]
+pub use expiry::ExpiringIndex;
+#[cfg(feature = "native")]
+pub use frozen::FrozenIndex;
+#[cfg(feature = "native")]
+pub use graph::GraphFormat;
+#[cfg(feature = "native")]
+pub use grouped::GroupMatches;
+#[cfg(feature = "native")]
+pub use ingest::IngestAs;
+#[cfg(feature = "layered")]
+pub use layered::{DeletionSet, LayeredIndex};
+#[cfg(feature = "native")]
+pub use matryoshka::TruncatedIndex;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub use metadata::file_metadata;
+#[cfg(feature = "native")]
+pub use metadata::{buffer_metadata, IndexMetadata};
+pub use metrics::MetricType;
+#[cfg(feature = "native")]
+pub use options::{
+    capabilities, linkage, simd_capabilities, Capabilities, Isa, Linkage, SimdCapabilities,
+};
+#[cfg(feature = "native")]
+pub use pages::{VectorAllocator, VectorPages};
+#[cfg(feature = "native")]
+pub use pq::{PqIndex, ProductQuantizer};
+#[cfg(feature = "native")]
+pub use reader::IndexReader;
+#[cfg(feature = "native")]
+pub use recommend::RecommendStrategy;
+#[cfg(feature = "native")]
+pub use reduce::{Projection, ReducedIndex};
+#[cfg(feature = "native")]
+pub use replication::{Change, ChangeLog};
+#[cfg(feature = "native")]
+pub use search::{RerankFunction, RerankMetric, ResultElement, SearchParams, SearchResults};
+#[cfg(feature = "native")]
+pub use self_check::{self_check, SelfCheckReport};
+#[cfg(feature = "native")]
+pub use semantic::{Embedder, SemanticIndex};
+#[cfg(feature = "native")]
+pub use snapshot::Snapshot;
+#[cfg(feature = "native")]
+pub use sparse::SparseIndex;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub use spill::SpilledIndex;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub use stats::compare_snapshots;
+#[cfg(feature = "native")]
+pub use stats::{IndexStats, LevelStats, MemoryBreakdown, SnapshotComparison, StatsDiff};
+#[cfg(all(feature = "native", not(no_fs)))]
+pub use sync::SyncReport;
+#[cfg(feature = "native")]
+pub use tags::TagSet;
+#[cfg(feature = "native")]
+pub use tenant::{TenantId, TenantIndex};
+#[cfg(feature = "native")]
+pub use verify::VerificationReport;
+#[cfg(all(feature = "native", not(no_fs)))]
+pub use wal::DurableIndex;
+
+/// Without the native layer, [`Index`] is backed by the pure-Rust implementation.
+#[cfg(all(not(feature = "native"), feature = "pure-rust"))]
+pub use pure::Index;
+
+#[cfg(all(feature = "native", not(no_fs)))]
+use std::path::Path;
+#[cfg(feature = "native")]
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+#[cfg(feature = "native")]
+use unwind::{catch_metric_panics, metric_trampoline, with_filter, GuardedMetric, PanicSlot};
+
 /// Represents custom metric functions for calculating distances between vectors in various formats.
 ///
 /// This enum allows the encapsulation of custom distance calculation logic for vectors of different
@@ -461,6 +1187,7 @@ pub use ffi::{IndexOptions, MetricKind, ScalarKind};
 /// ```
 ///
 /// In this example, `dimensions` should be defined and valid for the vectors `a` and `b`.
+#[cfg(feature = "native")]
 pub enum MetricFunction {
     B1X8Metric(std::boxed::Box<dyn Fn(*const b1x8, *const b1x8) -> Distance + Send + Sync>),
     I8Metric(std::boxed::Box<dyn Fn(*const i8, *const i8) -> Distance + Send + Sync>),
@@ -469,6 +1196,43 @@ pub enum MetricFunction {
     F64Metric(std::boxed::Box<dyn Fn(*const f64, *const f64) -> Distance + Send + Sync>),
 }
 
+/// A custom metric, pinned to a heap address that the native index keeps calling it through.
+///
+/// The address stays valid when the owning `Index` is moved, and the allocation is only freed
+/// after the native index, as `Index` drops its fields in declaration order.
+#[cfg(feature = "native")]
+struct MetricState(std::ptr::NonNull<GuardedMetric>);
+
+// SAFETY: The state uniquely owns a `MetricFunction`, whose closures are `Send` and `Sync`,
+// and shares a `PanicSlot`, which synchronizes itself.
+#[cfg(feature = "native")]
+unsafe impl Send for MetricState {}
+#[cfg(feature = "native")]
+unsafe impl Sync for MetricState {}
+
+#[cfg(feature = "native")]
+impl MetricState {
+    fn new(metric: MetricFunction, panic: Arc<PanicSlot>) -> Self {
+        let guarded = GuardedMetric { metric, panic };
+        let pinned = std::boxed::Box::into_raw(std::boxed::Box::new(guarded));
+        // SAFETY: `Box::into_raw` never returns a null pointer.
+        Self(unsafe { std::ptr::NonNull::new_unchecked(pinned) })
+    }
+
+    /// The stable address passed to the native index with `metric_trampoline`.
+    fn address(&self) -> usize {
+        self.0.as_ptr() as usize
+    }
+}
+
+#[cfg(feature = "native")]
+impl Drop for MetricState {
+    fn drop(&mut self) {
+        // SAFETY: The pointer came from `Box::into_raw` and is released exactly once.
+        drop(unsafe { std::boxed::Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
 /// Approximate Nearest Neighbors search index for dense vectors.
 ///
 /// The `Index` struct provides an abstraction over a dense vector space, allowing
@@ -505,9 +1269,115 @@ pub enum MetricFunction {
 /// ```
 /// For more examples, including how to add vectors to the index and perform searches,
 /// refer to the individual method documentation.
+///
+/// # Mutability
+///
+/// Insertions, removals, and runtime settings, like the expansion factors and the metric,
+/// go through a shared reference. Settings apply to the calls starting after the change.
+/// Operations replacing the whole contents, [`Index::reset`], [`Index::load`], [`Index::view`],
+/// and their buffer variants, take an exclusive reference, as no other call may run meanwhile.
+///
+/// # Concurrency
+///
+/// `Index` is `Send` and `Sync`, so it can be shared through an `Arc` or scoped threads without
+/// an extra `Mutex`, which would serialize every call:
+///
+/// - [`Index::add`], [`Index::search`], [`Index::filtered_search`], [`Index::get`],
+///   [`Index::remove`], [`Index::rename`], and the other lookups run concurrently. The native
+///   layer holds one context per hardware thread, and calls beyond that wait for a free one.
+/// - [`Index::reserve`], [`Index::save`], [`Index::save_to_buffer`], and the runtime settings
+///   wait for the calls in flight, and hold the next ones back until they finish.
+/// - Filters and custom metrics must not call those from inside the index, which would wait on
+///   the call running them, and deadlock. Searching the same index from them holds a second
+///   native context, and deadlocks once every context is held that way.
+///
+/// ```
+/// use usearch::{Index, IndexOptions};
+///
+/// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+/// std::thread::scope(|scope| {
+///     for thread in 0..8u64 {
+///         let index = &index;
+///         scope.spawn(move || {
+///             index.add(thread, &[1.0_f32, thread as f32]).unwrap();
+///             index.search(&[1.0_f32, 0.0], 1).unwrap();
+///         });
+///     }
+/// });
+/// assert_eq!(index.size(), 8);
+/// ```
+#[cfg(feature = "native")]
 pub struct Index {
     inner: cxx::UniquePtr<ffi::NativeIndex>,
-    metric_fn: Option<MetricFunction>,
+    /// Custom metrics registered so far, the current one last. Replaced metrics stay alive until
+    /// the index drops. Declared after `inner`, so that the native index is destroyed before the
+    /// metrics it calls.
+    metric_fn: Mutex<Vec<MetricState>>,
+    /// Panics of the custom metrics on native worker threads, not reported yet.
+    metric_panics: Arc<PanicSlot>,
+    /// Owned by the batched call running a custom metric on native worker threads, if any.
+    parallel_metric: Mutex<()>,
+    /// Shared by mutations, and taken exclusively to grow the capacity or take snapshots.
+    growth: RwLock<()>,
+    /// Whether vectors and queries are normalized before reaching the native index.
+    auto_normalize: bool,
+    /// The file last written by [`Index::sync`], and the keys changed since.
+    #[cfg(not(no_fs))]
+    journal: Mutex<Option<sync::SyncJournal>>,
+    /// Sequence numbers of the last changes, once [`Index::enable_replication`] was called.
+    replication: Mutex<Option<replication::ReplicationLog>>,
+    #[cfg(feature = "chaos")]
+    faults: chaos::FaultInjector,
+    /// Permits for the searches running on the blocking pool of the async runtime.
+    #[cfg(feature = "async")]
+    spawned: Arc<tokio::sync::Semaphore>,
+    /// The allocator the vectors are stored with, if not the default one. Declared after
+    /// `inner`, so that the native index releases its memory before the allocator drops.
+    vector_allocator: Option<pages::AllocatorState>,
+}
+
+// SAFETY: The native index synchronizes its lookups and graph updates internally, and hands out
+// its per-thread contexts under a lock, making callers wait once all of them are taken. Calls
+// reallocating or walking the whole graph, like `reserve`, `save`, and the setters, take the
+// growth lock exclusively, and all other native calls share it. The custom metrics it calls are
+// `Send + Sync`, and so are the filters, which only run on the calling thread.
+#[cfg(feature = "native")]
+unsafe impl Send for Index {}
+#[cfg(feature = "native")]
+unsafe impl Sync for Index {}
+
+#[cfg(feature = "native")]
+impl std::fmt::Debug for Index {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Index")
+            .field("dimensions", &self.dimensions())
+            .field("metric", &self.inner.metric_kind())
+            .field("quantization", &self.inner.scalar_kind())
+            .field("connectivity", &self.connectivity())
+            .field("multi", &self.inner.multi())
+            .field("size", &self.size())
+            .field("capacity", &self.capacity())
+            .field("memory_usage", &self.memory_usage())
+            .finish()
+    }
+}
+
+/// Summarizes the index on a single line, like
+/// `dimensions=256 metric=Cos quantization=F16 size=10 capacity=64 memory=1.2MiB`.
+#[cfg(feature = "native")]
+impl std::fmt::Display for Index {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "dimensions={} metric={:?} quantization={:?} size={} capacity={} memory={:.1}MiB",
+            self.dimensions(),
+            self.inner.metric_kind(),
+            self.inner.scalar_kind(),
+            self.size(),
+            self.capacity(),
+            self.memory_usage() as f64 / (1024.0 * 1024.0)
+        )
+    }
 }
 
 impl Default for ffi::IndexOptions {
@@ -520,6 +1390,10 @@ impl Default for ffi::IndexOptions {
             expansion_add: 0,
             expansion_search: 0,
             multi: false,
+            level_multiplier: 0.0,
+            level_seed: 0,
+            auto_normalize: false,
+            lock_memory: false,
         }
     }
 }
@@ -534,14 +1408,53 @@ impl Clone for ffi::IndexOptions {
             expansion_add: (self.expansion_add),
             expansion_search: (self.expansion_search),
             multi: (self.multi),
+            level_multiplier: (self.level_multiplier),
+            level_seed: (self.level_seed),
+            auto_normalize: (self.auto_normalize),
+            lock_memory: (self.lock_memory),
+        }
+    }
+}
+
+/// Lists the options on a single line, leaving out the level parameters, normalization and
+/// memory locking unless they differ from the defaults.
+impl std::fmt::Display for ffi::IndexOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "dimensions={} metric={:?} quantization={:?} connectivity={} expansion_add={} expansion_search={} multi={}",
+            self.dimensions,
+            self.metric,
+            self.quantization,
+            self.connectivity,
+            self.expansion_add,
+            self.expansion_search,
+            self.multi
+        )?;
+        if self.level_multiplier != 0.0 {
+            write!(f, " level_multiplier={}", self.level_multiplier)?;
+        }
+        if self.level_seed != 0 {
+            write!(f, " level_seed={}", self.level_seed)?;
         }
+        if self.auto_normalize {
+            write!(f, " auto_normalize=true")?;
+        }
+        if self.lock_memory {
+            write!(f, " lock_memory=true")?;
+        }
+        Ok(())
     }
 }
 
 /// The `VectorType` trait defines operations for managing and querying vectors
 /// in an index. It supports generic operations on vectors of different types,
 /// allowing for the addition, retrieval, and search of vectors within an index.
+#[cfg(feature = "native")]
 pub trait VectorType {
+    /// The scalar kind matching the in-memory representation of this type.
+    const SCALAR_KIND: ScalarKind;
+
     /// Adds a vector to the index under the specified key.
     ///
     /// # Parameters
@@ -556,6 +1469,21 @@ pub trait VectorType {
     where
         Self: Sized;
 
+    /// Adds vectors packed row-major to the index, one per key, inserting them in parallel
+    /// on the native side, within the current capacity.
+    ///
+    /// # Parameters
+    /// - `index`: A reference to the `Index` where the vectors are to be added.
+    /// - `keys`: The keys under which the vectors should be stored.
+    /// - `vectors`: A slice holding one vector per key, one after another.
+    ///
+    /// # Returns
+    /// - `Ok(())` if all vectors were successfully added to the index.
+    /// - `Err(cxx::Exception)` if an error occurred, in which case some vectors may be added.
+    fn add_many(index: &Index, keys: &[Key], vectors: &[Self]) -> Result<(), cxx::Exception>
+    where
+        Self: Sized;
+
     /// Retrieves a vector from the index by its key.
     ///
     /// # Parameters
@@ -571,6 +1499,12 @@ pub trait VectorType {
     where
         Self: Sized;
 
+    /// Like [`VectorType::get`], for callers already holding the growth lock of the index.
+    #[doc(hidden)]
+    fn get_unlocked(index: &Index, key: Key, buffer: &mut [Self]) -> Result<usize, cxx::Exception>
+    where
+        Self: Sized;
+
     /// Performs a search in the index using the given query vector, returning
     /// up to `count` closest matches.
     ///
@@ -609,6 +1543,94 @@ pub trait VectorType {
         Self: Sized,
         F: Fn(Key) -> bool;
 
+    /// Performs a search that stops traversing the graph once a time budget runs out,
+    /// returning the closest matches found so far.
+    ///
+    /// # Parameters
+    /// - `index`: A reference to the `Index` where the search is to be performed.
+    /// - `query`: A slice representing the query vector.
+    /// - `count`: The maximum number of matches to return.
+    /// - `budget_ns`: The time budget in nanoseconds, starting when the native search starts.
+    /// - `filter`: The address of an `extern "C" fn(Key, usize) -> bool` predicate, or zero.
+    /// - `filter_state`: The second argument passed to the predicate.
+    ///
+    /// # Returns
+    /// - `Ok(ffi::DeadlineMatches)` containing the matches and whether they are partial.
+    /// - `Err(cxx::Exception)` if an error occurred during the search operation.
+    ///
+    /// # Safety
+    /// A non-zero `filter` must be the address of such a function, valid to call with
+    /// `filter_state` for the duration of the search.
+    unsafe fn search_until(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+        budget_ns: u64,
+        filter: usize,
+        filter_state: usize,
+    ) -> Result<ffi::DeadlineMatches, cxx::Exception>
+    where
+        Self: Sized;
+
+    /// Performs a search like [`VectorType::search`], also counting the nodes visited,
+    /// the distances computed, and the hops taken by the graph traversal.
+    ///
+    /// # Parameters
+    /// - `index`: A reference to the `Index` where the search is to be performed.
+    /// - `query`: A slice representing the query vector.
+    /// - `count`: The maximum number of matches to return.
+    ///
+    /// # Returns
+    /// - `Ok(ffi::TracedMatches)` containing the matches and the cost of the search.
+    /// - `Err(cxx::Exception)` if an error occurred during the search operation.
+    fn search_traced(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+    ) -> Result<ffi::TracedMatches, cxx::Exception>
+    where
+        Self: Sized;
+
+    /// Performs a search like [`VectorType::search`], writing the matches into caller-provided
+    /// buffers instead of allocating them.
+    ///
+    /// # Parameters
+    /// - `index`: A reference to the `Index` where the search is to be performed.
+    /// - `query`: A slice representing the query vector.
+    /// - `keys`: The buffer receiving the keys of the matches, as many as it can hold.
+    /// - `distances`: The buffer receiving their distances, as long as `keys`.
+    ///
+    /// # Returns
+    /// - `Ok(usize)` indicating the number of matches written to the front of both buffers.
+    /// - `Err(cxx::Exception)` if an error occurred during the search operation.
+    fn search_into(
+        index: &Index,
+        query: &[Self],
+        keys: &mut [Key],
+        distances: &mut [Distance],
+    ) -> Result<usize, cxx::Exception>
+    where
+        Self: Sized;
+
+    /// Performs a search for every query packed row-major in `queries`, in parallel on the
+    /// native side, returning up to `count` matches per query.
+    ///
+    /// # Parameters
+    /// - `index`: A reference to the `Index` where the search is to be performed.
+    /// - `queries`: A slice holding the query vectors, one after another.
+    /// - `count`: The maximum number of matches to return per query.
+    ///
+    /// # Returns
+    /// - `Ok(ffi::BatchMatches)` containing the matches of every query, in query order.
+    /// - `Err(cxx::Exception)` if an error occurred during any of the searches.
+    fn search_many(
+        index: &Index,
+        queries: &[Self],
+        count: usize,
+    ) -> Result<ffi::BatchMatches, cxx::Exception>
+    where
+        Self: Sized;
+
     /// Changes the metric used for distance calculations within the index.
     ///
     /// # Parameters
@@ -620,22 +1642,37 @@ pub trait VectorType {
     /// - `Ok(())` if the metric was successfully changed.
     /// - `Err(cxx::Exception)` if an error occurred during the operation.
     fn change_metric(
-        index: &mut Index,
+        index: &Index,
         metric: std::boxed::Box<dyn Fn(*const Self, *const Self) -> Distance + Send + Sync>,
     ) -> Result<(), cxx::Exception>
     where
         Self: Sized;
 }
 
+#[cfg(feature = "native")]
 impl VectorType for f32 {
+    const SCALAR_KIND: ScalarKind = ScalarKind::F32;
+
     fn search(index: &Index, query: &[Self], count: usize) -> Result<ffi::Matches, cxx::Exception> {
-        index.inner.search_f32(query, count)
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_f32(&query, count)
     }
     fn get(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        Self::get_unlocked(index, key, vector)
+    }
+    fn get_unlocked(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
         index.inner.get_f32(key, vector)
     }
     fn add(index: &Index, key: Key, vector: &[Self]) -> Result<(), cxx::Exception> {
-        index.inner.add_f32(key, vector)
+        let vector = index.normalized(vector);
+        index.add_growing(key, || index.inner.add_f32(key, &vector))
+    }
+    fn add_many(index: &Index, keys: &[Key], vectors: &[Self]) -> Result<(), cxx::Exception> {
+        let vectors = index.normalized_rows(vectors);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.add_many_f32(keys, &vectors)
     }
     fn filtered_search<F>(
         index: &Index,
@@ -647,63 +1684,94 @@ impl VectorType for f32 {
         Self: Sized,
         F: Fn(Key) -> bool,
     {
-        // Trampoline is the function that knows how to call the Rust closure.
-        extern "C" fn trampoline<F: Fn(u64) -> bool>(key: u64, closure_address: usize) -> bool {
-            let closure = closure_address as *const F;
-            unsafe { (*closure)(key) }
-        }
-
-        // Temporarily cast the closure to a raw pointer for passing.
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline::<F> as *const ());
-            let closure_address: usize = &filter as *const F as usize;
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        with_filter(filter, |trampoline_fn, closure_address| {
             index
                 .inner
-                .filtered_search_f32(query, count, trampoline_fn, closure_address)
-        }
+                .filtered_search_f32(&query, count, trampoline_fn, closure_address)
+        })
+    }
+
+    unsafe fn search_until(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+        budget_ns: u64,
+        filter: usize,
+        filter_state: usize,
+    ) -> Result<ffi::DeadlineMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index
+            .inner
+            .search_until_f32(&query, count, budget_ns, filter, filter_state)
+    }
+
+    fn search_traced(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+    ) -> Result<ffi::TracedMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_traced_f32(&query, count)
+    }
+
+    fn search_into(
+        index: &Index,
+        query: &[Self],
+        keys: &mut [Key],
+        distances: &mut [Distance],
+    ) -> Result<usize, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_into_f32(&query, keys, distances)
+    }
+
+    fn search_many(
+        index: &Index,
+        queries: &[Self],
+        count: usize,
+    ) -> Result<ffi::BatchMatches, cxx::Exception> {
+        let queries = index.normalized_rows(queries);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_many_f32(&queries, count)
     }
 
     fn change_metric(
-        index: &mut Index,
+        index: &Index,
         metric: std::boxed::Box<dyn Fn(*const Self, *const Self) -> Distance + Send + Sync>,
     ) -> Result<(), cxx::Exception> {
-        // Store the metric function in the Index.
-        type MetricFn = fn(*const f32, *const f32) -> Distance;
-        index.metric_fn = Some(MetricFunction::F32Metric(metric));
-
-        // Trampoline is the function that knows how to call the Rust closure.
-        // The `first` is a pointer to the first vector, `second` is a pointer to the second vector,
-        // and `index_wrapper` is a pointer to the `index` itself, from which we can infer the metric function
-        // and the number of dimensions.
-        extern "C" fn trampoline(first: usize, second: usize, closure_address: usize) -> Distance {
-            let first_ptr = first as *const f32;
-            let second_ptr = second as *const f32;
-            let closure: MetricFn = unsafe { std::mem::transmute(closure_address) };
-            closure(first_ptr, second_ptr)
-        }
-
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline as *const ());
-            let closure_address = match index.metric_fn {
-                Some(MetricFunction::F32Metric(ref metric)) => metric as *const _ as usize,
-                _ => panic!("Expected F32Metric"),
-            };
-            index.inner.change_metric(trampoline_fn, closure_address)
-        }
-
+        index.register_metric(MetricFunction::F32Metric(metric));
         Ok(())
     }
 }
 
+#[cfg(feature = "native")]
 impl VectorType for i8 {
+    const SCALAR_KIND: ScalarKind = ScalarKind::I8;
+
     fn search(index: &Index, query: &[Self], count: usize) -> Result<ffi::Matches, cxx::Exception> {
-        index.inner.search_i8(query, count)
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_i8(&query, count)
     }
     fn get(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        Self::get_unlocked(index, key, vector)
+    }
+    fn get_unlocked(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
         index.inner.get_i8(key, vector)
     }
     fn add(index: &Index, key: Key, vector: &[Self]) -> Result<(), cxx::Exception> {
-        index.inner.add_i8(key, vector)
+        let vector = index.normalized(vector);
+        index.add_growing(key, || index.inner.add_i8(key, &vector))
+    }
+    fn add_many(index: &Index, keys: &[Key], vectors: &[Self]) -> Result<(), cxx::Exception> {
+        let vectors = index.normalized_rows(vectors);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.add_many_i8(keys, &vectors)
     }
     fn filtered_search<F>(
         index: &Index,
@@ -715,62 +1783,93 @@ impl VectorType for i8 {
         Self: Sized,
         F: Fn(Key) -> bool,
     {
-        // Trampoline is the function that knows how to call the Rust closure.
-        extern "C" fn trampoline<F: Fn(u64) -> bool>(key: u64, closure_address: usize) -> bool {
-            let closure = closure_address as *const F;
-            unsafe { (*closure)(key) }
-        }
-
-        // Temporarily cast the closure to a raw pointer for passing.
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline::<F> as *const ());
-            let closure_address: usize = &filter as *const F as usize;
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        with_filter(filter, |trampoline_fn, closure_address| {
             index
                 .inner
-                .filtered_search_i8(query, count, trampoline_fn, closure_address)
-        }
+                .filtered_search_i8(&query, count, trampoline_fn, closure_address)
+        })
+    }
+    unsafe fn search_until(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+        budget_ns: u64,
+        filter: usize,
+        filter_state: usize,
+    ) -> Result<ffi::DeadlineMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index
+            .inner
+            .search_until_i8(&query, count, budget_ns, filter, filter_state)
+    }
+
+    fn search_traced(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+    ) -> Result<ffi::TracedMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_traced_i8(&query, count)
+    }
+
+    fn search_into(
+        index: &Index,
+        query: &[Self],
+        keys: &mut [Key],
+        distances: &mut [Distance],
+    ) -> Result<usize, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_into_i8(&query, keys, distances)
     }
+
+    fn search_many(
+        index: &Index,
+        queries: &[Self],
+        count: usize,
+    ) -> Result<ffi::BatchMatches, cxx::Exception> {
+        let queries = index.normalized_rows(queries);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_many_i8(&queries, count)
+    }
+
     fn change_metric(
-        index: &mut Index,
+        index: &Index,
         metric: std::boxed::Box<dyn Fn(*const Self, *const Self) -> Distance + Send + Sync>,
     ) -> Result<(), cxx::Exception> {
-        // Store the metric function in the Index.
-        type MetricFn = fn(*const i8, *const i8) -> Distance;
-        index.metric_fn = Some(MetricFunction::I8Metric(metric));
-
-        // Trampoline is the function that knows how to call the Rust closure.
-        // The `first` is a pointer to the first vector, `second` is a pointer to the second vector,
-        // and `index_wrapper` is a pointer to the `index` itself, from which we can infer the metric function
-        // and the number of dimensions.
-        extern "C" fn trampoline(first: usize, second: usize, closure_address: usize) -> Distance {
-            let first_ptr = first as *const i8;
-            let second_ptr = second as *const i8;
-            let closure: MetricFn = unsafe { std::mem::transmute(closure_address) };
-            closure(first_ptr, second_ptr)
-        }
-
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline as *const ());
-            let closure_address = match index.metric_fn {
-                Some(MetricFunction::I8Metric(ref metric)) => metric as *const _ as usize,
-                _ => panic!("Expected I8Metric"),
-            };
-            index.inner.change_metric(trampoline_fn, closure_address)
-        }
-
+        index.register_metric(MetricFunction::I8Metric(metric));
         Ok(())
     }
 }
 
+#[cfg(feature = "native")]
 impl VectorType for f64 {
+    const SCALAR_KIND: ScalarKind = ScalarKind::F64;
+
     fn search(index: &Index, query: &[Self], count: usize) -> Result<ffi::Matches, cxx::Exception> {
-        index.inner.search_f64(query, count)
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_f64(&query, count)
     }
     fn get(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        Self::get_unlocked(index, key, vector)
+    }
+    fn get_unlocked(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
         index.inner.get_f64(key, vector)
     }
     fn add(index: &Index, key: Key, vector: &[Self]) -> Result<(), cxx::Exception> {
-        index.inner.add_f64(key, vector)
+        let vector = index.normalized(vector);
+        index.add_growing(key, || index.inner.add_f64(key, &vector))
+    }
+    fn add_many(index: &Index, keys: &[Key], vectors: &[Self]) -> Result<(), cxx::Exception> {
+        let vectors = index.normalized_rows(vectors);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.add_many_f64(keys, &vectors)
     }
     fn filtered_search<F>(
         index: &Index,
@@ -782,62 +1881,93 @@ impl VectorType for f64 {
         Self: Sized,
         F: Fn(Key) -> bool,
     {
-        // Trampoline is the function that knows how to call the Rust closure.
-        extern "C" fn trampoline<F: Fn(u64) -> bool>(key: u64, closure_address: usize) -> bool {
-            let closure = closure_address as *const F;
-            unsafe { (*closure)(key) }
-        }
-
-        // Temporarily cast the closure to a raw pointer for passing.
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline::<F> as *const ());
-            let closure_address: usize = &filter as *const F as usize;
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        with_filter(filter, |trampoline_fn, closure_address| {
             index
                 .inner
-                .filtered_search_f64(query, count, trampoline_fn, closure_address)
-        }
+                .filtered_search_f64(&query, count, trampoline_fn, closure_address)
+        })
+    }
+    unsafe fn search_until(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+        budget_ns: u64,
+        filter: usize,
+        filter_state: usize,
+    ) -> Result<ffi::DeadlineMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index
+            .inner
+            .search_until_f64(&query, count, budget_ns, filter, filter_state)
+    }
+
+    fn search_traced(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+    ) -> Result<ffi::TracedMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_traced_f64(&query, count)
+    }
+
+    fn search_into(
+        index: &Index,
+        query: &[Self],
+        keys: &mut [Key],
+        distances: &mut [Distance],
+    ) -> Result<usize, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_into_f64(&query, keys, distances)
+    }
+
+    fn search_many(
+        index: &Index,
+        queries: &[Self],
+        count: usize,
+    ) -> Result<ffi::BatchMatches, cxx::Exception> {
+        let queries = index.normalized_rows(queries);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_many_f64(&queries, count)
     }
+
     fn change_metric(
-        index: &mut Index,
+        index: &Index,
         metric: std::boxed::Box<dyn Fn(*const Self, *const Self) -> Distance + Send + Sync>,
     ) -> Result<(), cxx::Exception> {
-        // Store the metric function in the Index.
-        type MetricFn = fn(*const f64, *const f64) -> Distance;
-        index.metric_fn = Some(MetricFunction::F64Metric(metric));
-
-        // Trampoline is the function that knows how to call the Rust closure.
-        // The `first` is a pointer to the first vector, `second` is a pointer to the second vector,
-        // and `index_wrapper` is a pointer to the `index` itself, from which we can infer the metric function
-        // and the number of dimensions.
-        extern "C" fn trampoline(first: usize, second: usize, closure_address: usize) -> Distance {
-            let first_ptr = first as *const f64;
-            let second_ptr = second as *const f64;
-            let closure: MetricFn = unsafe { std::mem::transmute(closure_address) };
-            closure(first_ptr, second_ptr)
-        }
-
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline as *const ());
-            let closure_address = match index.metric_fn {
-                Some(MetricFunction::F64Metric(ref metric)) => metric as *const _ as usize,
-                _ => panic!("Expected F64Metric"),
-            };
-            index.inner.change_metric(trampoline_fn, closure_address)
-        }
-
+        index.register_metric(MetricFunction::F64Metric(metric));
         Ok(())
     }
 }
 
+#[cfg(feature = "native")]
 impl VectorType for f16 {
+    const SCALAR_KIND: ScalarKind = ScalarKind::F16;
+
     fn search(index: &Index, query: &[Self], count: usize) -> Result<ffi::Matches, cxx::Exception> {
-        index.inner.search_f16(f16::to_i16s(query), count)
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_f16(f16::to_i16s(&query), count)
     }
     fn get(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        Self::get_unlocked(index, key, vector)
+    }
+    fn get_unlocked(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
         index.inner.get_f16(key, f16::to_mut_i16s(vector))
     }
     fn add(index: &Index, key: Key, vector: &[Self]) -> Result<(), cxx::Exception> {
-        index.inner.add_f16(key, f16::to_i16s(vector))
+        let vector = index.normalized(vector);
+        index.add_growing(key, || index.inner.add_f16(key, f16::to_i16s(&vector)))
+    }
+    fn add_many(index: &Index, keys: &[Key], vectors: &[Self]) -> Result<(), cxx::Exception> {
+        let vectors = index.normalized_rows(vectors);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.add_many_f16(keys, f16::to_i16s(&vectors))
     }
     fn filtered_search<F>(
         index: &Index,
@@ -849,66 +1979,96 @@ impl VectorType for f16 {
         Self: Sized,
         F: Fn(Key) -> bool,
     {
-        // Trampoline is the function that knows how to call the Rust closure.
-        extern "C" fn trampoline<F: Fn(u64) -> bool>(key: u64, closure_address: usize) -> bool {
-            let closure = closure_address as *const F;
-            unsafe { (*closure)(key) }
-        }
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        with_filter(filter, |trampoline_fn, closure_address| {
+            index.inner.filtered_search_f16(
+                f16::to_i16s(&query),
+                count,
+                trampoline_fn,
+                closure_address,
+            )
+        })
+    }
+
+    unsafe fn search_until(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+        budget_ns: u64,
+        filter: usize,
+        filter_state: usize,
+    ) -> Result<ffi::DeadlineMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index
+            .inner
+            .search_until_f16(f16::to_i16s(&query), count, budget_ns, filter, filter_state)
+    }
+
+    fn search_traced(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+    ) -> Result<ffi::TracedMatches, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_traced_f16(f16::to_i16s(&query), count)
+    }
+
+    fn search_into(
+        index: &Index,
+        query: &[Self],
+        keys: &mut [Key],
+        distances: &mut [Distance],
+    ) -> Result<usize, cxx::Exception> {
+        let query = index.normalized(query);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index
+            .inner
+            .search_into_f16(f16::to_i16s(&query), keys, distances)
+    }
 
-        // Temporarily cast the closure to a raw pointer for passing.
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline::<F> as *const ());
-            let closure_address: usize = &filter as *const F as usize;
-            index.inner.filtered_search_f16(
-                f16::to_i16s(query),
-                count,
-                trampoline_fn,
-                closure_address,
-            )
-        }
+    fn search_many(
+        index: &Index,
+        queries: &[Self],
+        count: usize,
+    ) -> Result<ffi::BatchMatches, cxx::Exception> {
+        let queries = index.normalized_rows(queries);
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_many_f16(f16::to_i16s(&queries), count)
     }
 
     fn change_metric(
-        index: &mut Index,
+        index: &Index,
         metric: std::boxed::Box<dyn Fn(*const Self, *const Self) -> Distance + Send + Sync>,
     ) -> Result<(), cxx::Exception> {
-        // Store the metric function in the Index.
-        type MetricFn = fn(*const f16, *const f16) -> Distance;
-        index.metric_fn = Some(MetricFunction::F16Metric(metric));
-
-        // Trampoline is the function that knows how to call the Rust closure.
-        // The `first` is a pointer to the first vector, `second` is a pointer to the second vector,
-        // and `index_wrapper` is a pointer to the `index` itself, from which we can infer the metric function
-        // and the number of dimensions.
-        extern "C" fn trampoline(first: usize, second: usize, closure_address: usize) -> Distance {
-            let first_ptr = first as *const f16;
-            let second_ptr = second as *const f16;
-            let closure: MetricFn = unsafe { std::mem::transmute(closure_address) };
-            closure(first_ptr, second_ptr)
-        }
-
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline as *const ());
-            let closure_address = match index.metric_fn {
-                Some(MetricFunction::F16Metric(ref metric)) => metric as *const _ as usize,
-                _ => panic!("Expected F16Metric"),
-            };
-            index.inner.change_metric(trampoline_fn, closure_address)
-        }
-
+        index.register_metric(MetricFunction::F16Metric(metric));
         Ok(())
     }
 }
 
+#[cfg(feature = "native")]
 impl VectorType for b1x8 {
+    const SCALAR_KIND: ScalarKind = ScalarKind::B1;
+
     fn search(index: &Index, query: &[Self], count: usize) -> Result<ffi::Matches, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
         index.inner.search_b1x8(b1x8::to_u8s(query), count)
     }
     fn get(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        Self::get_unlocked(index, key, vector)
+    }
+    fn get_unlocked(index: &Index, key: Key, vector: &mut [Self]) -> Result<usize, cxx::Exception> {
         index.inner.get_b1x8(key, b1x8::to_mut_u8s(vector))
     }
     fn add(index: &Index, key: Key, vector: &[Self]) -> Result<(), cxx::Exception> {
-        index.inner.add_b1x8(key, b1x8::to_u8s(vector))
+        index.add_growing(key, || index.inner.add_b1x8(key, b1x8::to_u8s(vector)))
+    }
+    fn add_many(index: &Index, keys: &[Key], vectors: &[Self]) -> Result<(), cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.add_many_b1x8(keys, b1x8::to_u8s(vectors))
     }
     fn filtered_search<F>(
         index: &Index,
@@ -920,66 +2080,119 @@ impl VectorType for b1x8 {
         Self: Sized,
         F: Fn(Key) -> bool,
     {
-        // Trampoline is the function that knows how to call the Rust closure.
-        extern "C" fn trampoline<F: Fn(u64) -> bool>(key: u64, closure_address: usize) -> bool {
-            let closure = closure_address as *const F;
-            unsafe { (*closure)(key) }
-        }
-
-        // Temporarily cast the closure to a raw pointer for passing.
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline::<F> as *const ());
-            let closure_address: usize = &filter as *const F as usize;
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        with_filter(filter, |trampoline_fn, closure_address| {
             index.inner.filtered_search_b1x8(
                 b1x8::to_u8s(query),
                 count,
                 trampoline_fn,
                 closure_address,
             )
-        }
+        })
+    }
+
+    unsafe fn search_until(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+        budget_ns: u64,
+        filter: usize,
+        filter_state: usize,
+    ) -> Result<ffi::DeadlineMatches, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index
+            .inner
+            .search_until_b1x8(b1x8::to_u8s(query), count, budget_ns, filter, filter_state)
+    }
+
+    fn search_traced(
+        index: &Index,
+        query: &[Self],
+        count: usize,
+    ) -> Result<ffi::TracedMatches, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_traced_b1x8(b1x8::to_u8s(query), count)
+    }
+
+    fn search_into(
+        index: &Index,
+        query: &[Self],
+        keys: &mut [Key],
+        distances: &mut [Distance],
+    ) -> Result<usize, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index
+            .inner
+            .search_into_b1x8(b1x8::to_u8s(query), keys, distances)
+    }
+
+    fn search_many(
+        index: &Index,
+        queries: &[Self],
+        count: usize,
+    ) -> Result<ffi::BatchMatches, cxx::Exception> {
+        let _shared = index.growth.read().unwrap_or_else(PoisonError::into_inner);
+        index.inner.search_many_b1x8(b1x8::to_u8s(queries), count)
     }
 
     fn change_metric(
-        index: &mut Index,
+        index: &Index,
         metric: std::boxed::Box<dyn Fn(*const Self, *const Self) -> Distance + Send + Sync>,
     ) -> Result<(), cxx::Exception> {
-        // Store the metric function in the Index.
-        type MetricFn = fn(*const b1x8, *const b1x8) -> Distance;
-        index.metric_fn = Some(MetricFunction::B1X8Metric(metric));
-
-        // Trampoline is the function that knows how to call the Rust closure.
-        // The `first` is a pointer to the first vector, `second` is a pointer to the second vector,
-        // and `index_wrapper` is a pointer to the `index` itself, from which we can infer the metric function
-        // and the number of dimensions.
-        extern "C" fn trampoline(first: usize, second: usize, closure_address: usize) -> Distance {
-            let first_ptr = first as *const b1x8;
-            let second_ptr = second as *const b1x8;
-            let closure: MetricFn = unsafe { std::mem::transmute(closure_address) };
-            closure(first_ptr, second_ptr)
-        }
-
-        unsafe {
-            let trampoline_fn: usize = std::mem::transmute(trampoline as *const ());
-            let closure_address = match index.metric_fn {
-                Some(MetricFunction::B1X8Metric(ref metric)) => metric as *const _ as usize,
-                _ => panic!("Expected B1X8Metric"),
-            };
-            index.inner.change_metric(trampoline_fn, closure_address)
-        }
-
+        index.register_metric(MetricFunction::B1X8Metric(metric));
         Ok(())
     }
 }
 
+#[cfg(feature = "native")]
 impl Index {
-    pub fn new(options: &ffi::IndexOptions) -> Result<Self, cxx::Exception> {
-        match ffi::new_native_index(options) {
-            Ok(inner) => Result::Ok(Self {
+    /// Creates a new index, after validating the options with `IndexOptions::validate`.
+    ///
+    /// With `lock_memory` set, the memory of the index is locked with [`Index::lock_memory`] as
+    /// it is allocated.
+    pub fn new(options: &ffi::IndexOptions) -> Result<Self, IndexError> {
+        options.validate()?;
+        let index = match ffi::new_native_index(options) {
+            Ok(inner) => Self {
                 inner,
-                metric_fn: None,
-            }),
-            Err(err) => Err(err),
+                metric_fn: Mutex::new(Vec::new()),
+                metric_panics: Arc::default(),
+                parallel_metric: Mutex::new(()),
+                growth: RwLock::new(()),
+                auto_normalize: options.auto_normalize,
+                #[cfg(not(no_fs))]
+                journal: Mutex::new(None),
+                replication: Mutex::new(None),
+                #[cfg(feature = "chaos")]
+                faults: chaos::FaultInjector::default(),
+                #[cfg(feature = "async")]
+                spawned: spawned::spawn_limit(),
+                vector_allocator: None,
+            },
+            Err(err) => return Err(err.into()),
+        };
+        if options.lock_memory {
+            index.lock_memory()?;
         }
+        Ok(index)
+    }
+
+    /// Makes the native index call a custom metric, replacing any previous one.
+    fn register_metric(&self, metric: MetricFunction) {
+        let state = MetricState::new(metric, Arc::clone(&self.metric_panics));
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let mut metrics = self
+            .metric_fn
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.register_metric_state(&state);
+        metrics.push(state);
+    }
+
+    /// Points the native index at an already pinned custom metric.
+    pub(crate) fn register_metric_state(&self, state: &MetricState) {
+        let trampoline_fn: usize = metric_trampoline as *const () as usize;
+        self.inner.change_metric(trampoline_fn, state.address());
     }
 
     /// Retrieves the expansion value used during index creation.
@@ -993,23 +2206,37 @@ impl Index {
     }
 
     /// Updates the expansion value used during index creation. Rarely used.
+    /// Every insertion reads it once, so the change applies to insertions starting afterwards.
     pub fn change_expansion_add(self: &Index, n: usize) {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
         self.inner.change_expansion_add(n)
     }
 
     /// Updates the expansion value used during search operations.
+    /// Every search reads it once, so the change applies to searches starting afterwards.
     pub fn change_expansion_search(self: &Index, n: usize) {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
         self.inner.change_expansion_search(n)
     }
 
     /// Changes the metric kind used to calculate the distance between vectors.
+    /// Applies to the calls starting afterwards, like [`Index::change_metric`].
     pub fn change_metric_kind(self: &Index, metric: ffi::MetricKind) {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
         self.inner.change_metric_kind(metric)
     }
 
     /// Overrides the metric function used to calculate the distance between vectors.
+    ///
+    /// Like the other runtime settings, the metric changes through a shared reference, once the
+    /// calls in flight finish, and applies to the calls starting afterwards. Replaced metrics
+    /// stay alive until the index drops.
+    ///
+    /// Panics of the metric are caught before they reach the native layer, counting as the
+    /// largest distance, and fail the insertion or search that called it. A failed insertion
+    /// still adds its vector, possibly poorly linked.
     pub fn change_metric<T: VectorType>(
-        self: &mut Index,
+        self: &Index,
         metric: std::boxed::Box<dyn Fn(*const T, *const T) -> Distance + Send + Sync>,
     ) {
         T::change_metric(self, metric).unwrap();
@@ -1033,13 +2260,17 @@ impl Index {
     ///
     /// # Returns
     ///
-    /// A `Result` containing the matches found.
+    /// A `Result` containing the matches found, or `IndexError::DimensionMismatch` if the query
+    /// doesn't have the dimensions of the index.
     pub fn search<T: VectorType>(
         self: &Index,
         query: &[T],
         count: usize,
-    ) -> Result<ffi::Matches, cxx::Exception> {
-        T::search(self, query, count)
+    ) -> Result<ffi::Matches, IndexError> {
+        self.check_dimensions::<T>(query.len())?;
+        self.inject_fault("search")?;
+        let matches = self.guard_metric(|| T::search(self, query, count))?;
+        Ok(matches?)
     }
 
     /// Performs k-Approximate Nearest Neighbors (kANN) Search for closest vectors to the provided query
@@ -1053,27 +2284,183 @@ impl Index {
     ///
     /// # Returns
     ///
-    /// A `Result` containing the matches found.
+    /// A `Result` containing the matches found, or an error carrying the panic message if the
+    /// filter panicked. The panic is caught before it reaches the native layer, and the filter
+    /// isn't called again during that search.
     pub fn filtered_search<T: VectorType, F>(
         self: &Index,
         query: &[T],
         count: usize,
         filter: F,
-    ) -> Result<ffi::Matches, cxx::Exception>
+    ) -> Result<ffi::Matches, IndexError>
     where
         F: Fn(Key) -> bool,
     {
-        T::filtered_search(self, query, count, filter)
+        self.check_dimensions::<T>(query.len())?;
+        self.inject_fault("search")?;
+        let matches = self.guard_metric(|| T::filtered_search(self, query, count, filter))?;
+        Ok(matches?)
+    }
+
+    /// Performs kANN Search like [`Index::search`], writing the matches into caller-provided
+    /// buffers, so loops running many queries don't allocate results for each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `keys` - The buffer receiving the keys of the matches, closest first. Its length is the
+    ///   maximum number of neighbors to search for.
+    /// * `distances` - The buffer receiving the distances of the matches, as long as `keys`.
+    ///
+    /// # Returns
+    ///
+    /// The number of matches written to the front of both buffers, which is smaller than their
+    /// length if the index holds fewer vectors, `IndexError::DimensionMismatch` if the query
+    /// doesn't have the dimensions of the index, or an error if the buffers differ in length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[1.0_f32, 0.0]).unwrap();
+    /// index.add(2, &[0.0_f32, 1.0]).unwrap();
+    ///
+    /// let mut keys = [0; 10];
+    /// let mut distances = [0.0; 10];
+    /// for query in [[0.9_f32, 0.0], [0.0, 0.9]] {
+    ///     let found = index.search_into(&query, &mut keys, &mut distances).unwrap();
+    ///     assert_eq!(found, 2);
+    ///     assert!(distances[0] <= distances[1]);
+    /// }
+    /// assert_eq!(keys[..2], [2, 1]);
+    /// ```
+    pub fn search_into<T: VectorType>(
+        self: &Index,
+        query: &[T],
+        keys: &mut [Key],
+        distances: &mut [Distance],
+    ) -> Result<usize, IndexError> {
+        self.check_dimensions::<T>(query.len())?;
+        self.inject_fault("search")?;
+        let found = self.guard_metric(|| T::search_into(self, query, keys, distances))?;
+        Ok(found?)
     }
 
-    /// Adds a vector with a specified key to the index.
+    /// Adds a vector with a specified key to the index, growing the capacity if it is full.
     ///
     /// # Arguments
     ///
     /// * `key` - The key associated with the vector.
     /// * `vector` - A slice containing the vector data.
-    pub fn add<T: VectorType>(self: &Index, key: Key, vector: &[T]) -> Result<(), cxx::Exception> {
-        T::add(self, key, vector)
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the vector doesn't have the dimensions of the index,
+    /// which is checked before it reaches the native layer.
+    pub fn add<T: VectorType>(self: &Index, key: Key, vector: &[T]) -> Result<(), IndexError> {
+        self.check_dimensions::<T>(vector.len())?;
+        Ok(T::add(self, key, vector)?)
+    }
+
+    /// Adds many vectors at once, packed row-major, crossing into the native layer only once.
+    ///
+    /// The native index inserts them in parallel on all hardware threads, after the capacity
+    /// is grown to fit all of them, so concurrent insertions from other threads may still run
+    /// out of room.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys associated with the vectors.
+    /// * `vectors` - A slice holding one vector per key, one after another.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if `vectors` doesn't hold exactly one vector per key, or
+    /// the first native error, like a repeated key in a regular index, in which case the other
+    /// vectors may be partially inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// let keys: Vec<u64> = (0..1000).collect();
+    /// let vectors: Vec<f32> = keys.iter().flat_map(|&key| [key as f32, 0.0]).collect();
+    /// index.add_many(&keys, &vectors).unwrap();
+    ///
+    /// assert_eq!(index.size(), 1000);
+    /// assert_eq!(index.search(&[41.9_f32, 0.0], 1).unwrap().keys, vec![42]);
+    /// ```
+    pub fn add_many<T: VectorType>(&self, keys: &[Key], vectors: &[T]) -> Result<(), IndexError> {
+        let expected = keys.len() * self.vector_length::<T>();
+        if vectors.len() != expected {
+            return Err(IndexError::DimensionMismatch {
+                expected,
+                got: vectors.len(),
+            });
+        }
+        if keys.is_empty() {
+            return Ok(());
+        }
+        self.inject_fault("add")?;
+        let needed = self.size() + keys.len();
+        if needed > self.capacity() {
+            self.reserve(needed.max(self.capacity() * 2))?;
+        }
+        let before: Vec<usize> = keys.iter().map(|&key| self.count(key)).collect();
+        let added = self.guard_parallel_metric(|| T::add_many(self, keys, vectors));
+        match &added {
+            // The vectors are inserted even if the metric panicked, linked as if they were far from all.
+            Ok(Ok(())) | Err(_) => self.track_changes(keys),
+            // Only some tasks of a failed batch insert their vectors, in no particular order.
+            Ok(Err(_)) => {
+                let inserted: Vec<Key> = keys
+                    .iter()
+                    .zip(&before)
+                    .filter(|&(&key, &count)| self.count(key) > count)
+                    .map(|(&key, _)| key)
+                    .collect();
+                self.track_changes(&inserted);
+            }
+        }
+        added??;
+        Ok(())
+    }
+
+    /// The number of `T` elements holding one vector, with a bit per dimension in `b1x8` slices.
+    pub(crate) fn vector_length<T: VectorType>(&self) -> usize {
+        match T::SCALAR_KIND {
+            ScalarKind::B1 => self.dimensions().div_ceil(8),
+            _ => self.dimensions(),
+        }
+    }
+
+    /// Checks that a slice of `T` holds exactly one vector of the index dimensions, which take
+    /// a bit each in `b1x8` slices, rounded up to whole bytes.
+    pub(crate) fn check_dimensions<T: VectorType>(&self, length: usize) -> Result<(), IndexError> {
+        let expected = self.vector_length::<T>();
+        if length != expected {
+            return Err(IndexError::DimensionMismatch {
+                expected,
+                got: length,
+            });
+        }
+        Ok(())
     }
 
     /// Extracts one or more vectors matching the specified key.
@@ -1106,27 +2493,144 @@ impl Index {
         self: &Index,
         key: Key,
         vector: &mut Vec<T>,
+    ) -> Result<usize, cxx::Exception> {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        self.export_unlocked(key, vector)
+    }
+
+    /// Like [`Index::export`], for callers already holding the growth lock.
+    pub(crate) fn export_unlocked<T: VectorType + Default + Clone>(
+        &self,
+        key: Key,
+        vector: &mut Vec<T>,
     ) -> Result<usize, cxx::Exception> {
         let dim = self.dimensions();
         let max_matches = self.count(key);
         vector.resize(dim * max_matches, T::default());
-        let matches = T::get(self, key, &mut vector[..]);
-        if matches.is_err() {
-            return matches;
-        }
-        vector.resize(dim * matches.as_ref().unwrap(), T::default());
-        return matches;
+        let matches = T::get_unlocked(self, key, &mut vector[..])?;
+        vector.resize(dim * matches, T::default());
+        Ok(matches)
     }
 
     /// Reserves memory for a specified number of incoming vectors.
+    /// Insertions grow the capacity on their own, so this only avoids repeated reallocations.
     ///
     /// # Arguments
     ///
     /// * `capacity` - The desired total capacity, including the current size.
     pub fn reserve(self: &Index, capacity: usize) -> Result<(), cxx::Exception> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
         self.inner.reserve(capacity)
     }
 
+    /// Runs a native insertion, doubling the capacity first if the index is full.
+    ///
+    /// Insertions share the growth lock, while growing takes it exclusively, so concurrent
+    /// insertions never race a reallocation of the native index. The native layer also needs
+    /// a first reservation to set up its per-thread contexts, which happens here as well.
+    fn add_growing<F>(&self, key: Key, add: F) -> Result<(), cxx::Exception>
+    where
+        F: Fn() -> Result<(), cxx::Exception>,
+    {
+        self.inject_fault("add")?;
+        let inserted = match self.guard_metric(|| self.insert_growing(add)) {
+            Ok(inserted) => inserted,
+            // The vector is inserted even if the metric panicked, linked as if it were far from all.
+            Err(panic) => {
+                self.track_changes(&[key]);
+                return Err(panic);
+            }
+        };
+        inserted?;
+        self.track_changes(&[key]);
+        Ok(())
+    }
+
+    /// Runs a native call, and reports a panic of the custom metric it caught on the calling
+    /// thread as a native error, in place of the result.
+    pub(crate) fn guard_metric<R>(&self, call: impl FnOnce() -> R) -> Result<R, cxx::Exception> {
+        let (result, panic) = catch_metric_panics(call);
+        if let Some(message) = panic {
+            ffi::raise_callback_panic(&message)?;
+        }
+        Ok(result)
+    }
+
+    /// Runs a native call spreading its work over native worker threads, like
+    /// [`Index::guard_metric`], also reporting the panics of the custom metric on the workers.
+    ///
+    /// Those are collected in a slot of the index, so batched calls running a custom metric
+    /// take turns, each of them using all hardware threads anyway.
+    pub(crate) fn guard_parallel_metric<R>(
+        &self,
+        call: impl FnOnce() -> R,
+    ) -> Result<R, cxx::Exception> {
+        let custom = !(self.metric_fn.lock())
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty();
+        if !custom {
+            return Ok(call());
+        }
+        let _turn = self
+            .parallel_metric
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.metric_panics.clear();
+        let result = self.guard_metric(call)?;
+        self.metric_panics.take()?;
+        Ok(result)
+    }
+
+    /// Retries the insertion until it finds a free slot, growing the index in between.
+    fn insert_growing<F>(&self, add: F) -> Result<(), cxx::Exception>
+    where
+        F: Fn() -> Result<(), cxx::Exception>,
+    {
+        loop {
+            {
+                let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+                if self.size() < self.capacity() {
+                    match add() {
+                        Ok(()) => return Ok(()),
+                        // Another thread took the last free slot, so grow and retry.
+                        Err(_) if self.size() >= self.capacity() => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+
+            let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+            let capacity = self.capacity();
+            if self.size() >= capacity {
+                self.inner.reserve((capacity * 2).max(64))?;
+                if self.capacity() <= capacity {
+                    // Surface the native error explaining why the index can't grow.
+                    return add();
+                }
+            }
+        }
+    }
+
+    /// Fault injection is compiled out without the `chaos` feature.
+    #[cfg(not(feature = "chaos"))]
+    #[inline(always)]
+    fn inject_fault(&self, _operation: &str) -> Result<(), cxx::Exception> {
+        Ok(())
+    }
+
+    /// The bytes every vector occupies in the native index, and in its snapshots.
+    fn vector_bytes(&self) -> usize {
+        let dimensions = self.dimensions();
+        match self.inner.scalar_kind() {
+            ScalarKind::F64 => dimensions * 8,
+            ScalarKind::F32 => dimensions * 4,
+            ScalarKind::F16 => dimensions * 2,
+            ScalarKind::I8 => dimensions,
+            ScalarKind::B1 => dimensions.div_ceil(8),
+            _ => 0,
+        }
+    }
+
     /// Retrieves the number of dimensions in the vectors indexed.
     pub fn dimensions(self: &Index) -> usize {
         self.inner.dimensions()
@@ -1137,6 +2641,17 @@ impl Index {
         self.inner.connectivity()
     }
 
+    /// Retrieves the multiplier of the random levels of new nodes, `mL` in the paper,
+    /// resolving the zero default to `1 / ln(connectivity)`.
+    pub fn level_multiplier(self: &Index) -> f64 {
+        self.inner.level_multiplier()
+    }
+
+    /// Retrieves the seed of the generators drawing the levels of new nodes.
+    pub fn level_seed(self: &Index) -> u64 {
+        self.inner.level_seed()
+    }
+
     /// Retrieves the current number of vectors in the index.
     pub fn size(self: &Index) -> usize {
         self.inner.size()
@@ -1149,6 +2664,7 @@ impl Index {
 
     /// Reports expected file size after serialization.
     pub fn serialized_length(self: &Index) -> usize {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
         self.inner.serialized_length()
     }
 
@@ -1162,7 +2678,12 @@ impl Index {
     ///
     /// `true` if the vector is successfully removed, `false` otherwise.
     pub fn remove(self: &Index, key: Key) -> Result<usize, cxx::Exception> {
-        self.inner.remove(key)
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        let removed = self.inner.remove(key)?;
+        if removed > 0 {
+            self.track_changes(&[key]);
+        }
+        Ok(removed)
     }
 
     /// Renames the vector under a specific key.
@@ -1176,7 +2697,12 @@ impl Index {
     ///
     /// `true` if the vector is renamed, `false` otherwise.
     pub fn rename(self: &Index, from: Key, to: Key) -> Result<usize, cxx::Exception> {
-        self.inner.rename(from, to)
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        let renamed = self.inner.rename(from, to)?;
+        if renamed > 0 {
+            self.track_changes(&[from, to]);
+        }
+        Ok(renamed)
     }
 
     /// Checks if the index contains a vector with a specified key.
@@ -1205,41 +2731,129 @@ impl Index {
         self.inner.count(key)
     }
 
+    /// Exports the keys of the vectors present in the index, in no particular order.
+    /// Keys with several vectors are repeated, once per vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - A slice receiving the keys, limiting how many are exported.
+    /// * `offset` - The number of keys to skip, for pagination.
+    ///
+    /// # Returns
+    ///
+    /// Number of keys written into the `keys` slice.
+    pub fn export_keys(self: &Index, keys: &mut [Key], offset: usize) -> usize {
+        self.inner.export_keys(keys, offset)
+    }
+
+    /// Collects the keys of all vectors present in the index, in no particular order.
+    pub fn keys(self: &Index) -> Vec<Key> {
+        let mut keys = vec![0; self.size()];
+        let exported = self.export_keys(&mut keys, 0);
+        keys.truncate(exported);
+        keys
+    }
+
     /// Saves the index to a specified file.
     ///
+    /// Paths are passed to the native layer as their raw bytes, so paths that aren't valid
+    /// UTF-8 work on Unix. Paths containing NUL bytes are rejected. On Windows, the native
+    /// layer opens files with the narrow APIs, which read non-ASCII paths correctly only with
+    /// the UTF-8 code page enabled.
+    ///
     /// # Arguments
     ///
     /// * `path` - The file path where the index will be saved.
-    pub fn save(self: &Index, path: &str) -> Result<(), cxx::Exception> {
-        self.inner.save(path)
+    ///
+    /// The file is read once more after being written, to seal it with a checksum verified on load.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(self: &Index, path: P) -> Result<(), IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        self.save_exclusive(path.as_ref())
     }
 
-    /// Loads the index from a specified file.
+    /// Saves the index to a file, while the caller excludes concurrent mutations.
+    #[cfg(not(no_fs))]
+    pub(crate) fn save_exclusive(&self, path: &Path) -> Result<(), IndexError> {
+        self.inject_fault("save")?;
+        self.inner.save(path.as_os_str().as_encoded_bytes())?;
+        checksum::seal_file(path)
+    }
+
+    /// Loads the index from a specified file, replaying the changes appended by [`Index::sync`].
+    /// Files produced by an incompatible version are rejected with `IndexError::IncompatibleFormat`,
+    /// and truncated or corrupted ones with `IndexError::CorruptSnapshot`.
     ///
     /// # Arguments
     ///
     /// * `path` - The file path from where the index will be loaded.
-    pub fn load(self: &Index, path: &str) -> Result<(), cxx::Exception> {
-        self.inner.load(path)
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(self: &mut Index, path: P) -> Result<(), IndexError> {
+        let path = path.as_ref();
+        checksum::verify_file(path, self.vector_bytes())?;
+        self.inner.load(path.as_os_str().as_encoded_bytes())?;
+        self.replay_synced(path)?;
+        self.track_replaced();
+        Ok(())
     }
 
     /// Creates a view of the index from a file without loading it into memory.
+    /// Files produced by an incompatible version are rejected with `IndexError::IncompatibleFormat`,
+    /// and files holding changes appended by [`Index::sync`] with `IndexError::Io`.
+    /// Only the header is read, rejecting files shorter than their sealed length with
+    /// `IndexError::CorruptSnapshot`, so corrupted contents go unnoticed. Use
+    /// [`Index::view_verified`] to check the checksum as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path from where the view will be created.
+    #[cfg(not(no_fs))]
+    pub fn view<P: AsRef<Path>>(self: &mut Index, path: P) -> Result<(), IndexError> {
+        let path = path.as_ref();
+        checksum::verify_file_header(path, self.vector_bytes())?;
+        self.view_checked(path)
+    }
+
+    /// Creates a view of the index from a file like [`Index::view`], after reading the whole
+    /// file once to verify its checksum, rejecting corrupted ones with
+    /// `IndexError::CorruptSnapshot`.
     ///
     /// # Arguments
     ///
     /// * `path` - The file path from where the view will be created.
-    pub fn view(self: &Index, path: &str) -> Result<(), cxx::Exception> {
-        self.inner.view(path)
+    #[cfg(not(no_fs))]
+    pub fn view_verified<P: AsRef<Path>>(self: &mut Index, path: P) -> Result<(), IndexError> {
+        let path = path.as_ref();
+        checksum::verify_file(path, self.vector_bytes())?;
+        self.view_checked(path)
+    }
+
+    /// Memory-maps a file whose snapshot was checked by the caller.
+    #[cfg(not(no_fs))]
+    fn view_checked(&mut self, path: &Path) -> Result<(), IndexError> {
+        self.forget_sync();
+        self.inner.view(path.as_os_str().as_encoded_bytes())?;
+        let unsynced = sync::check_unsynced(path, self.serialized_length());
+        if unsynced.is_err() {
+            self.inner.reset()?;
+        }
+        self.track_replaced();
+        unsynced
     }
 
     /// Erases all members from the index, closes files, and returns RAM to OS.
-    pub fn reset(self: &Index) -> Result<(), cxx::Exception> {
-        self.inner.reset()
+    pub fn reset(self: &mut Index) -> Result<(), cxx::Exception> {
+        #[cfg(not(no_fs))]
+        self.forget_sync();
+        self.inner.reset()?;
+        self.track_replaced();
+        Ok(())
     }
 
     /// A relatively accurate lower bound on the amount of memory consumed by the system.
     /// In practice, its error will be below 10%.
     pub fn memory_usage(self: &Index) -> usize {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
         self.inner.memory_usage()
     }
 
@@ -1248,8 +2862,12 @@ impl Index {
     /// # Arguments
     ///
     /// * `path` - The file path where the index will be saved.
-    pub fn save_to_buffer(self: &Index, buffer: &mut [u8]) -> Result<(), cxx::Exception> {
-        self.inner.save_to_buffer(buffer)
+    pub fn save_to_buffer(self: &Index, buffer: &mut [u8]) -> Result<(), IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        self.inject_fault("save")?;
+        self.inner.save_to_buffer(buffer)?;
+        let length = self.inner.serialized_length();
+        checksum::seal_buffer(&mut buffer[..length])
     }
 
     /// Loads the index from a specified file.
@@ -1257,8 +2875,13 @@ impl Index {
     /// # Arguments
     ///
     /// * `path` - The file path from where the index will be loaded.
-    pub fn load_from_buffer(self: &Index, buffer: &[u8]) -> Result<(), cxx::Exception> {
-        self.inner.load_from_buffer(buffer)
+    pub fn load_from_buffer(self: &mut Index, buffer: &[u8]) -> Result<(), IndexError> {
+        checksum::verify_buffer(buffer, self.vector_bytes())?;
+        #[cfg(not(no_fs))]
+        self.forget_sync();
+        self.inner.load_from_buffer(buffer)?;
+        self.track_replaced();
+        Ok(())
     }
 
     /// Creates a view of the index from a file without loading it into memory.
@@ -1266,33 +2889,60 @@ impl Index {
     /// # Arguments
     ///
     /// * `path` - The file path from where the view will be created.
-    pub fn view_from_buffer(self: &Index, buffer: &[u8]) -> Result<(), cxx::Exception> {
-        self.inner.view_from_buffer(buffer)
+    pub fn view_from_buffer(self: &mut Index, buffer: &[u8]) -> Result<(), IndexError> {
+        checksum::verify_buffer(buffer, self.vector_bytes())?;
+        #[cfg(not(no_fs))]
+        self.forget_sync();
+        self.inner.view_from_buffer(buffer)?;
+        self.track_replaced();
+        Ok(())
     }
 }
 
-pub fn new_index(options: &ffi::IndexOptions) -> Result<Index, cxx::Exception> {
+pub fn new_index(options: &ffi::IndexOptions) -> Result<Index, IndexError> {
     Index::new(options)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "native"))]
+#[allow(clippy::field_reassign_with_default)]
 mod tests {
     use crate::ffi::IndexOptions;
     use crate::ffi::MetricKind;
     use crate::ffi::ScalarKind;
 
     use crate::b1x8;
-    use crate::f16;
     use crate::new_index;
-    use crate::Distance;
     use crate::Index;
+    use crate::IndexError;
     use crate::Key;
+    use crate::VectorType;
 
     use std::env;
 
+    static_assertions::assert_impl_all!(Index: Send, Sync);
+    static_assertions::assert_impl_all!(crate::FrozenIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::Collection: Send, Sync);
+    static_assertions::assert_impl_all!(crate::CachedIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::ExpiringIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::TenantIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::TruncatedIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::PqIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::ReducedIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::Snapshot: Send, Sync);
+    static_assertions::assert_impl_all!(crate::SparseIndex: Send, Sync);
+    static_assertions::assert_impl_all!(crate::IndexReader: Clone, Send, Sync);
+    #[cfg(not(no_fs))]
+    static_assertions::assert_impl_all!(crate::DurableIndex: Send, Sync);
+    #[cfg(not(no_fs))]
+    static_assertions::assert_impl_all!(crate::SpilledIndex: Send, Sync);
+    #[cfg(feature = "layered")]
+    static_assertions::assert_impl_all!(crate::LayeredIndex: Send, Sync);
+    #[cfg(feature = "pure-rust")]
+    static_assertions::assert_impl_all!(crate::pure::Index: Send, Sync);
+
     #[test]
     fn print_specs() {
-        print!("--------------------------------------------------\n");
+        println!("--------------------------------------------------");
         println!("OS: {}", env::consts::OS);
         println!(
             "Rust version: {}",
@@ -1360,7 +3010,7 @@ mod tests {
             "b1 hardware acceleration: {}",
             b1_index.hardware_acceleration()
         );
-        print!("--------------------------------------------------\n");
+        println!("--------------------------------------------------");
     }
 
     #[test]
@@ -1388,12 +3038,12 @@ mod tests {
         assert_eq!(found_vec, first.to_vec());
 
         // Test using slice
-        let mut found_slice = [0.0 as f32; 5];
+        let mut found_slice = [0.0_f32; 5];
         assert_eq!(index.get(1, &mut found_slice).unwrap(), 1);
         assert_eq!(found_slice, first);
 
         // Create a slice with incorrect size
-        let mut found = [0.0 as f32; 6]; // This isn't a multiple of the index's dimensions.
+        let mut found = [0.0_f32; 6]; // This isn't a multiple of the index's dimensions.
         let result = index.get(1, &mut found);
         assert!(result.is_err());
     }
@@ -1442,22 +3092,22 @@ mod tests {
         let id4 = 483367403120624233;
 
         assert!(index.add(id1, &first).is_ok());
-        let mut found_slice = [0.0 as f32; 4];
+        let mut found_slice = [0.0_f32; 4];
         assert_eq!(index.get(id1, &mut found_slice).unwrap(), 1);
         assert!(index.remove(id1).is_ok());
 
         assert!(index.add(id2, &second).is_ok());
-        let mut found_slice = [0.0 as f32; 4];
+        let mut found_slice = [0.0_f32; 4];
         assert_eq!(index.get(id2, &mut found_slice).unwrap(), 1);
         assert!(index.remove(id2).is_ok());
 
         assert!(index.add(id3, &second).is_ok());
-        let mut found_slice = [0.0 as f32; 4];
+        let mut found_slice = [0.0_f32; 4];
         assert_eq!(index.get(id3, &mut found_slice).unwrap(), 1);
         assert!(index.remove(id3).is_ok());
 
         assert!(index.add(id4, &second).is_ok());
-        let mut found_slice = [0.0 as f32; 4];
+        let mut found_slice = [0.0_f32; 4];
         assert_eq!(index.get(id4, &mut found_slice).unwrap(), 1);
         assert!(index.remove(id4).is_ok());
 
@@ -1469,7 +3119,7 @@ mod tests {
         let mut options = IndexOptions::default();
         options.dimensions = 5;
 
-        let index = Index::new(&options).unwrap();
+        let mut index = Index::new(&options).unwrap();
 
         assert!(index.expansion_add() > 0);
         assert!(index.expansion_search() > 0);
@@ -1483,7 +3133,7 @@ mod tests {
         let first: [f32; 5] = [0.2, 0.1, 0.2, 0.1, 0.3];
         let second: [f32; 5] = [0.3, 0.2, 0.4, 0.0, 0.1];
 
-        print!("--------------------------------------------------\n");
+        println!("--------------------------------------------------");
         println!(
             "before add, memory_usage: {} \
             cap: {} \
@@ -1518,12 +3168,16 @@ mod tests {
         let results = index.search(&first, 10).unwrap();
         println!("{:?}", results);
         assert_eq!(results.keys.len(), 2);
-        print!("--------------------------------------------------\n");
+        println!("--------------------------------------------------");
 
         // Validate serialization
-        assert!(index.save("index.rust.usearch").is_ok());
-        assert!(index.load("index.rust.usearch").is_ok());
-        assert!(index.view("index.rust.usearch").is_ok());
+        #[cfg(not(no_fs))]
+        {
+            let path = env::temp_dir().join("index.rust.usearch");
+            assert!(index.save(&path).is_ok());
+            assert!(index.load(&path).is_ok());
+            assert!(index.view(&path).is_ok());
+        }
 
         // Make sure every function is called at least once
         assert!(new_index(&options).is_ok());
@@ -1536,11 +3190,10 @@ mod tests {
         options.dimensions = 2;
         assert!(new_index(&options).is_ok());
 
-        let mut serialization_buffer = Vec::new();
-        serialization_buffer.resize(index.serialized_length(), 0);
+        let mut serialization_buffer = vec![0; index.serialized_length()];
         assert!(index.save_to_buffer(&mut serialization_buffer).is_ok());
 
-        let deserialized_index = new_index(&options).unwrap();
+        let mut deserialized_index = new_index(&options).unwrap();
         assert!(deserialized_index
             .load_from_buffer(&serialization_buffer)
             .is_ok());
@@ -1619,7 +3272,8 @@ mod tests {
     fn test_change_distance_function() {
         let mut options = IndexOptions::default();
         options.dimensions = 2; // Adjusted for simplicity in creating test vectors
-        let mut index = Index::new(&options).unwrap();
+        options.quantization = ScalarKind::F32; // Matching the scalars the metric reads
+        let index = Index::new(&options).unwrap();
         index.reserve(10).unwrap();
 
         // Adding a simple vector to test the distance function changes
@@ -1638,6 +3292,74 @@ mod tests {
         index.change_metric(stateful_distance);
     }
 
+    #[test]
+    fn test_custom_metric_survives_moves() {
+        let options = IndexOptions {
+            dimensions: 2,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.change_metric::<f32>(Box::new(|a, b| unsafe {
+            let a = std::slice::from_raw_parts(a, 2);
+            let b = std::slice::from_raw_parts(b, 2);
+            (a[0] - b[0]).abs() * 2.0 + (a[1] - b[1]).abs() * 0.7
+        }));
+        index.add(1, &[1.0_f32, 0.0]).unwrap();
+        index.add(2, &[0.0_f32, 1.0]).unwrap();
+
+        // The metric keeps working after the index moves, to the heap and back.
+        let boxed = Box::new(index);
+        assert_eq!(boxed.search(&[0.0_f32, 0.8], 1).unwrap().keys, vec![2]);
+        let mut moved = vec![*boxed];
+        assert_eq!(moved[0].search(&[0.9_f32, 0.0], 1).unwrap().keys, vec![1]);
+        let index = moved.pop().unwrap();
+        let matches = index.search(&[0.0_f32, 0.0], 2).unwrap();
+        assert_eq!(matches.keys, vec![2, 1]);
+        assert_eq!(matches.distances, vec![0.7, 2.0]);
+    }
+
+    #[test]
+    fn test_metric_setters_across_threads() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add(1, &[1.0_f32, 0.0]).unwrap();
+        index.add(2, &[0.0_f32, 1.0]).unwrap();
+
+        // Settings change through shared references, from any thread.
+        std::thread::scope(|scope| {
+            scope.spawn(|| index.change_metric::<f32>(Box::new(|_, _| 1.0)));
+            scope.spawn(|| index.change_expansion_search(32));
+            scope.spawn(|| assert_eq!(index.search(&[0.0_f32, 0.0], 2).unwrap().keys.len(), 2));
+        });
+        assert_eq!(index.expansion_search(), 32);
+
+        // Replaced metrics keep their captured state until the index drops.
+        let state = std::sync::Arc::new(2.0_f32);
+        let captured = std::sync::Arc::clone(&state);
+        index.change_metric::<f32>(Box::new(move |_, _| *captured));
+        assert_eq!(std::sync::Arc::strong_count(&state), 2);
+        assert_eq!(
+            index.search(&[0.0_f32, 0.0], 1).unwrap().distances,
+            vec![2.0]
+        );
+        index.change_metric::<f32>(Box::new(|_, _| 0.0));
+        assert_eq!(
+            index.search(&[0.0_f32, 0.0], 1).unwrap().distances,
+            vec![0.0]
+        );
+        let captured = std::sync::Arc::clone(&state);
+        index.change_metric::<f32>(Box::new(move |_, _| *captured));
+        assert_eq!(std::sync::Arc::strong_count(&state), 3);
+        drop(index);
+        assert_eq!(std::sync::Arc::strong_count(&state), 1);
+    }
+
     #[test]
     fn test_binary_vectors_and_hamming_distance() {
         let index = Index::new(&IndexOptions {
@@ -1667,4 +3389,354 @@ mod tests {
         assert_eq!(results.keys[1], 42);
         assert_eq!(results.distances[1], 6.0);
     }
+
+    #[test]
+    fn test_concurrent_add_without_reserve() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        assert_eq!(index.capacity(), 0);
+
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get().min(4)) as u64;
+        std::thread::scope(|scope| {
+            for thread in 0..threads {
+                let index = &index;
+                scope.spawn(move || {
+                    for i in 0..250u64 {
+                        let key = thread * 1000 + i;
+                        index.add(key, &[key as f32; 4]).unwrap();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(index.size(), threads as usize * 250);
+        assert!(index.capacity() >= index.size());
+        assert!(index.contains((threads - 1) * 1000 + 249));
+    }
+
+    #[test]
+    fn test_concurrent_get_while_growing() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..16u64 {
+            index.add(key, &[key as f32; 4]).unwrap();
+        }
+
+        // Readers copy vectors out while the writers keep reallocating the vector lookup.
+        let done = std::sync::atomic::AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            for _ in 0..2 {
+                let (index, done) = (&index, &done);
+                scope.spawn(move || {
+                    let mut found = [0.0_f32; 4];
+                    let mut exported: Vec<f32> = Vec::new();
+                    while !done.load(std::sync::atomic::Ordering::Relaxed) {
+                        for key in 0..16u64 {
+                            assert_eq!(index.get(key, &mut found).unwrap(), 1);
+                            assert_eq!(found, [key as f32; 4]);
+                            assert_eq!(index.export(key, &mut exported).unwrap(), 1);
+                            assert_eq!(exported, [key as f32; 4]);
+                        }
+                    }
+                });
+            }
+            let writers: Vec<_> = (0..2u64)
+                .map(|thread| {
+                    let index = &index;
+                    scope.spawn(move || {
+                        for i in 0..2000u64 {
+                            let key = 1000 + thread * 10_000 + i;
+                            index.add(key, &[key as f32; 4]).unwrap();
+                        }
+                    })
+                })
+                .collect();
+            for writer in writers {
+                writer.join().unwrap();
+            }
+            done.store(true, std::sync::atomic::Ordering::Relaxed);
+        });
+        assert_eq!(index.size(), 16 + 2 * 2000);
+    }
+
+    #[test]
+    fn test_oversubscribed_threads() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = std::sync::Arc::new(Index::new(&options).unwrap());
+
+        // Several times more threads than native contexts, mixing every kind of shared call.
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get()) as u64 * 4;
+        let handles: Vec<_> = (0..threads)
+            .map(|thread| {
+                let index = std::sync::Arc::clone(&index);
+                std::thread::spawn(move || {
+                    for i in 0..50u64 {
+                        let key = thread * 1000 + i;
+                        index.add(key, &[key as f32; 4]).unwrap();
+                        assert_eq!(index.search(&[key as f32; 4], 1).unwrap().keys.len(), 1);
+                        let matches = index
+                            .filtered_search(&[key as f32; 4], 2, |found| found != key)
+                            .unwrap();
+                        assert!(!matches.keys.contains(&key));
+                        if i % 10 == 0 {
+                            index.change_expansion_search(64 + i as usize);
+                            assert!(index.snapshot().unwrap().metadata().size > 0);
+                            assert_eq!(index.remove(key).unwrap(), 1);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(index.size(), threads as usize * 45);
+        assert!(!index.contains(1000) && index.contains(1001));
+    }
+
+    #[test]
+    fn test_debug_and_display() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 8,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.to_string(),
+            "dimensions=2 metric=L2sq quantization=F32 connectivity=8 expansion_add=0 expansion_search=0 multi=false"
+        );
+        let index = Index::new(&options).unwrap();
+        index.reserve(10).unwrap();
+        index.add(42, &[1.0_f32, 0.0]).unwrap();
+        index.add(7, &[2.0_f32, 0.0]).unwrap();
+
+        let summary = index.to_string();
+        assert!(summary.starts_with("dimensions=2 metric=L2sq quantization=F32 size=2 capacity="));
+        assert!(summary.ends_with("MiB"));
+        let debug = format!("{:?}", index);
+        assert!(debug.starts_with("Index { dimensions: 2, metric: L2sq, quantization: F32"));
+        assert!(debug.contains("size: 2"));
+
+        let matches = index.search(&[0.5_f32, 0.0], 2).unwrap();
+        assert_eq!(matches.to_string(), "[42: 0.25, 7: 2.25]");
+        assert_eq!(index.search(&[0.5_f32, 0.0], 0).unwrap().to_string(), "[]");
+    }
+
+    #[cfg(not(no_fs))]
+    #[test]
+    fn test_save_load_paths() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add(1, &[1.0_f32, 2.0]).unwrap();
+
+        let directory = std::env::temp_dir();
+        let path = directory.join("usearch-path-test.usearch");
+        index.save(&path).unwrap();
+        let mut loaded = Index::new(&options).unwrap();
+        loaded.load(path.as_path()).unwrap();
+        assert_eq!(loaded.size(), 1);
+        std::fs::remove_file(&path).unwrap();
+
+        // Paths are passed as raw bytes, rather than as UTF-8 strings.
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let name = std::ffi::OsStr::from_bytes(b"usearch-\xff-test.usearch");
+            let path = directory.join(name);
+            index.save(&path).unwrap();
+            assert!(path.exists());
+            let mut viewed = Index::new(&options).unwrap();
+            viewed.view(&path).unwrap();
+            assert_eq!(viewed.size(), 1);
+            std::fs::remove_file(&path).unwrap();
+        }
+
+        // Truncating at a NUL byte would silently target another file.
+        assert!(index.save("usearch-nul\0.usearch").is_err());
+        assert!(index.save("").is_err());
+    }
+
+    #[test]
+    fn test_dimension_checks() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add(1, &[1.0_f32, 2.0]).unwrap();
+        assert!(matches!(
+            index.add(2, &[1.0_f32]),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 1
+            })
+        ));
+        assert!(matches!(
+            index.search(&[1.0_f32, 2.0, 3.0], 1),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 3
+            })
+        ));
+        assert!(matches!(
+            index.filtered_search(&[1.0_f64], 1, |_| true),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+        assert_eq!(index.size(), 1);
+
+        // The native layer checks the length too, for callers of the low-level methods.
+        assert!(f32::search(&index, &[1.0], 1).is_err());
+        assert!(f32::add(&index, 3, &[1.0, 2.0, 3.0]).is_err());
+
+        // Binary vectors take a bit per dimension.
+        let binary = Index::new(&IndexOptions {
+            dimensions: 12,
+            metric: MetricKind::Hamming,
+            quantization: ScalarKind::B1,
+            ..Default::default()
+        })
+        .unwrap();
+        binary.add(1, b1x8::from_u8s(&[0xFF, 0x0F])).unwrap();
+        assert!(matches!(
+            binary.add(2, b1x8::from_u8s(&[0xFF])),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 1
+            })
+        ));
+        let matches = binary.search(b1x8::from_u8s(&[0xFF, 0x0F]), 1).unwrap();
+        assert_eq!(matches.keys, vec![1]);
+    }
+
+    #[test]
+    fn test_search_into() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..10u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+
+        let mut keys = [Key::MAX; 4];
+        let mut distances = [f32::MAX; 4];
+        let found = index
+            .search_into(&[3.2_f32, 0.0], &mut keys, &mut distances)
+            .unwrap();
+        let matches = index.search(&[3.2_f32, 0.0], 4).unwrap();
+        assert_eq!(found, 4);
+        assert_eq!(keys.to_vec(), matches.keys);
+        assert_eq!(distances.to_vec(), matches.distances);
+
+        // Larger buffers are filled up to the size of the index, and left untouched after it.
+        let mut keys = [Key::MAX; 16];
+        let mut distances = [f32::MAX; 16];
+        let found = index
+            .search_into(&[0.0_f64, 0.0], &mut keys, &mut distances)
+            .unwrap();
+        assert_eq!(found, 10);
+        assert_eq!(keys[0], 0);
+        assert!(keys[10..].iter().all(|&key| key == Key::MAX));
+
+        assert!(matches!(
+            index.search_into(&[0.0_f32], &mut keys, &mut distances),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+        assert!(index
+            .search_into(&[0.0_f32, 0.0], &mut keys, &mut distances[..4])
+            .is_err());
+    }
+
+    #[test]
+    fn test_add_many() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let keys: Vec<Key> = (0..5000).collect();
+        let vectors: Vec<f32> = keys
+            .iter()
+            .flat_map(|&key| [key as f32, 1.0, -1.0])
+            .collect();
+        index.add_many(&keys, &vectors).unwrap();
+        assert_eq!(index.size(), 5000);
+        let mut vector = [0.0_f32; 3];
+        index.get(4242, &mut vector).unwrap();
+        assert_eq!(vector, [4242.0, 1.0, -1.0]);
+        let matches = index.search(&[1234.0_f32, 1.0, -1.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![1234]);
+
+        assert!(matches!(
+            index.add_many(&[5000, 5001], &[0.0_f32; 5]),
+            Err(IndexError::DimensionMismatch {
+                expected: 6,
+                got: 5
+            })
+        ));
+        // Regular indexes reject repeated keys, and report it after inserting the others.
+        assert!(index.add_many(&[5000, 7], &[0.0_f32; 6]).is_err());
+        assert!(index.contains(5000));
+        index.add_many::<f32>(&[], &[]).unwrap();
+
+        // Rows are normalized one by one.
+        let normalized = Index::new(&IndexOptions {
+            metric: MetricKind::Cos,
+            auto_normalize: true,
+            ..options
+        })
+        .unwrap();
+        normalized
+            .add_many(&[1, 2], &[3.0_f64, 4.0, 0.0, 0.0, 0.0, 2.0])
+            .unwrap();
+        let mut vector = [0.0_f32; 3];
+        normalized.get(1, &mut vector).unwrap();
+        assert_eq!(vector, [0.6, 0.8, 0.0]);
+        normalized.get(2, &mut vector).unwrap();
+        assert_eq!(vector, [0.0, 0.0, 1.0]);
+
+        // Binary vectors take a byte per eight dimensions.
+        let binary = Index::new(&IndexOptions {
+            dimensions: 12,
+            metric: MetricKind::Hamming,
+            quantization: ScalarKind::B1,
+            ..Default::default()
+        })
+        .unwrap();
+        binary
+            .add_many(&[1, 2], b1x8::from_u8s(&[0xFF, 0x0F, 0x00, 0x00]))
+            .unwrap();
+        let matches = binary.search(b1x8::from_u8s(&[0x01, 0x00]), 1).unwrap();
+        assert_eq!(matches.keys, vec![2]);
+    }
 }
@@ -0,0 +1,251 @@
+//! Periodic light-weight integrity scans of a serving index.
+//!
+//! Silent corruption, like vectors overwritten with NaNs or graph regions that can no longer
+//! be reached, rarely fails loudly: the index keeps answering, just worse. [`Index::health_scan`]
+//! samples members with random probe queries, checks their stored vectors are finite and that
+//! each can still be found by searching for itself, and reports the memory footprint.
+//!
+//! [`HealthMonitor`] repeats the scan once its interval expires and hands every report to a
+//! callback. `Index` can't be shared across threads, so the monitor is driven by
+//! [`HealthMonitor::tick`] from the thread owning the index, like a service's housekeeping loop.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{Index, IndexError, Key};
+
+/// Number of keys returned by every probe and reachability query.
+const KEYS_PER_PROBE: usize = 8;
+
+/// Findings of a single [`Index::health_scan`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// Number of vectors present in the index.
+    pub size: usize,
+    /// Total capacity of the index, including reserved space.
+    pub capacity: usize,
+    /// Memory usage of the index in bytes.
+    pub memory_usage: usize,
+    /// Number of distinct keys sampled by the probe queries.
+    pub sampled: usize,
+    /// Sampled keys whose stored vectors contain NaN or infinite components.
+    pub non_finite: Vec<Key>,
+    /// Sampled keys that a search for their own vector failed to find.
+    pub unreachable: Vec<Key>,
+    /// Wall-clock time spent on the scan.
+    pub elapsed: Duration,
+}
+
+impl HealthReport {
+    /// Whether the scan found neither non-finite vectors nor unreachable members.
+    pub fn is_healthy(&self) -> bool {
+        self.non_finite.is_empty() && self.unreachable.is_empty()
+    }
+}
+
+impl Index {
+    /// Runs a light integrity check over a sample of the index members.
+    ///
+    /// Members are discovered with random `f32` probe queries and then through the neighbors of
+    /// sampled members, so the scan suits floating-point and `i8` indexes. Every sampled key is
+    /// fetched, scanned for non-finite components, and searched for with its own vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - The number of distinct keys to check.
+    ///
+    /// # Returns
+    ///
+    /// A [`HealthReport`], or the first error raised by the native layer.
+    pub fn health_scan(&self, sample: usize) -> Result<HealthReport, IndexError> {
+        let started = Instant::now();
+        let dimensions = self.dimensions();
+        // Seeded from the clock, so that consecutive scans sample different members.
+        let mut state = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_nanos() as u64)
+            | 1;
+        let mut probe = vec![0.0_f32; dimensions];
+        let mut vector = vec![0.0_f32; dimensions];
+
+        // Keys are discovered from random probes, then from the neighbors of already sampled keys.
+        let target = sample.min(self.size());
+        let mut seen = HashSet::new();
+        let mut keys = Vec::with_capacity(target);
+        let mut non_finite = Vec::new();
+        let mut unreachable = Vec::new();
+        let mut probes_left = sample;
+        let mut next = 0;
+        while keys.len() < target || next < keys.len() {
+            if next == keys.len() {
+                if probes_left == 0 {
+                    break;
+                }
+                probes_left -= 1;
+                for value in probe.iter_mut() {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    *value = (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0;
+                }
+                let matches = self.search(&probe, KEYS_PER_PROBE)?;
+                for key in matches.keys {
+                    if keys.len() < target && seen.insert(key) {
+                        keys.push(key);
+                    }
+                }
+                continue;
+            }
+
+            let key = keys[next];
+            next += 1;
+            if self.get(key, &mut vector)? == 0 {
+                continue;
+            }
+            if vector.iter().any(|value| !value.is_finite()) {
+                non_finite.push(key);
+                continue;
+            }
+            let matches = self.search(&vector, KEYS_PER_PROBE)?;
+            let found = matches.keys.contains(&key)
+                || self
+                    .filtered_search(&vector, 1, |candidate| candidate == key)?
+                    .keys
+                    .contains(&key);
+            if !found {
+                unreachable.push(key);
+            }
+            for neighbor in matches.keys {
+                if keys.len() < target && seen.insert(neighbor) {
+                    keys.push(neighbor);
+                }
+            }
+        }
+        non_finite.sort_unstable();
+        unreachable.sort_unstable();
+
+        Ok(HealthReport {
+            size: self.size(),
+            capacity: self.capacity(),
+            memory_usage: self.memory_usage(),
+            sampled: keys.len(),
+            non_finite,
+            unreachable,
+            elapsed: started.elapsed(),
+        })
+    }
+}
+
+/// Re-runs [`Index::health_scan`] whenever its interval expires, passing the report to a callback.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use usearch::maintenance::HealthMonitor;
+/// use usearch::{Index, IndexOptions};
+///
+/// let index = Index::new(&IndexOptions { dimensions: 4, ..Default::default() }).unwrap();
+/// let mut monitor = HealthMonitor::new(Duration::from_secs(300), |report| {
+///     if !report.is_healthy() {
+///         eprintln!("Index integrity scan failed: {:?}", report);
+///     }
+/// });
+///
+/// // Somewhere in the housekeeping loop of the service:
+/// monitor.tick(&index).unwrap();
+/// ```
+pub struct HealthMonitor {
+    interval: Duration,
+    sample: usize,
+    last_scan: Option<Instant>,
+    callback: Box<dyn FnMut(&HealthReport) + Send>,
+}
+
+impl HealthMonitor {
+    /// Creates a monitor scanning 64 keys per run, with the first scan due immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The minimum time between two scans.
+    /// * `callback` - Receives the report of every scan.
+    pub fn new<F>(interval: Duration, callback: F) -> Self
+    where
+        F: FnMut(&HealthReport) + Send + 'static,
+    {
+        Self {
+            interval,
+            sample: 64,
+            last_scan: None,
+            callback: Box::new(callback),
+        }
+    }
+
+    /// Changes the number of keys checked per scan.
+    pub fn with_sample(mut self, sample: usize) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    /// Whether the interval since the last scan has expired.
+    pub fn is_due(&self) -> bool {
+        self.last_scan
+            .is_none_or(|last_scan| last_scan.elapsed() >= self.interval)
+    }
+
+    /// Scans the index if due, and passes the report to the callback.
+    ///
+    /// # Returns
+    ///
+    /// The report, if a scan was run.
+    pub fn tick(&mut self, index: &Index) -> Result<Option<HealthReport>, IndexError> {
+        if !self.is_due() {
+            return Ok(None);
+        }
+        self.last_scan = Some(Instant::now());
+        let report = index.health_scan(self.sample)?;
+        (self.callback)(&report);
+        Ok(Some(report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_health_scan_and_monitor() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(100).unwrap();
+        for key in 0..100 {
+            let value = key as f32 / 100.0;
+            index
+                .add(key, &[value, 1.0 - value, value * value, 0.5])
+                .unwrap();
+        }
+
+        let report = index.health_scan(1000).unwrap();
+        assert_eq!(report.sampled, report.size);
+        assert!(report.unreachable.is_empty());
+        assert!(report.memory_usage > 0);
+        assert!(report.is_healthy());
+
+        let reports = Arc::new(Mutex::new(0));
+        let counter = reports.clone();
+        let mut monitor = HealthMonitor::new(Duration::from_secs(3600), move |_| {
+            *counter.lock().unwrap() += 1;
+        })
+        .with_sample(10);
+        assert_eq!(monitor.tick(&index).unwrap().unwrap().sampled, 10);
+        assert!(monitor.tick(&index).unwrap().is_none());
+        assert_eq!(*reports.lock().unwrap(), 1);
+    }
+}
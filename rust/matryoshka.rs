@@ -0,0 +1,252 @@
+//! Indexes over the leading dimensions of longer, Matryoshka-style embeddings.
+//!
+//! Models trained with Matryoshka representation learning, like recent OpenAI embeddings, keep
+//! most of the signal in the first dimensions, so a 1024-dimensional vector can be indexed by its
+//! first 256 scalars at a fraction of the memory. [`Index::add_truncated`] slices vectors that way,
+//! and [`TruncatedIndex`] does it consistently for insertions and queries, optionally keeping the
+//! full vectors aside to re-rank the candidates with every dimension.
+
+use std::collections::HashMap;
+
+use crate::ffi::Matches;
+use crate::metrics::distance;
+use crate::{Distance, Index, IndexError, IndexOptions, Key, MetricKind, VectorType};
+
+fn check_truncation(dimensions: usize, length: usize, use_dims: usize) -> Result<(), IndexError> {
+    if use_dims != dimensions {
+        return Err(IndexError::DimensionMismatch {
+            expected: dimensions,
+            got: use_dims,
+        });
+    }
+    if length < use_dims {
+        return Err(IndexError::DimensionMismatch {
+            expected: use_dims,
+            got: length,
+        });
+    }
+    Ok(())
+}
+
+impl Index {
+    /// Adds the first `use_dims` scalars of a longer vector under the specified key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the vector.
+    /// * `vector` - The full vector, at least `use_dims` long.
+    /// * `use_dims` - The number of leading scalars to index, matching the index dimensions.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if `use_dims` differs from the index dimensions,
+    /// or if the vector is shorter than `use_dims`.
+    pub fn add_truncated<T: VectorType>(
+        &self,
+        key: Key,
+        vector: &[T],
+        use_dims: usize,
+    ) -> Result<(), IndexError> {
+        check_truncation(self.dimensions(), vector.len(), use_dims)?;
+        Ok(T::add(self, key, &vector[..use_dims])?)
+    }
+}
+
+/// An index over the first dimensions of longer vectors, slicing insertions and queries alike.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::matryoshka::TruncatedIndex;
+/// use usearch::{IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::Cos,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let mut index = TruncatedIndex::new(&options, 4).unwrap().with_full_vectors();
+/// index.add(1, &[1.0, 0.0, 0.0, 1.0]).unwrap();
+/// index.add(2, &[1.0, 0.0, 1.0, 0.0]).unwrap();
+///
+/// // Both vectors share their first two dimensions, only the full ones tell them apart.
+/// let matches = index.search_full(&[1.0, 0.0, 1.0, 0.1], 1, 0).unwrap();
+/// assert_eq!(matches.keys, vec![2]);
+/// ```
+pub struct TruncatedIndex {
+    index: Index,
+    metric: MetricKind,
+    full_dimensions: usize,
+    full_vectors: Option<HashMap<Key, Vec<f32>>>,
+}
+
+impl TruncatedIndex {
+    /// Creates an empty index over the first `options.dimensions` scalars of vectors that are
+    /// `full_dimensions` long.
+    pub fn new(options: &IndexOptions, full_dimensions: usize) -> Result<Self, IndexError> {
+        if full_dimensions < options.dimensions {
+            return Err(IndexError::InvalidOptions {
+                reason: format!(
+                    "Can't index {} dimensions of {}-dimensional vectors",
+                    options.dimensions, full_dimensions
+                ),
+            });
+        }
+        Ok(Self {
+            index: Index::new(options)?,
+            metric: options.metric,
+            full_dimensions,
+            full_vectors: None,
+        })
+    }
+
+    /// Keeps the full vectors aside, enabling [`TruncatedIndex::search_full`].
+    pub fn with_full_vectors(mut self) -> Self {
+        self.full_vectors.get_or_insert_with(HashMap::new);
+        self
+    }
+
+    /// The underlying index over the truncated vectors.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// The length of the full vectors.
+    pub fn full_dimensions(&self) -> usize {
+        self.full_dimensions
+    }
+
+    /// The full vector stored under a key, if full vectors are kept.
+    pub fn full_vector(&self, key: Key) -> Option<&[f32]> {
+        self.full_vectors.as_ref()?.get(&key).map(Vec::as_slice)
+    }
+
+    fn check_full(&self, vector: &[f32]) -> Result<(), IndexError> {
+        if vector.len() != self.full_dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.full_dimensions,
+                got: vector.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Indexes the leading dimensions of a full vector, keeping it aside if configured to.
+    pub fn add(&mut self, key: Key, vector: &[f32]) -> Result<(), IndexError> {
+        self.check_full(vector)?;
+        self.index
+            .add_truncated(key, vector, self.index.dimensions())?;
+        if let Some(full_vectors) = &mut self.full_vectors {
+            full_vectors.insert(key, vector.to_vec());
+        }
+        Ok(())
+    }
+
+    /// Removes the vectors stored under a key.
+    pub fn remove(&mut self, key: Key) -> Result<usize, IndexError> {
+        if let Some(full_vectors) = &mut self.full_vectors {
+            full_vectors.remove(&key);
+        }
+        Ok(self.index.remove(key)?)
+    }
+
+    /// Searches with the leading dimensions of a full query vector.
+    pub fn search(&self, query: &[f32], count: usize) -> Result<Matches, IndexError> {
+        self.check_full(query)?;
+        self.index.search(&query[..self.index.dimensions()], count)
+    }
+
+    /// Searches with the leading dimensions, then re-ranks `pool` candidates by the distance
+    /// between the full vectors, with the metric of the index. Zero stands for four times `count`.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if the index doesn't keep full vectors.
+    pub fn search_full(
+        &self,
+        query: &[f32],
+        count: usize,
+        pool: usize,
+    ) -> Result<Matches, IndexError> {
+        let Some(full_vectors) = &self.full_vectors else {
+            return Err(IndexError::InvalidOptions {
+                reason: "Full vectors aren't kept, see `TruncatedIndex::with_full_vectors`"
+                    .to_owned(),
+            });
+        };
+        let pool = match pool {
+            0 => count * 4,
+            pool => pool.max(count),
+        };
+        let candidates = self.search(query, pool)?;
+
+        let mut ranked: Vec<(Distance, Key)> = candidates
+            .keys
+            .iter()
+            .filter_map(|key| {
+                let vector = full_vectors.get(key)?;
+                Some((distance(self.metric, query, vector), *key))
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ranked.truncate(count);
+        Ok(Matches {
+            keys: ranked.iter().map(|&(_, key)| key).collect(),
+            distances: ranked.iter().map(|&(distance, _)| distance).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScalarKind;
+
+    #[test]
+    fn test_truncated_index() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add_truncated(1, &[1.0_f32, 2.0, 3.0], 2).unwrap();
+        let mut vector: Vec<f32> = Vec::new();
+        index.export(1, &mut vector).unwrap();
+        assert_eq!(vector, vec![1.0, 2.0]);
+        assert!(matches!(
+            index.add_truncated(2, &[1.0_f32, 2.0, 3.0], 3),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 3
+            })
+        ));
+        assert!(matches!(
+            index.add_truncated(2, &[1.0_f32], 2),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 1
+            })
+        ));
+
+        let mut truncated = TruncatedIndex::new(&options, 3).unwrap();
+        truncated.add(1, &[0.0, 0.0, 5.0]).unwrap();
+        truncated.add(2, &[0.5, 0.0, 0.0]).unwrap();
+        assert_eq!(truncated.search(&[0.0, 0.0, 0.0], 1).unwrap().keys, vec![1]);
+        assert!(truncated.full_vector(1).is_none());
+        assert!(truncated.search_full(&[0.0, 0.0, 0.0], 1, 0).is_err());
+
+        let mut truncated = TruncatedIndex::new(&options, 3)
+            .unwrap()
+            .with_full_vectors();
+        truncated.add(1, &[0.0, 0.0, 5.0]).unwrap();
+        truncated.add(2, &[0.5, 0.0, 0.0]).unwrap();
+        let matches = truncated.search_full(&[0.0, 0.0, 0.0], 1, 2).unwrap();
+        assert_eq!(matches.keys, vec![2]);
+        assert_eq!(matches.distances, vec![0.25]);
+        assert_eq!(truncated.remove(2).unwrap(), 1);
+        assert!(truncated.full_vector(2).is_none());
+    }
+}
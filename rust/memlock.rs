@@ -0,0 +1,116 @@
+//! Locking the memory of an index in RAM.
+//!
+//! Under memory pressure the operating system pages out whatever wasn't touched recently, and
+//! the next searches stall on disk reads for every node and vector they visit. [`Index::lock_memory`]
+//! locks the nodes, the vectors, and the mapped file of a view with `mlock`, so they stay resident.
+//! Locked memory counts towards the `RLIMIT_MEMLOCK` limit of the process, which is only 8 MB by
+//! default on many Linux distributions, and exceeding it is reported as
+//! [`IndexError::MemoryLockLimit`].
+
+use std::io;
+use std::sync::PoisonError;
+
+use crate::{Index, IndexError};
+
+impl Index {
+    /// Locks the memory of the index in RAM, so it is never paged out.
+    ///
+    /// Covers the nodes and vectors already allocated, those allocated as the index grows, and
+    /// the file of a view, including views opened later. Calling it again locks the same memory
+    /// once more, which the operating system doesn't count twice.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes locked, `IndexError::MemoryLockLimit` if they don't fit within the
+    /// `RLIMIT_MEMLOCK` limit of the process, or `IndexError::Io` with any other failure. Nothing
+    /// stays locked after a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexError, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.reserve(100).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    /// match index.lock_memory() {
+    ///     Ok(bytes) => assert!(index.memory_locked() && bytes > 0),
+    ///     Err(IndexError::MemoryLockLimit { requested, limit }) => assert!(requested > 0 && limit > 0),
+    ///     Err(err) => panic!("{}", err),
+    /// }
+    /// index.unlock_memory();
+    /// assert!(!index.memory_locked());
+    /// ```
+    pub fn lock_memory(&self) -> Result<usize, IndexError> {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        let lock = self.inner.lock_memory();
+        if lock.error == 0 {
+            return Ok(lock.bytes);
+        }
+        let error = io::Error::from_raw_os_error(lock.error);
+        let limited = matches!(
+            error.kind(),
+            io::ErrorKind::OutOfMemory | io::ErrorKind::PermissionDenied
+        );
+        if limited || lock.bytes > lock.limit_bytes {
+            return Err(IndexError::MemoryLockLimit {
+                requested: lock.bytes,
+                limit: lock.limit_bytes,
+            });
+        }
+        Err(IndexError::Io(error))
+    }
+
+    /// Lets the memory locked with [`Index::lock_memory`] be paged out again.
+    pub fn unlock_memory(&self) {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        self.inner.unlock_memory();
+    }
+
+    /// Whether the memory of the index is locked in RAM.
+    pub fn memory_locked(&self) -> bool {
+        self.inner.memory_locked()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_lock_memory() {
+        let options = IndexOptions {
+            dimensions: 8,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            lock_memory: true,
+            ..Default::default()
+        };
+        // An empty index locks nothing, so it fits within any limit.
+        let index = Index::new(&options).unwrap();
+        assert!(index.memory_locked());
+        assert!(index.options().lock_memory);
+        assert!(index.options().to_string().ends_with(" lock_memory=true"));
+
+        index.reserve(1000).unwrap();
+        for key in 0..1000u64 {
+            index.add(key, &[key as f32; 8]).unwrap();
+        }
+        match index.lock_memory() {
+            Ok(bytes) => assert!(bytes >= 1000 * 8 * 4),
+            Err(IndexError::MemoryLockLimit { requested, limit }) => {
+                assert!(requested >= 1000 * 8 * 4);
+                assert!(limit < usize::MAX);
+                assert!(!index.memory_locked());
+            }
+            Err(err) => panic!("{}", err),
+        }
+        assert_eq!(index.search(&[7.0_f32; 8], 1).unwrap().keys, vec![7]);
+
+        index.unlock_memory();
+        assert!(!index.memory_locked());
+        assert!(!index.options().lock_memory);
+        index.add(1000, &[1000.0_f32; 8]).unwrap();
+        assert_eq!(index.size(), 1001);
+    }
+}
@@ -0,0 +1,274 @@
+//! Inspection of serialized indexes without loading them.
+//!
+//! Every serialized index contains a 64-byte header with the format version and the index
+//! properties. It is preceded by the matrix of vectors, unless they were excluded, whose
+//! shape is stored either as two 32-bit or as two 64-bit integers:
+//!
+//! ```text
+//! [rows, columns] [vectors...] [header: 64 bytes] [graph...]
+//! ```
+//!
+//! [`file_metadata`] and [`buffer_metadata`] parse that header, and [`Index::load`],
+//! [`Index::view`], and their buffer-based counterparts use it to reject incompatible files with
+//! a typed [`IndexError::IncompatibleFormat`] before handing them to the native layer.
+//!
+//! [`Index::load`]: crate::Index::load
+//! [`Index::view`]: crate::Index::view
+
+#[cfg(not(no_fs))]
+use std::fs::File;
+#[cfg(not(no_fs))]
+use std::io::{Read, Seek, SeekFrom};
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::{IndexError, MetricKind, ScalarKind};
+
+const MAGIC: &[u8; 7] = b"usearch";
+pub(crate) const HEADER_LENGTH: usize = 64;
+
+/// Major version of the serialization format supported by the native layer.
+pub const FORMAT_VERSION_MAJOR: u16 = 2;
+
+/// Native scalar kind codes of the key and slot types used by [`crate::Index`].
+const KEY_KIND_U64: u8 = 14;
+const SLOT_KIND_U32: u8 = 15;
+
+/// Properties of a serialized index, extracted from its header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexMetadata {
+    /// Version of the library that produced the file, as `(major, minor, patch)`.
+    pub version: (u16, u16, u16),
+    /// The metric kind the index was built with.
+    pub metric: MetricKind,
+    /// The scalar kind used to store the vectors.
+    pub quantization: ScalarKind,
+    /// Number of dimensions of every vector.
+    pub dimensions: usize,
+    /// Number of vectors present in the index.
+    pub size: usize,
+    /// Number of vectors removed, but still occupying slots.
+    pub deleted: usize,
+    /// Whether multiple vectors can share a key.
+    pub multi: bool,
+    /// Whether the vectors are stored in the file, or only the graph.
+    pub includes_vectors: bool,
+    key_kind: u8,
+    slot_kind: u8,
+}
+
+impl IndexMetadata {
+    /// Checks if the native layer of this build can load the described index.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::IncompatibleFormat` describing the first mismatch, if any.
+    pub fn check_compatible(&self) -> Result<(), IndexError> {
+        let (major, minor, patch) = self.version;
+        if major != FORMAT_VERSION_MAJOR {
+            return Err(IndexError::IncompatibleFormat {
+                found: format!("format v{}.{}.{}", major, minor, patch),
+                expected: format!("format v{}.x", FORMAT_VERSION_MAJOR),
+            });
+        }
+        if self.key_kind != KEY_KIND_U64 || self.slot_kind != SLOT_KIND_U32 {
+            return Err(IndexError::IncompatibleFormat {
+                found: format!(
+                    "key kind #{} and slot kind #{}",
+                    self.key_kind, self.slot_kind
+                ),
+                expected: "64-bit keys and 32-bit slots".to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    fn parse(header: &[u8; HEADER_LENGTH], includes_vectors: bool) -> Self {
+        let u16_at = |at: usize| u16::from_le_bytes([header[at], header[at + 1]]);
+        let u64_at = |at: usize| u64::from_le_bytes(header[at..at + 8].try_into().unwrap());
+        Self {
+            version: (u16_at(7), u16_at(9), u16_at(11)),
+            metric: metric_from_code(header[13]),
+            quantization: scalar_from_code(header[14]),
+            key_kind: header[15],
+            slot_kind: header[16],
+            size: u64_at(17) as usize,
+            deleted: u64_at(25) as usize,
+            dimensions: u64_at(33) as usize,
+            multi: header[41] != 0,
+            includes_vectors,
+        }
+    }
+}
+
+fn metric_from_code(code: u8) -> MetricKind {
+    match code {
+        b'i' => MetricKind::IP,
+        b'e' => MetricKind::L2sq,
+        b'c' => MetricKind::Cos,
+        b'p' => MetricKind::Pearson,
+        b'h' => MetricKind::Haversine,
+        b'd' => MetricKind::Divergence,
+        b'b' => MetricKind::Hamming,
+        b't' => MetricKind::Tanimoto,
+        b's' => MetricKind::Sorensen,
+        _ => MetricKind::Unknown,
+    }
+}
+
+fn scalar_from_code(code: u8) -> ScalarKind {
+    match code {
+        1 => ScalarKind::B1,
+        10 => ScalarKind::F64,
+        11 => ScalarKind::F32,
+        12 => ScalarKind::F16,
+        23 => ScalarKind::I8,
+        _ => ScalarKind::Unknown,
+    }
+}
+
+fn not_an_index() -> IndexError {
+    IndexError::IncompatibleFormat {
+        found: "no USearch header".to_owned(),
+        expected: format!("format v{}.x", FORMAT_VERSION_MAJOR),
+    }
+}
+
+/// Candidate header offsets: right at the start, or after a 32-bit or a 64-bit matrix.
+fn header_candidates(prefix: &[u8; 16]) -> [(u64, bool); 3] {
+    let rows_u32 = u32::from_le_bytes(prefix[0..4].try_into().unwrap()) as u64;
+    let cols_u32 = u32::from_le_bytes(prefix[4..8].try_into().unwrap()) as u64;
+    let rows_u64 = u64::from_le_bytes(prefix[0..8].try_into().unwrap());
+    let cols_u64 = u64::from_le_bytes(prefix[8..16].try_into().unwrap());
+    [
+        (0, false),
+        (rows_u32.saturating_mul(cols_u32).saturating_add(8), true),
+        (rows_u64.saturating_mul(cols_u64).saturating_add(16), true),
+    ]
+}
+
+/// Reads the metadata of a serialized index without loading it.
+///
+/// # Arguments
+///
+/// * `path` - The path to a file produced by [`crate::Index::save`].
+///
+/// # Examples
+///
+/// ```no_run
+/// let metadata = usearch::file_metadata("index.usearch").unwrap();
+/// println!("{} vectors of {} dimensions", metadata.size, metadata.dimensions);
+/// ```
+#[cfg(not(no_fs))]
+pub fn file_metadata<P: AsRef<Path>>(path: P) -> Result<IndexMetadata, IndexError> {
+    let mut file = File::open(path)?;
+    let file_length = file.metadata()?.len();
+    Ok(locate_in_file(&mut file, file_length)?.1)
+}
+
+/// Finds the header of an index serialized into a file, returning its offset and metadata.
+#[cfg(not(no_fs))]
+pub(crate) fn locate_in_file(
+    file: &mut File,
+    file_length: u64,
+) -> Result<(u64, IndexMetadata), IndexError> {
+    let mut prefix = [0u8; 16];
+    let mut header = [0u8; HEADER_LENGTH];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut prefix[..file_length.min(16) as usize])?;
+
+    for (offset, includes_vectors) in header_candidates(&prefix) {
+        if offset.saturating_add(HEADER_LENGTH as u64) > file_length {
+            continue;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(&mut header)?;
+        if header.starts_with(MAGIC) {
+            return Ok((offset, IndexMetadata::parse(&header, includes_vectors)));
+        }
+    }
+    Err(not_an_index())
+}
+
+/// Reads the metadata of an index serialized into a buffer, without loading it.
+///
+/// # Arguments
+///
+/// * `buffer` - A buffer populated with [`crate::Index::save_to_buffer`].
+pub fn buffer_metadata(buffer: &[u8]) -> Result<IndexMetadata, IndexError> {
+    Ok(locate_in_buffer(buffer)?.1)
+}
+
+/// Finds the header of an index serialized into a buffer, returning its offset and metadata.
+pub(crate) fn locate_in_buffer(buffer: &[u8]) -> Result<(usize, IndexMetadata), IndexError> {
+    let mut prefix = [0u8; 16];
+    let prefix_length = buffer.len().min(16);
+    prefix[..prefix_length].copy_from_slice(&buffer[..prefix_length]);
+
+    for (offset, includes_vectors) in header_candidates(&prefix) {
+        let Ok(offset) = usize::try_from(offset) else {
+            continue;
+        };
+        let Some(header) = buffer.get(offset..offset.saturating_add(HEADER_LENGTH)) else {
+            continue;
+        };
+        if header.starts_with(MAGIC) {
+            let metadata = IndexMetadata::parse(header.try_into().unwrap(), includes_vectors);
+            return Ok((offset, metadata));
+        }
+    }
+    Err(not_an_index())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Index, IndexOptions};
+
+    #[test]
+    fn test_metadata_and_incompatible_versions() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(10).unwrap();
+        index.add(1, &[0.1_f32, 0.2, 0.3]).unwrap();
+        index.add(2, &[0.3_f32, 0.2, 0.1]).unwrap();
+
+        let mut buffer = vec![0u8; index.serialized_length()];
+        index.save_to_buffer(&mut buffer).unwrap();
+        let metadata = buffer_metadata(&buffer).unwrap();
+        assert_eq!(metadata.dimensions, 3);
+        assert_eq!(metadata.size, 2);
+        assert_eq!(metadata.metric, MetricKind::L2sq);
+        assert_eq!(metadata.quantization, ScalarKind::F32);
+        assert!(metadata.includes_vectors);
+        assert_eq!(metadata.version.0, FORMAT_VERSION_MAJOR);
+
+        #[cfg(not(no_fs))]
+        {
+            let path = std::env::temp_dir().join("usearch.rust.metadata.usearch");
+            index.save(&path).unwrap();
+            assert_eq!(file_metadata(&path).unwrap(), metadata);
+            std::fs::remove_file(&path).unwrap();
+        }
+
+        // Pretend the file was produced by a future major version.
+        let header = buffer.windows(7).position(|w| w == MAGIC).unwrap();
+        buffer[header + 7] = 9;
+        let mut restored = Index::new(&options).unwrap();
+        match restored.load_from_buffer(&buffer) {
+            Err(IndexError::IncompatibleFormat { found, .. }) => {
+                assert!(found.starts_with("format v9."))
+            }
+            other => panic!("Unexpected result: {:?}", other.err()),
+        }
+        assert!(matches!(
+            restored.load_from_buffer(&[0u8; 100]),
+            Err(IndexError::IncompatibleFormat { .. })
+        ));
+    }
+}
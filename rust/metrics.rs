@@ -0,0 +1,273 @@
+//! Distance functions in Rust, evaluated over `f32` vectors.
+//!
+//! These mirror the built-in metrics of the native layer, without SIMD acceleration. They back the
+//! pure-Rust backend and the re-ranking of search results with a secondary metric, where only a
+//! small pool of candidates has to be compared.
+//!
+//! Raw distances aren't comparable across metrics, so [`MetricType`] maps them to similarities
+//! in the `[0, 1]` range, letting applications apply one threshold whatever the metric.
+
+use crate::{Distance, MetricKind};
+
+/// Computes the distance between two vectors of equal length with one of the built-in metrics.
+/// Binary vectors are expected unpacked, as `0.0`/`1.0` values. Returns `NaN` for unknown metrics.
+///
+/// Pairs of zero vectors, which `Cos`, `Tanimoto`, and `Sorensen` can't normalize, are at a
+/// distance of zero, and a zero vector is at the largest distance of one from any other.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{metrics, MetricKind};
+///
+/// assert_eq!(metrics::distance(MetricKind::L2sq, &[1.0, 0.0], &[0.0, 1.0]), 2.0);
+/// assert!(metrics::distance(MetricKind::Cos, &[1.0, 0.0], &[2.0, 0.0]).abs() < 1e-6);
+/// ```
+pub fn distance(metric: MetricKind, a: &[f32], b: &[f32]) -> Distance {
+    let dot = || a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+    match metric {
+        MetricKind::IP => 1.0 - dot(),
+        MetricKind::L2sq => a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum(),
+        MetricKind::Cos => {
+            let a2: f32 = a.iter().map(|x| x * x).sum();
+            let b2: f32 = b.iter().map(|x| x * x).sum();
+            match (a2 == 0.0, b2 == 0.0) {
+                (true, true) => 0.0,
+                (false, false) => 1.0 - dot() / (a2.sqrt() * b2.sqrt()),
+                _ => 1.0,
+            }
+        }
+        MetricKind::Pearson => {
+            let n = a.len() as f32;
+            if a.len() <= 1 {
+                return 0.0;
+            }
+            let (a_sum, b_sum): (f32, f32) = (a.iter().sum(), b.iter().sum());
+            let a2: f32 = a.iter().map(|x| x * x).sum();
+            let b2: f32 = b.iter().map(|x| x * x).sum();
+            let denominator = (n * a2 - a_sum * a_sum) * (n * b2 - b_sum * b_sum);
+            if denominator == 0.0 {
+                return 0.0;
+            }
+            1.0 - (n * dot() - a_sum * b_sum) / denominator.sqrt()
+        }
+        MetricKind::Haversine => {
+            let (lat_a, lon_a, lat_b, lon_b) = (a[0], a[1], b[0], b[1]);
+            let lat_delta = (lat_b - lat_a).to_radians() / 2.0;
+            let lon_delta = (lon_b - lon_a).to_radians() / 2.0;
+            let x = lat_delta.sin().powi(2)
+                + lat_a.to_radians().cos() * lat_b.to_radians().cos() * lon_delta.sin().powi(2);
+            2.0 * x.sqrt().asin()
+        }
+        MetricKind::Divergence => {
+            let epsilon = f32::EPSILON;
+            let (mut kld_pm, mut kld_qm) = (0.0, 0.0);
+            for (&p, &q) in a.iter().zip(b) {
+                let m = (p + q) / 2.0 + epsilon;
+                kld_pm += p * ((p + epsilon) / m).ln();
+                kld_qm += q * ((q + epsilon) / m).ln();
+            }
+            (kld_pm + kld_qm) / 2.0
+        }
+        MetricKind::Hamming => a.iter().zip(b).filter(|(x, y)| x != y).count() as f32,
+        MetricKind::Tanimoto | MetricKind::Sorensen => {
+            let (mut both, mut either, mut total) = (0.0, 0.0, 0.0);
+            for (&x, &y) in a.iter().zip(b) {
+                both += x * y;
+                either += (x + y).min(1.0);
+                total += x + y;
+            }
+            match metric {
+                _ if total == 0.0 => 0.0,
+                MetricKind::Tanimoto => 1.0 - both / either,
+                _ => 1.0 - 2.0 * both / total,
+            }
+        }
+        _ => Distance::NAN,
+    }
+}
+
+/// Converts the raw distances of a metric into similarities in the `[0, 1]` range,
+/// where `1.0` stands for identical vectors.
+///
+/// Implemented for [`MetricKind`], and for closures, to plug in custom scales.
+pub trait MetricType {
+    /// Maps a distance to a similarity in the `[0, 1]` range.
+    fn similarity(&self, distance: Distance) -> f32;
+
+    /// Maps a similarity back to the largest distance scoring at least that similarity, turning
+    /// similarity thresholds into distance bounds. Thresholds met by every distance map to
+    /// infinity, and the ones met by none to negative infinity.
+    ///
+    /// The default implementation bisects the non-negative distances, assuming the similarity
+    /// never grows with the distance.
+    fn distance_for_similarity(&self, similarity: f32) -> Distance {
+        if self.similarity(0.0) < similarity {
+            return Distance::NEG_INFINITY;
+        }
+        let (mut low, mut high) = (0.0, 1.0);
+        while self.similarity(high) >= similarity {
+            if high > Distance::MAX / 2.0 {
+                return Distance::INFINITY;
+            }
+            low = high;
+            high *= 2.0;
+        }
+        for _ in 0..64 {
+            let middle = low + (high - low) / 2.0;
+            if middle <= low || middle >= high {
+                break;
+            }
+            if self.similarity(middle) >= similarity {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
+
+impl MetricType for MetricKind {
+    /// Uses `1 - distance` for the metrics bounded by one, like `Cos`, `IP`, `Pearson`, `Tanimoto`,
+    /// and `Sorensen`, `exp(-distance)` for `L2sq`, and scales `Haversine` by half the circumference
+    /// and `Divergence` by its `ln 2` upper bound. Unbounded `Hamming` distances and unknown
+    /// metrics use `1 / (1 + distance)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::metrics::MetricType;
+    /// use usearch::MetricKind;
+    ///
+    /// assert_eq!(MetricKind::Cos.similarity(0.25), 0.75);
+    /// assert_eq!(MetricKind::L2sq.similarity(0.0), 1.0);
+    /// assert_eq!(MetricKind::Hamming.similarity(3.0), 0.25);
+    /// ```
+    fn similarity(&self, distance: Distance) -> f32 {
+        let similarity = match *self {
+            MetricKind::IP
+            | MetricKind::Cos
+            | MetricKind::Pearson
+            | MetricKind::Tanimoto
+            | MetricKind::Sorensen => 1.0 - distance,
+            MetricKind::L2sq => (-distance).exp(),
+            MetricKind::Haversine => 1.0 - distance / std::f32::consts::PI,
+            MetricKind::Divergence => 1.0 - distance / std::f32::consts::LN_2,
+            _ => 1.0 / (1.0 + distance.max(0.0)),
+        };
+        similarity.clamp(0.0, 1.0)
+    }
+
+    /// Inverts [`MetricType::similarity`] in closed form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::metrics::MetricType;
+    /// use usearch::MetricKind;
+    ///
+    /// assert_eq!(MetricKind::Cos.distance_for_similarity(0.75), 0.25);
+    /// assert_eq!(MetricKind::L2sq.distance_for_similarity(1.0), 0.0);
+    /// assert_eq!(MetricKind::Hamming.distance_for_similarity(0.25), 3.0);
+    /// ```
+    fn distance_for_similarity(&self, similarity: f32) -> Distance {
+        if similarity <= 0.0 {
+            return Distance::INFINITY;
+        }
+        if similarity > 1.0 {
+            return Distance::NEG_INFINITY;
+        }
+        match *self {
+            MetricKind::IP
+            | MetricKind::Cos
+            | MetricKind::Pearson
+            | MetricKind::Tanimoto
+            | MetricKind::Sorensen => 1.0 - similarity,
+            MetricKind::L2sq => -similarity.ln(),
+            MetricKind::Haversine => (1.0 - similarity) * std::f32::consts::PI,
+            MetricKind::Divergence => (1.0 - similarity) * std::f32::consts::LN_2,
+            _ => 1.0 / similarity - 1.0,
+        }
+    }
+}
+
+impl<F: Fn(Distance) -> f32> MetricType for F {
+    fn similarity(&self, distance: Distance) -> f32 {
+        self(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(metric: MetricKind, a: &[f32], b: &[f32], expected: Distance) {
+        let actual = distance(metric, a, b);
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "{:?} distance is {}, expected {}",
+            metric,
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        assert_close(MetricKind::IP, &[0.6, 0.8], &[0.8, 0.6], 0.04);
+        assert_close(MetricKind::L2sq, &[1.0, 2.0, 3.0], &[4.0, 6.0, 3.0], 25.0);
+        assert_close(MetricKind::Cos, &[1.0, 0.0], &[0.0, 1.0], 1.0);
+        assert_close(MetricKind::Cos, &[1.0, 1.0], &[2.0, 2.0], 0.0);
+        assert_close(MetricKind::Pearson, &[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0], 0.0);
+        assert_close(MetricKind::Pearson, &[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0], 2.0);
+        assert_close(
+            MetricKind::Haversine,
+            &[0.0, 0.0],
+            &[0.0, 90.0],
+            std::f32::consts::FRAC_PI_2,
+        );
+        assert_close(
+            MetricKind::Haversine,
+            &[0.0, 0.0],
+            &[90.0, 0.0],
+            std::f32::consts::FRAC_PI_2,
+        );
+        assert_close(MetricKind::Divergence, &[0.5, 0.5], &[0.5, 0.5], 0.0);
+        assert_close(
+            MetricKind::Divergence,
+            &[1.0, 0.0],
+            &[0.0, 1.0],
+            std::f32::consts::LN_2,
+        );
+        assert_close(
+            MetricKind::Hamming,
+            &[1.0, 0.0, 1.0, 1.0],
+            &[0.0, 0.0, 1.0, 0.0],
+            2.0,
+        );
+        assert_close(
+            MetricKind::Tanimoto,
+            &[1.0, 1.0, 0.0, 0.0],
+            &[1.0, 0.0, 1.0, 0.0],
+            2.0 / 3.0,
+        );
+        assert_close(
+            MetricKind::Sorensen,
+            &[1.0, 1.0, 0.0, 0.0],
+            &[1.0, 0.0, 1.0, 0.0],
+            0.5,
+        );
+        assert!(distance(MetricKind::Unknown, &[1.0], &[1.0]).is_nan());
+    }
+
+    #[test]
+    fn test_zero_vectors() {
+        let (zero, one) = ([0.0; 4], [0.0, 1.0, 0.0, 1.0]);
+        for metric in [MetricKind::Cos, MetricKind::Tanimoto, MetricKind::Sorensen] {
+            assert_eq!(distance(metric, &zero, &zero), 0.0, "{:?}", metric);
+            assert_eq!(distance(metric, &zero, &one), 1.0, "{:?}", metric);
+            assert_eq!(distance(metric, &one, &zero), 1.0, "{:?}", metric);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! L2 normalization of vectors, for inner-product and cosine searches.
+//!
+//! The inner product only ranks vectors by angle once they all have a unit length, and
+//! forgetting to normalize them is a common source of poor recall. [`normalize`] scales a vector
+//! in place for every scalar kind the index accepts, and indexes created with
+//! `auto_normalize` set in their [`IndexOptions`](crate::IndexOptions) do it on every insertion
+//! and query. Norms are accumulated in independent lanes, which the compiler turns into SIMD
+//! instructions on targets supporting them.
+
+use std::borrow::Cow;
+
+use crate::{b1x8, f16, Index};
+
+/// Number of independent accumulators, wide enough for 256-bit registers of `f32`.
+const LANES: usize = 8;
+
+/// Scalars of vectors that can be scaled to a unit L2 norm.
+pub trait Normalize: Sized {
+    /// Scales the vector in place to a unit L2 norm, leaving zero vectors unchanged.
+    fn normalize(vector: &mut [Self]);
+}
+
+macro_rules! impl_float_normalize {
+    ($type:ty) => {
+        impl Normalize for $type {
+            fn normalize(vector: &mut [Self]) {
+                let mut lanes = [0.0 as $type; LANES];
+                let chunks = vector.chunks_exact(LANES);
+                let tail: $type = chunks.remainder().iter().map(|&x| x * x).sum();
+                for chunk in chunks {
+                    for (lane, &x) in lanes.iter_mut().zip(chunk) {
+                        *lane += x * x;
+                    }
+                }
+                let norm = (lanes.iter().sum::<$type>() + tail).sqrt();
+                if norm > 0.0 && norm.is_finite() {
+                    let inverse = 1.0 / norm;
+                    vector.iter_mut().for_each(|x| *x *= inverse);
+                }
+            }
+        }
+    };
+}
+
+impl_float_normalize!(f32);
+impl_float_normalize!(f64);
+
+impl Normalize for f16 {
+    fn normalize(vector: &mut [Self]) {
+        let mut widened: Vec<f32> = vector.iter().map(|x| x.to_f32()).collect();
+        f32::normalize(&mut widened);
+        for (x, &value) in vector.iter_mut().zip(&widened) {
+            *x = f16::from_f32(value);
+        }
+    }
+}
+
+/// The `i8` value standing for `1.0`, as in the conversions of the native layer.
+const I8_UNIT: f32 = 100.0;
+
+impl Normalize for i8 {
+    /// Scales the vector to a norm of 100, the fixed-point unit of `i8` vectors.
+    fn normalize(vector: &mut [Self]) {
+        let squares: i64 = vector.iter().map(|&x| i64::from(x) * i64::from(x)).sum();
+        if squares > 0 {
+            let scale = I8_UNIT / (squares as f32).sqrt();
+            for x in vector {
+                *x = (f32::from(*x) * scale).round() as i8;
+            }
+        }
+    }
+}
+
+impl Normalize for b1x8 {
+    /// Leaves the vector unchanged, as binary vectors are compared bit by bit.
+    fn normalize(_vector: &mut [Self]) {}
+}
+
+/// Scales the vector in place to a unit L2 norm, leaving zero vectors unchanged.
+///
+/// # Examples
+///
+/// ```
+/// let mut vector = [3.0_f32, 4.0];
+/// usearch::normalize::normalize(&mut vector);
+/// assert_eq!(vector, [0.6, 0.8]);
+/// ```
+pub fn normalize<T: Normalize>(vector: &mut [T]) {
+    T::normalize(vector)
+}
+
+/// Copies the vector scaled to a unit L2 norm.
+pub fn normalized<T: Normalize + Clone>(vector: &[T]) -> Vec<T> {
+    let mut copy = vector.to_vec();
+    T::normalize(&mut copy);
+    copy
+}
+
+impl Index {
+    /// Whether vectors are scaled to a unit L2 norm when added, and queries when searched.
+    /// Vectors read back with [`Index::get`] are the normalized ones.
+    pub fn auto_normalize(&self) -> bool {
+        self.auto_normalize
+    }
+
+    /// The vector as passed to the native index: a normalized copy with auto-normalization.
+    pub(crate) fn normalized<'a, T: Normalize + Clone>(&self, vector: &'a [T]) -> Cow<'a, [T]> {
+        if self.auto_normalize {
+            Cow::Owned(normalized(vector))
+        } else {
+            Cow::Borrowed(vector)
+        }
+    }
+
+    /// Vectors packed row-major as passed to the native index, each normalized on its own.
+    pub(crate) fn normalized_rows<'a, T: Normalize + Clone>(
+        &self,
+        vectors: &'a [T],
+    ) -> Cow<'a, [T]> {
+        if self.auto_normalize {
+            let mut copy = vectors.to_vec();
+            copy.chunks_mut(self.dimensions().max(1))
+                .for_each(T::normalize);
+            Cow::Owned(copy)
+        } else {
+            Cow::Borrowed(vectors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_normalize() {
+        // Long enough to go through the lanes and the tail.
+        let vector: Vec<f32> = (1..=19).map(|x| x as f32).collect();
+        let norm = |vector: &[f32]| vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm(&normalized(&vector)) - 1.0).abs() < 1e-6);
+        let doubles: Vec<f64> = vector.iter().map(|&x| f64::from(x)).collect();
+        let doubles = normalized(&doubles);
+        assert!((doubles.iter().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-12);
+        let halves = normalized(&[f16::from_f32(3.0), f16::from_f32(4.0)]);
+        let expected = [f16::from_f32(0.6), f16::from_f32(0.8)];
+        assert_eq!(f16::to_i16s(&halves), f16::to_i16s(&expected));
+        assert_eq!(normalized(&[30_i8, -40]), vec![60, -80]);
+        assert_eq!(normalized(&[0.0_f32; 3]), vec![0.0; 3]);
+        assert_eq!(normalized(&[0_i8; 3]), vec![0; 3]);
+
+        // With auto-normalization, scaled copies of a vector land on the same point.
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::IP,
+            quantization: ScalarKind::F32,
+            auto_normalize: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        assert!(index.auto_normalize());
+        index.add(1, &[3.0_f32, 4.0]).unwrap();
+        index.add(2, &[10.0_f32, 0.0]).unwrap();
+        let mut stored = [0.0_f32; 2];
+        index.get(1, &mut stored).unwrap();
+        assert_eq!(stored, [0.6, 0.8]);
+        let matches = index.search(&[30.0_f32, 40.0], 2).unwrap();
+        assert_eq!(matches.keys, vec![1, 2]);
+        assert!(matches.distances[0].abs() < 1e-6);
+        let matches = index.search(&[0.3_f64, 0.4], 1).unwrap();
+        assert_eq!(matches.keys, vec![1]);
+        assert!(matches.distances[0].abs() < 1e-6);
+        assert!(index.options().auto_normalize);
+    }
+}
@@ -0,0 +1,366 @@
+//! One shard per NUMA node, built and searched by threads pinned to that node.
+//!
+//! On multi-socket servers, every socket reaches its own memory faster than the memory of the
+//! others. An index built by threads spread across sockets ends up spread across them too, and
+//! most of the vectors a search visits then cross the interconnect. [`NumaShardedIndex`] keeps an
+//! index per NUMA node, with a pool of worker threads pinned to the CPUs of that node. Vectors are
+//! added to the shard of their key by its workers, so their memory is first touched, and placed,
+//! on the same node, and queries fan out to all shards, each searching its own memory.
+//!
+//! Nodes are read from `/sys/devices/system/node` on Linux. Elsewhere, and on single-socket
+//! machines, a single node covers every CPU, and threads aren't pinned.
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+use crate::ffi::Matches;
+use crate::{Distance, Index, IndexError, IndexOptions, Key, VectorType};
+
+/// A NUMA node, and the CPUs attached to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaNode {
+    /// The number of the node, as in `/sys/devices/system/node/node{id}`.
+    pub id: usize,
+    /// The CPUs of the node, which its workers are pinned to.
+    pub cpus: Vec<usize>,
+}
+
+/// Parses a CPU list of the kernel, like `0-3,8-11`.
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        let bounds: Option<(usize, usize)> = match range.split_once('-') {
+            Some((first, last)) => first.parse().ok().zip(last.parse().ok()),
+            None => range.parse().ok().map(|cpu| (cpu, cpu)),
+        };
+        if let Some((first, last)) = bounds {
+            cpus.extend(first..=last);
+        }
+    }
+    cpus
+}
+
+/// Lists the NUMA nodes with CPUs, in the order of their numbers.
+///
+/// # Returns
+///
+/// The nodes found in `/sys/devices/system/node`, or a single node covering every CPU when the
+/// topology can't be read.
+pub fn numa_nodes() -> Vec<NumaNode> {
+    let mut nodes: Vec<NumaNode> = std::fs::read_dir("/sys/devices/system/node")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let id = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("node")?
+                .parse()
+                .ok()?;
+            let cpus = parse_cpu_list(&std::fs::read_to_string(entry.path().join("cpulist")).ok()?);
+            Some(NumaNode { id, cpus })
+        })
+        .filter(|node| !node.cpus.is_empty())
+        .collect();
+    nodes.sort_by_key(|node| node.id);
+    if nodes.is_empty() {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        nodes.push(NumaNode {
+            id: 0,
+            cpus: (0..threads).collect(),
+        });
+    }
+    nodes
+}
+
+/// Restricts the calling thread to the given CPUs. Best effort, as the CPUs may be outside of
+/// the cgroup of the process, in which case the thread keeps running anywhere.
+#[cfg(target_os = "linux")]
+fn pin_current_thread(cpus: &[usize]) {
+    // SAFETY: `cpu_set_t` is a plain bit-set, valid when zeroed.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus.iter().filter(|&&cpu| cpu < libc::CPU_SETSIZE as usize) {
+        // SAFETY: The CPU is within the bounds of the set.
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+    // SAFETY: The set is initialized, and its size is passed along.
+    unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_current_thread(_cpus: &[usize]) {}
+
+type Job = Box<dyn FnOnce(&Index) + Send>;
+
+/// The index of one node, and the workers pinned to it.
+struct Shard {
+    node: NumaNode,
+    index: Arc<Index>,
+    jobs: Option<mpsc::Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl Shard {
+    /// Starts the workers of a node, and creates the index on one of them.
+    fn new(node: NumaNode, options: &IndexOptions) -> Result<Self, IndexError> {
+        // The index is created by a pinned thread, so its own structures live on the node.
+        let cpus = node.cpus.clone();
+        let options = options.clone();
+        let index = std::thread::spawn(move || {
+            pin_current_thread(&cpus);
+            Index::new(&options)
+        })
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))?;
+        let index = Arc::new(index);
+
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..node.cpus.len().max(1))
+            .map(|_| {
+                let index = Arc::clone(&index);
+                let receiver = Arc::clone(&receiver);
+                let cpus = node.cpus.clone();
+                std::thread::spawn(move || {
+                    pin_current_thread(&cpus);
+                    loop {
+                        let job = receiver
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .recv();
+                        match job {
+                            Ok(job) => job(&index),
+                            Err(_) => break,
+                        }
+                    }
+                })
+            })
+            .collect();
+        Ok(Self {
+            node,
+            index,
+            jobs: Some(jobs),
+            workers,
+        })
+    }
+
+    /// Runs a job on a worker of the node, and returns a receiver of its result.
+    fn submit<R, F>(&self, job: F) -> mpsc::Receiver<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&Index) -> R + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let job: Job = Box::new(move |index| {
+            let _ = sender.send(job(index));
+        });
+        if let Some(jobs) = &self.jobs {
+            let _ = jobs.send(job);
+        }
+        receiver
+    }
+}
+
+/// Waits for the result of a job, panicking if the worker running it panicked.
+fn wait<R>(receiver: mpsc::Receiver<R>) -> R {
+    receiver
+        .recv()
+        .expect("A worker of a NUMA shard panicked while running a job")
+}
+
+/// An index split into one shard per NUMA node, each built and searched on its own node.
+///
+/// Keys are assigned to shards by their value, so every key lives in a single shard, and
+/// searches merge the closest matches of all shards.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::numa::NumaShardedIndex;
+/// use usearch::{IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let index = NumaShardedIndex::new(&options).unwrap();
+/// println!("Shards on nodes {:?}", index.nodes().map(|node| node.id).collect::<Vec<_>>());
+/// for key in 0..100u64 {
+///     index.add(key, &[key as f32, 0.0]).unwrap();
+/// }
+/// assert_eq!(index.size(), 100);
+/// assert_eq!(index.search(&[41.8_f32, 0.0], 2).unwrap().keys, vec![42, 41]);
+/// ```
+pub struct NumaShardedIndex {
+    shards: Vec<Shard>,
+}
+
+impl NumaShardedIndex {
+    /// Creates an empty shard on every NUMA node found by [`numa_nodes`].
+    pub fn new(options: &IndexOptions) -> Result<Self, IndexError> {
+        Self::with_nodes(options, numa_nodes())
+    }
+
+    /// Creates an empty shard on every given node, like a subset of [`numa_nodes`].
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if no node is given, or the first error creating a shard.
+    pub fn with_nodes(options: &IndexOptions, nodes: Vec<NumaNode>) -> Result<Self, IndexError> {
+        if nodes.is_empty() {
+            return Err(IndexError::InvalidOptions {
+                reason: "A sharded index needs at least one NUMA node".to_owned(),
+            });
+        }
+        let shards = nodes
+            .into_iter()
+            .map(|node| Shard::new(node, options))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { shards })
+    }
+
+    /// The nodes of the shards, in shard order.
+    pub fn nodes(&self) -> impl Iterator<Item = &NumaNode> {
+        self.shards.iter().map(|shard| &shard.node)
+    }
+
+    /// The number of shards.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// The index of a shard, to inspect or save it.
+    pub fn shard(&self, position: usize) -> Option<&Index> {
+        self.shards.get(position).map(|shard| &*shard.index)
+    }
+
+    /// The shard a key is stored in.
+    fn shard_of(&self, key: Key) -> &Shard {
+        &self.shards[(key % self.shards.len() as u64) as usize]
+    }
+
+    /// The number of vectors across all shards.
+    pub fn size(&self) -> usize {
+        self.shards.iter().map(|shard| shard.index.size()).sum()
+    }
+
+    /// Checks if a key is present in its shard.
+    pub fn contains(&self, key: Key) -> bool {
+        self.shard_of(key).index.contains(key)
+    }
+
+    /// Reserves capacity for `capacity` vectors, split evenly across the shards, by the workers
+    /// of every shard.
+    pub fn reserve(&self, capacity: usize) -> Result<(), IndexError> {
+        let per_shard = capacity.div_ceil(self.shards.len());
+        let pending: Vec<_> = self
+            .shards
+            .iter()
+            .map(|shard| shard.submit(move |index| index.reserve(per_shard)))
+            .collect();
+        for receiver in pending {
+            wait(receiver)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a vector to the shard of its key, on a worker of that shard's node.
+    pub fn add<T>(&self, key: Key, vector: &[T]) -> Result<(), IndexError>
+    where
+        T: VectorType + Copy + Send + 'static,
+    {
+        let vector = vector.to_vec();
+        wait(
+            self.shard_of(key)
+                .submit(move |index| index.add(key, &vector)),
+        )
+    }
+
+    /// Removes a key from its shard, returning the number of vectors removed.
+    pub fn remove(&self, key: Key) -> Result<usize, IndexError> {
+        Ok(self.shard_of(key).index.remove(key)?)
+    }
+
+    /// Searches every shard on its own node, and merges the `count` closest matches.
+    pub fn search<T>(&self, query: &[T], count: usize) -> Result<Matches, IndexError>
+    where
+        T: VectorType + Copy + Send + Sync + 'static,
+    {
+        let query: Arc<[T]> = query.into();
+        let pending: Vec<_> = self
+            .shards
+            .iter()
+            .map(|shard| {
+                let query = Arc::clone(&query);
+                shard.submit(move |index| index.search(&query, count))
+            })
+            .collect();
+        let mut merged: Vec<(Distance, Key)> = Vec::with_capacity(count * self.shards.len());
+        for receiver in pending {
+            let matches = wait(receiver)?;
+            merged.extend(matches.distances.into_iter().zip(matches.keys));
+        }
+        merged.sort_by(|a, b| a.0.total_cmp(&b.0));
+        merged.truncate(count);
+        let (distances, keys) = merged.into_iter().unzip();
+        Ok(Matches { keys, distances })
+    }
+}
+
+impl Drop for NumaShardedIndex {
+    fn drop(&mut self) {
+        for shard in &mut self.shards {
+            shard.jobs = None;
+            for worker in shard.workers.drain(..) {
+                let _ = worker.join();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetricKind, ScalarKind};
+
+    #[test]
+    fn test_numa_sharded_index() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list(""), Vec::<usize>::new());
+        let nodes = numa_nodes();
+        assert!(!nodes.is_empty());
+        assert!(nodes.iter().all(|node| !node.cpus.is_empty()));
+
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        // Three shards sharing the CPUs of the first node stand in for a multi-socket machine.
+        let shared = vec![nodes[0].clone(); 3];
+        let index = NumaShardedIndex::with_nodes(&options, shared).unwrap();
+        assert_eq!(index.shards(), 3);
+        index.reserve(300).unwrap();
+        for key in 0..300u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+        assert_eq!(index.size(), 300);
+        assert!((0..3).all(|shard| index.shard(shard).unwrap().size() == 100));
+        assert!(index.contains(299));
+
+        let matches = index.search(&[150.2_f32, 0.0], 3).unwrap();
+        assert_eq!(matches.keys, vec![150, 151, 149]);
+        assert!(matches.distances.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        assert_eq!(index.remove(150).unwrap(), 1);
+        assert!(!index.contains(150));
+        assert_eq!(index.search(&[150.2_f32, 0.0], 1).unwrap().keys, vec![151]);
+        assert!(index.add(1000, &[1.0_f32]).is_err());
+        assert!(NumaShardedIndex::with_nodes(&options, Vec::new()).is_err());
+    }
+}
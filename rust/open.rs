@@ -0,0 +1,188 @@
+//! Creating indexes straight from serialized files.
+//!
+//! [`Index::load`] and [`Index::view`] replace the contents of an existing index, which must
+//! have been created with the dimensions, metric and scalar kind of the file. [`Index::open`]
+//! and [`Index::open_view`] read those from the file header instead, and
+//! [`Index::open_expecting`] additionally checks them against the options the caller relies on,
+//! like those its types were written for.
+
+use std::path::Path;
+
+use crate::{file_metadata, Index, IndexError, IndexOptions};
+
+impl Index {
+    /// Options matching the header of a serialized index, with defaults for everything else.
+    fn file_options(path: &Path) -> Result<IndexOptions, IndexError> {
+        let metadata = file_metadata(path)?;
+        Ok(IndexOptions {
+            dimensions: metadata.dimensions,
+            metric: metadata.metric,
+            quantization: metadata.quantization,
+            multi: metadata.multi,
+            ..Default::default()
+        })
+    }
+
+    /// Creates an index configured from the header of a file, and loads the file into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 3,
+    ///     metric: MetricKind::IP,
+    ///     quantization: ScalarKind::F16,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[0.5_f32, 0.5, 0.0]).unwrap();
+    /// let path = std::env::temp_dir().join("usearch-open-example.usearch");
+    /// index.save(&path).unwrap();
+    ///
+    /// let opened = Index::open(&path).unwrap();
+    /// assert_eq!(opened.dimensions(), 3);
+    /// assert_eq!(opened.options().metric, MetricKind::IP);
+    /// assert_eq!(opened.size(), 1);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Index, IndexError> {
+        let path = path.as_ref();
+        let mut index = Index::new(&Self::file_options(path)?)?;
+        index.load(path)?;
+        Ok(index)
+    }
+
+    /// Creates an index configured from the header of a file, and memory-maps the file into it,
+    /// like [`Index::view`].
+    pub fn open_view<P: AsRef<Path>>(path: P) -> Result<Index, IndexError> {
+        let path = path.as_ref();
+        let mut index = Index::new(&Self::file_options(path)?)?;
+        index.view(path)?;
+        Ok(index)
+    }
+
+    /// Loads a file into an index created from `expected`, after checking that the file holds
+    /// vectors of the expected dimensions, metric, scalar kind and multiplicity.
+    ///
+    /// The other options, like the expansion factors, which files don't store, are applied
+    /// to the loaded index.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the dimensions differ, and
+    /// `IndexError::InvalidOptions` if the metric, the scalar kind, or the multiplicity differ.
+    pub fn open_expecting<P: AsRef<Path>>(
+        path: P,
+        expected: &IndexOptions,
+    ) -> Result<Index, IndexError> {
+        let path = path.as_ref();
+        let found = Self::file_options(path)?;
+        if found.dimensions != expected.dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: expected.dimensions,
+                got: found.dimensions,
+            });
+        }
+        let mismatch =
+            |property: &str, expected: &dyn std::fmt::Debug, got: &dyn std::fmt::Debug| {
+                Err(IndexError::InvalidOptions {
+                    reason: format!(
+                        "The file holds an index with {property} {got:?}, expected {expected:?}"
+                    ),
+                })
+            };
+        if found.metric != expected.metric {
+            return mismatch("metric", &expected.metric, &found.metric);
+        }
+        if found.quantization != expected.quantization {
+            return mismatch("scalar kind", &expected.quantization, &found.quantization);
+        }
+        if found.multi != expected.multi {
+            return mismatch("multi", &expected.multi, &found.multi);
+        }
+        let mut index = Index::new(expected)?;
+        index.load(path)?;
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_open() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            connectivity: 12,
+            multi: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(3).unwrap();
+        index.add(1, &[0.5_f32, 0.0]).unwrap();
+        index.add(1, &[0.0_f32, 0.5]).unwrap();
+        index.add(2, &[-0.5_f32, 0.0]).unwrap();
+        let path = std::env::temp_dir().join("usearch-open-test.usearch");
+        index.save(&path).unwrap();
+
+        let opened = Index::open(&path).unwrap();
+        assert_eq!(opened.size(), 3);
+        assert_eq!(opened.count(1), 2);
+        let found = opened.options();
+        assert_eq!(found.quantization, ScalarKind::I8);
+        assert_eq!(found.connectivity, 12);
+        assert!(found.multi);
+        opened.add(3, &[0.25_f32, 0.25]).unwrap();
+
+        let viewed = Index::open_view(&path).unwrap();
+        let matches = viewed.search(&[-0.4_f32, 0.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![2]);
+
+        let expected = IndexOptions {
+            expansion_search: 77,
+            ..options.clone()
+        };
+        let checked = Index::open_expecting(&path, &expected).unwrap();
+        assert_eq!(checked.size(), 3);
+        assert_eq!(checked.expansion_search(), 77);
+        let wrong = [
+            IndexOptions {
+                quantization: ScalarKind::F32,
+                ..options.clone()
+            },
+            IndexOptions {
+                metric: MetricKind::Cos,
+                ..options.clone()
+            },
+            IndexOptions {
+                multi: false,
+                ..options.clone()
+            },
+        ];
+        for options in &wrong {
+            assert!(matches!(
+                Index::open_expecting(&path, options),
+                Err(IndexError::InvalidOptions { .. })
+            ));
+        }
+        let wider = IndexOptions {
+            dimensions: 3,
+            ..options
+        };
+        assert!(matches!(
+            Index::open_expecting(&path, &wider),
+            Err(IndexError::DimensionMismatch {
+                expected: 3,
+                got: 2
+            })
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(Index::open(&path).is_err());
+    }
+}
@@ -0,0 +1,498 @@
+//! Validation of [`IndexOptions`] and probing of the capabilities of the compiled binary.
+//!
+//! The native layer accepts some combinations that can't work, like bit-level metrics over
+//! floating-point vectors, which silently reinterpret the floats as bit-sets. [`IndexOptions::validate`]
+//! rejects those before any native call, and [`Index::new`] runs it on every construction.
+
+#[cfg(feature = "native")]
+use crate::Index;
+use crate::{IndexError, IndexOptions, MetricKind, ScalarKind};
+
+/// All scalar kinds the Rust layer can exchange with the native layer.
+#[cfg(feature = "native")]
+const SCALAR_KINDS: [ScalarKind; 5] = [
+    ScalarKind::F64,
+    ScalarKind::F32,
+    ScalarKind::F16,
+    ScalarKind::I8,
+    ScalarKind::B1,
+];
+
+fn invalid(reason: String) -> IndexError {
+    IndexError::InvalidOptions { reason }
+}
+
+impl IndexOptions {
+    /// Checks the options for impossible combinations, without touching the native layer.
+    ///
+    /// - The number of dimensions must be positive.
+    /// - The metric and the scalar kind must be known.
+    /// - Bit-level metrics (`Hamming`, `Tanimoto`, `Sorensen`) require `ScalarKind::B1`, and vice versa.
+    /// - `Haversine` requires 2 dimensions, latitude and longitude, stored as `F32` or `F64`.
+    /// - `Divergence` requires floating-point scalars.
+    /// - The level multiplier must be finite and non-negative.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` describing the first violated rule, if any.
+    pub fn validate(&self) -> Result<(), IndexError> {
+        if self.dimensions == 0 {
+            return Err(invalid(
+                "The number of dimensions must be positive".to_owned(),
+            ));
+        }
+        if self.metric == MetricKind::Unknown {
+            return Err(invalid("The metric kind must be specified".to_owned()));
+        }
+        if self.quantization == ScalarKind::Unknown {
+            return Err(invalid("The scalar kind must be specified".to_owned()));
+        }
+        if !self.level_multiplier.is_finite() || self.level_multiplier < 0.0 {
+            return Err(invalid(format!(
+                "The level multiplier must be finite and non-negative, got {}",
+                self.level_multiplier
+            )));
+        }
+
+        let binary_metric = matches!(
+            self.metric,
+            MetricKind::Hamming | MetricKind::Tanimoto | MetricKind::Sorensen
+        );
+        let binary_scalar = self.quantization == ScalarKind::B1;
+        if binary_metric && !binary_scalar {
+            return Err(invalid(format!(
+                "{:?} is a bit-level metric and requires B1 scalars, got {:?}",
+                self.metric, self.quantization
+            )));
+        }
+        if binary_scalar && !binary_metric {
+            return Err(invalid(format!(
+                "B1 scalars require a bit-level metric, like Hamming, got {:?}",
+                self.metric
+            )));
+        }
+
+        match self.metric {
+            MetricKind::Haversine if self.dimensions != 2 => Err(invalid(format!(
+                "Haversine requires 2 dimensions, latitude and longitude, got {}",
+                self.dimensions
+            ))),
+            MetricKind::Haversine
+                if !matches!(self.quantization, ScalarKind::F32 | ScalarKind::F64) =>
+            {
+                Err(invalid(format!(
+                    "Haversine requires F32 or F64 scalars, got {:?}",
+                    self.quantization
+                )))
+            }
+            MetricKind::Divergence if self.quantization == ScalarKind::I8 => Err(invalid(
+                "Divergence requires floating-point scalars, got I8".to_owned(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl Index {
+    /// Reconstructs the options of the index, as needed to create an identical empty one.
+    ///
+    /// Everything but `auto_normalize` is read from the native index, so loaded and viewed
+    /// indexes report the options they were saved with. The level multiplier is reported
+    /// resolved, like by [`Index::level_multiplier`], rather than as the zero default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 16,
+    ///     metric: MetricKind::Cos,
+    ///     quantization: ScalarKind::F16,
+    ///     connectivity: 12,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// let twin = Index::new(&index.options()).unwrap();
+    /// assert_eq!(twin.options(), index.options());
+    /// assert_eq!(index.options().connectivity, 12);
+    /// ```
+    pub fn options(&self) -> IndexOptions {
+        IndexOptions {
+            dimensions: self.dimensions(),
+            metric: self.inner.metric_kind(),
+            quantization: self.inner.scalar_kind(),
+            connectivity: self.connectivity(),
+            expansion_add: self.expansion_add(),
+            expansion_search: self.expansion_search(),
+            multi: self.inner.multi(),
+            level_multiplier: self.level_multiplier(),
+            level_seed: self.level_seed(),
+            auto_normalize: self.auto_normalize,
+            lock_memory: self.memory_locked(),
+        }
+    }
+}
+
+/// Features of the compiled binary and the host it runs on, reported by [`capabilities`].
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capabilities {
+    /// Scalar kinds that can be used to construct an index.
+    pub scalar_kinds: Vec<ScalarKind>,
+    /// The SIMD instruction set picked for every supported scalar kind, like `"haswell"` or `"serial"`.
+    pub hardware_acceleration: Vec<(ScalarKind, String)>,
+    /// Whether the SimSIMD kernels with dynamic dispatch were compiled in.
+    pub simsimd: bool,
+    /// Whether the OpenMP runtime is used for parallelism.
+    pub openmp: bool,
+    /// Whether half-precision arithmetic is emulated with the `fp16` library.
+    pub fp16lib: bool,
+}
+
+/// How the native layer was linked into the binary, reported by [`linkage`].
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Linkage {
+    /// The vendored C++ sources were compiled by the build script.
+    Vendored,
+    /// A pre-built static `libusearch` was linked, from the given directory or the default search paths.
+    Static {
+        /// The `USEARCH_SYS_LIB` directory, if one was set.
+        path: Option<String>,
+    },
+    /// A pre-built shared `libusearch` was linked, from the given directory or the default search paths.
+    Dynamic {
+        /// The `USEARCH_SYS_LIB` directory, if one was set.
+        path: Option<String>,
+    },
+}
+
+/// Reports whether the native layer was compiled from the vendored sources or linked from a
+/// system-installed library, selected with the `system-lib` feature or the `USEARCH_SYS_LIB` variable.
+///
+/// # Examples
+///
+/// ```
+/// println!("USearch linkage: {:?}", usearch::linkage());
+/// ```
+#[cfg(feature = "native")]
+pub fn linkage() -> Linkage {
+    let path = Some(env!("USEARCH_SYS_LIB"))
+        .filter(|path| !path.is_empty())
+        .map(str::to_owned);
+    match env!("USEARCH_LINKAGE") {
+        "static" => Linkage::Static { path },
+        "dylib" => Linkage::Dynamic { path },
+        _ => Linkage::Vendored,
+    }
+}
+
+/// Reports which scalar kinds and SIMD instruction sets the compiled binary supports on this host.
+///
+/// Probes the native layer by constructing a tiny index for every scalar kind,
+/// so it is cheap, but shouldn't be called on a hot path.
+///
+/// # Examples
+///
+/// ```
+/// let capabilities = usearch::capabilities();
+/// assert!(capabilities.scalar_kinds.contains(&usearch::ScalarKind::F32));
+/// for (kind, isa) in &capabilities.hardware_acceleration {
+///     println!("{:?}: {}", kind, isa);
+/// }
+/// ```
+#[cfg(feature = "native")]
+pub fn capabilities() -> Capabilities {
+    let mut scalar_kinds = Vec::new();
+    let mut hardware_acceleration = Vec::new();
+    for quantization in SCALAR_KINDS {
+        let options = IndexOptions {
+            dimensions: 8,
+            metric: if quantization == ScalarKind::B1 {
+                MetricKind::Hamming
+            } else {
+                MetricKind::Cos
+            },
+            quantization,
+            ..Default::default()
+        };
+        if let Ok(index) = Index::new(&options) {
+            scalar_kinds.push(quantization);
+            hardware_acceleration.push((quantization, index.hardware_acceleration()));
+        }
+    }
+
+    Capabilities {
+        scalar_kinds,
+        hardware_acceleration,
+        simsimd: cfg!(feature = "simsimd"),
+        openmp: cfg!(feature = "openmp"),
+        fp16lib: cfg!(feature = "fp16lib"),
+    }
+}
+
+/// A family of SIMD instruction sets the distance kernels can be compiled for.
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Isa {
+    /// Arm NEON.
+    Neon,
+    /// Arm Scalable Vector Extension.
+    Sve,
+    /// x86 AVX2, the `haswell` kernels.
+    Avx2,
+    /// x86 AVX-512, the `skylake`, `ice` and `sapphire` kernels.
+    Avx512,
+}
+
+#[cfg(feature = "native")]
+impl Isa {
+    /// Every instruction set, in the order they are reported.
+    pub const ALL: [Isa; 4] = [Isa::Neon, Isa::Sve, Isa::Avx2, Isa::Avx512];
+
+    /// The lowercase name accepted by the `USEARCH_DISABLE_ISA` build variable.
+    pub fn name(self) -> &'static str {
+        match self {
+            Isa::Neon => "neon",
+            Isa::Sve => "sve",
+            Isa::Avx2 => "avx2",
+            Isa::Avx512 => "avx512",
+        }
+    }
+
+    /// Maps the name of the native kernels, as in [`Index::hardware_acceleration`], to the
+    /// instruction set they use, or `None` for the serial ones.
+    fn of_kernels(kernels: &str) -> Option<Isa> {
+        match kernels {
+            "neon" => Some(Isa::Neon),
+            "sve" => Some(Isa::Sve),
+            "haswell" => Some(Isa::Avx2),
+            "skylake" | "ice" | "sapphire" => Some(Isa::Avx512),
+            _ => None,
+        }
+    }
+
+    /// Whether the host CPU supports the instruction set.
+    fn detected(self) -> bool {
+        match self {
+            #[cfg(target_arch = "aarch64")]
+            Isa::Neon => std::arch::is_aarch64_feature_detected!("neon"),
+            #[cfg(target_arch = "aarch64")]
+            Isa::Sve => std::arch::is_aarch64_feature_detected!("sve"),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx2 => std::arch::is_x86_feature_detected!("avx2"),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx512 => std::arch::is_x86_feature_detected!("avx512f"),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+}
+
+/// The SIMD instruction sets of the host and the ones the kernels use, reported by
+/// [`simd_capabilities`].
+#[cfg(feature = "native")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimdCapabilities {
+    /// Instruction sets the host CPU supports, whether or not the kernels use them.
+    pub supported: Vec<Isa>,
+    /// Instruction sets excluded at build time with the `USEARCH_DISABLE_ISA` variable.
+    pub disabled: Vec<Isa>,
+    /// The instruction set the `f32` kernels dispatch to, or `None` if they run serially.
+    pub active: Option<Isa>,
+    /// The name of the `f32` kernels, like `"haswell"`, `"sapphire"` or `"serial"`.
+    pub kernels: String,
+}
+
+/// Reports which SIMD instruction set the compiled distance kernels use on this host.
+///
+/// The kernels are picked at runtime among the ones compiled in, which are all of those of the
+/// target architecture with the `simsimd` feature, minus the ones listed in the comma-separated
+/// `USEARCH_DISABLE_ISA` variable at build time, like `USEARCH_DISABLE_ISA=avx512,sve`. Disabling
+/// the newer instruction sets makes benchmarks comparable across machines.
+///
+/// # Examples
+///
+/// ```
+/// let simd = usearch::simd_capabilities();
+/// println!("Kernels: {} ({:?})", simd.kernels, simd.active);
+/// assert!(simd.active.is_none_or(|isa| !simd.disabled.contains(&isa)));
+/// ```
+#[cfg(feature = "native")]
+pub fn simd_capabilities() -> SimdCapabilities {
+    let disabled: Vec<Isa> = Isa::ALL
+        .into_iter()
+        .filter(|isa| {
+            env!("USEARCH_DISABLE_ISA")
+                .split(',')
+                .any(|name| name == isa.name())
+        })
+        .collect();
+    let options = IndexOptions {
+        dimensions: 8,
+        metric: MetricKind::Cos,
+        quantization: ScalarKind::F32,
+        ..Default::default()
+    };
+    let kernels = Index::new(&options)
+        .map(|index| index.hardware_acceleration())
+        .unwrap_or_else(|_| "serial".to_owned());
+    SimdCapabilities {
+        supported: Isa::ALL.into_iter().filter(|isa| isa.detected()).collect(),
+        disabled,
+        active: Isa::of_kernels(&kernels),
+        kernels,
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_rejects_impossible_combinations() {
+        let valid = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::Haversine,
+            quantization: ScalarKind::F64,
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+
+        let invalid = [
+            IndexOptions {
+                dimensions: 3,
+                ..valid.clone()
+            },
+            IndexOptions {
+                quantization: ScalarKind::F16,
+                ..valid.clone()
+            },
+            IndexOptions {
+                metric: MetricKind::Hamming,
+                quantization: ScalarKind::F32,
+                ..valid.clone()
+            },
+            IndexOptions {
+                metric: MetricKind::Cos,
+                quantization: ScalarKind::B1,
+                ..valid.clone()
+            },
+            IndexOptions {
+                dimensions: 0,
+                metric: MetricKind::Cos,
+                ..valid.clone()
+            },
+            IndexOptions {
+                level_multiplier: -1.0,
+                ..valid.clone()
+            },
+        ];
+        for options in invalid {
+            assert!(matches!(
+                options.validate(),
+                Err(IndexError::InvalidOptions { .. })
+            ));
+            assert!(Index::new(&options).is_err());
+        }
+
+        let capabilities = capabilities();
+        assert_eq!(capabilities.scalar_kinds.len(), SCALAR_KINDS.len());
+        assert!(capabilities
+            .hardware_acceleration
+            .iter()
+            .all(|(_, isa)| !isa.is_empty()));
+        let simd = simd_capabilities();
+        assert!(!simd.kernels.is_empty());
+        if let Some(isa) = simd.active {
+            assert!(simd.supported.contains(&isa));
+            assert!(!simd.disabled.contains(&isa));
+        }
+        if !cfg!(feature = "simsimd") {
+            assert_eq!(simd.active, None);
+        }
+        if !cfg!(feature = "system-lib") && env!("USEARCH_SYS_LIB").is_empty() {
+            assert_eq!(linkage(), Linkage::Vendored);
+        }
+    }
+
+    #[test]
+    fn test_level_generation() {
+        let build = |level_multiplier: f64, level_seed: u64| {
+            let options = IndexOptions {
+                dimensions: 2,
+                metric: MetricKind::L2sq,
+                quantization: ScalarKind::F32,
+                connectivity: 8,
+                level_multiplier,
+                level_seed,
+                ..Default::default()
+            };
+            let index = Index::new(&options).unwrap();
+            index.reserve(16).unwrap();
+            // Growing past the reservation must not replay the levels drawn so far.
+            for key in 0..2000u64 {
+                index.add(key, &[key as f32, 0.0]).unwrap();
+            }
+            index
+        };
+        let level_nodes = |index: &Index| -> Vec<usize> {
+            index.stats().levels.iter().map(|l| l.nodes).collect()
+        };
+
+        let index = build(0.0, 42);
+        assert_eq!(index.level_seed(), 42);
+        assert!((index.level_multiplier() - 1.0 / 8f64.ln()).abs() < 1e-12);
+        assert_eq!(index.options().level_seed, 42);
+        assert_eq!(level_nodes(&index), level_nodes(&build(0.0, 42)));
+        assert_ne!(level_nodes(&index), level_nodes(&build(0.0, 7)));
+
+        // Taller graphs promote more nodes to the upper levels.
+        let tall = build(1.0, 42);
+        assert_eq!(tall.level_multiplier(), 1.0);
+        assert!(level_nodes(&tall)[1] > level_nodes(&index)[1]);
+        assert!(tall.stats().levels.len() > index.stats().levels.len());
+    }
+
+    #[test]
+    fn test_options_round_trip() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::IP,
+            quantization: ScalarKind::I8,
+            connectivity: 10,
+            expansion_add: 64,
+            expansion_search: 32,
+            multi: true,
+            level_multiplier: 0.5,
+            level_seed: 7,
+            auto_normalize: true,
+            lock_memory: false,
+        };
+        let index = Index::new(&options).unwrap();
+        assert_eq!(index.options(), options);
+        index.add(1, &[0.5_f32, 0.5, 0.0]).unwrap();
+
+        // A serialized index reports the options it was saved with, once loaded into an index
+        // created from those same options.
+        let mut buffer = vec![0; index.serialized_length()];
+        index.save_to_buffer(&mut buffer).unwrap();
+        let mut loaded = Index::new(&index.options()).unwrap();
+        loaded.load_from_buffer(&buffer).unwrap();
+        assert_eq!(loaded.options(), options);
+        assert_eq!(loaded.size(), 1);
+
+        let defaults = Index::new(&IndexOptions {
+            dimensions: 3,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            defaults.options().level_multiplier,
+            defaults.level_multiplier()
+        );
+        assert!(defaults.options().level_multiplier > 0.0);
+    }
+}
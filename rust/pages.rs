@@ -0,0 +1,203 @@
+//! Choosing the memory the vectors of an index are stored in.
+//!
+//! The native index stores vectors in large arenas, mapped from the operating system with regular
+//! pages by default. Searches over multi-gigabyte indexes read vectors all over those arenas, and
+//! with 4 KB pages most of the reads miss the TLB. [`Index::change_vector_pages`] backs the arenas
+//! with 2 MB huge pages instead, and [`Index::change_vector_allocator`] hands them to a custom
+//! [`VectorAllocator`], like one placing them on a specific NUMA node. Both apply to empty indexes
+//! only, as arenas are released the way they were allocated.
+
+use std::alloc::Layout;
+use std::panic::AssertUnwindSafe;
+use std::sync::PoisonError;
+
+pub use crate::ffi::VectorPages;
+use crate::{Index, IndexError};
+
+/// Alignment of the arenas returned by a [`VectorAllocator`].
+const ARENA_ALIGNMENT: usize = 64;
+
+/// A source of memory for the vectors of an index, set with [`Index::change_vector_allocator`].
+///
+/// The index asks for a few arenas of several megabytes, doubling in size, and releases all of them
+/// at once when it is reset, cleared, or dropped.
+pub trait VectorAllocator: Send + Sync {
+    /// Allocates `bytes` of memory, aligned to at least 64 bytes.
+    ///
+    /// The index can't recover from a missing arena, so returning a null pointer or panicking
+    /// aborts the process, like any other failed allocation.
+    fn allocate(&self, bytes: usize) -> *mut u8;
+
+    /// Releases an arena.
+    ///
+    /// # Safety
+    ///
+    /// `pointer` was returned by [`VectorAllocator::allocate`] of the same allocator, called with
+    /// the same `bytes`, and isn't used afterwards.
+    unsafe fn deallocate(&self, pointer: *mut u8, bytes: usize);
+}
+
+/// A custom allocator, pinned to a heap address the native index calls it through.
+pub(crate) struct AllocatorState(Box<Box<dyn VectorAllocator>>);
+
+// The index only calls the allocator through the trampolines, which never unwind, so a panic
+// elsewhere can't leave the allocator observed half-updated.
+impl std::panic::UnwindSafe for AllocatorState {}
+impl std::panic::RefUnwindSafe for AllocatorState {}
+
+impl AllocatorState {
+    /// The stable address passed to the native index with the trampolines.
+    fn address(&self) -> usize {
+        &*self.0 as *const Box<dyn VectorAllocator> as usize
+    }
+}
+
+fn arena_layout(bytes: usize) -> Layout {
+    Layout::from_size_align(bytes.max(1), ARENA_ALIGNMENT).unwrap_or(Layout::new::<u8>())
+}
+
+/// Allocates an arena on behalf of the native index, given the address of an `AllocatorState`.
+extern "C" fn allocate_trampoline(bytes: usize, state: usize) -> *mut u8 {
+    // SAFETY: The address comes from an `AllocatorState`, which outlives the native index.
+    let allocator = unsafe { &*(state as *const Box<dyn VectorAllocator>) };
+    match std::panic::catch_unwind(AssertUnwindSafe(|| allocator.allocate(bytes))) {
+        Ok(pointer) if !pointer.is_null() => pointer,
+        _ => std::alloc::handle_alloc_error(arena_layout(bytes)),
+    }
+}
+
+/// Releases an arena on behalf of the native index, given the address of an `AllocatorState`.
+extern "C" fn deallocate_trampoline(pointer: *mut u8, bytes: usize, state: usize) {
+    // SAFETY: The address comes from an `AllocatorState`, which outlives the native index.
+    let allocator = unsafe { &*(state as *const Box<dyn VectorAllocator>) };
+    // SAFETY: The native index only releases arenas it got from `allocate_trampoline`.
+    let released = std::panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+        allocator.deallocate(pointer, bytes)
+    }));
+    if released.is_err() {
+        std::process::abort();
+    }
+}
+
+impl Index {
+    /// Changes the pages the vectors are stored in, before any vector is added.
+    ///
+    /// Huge pages reduce TLB misses of searches over indexes larger than a few gigabytes. They are
+    /// only available on Linux, and other platforms keep using regular pages. The choice isn't
+    /// saved, so loaded indexes and copies start with regular pages.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::Native` if the index already stores vectors, or views a file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, VectorPages};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.change_vector_pages(VectorPages::TransparentHuge).unwrap();
+    /// index.reserve(10).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    /// assert!(index.change_vector_pages(VectorPages::Regular).is_err());
+    /// ```
+    pub fn change_vector_pages(&self, pages: VectorPages) -> Result<(), IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        Ok(self.inner.change_vector_pages(pages)?)
+    }
+
+    /// Stores the vectors in memory from a custom allocator, before any vector is added.
+    ///
+    /// The allocator is kept until the index drops, and replaces any previous one.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::Native` if the index already stores vectors, or views a file.
+    pub fn change_vector_allocator<A>(&mut self, allocator: A) -> Result<(), IndexError>
+    where
+        A: VectorAllocator + 'static,
+    {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let state = AllocatorState(Box::new(Box::new(allocator)));
+        self.inner.change_vector_allocator(
+            allocate_trampoline as *const () as usize,
+            deallocate_trampoline as *const () as usize,
+            state.address(),
+        )?;
+        self.vector_allocator = Some(state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    /// Counts the bytes it hands out through the global allocator.
+    struct Counting(Arc<AtomicUsize>);
+
+    impl VectorAllocator for Counting {
+        fn allocate(&self, bytes: usize) -> *mut u8 {
+            self.0.fetch_add(bytes, Ordering::SeqCst);
+            // SAFETY: The layout has a non-zero size.
+            unsafe { std::alloc::alloc(arena_layout(bytes)) }
+        }
+
+        unsafe fn deallocate(&self, pointer: *mut u8, bytes: usize) {
+            self.0.fetch_sub(bytes, Ordering::SeqCst);
+            std::alloc::dealloc(pointer, arena_layout(bytes))
+        }
+    }
+
+    #[test]
+    fn test_vector_pages() {
+        let options = IndexOptions {
+            dimensions: 16,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        for pages in [
+            VectorPages::Regular,
+            VectorPages::TransparentHuge,
+            VectorPages::HugeTlb,
+        ] {
+            let index = Index::new(&options).unwrap();
+            index.change_vector_pages(pages).unwrap();
+            index.reserve(1000).unwrap();
+            for key in 0..1000u64 {
+                index.add(key, &[key as f32; 16]).unwrap();
+            }
+            assert_eq!(index.search(&[500.2_f32; 16], 1).unwrap().keys, vec![500]);
+            assert!(index.change_vector_pages(VectorPages::Regular).is_err());
+        }
+
+        let allocated = Arc::new(AtomicUsize::new(0));
+        let mut index = Index::new(&options).unwrap();
+        index
+            .change_vector_allocator(Counting(Arc::clone(&allocated)))
+            .unwrap();
+        index.reserve(1000).unwrap();
+        for key in 0..1000u64 {
+            index.add(key, &[key as f32; 16]).unwrap();
+        }
+        assert!(allocated.load(Ordering::SeqCst) >= 1000 * 16 * 4);
+        assert_eq!(index.get_array::<f32, 16>(7).unwrap(), Some([7.0; 16]));
+        assert!(index
+            .change_vector_allocator(Counting(Arc::clone(&allocated)))
+            .is_err());
+
+        // Resetting hands the arenas back to the allocator, which keeps serving the new ones.
+        index.reset().unwrap();
+        assert_eq!(allocated.load(Ordering::SeqCst), 0);
+        index.reserve(10).unwrap();
+        index.add(1, &[1.0_f32; 16]).unwrap();
+        assert!(allocated.load(Ordering::SeqCst) > 0);
+        drop(index);
+        assert_eq!(allocated.load(Ordering::SeqCst), 0);
+    }
+}
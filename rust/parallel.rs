@@ -0,0 +1,298 @@
+//! Many queries at once, spread across threads.
+//!
+//! Serving batches of queries one by one leaves all but one core idle. The searches here split a
+//! batch into contiguous chunks, one per available hardware thread, as the native layer holds
+//! one search context per hardware thread, and return the results in query order.
+//! [`Index::filtered_batch_search`] shares a single filter, like a closure over a bitmap of
+//! allowed keys, between all of them. [`Index::search_many`] takes the queries packed into a
+//! single slice instead, and leaves the threading to the native layer, which packs the matches
+//! into the flat arrays of [`BatchMatches`].
+
+use crate::ffi::{BatchMatches, Matches};
+use crate::search::{elements, ResultElement};
+use crate::{Distance, Index, IndexError, Key, VectorType};
+
+/// Runs a fallible search for every query, on up to one thread per hardware thread.
+pub(crate) fn in_parallel<Q, R, E, S>(index: &Index, queries: &[Q], search: S) -> Result<Vec<R>, E>
+where
+    Q: Sync,
+    R: Send,
+    E: Send,
+    S: Fn(&Index, &Q) -> Result<R, E> + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = queries.len().div_ceil(threads).max(1);
+    if queries.len() <= chunk_size {
+        return queries.iter().map(|query| search(index, query)).collect();
+    }
+
+    let search = &search;
+    let chunks: Vec<Vec<Result<R, E>>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = queries
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || chunk.iter().map(|query| search(index, query)).collect())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    chunks.into_iter().flatten().collect()
+}
+
+impl Index {
+    /// Searches for the nearest neighbors of many queries in parallel, all with the same filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - The query vectors.
+    /// * `count` - The maximum number of neighbors to search for, per query.
+    /// * `filter` - A closure returning `true` for the keys that may be included in the results,
+    ///   called concurrently from several threads.
+    ///
+    /// # Returns
+    ///
+    /// The matches of every query, in query order, or the first error encountered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 1,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// for key in 0..10u64 {
+    ///     index.add(key, &[key as f32]).unwrap();
+    /// }
+    ///
+    /// let allowed: HashSet<u64> = [1, 5, 9].into();
+    /// let queries = [[0.0_f32], [6.0], [10.0]];
+    /// let results = index
+    ///     .filtered_batch_search(&queries, 1, |key| allowed.contains(&key))
+    ///     .unwrap();
+    /// let keys: Vec<u64> = results.iter().map(|matches| matches.keys[0]).collect();
+    /// assert_eq!(keys, vec![1, 5, 9]);
+    /// ```
+    pub fn filtered_batch_search<T, Q, F>(
+        &self,
+        queries: &[Q],
+        count: usize,
+        filter: F,
+    ) -> Result<Vec<Matches>, IndexError>
+    where
+        T: VectorType,
+        Q: AsRef<[T]> + Sync,
+        F: Fn(Key) -> bool + Sync,
+    {
+        let filter = &filter;
+        in_parallel(self, queries, |index, query| {
+            index.filtered_search(query.as_ref(), count, filter)
+        })
+    }
+}
+
+impl Index {
+    /// Searches for the nearest neighbors of queries packed row-major, crossing into the native
+    /// layer once, which spreads the queries across all hardware threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `queries` - A slice holding the query vectors, one after another.
+    /// * `count` - The maximum number of neighbors to search for, per query.
+    ///
+    /// # Returns
+    ///
+    /// The matches of every query, in query order, `IndexError::DimensionMismatch` if `queries`
+    /// doesn't hold a whole number of vectors, or the first native error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// for key in 0..10u64 {
+    ///     index.add(key, &[key as f32, 0.0]).unwrap();
+    /// }
+    ///
+    /// let results = index.search_many(&[0.9_f32, 0.0, 7.2, 0.0], 2).unwrap();
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results.keys_of(0), &[1, 0]);
+    /// assert_eq!(results.keys_of(1), &[7, 8]);
+    /// ```
+    pub fn search_many<T: VectorType>(
+        &self,
+        queries: &[T],
+        count: usize,
+    ) -> Result<BatchMatches, IndexError> {
+        let length = self.vector_length::<T>();
+        if length == 0 || !queries.len().is_multiple_of(length) {
+            return Err(IndexError::DimensionMismatch {
+                expected: queries.len().div_ceil(length.max(1)) * length,
+                got: queries.len(),
+            });
+        }
+        self.inject_fault("search")?;
+        let results = self.guard_parallel_metric(|| T::search_many(self, queries, count))?;
+        Ok(results?)
+    }
+}
+
+impl BatchMatches {
+    /// The number of queries.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Whether there were no queries.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The keys of the matches of a query, closest first.
+    ///
+    /// # Panics
+    ///
+    /// If `query` isn't smaller than [`BatchMatches::len`].
+    pub fn keys_of(&self, query: usize) -> &[Key] {
+        &self.keys[self.row(query)]
+    }
+
+    /// The distances of the matches of a query, closest first.
+    ///
+    /// # Panics
+    ///
+    /// If `query` isn't smaller than [`BatchMatches::len`].
+    pub fn distances_of(&self, query: usize) -> &[Distance] {
+        &self.distances[self.row(query)]
+    }
+
+    /// Iterates over the matches of a query, closest first.
+    ///
+    /// # Panics
+    ///
+    /// If `query` isn't smaller than [`BatchMatches::len`].
+    pub fn elements_of(&self, query: usize) -> impl Iterator<Item = ResultElement> + '_ {
+        elements(self.keys_of(query), self.distances_of(query))
+    }
+
+    /// Copies the matches of a query into separate [`Matches`].
+    ///
+    /// # Panics
+    ///
+    /// If `query` isn't smaller than [`BatchMatches::len`].
+    pub fn matches_of(&self, query: usize) -> Matches {
+        Matches {
+            keys: self.keys_of(query).to_vec(),
+            distances: self.distances_of(query).to_vec(),
+        }
+    }
+
+    /// The slots of a query holding its matches.
+    fn row(&self, query: usize) -> std::ops::Range<usize> {
+        let start = query * self.count;
+        start..start + self.counts[query]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_filtered_batch_search() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..1000u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+
+        let queries: Vec<Vec<f32>> = (0..100).map(|i| vec![i as f32 * 10.0, 0.0]).collect();
+        let results = index
+            .filtered_batch_search(&queries, 3, |key| key % 3 == 0)
+            .unwrap();
+        assert_eq!(results.len(), queries.len());
+        for (query, matches) in queries.iter().zip(&results) {
+            assert!(!matches.keys.is_empty());
+            assert!(matches.keys.iter().all(|key| key % 3 == 0));
+            let closest = matches.keys[0] as f32;
+            assert!(
+                (closest - query[0]).abs() <= 3.0,
+                "{} for {}",
+                closest,
+                query[0]
+            );
+        }
+
+        let none: Vec<Vec<f32>> = Vec::new();
+        assert!(index
+            .filtered_batch_search(&none, 3, |_| true)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_many() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..1000u64 {
+            index.add(key, &[key as f32 / 4.0, 0.0]).unwrap();
+        }
+
+        let queries: Vec<f32> = (0..500).flat_map(|i| [i as f32 / 2.0, 0.0]).collect();
+        let results = index.search_many(&queries, 3).unwrap();
+        assert_eq!(results.len(), 500);
+        for (i, query) in queries.chunks(2).enumerate() {
+            let matches = index.search(query, 3).unwrap();
+            assert_eq!(results.keys_of(i), matches.keys);
+            assert_eq!(results.distances_of(i), matches.distances);
+            assert_eq!(results.matches_of(i).keys, matches.keys);
+            assert_eq!(results.elements_of(i).next().unwrap().key, 2 * i as u64);
+        }
+
+        // Rows of queries with fewer matches than slots are shorter.
+        let small = Index::new(&options).unwrap();
+        small.add(1, &[0.0_f32, 0.0]).unwrap();
+        let results = small.search_many(&[0.0_f64, 0.0, 1.0, 1.0], 5).unwrap();
+        assert_eq!(results.keys_of(0), &[1]);
+        assert_eq!(results.keys_of(1), &[1]);
+
+        assert!(index.search_many::<f32>(&[], 3).unwrap().is_empty());
+        assert!(matches!(
+            index.search_many(&[0.0_f32; 3], 3),
+            Err(IndexError::DimensionMismatch {
+                expected: 4,
+                got: 3
+            })
+        ));
+    }
+}
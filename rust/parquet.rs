@@ -0,0 +1,253 @@
+//! Export and import of index contents as Parquet files, for migrations between vector stores.
+//!
+//! Enabled with the `parquet` feature. Files follow the layout used by LanceDB and most
+//! dataframe libraries, with one row per stored vector:
+//!
+//! - `key` - a non-nullable `UInt64` column.
+//! - `vector` - a non-nullable `FixedSizeList<Float32>` column, as long as the index dimensions.
+//! - `payload` - an optional nullable `Utf8` column, like the text of a document.
+//!
+//! Vectors are exported as `f32`, whatever the scalar kind of the index. Imports go through
+//! [`Index::add_arrow`], so they also accept `Float64` and `Int8` lists, without copying them.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::basic::Compression;
+use ::parquet::file::properties::WriterProperties;
+use arrow_array::{
+    Array, ArrayRef, FixedSizeListArray, Float32Array, RecordBatch, StringArray, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::{Index, IndexError, Key};
+
+/// Number of rows written in every record batch.
+const ROWS_PER_BATCH: usize = 8192;
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(err: E) -> IndexError {
+    IndexError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
+fn schema(dimensions: usize, payloads: bool) -> Schema {
+    let item = Arc::new(Field::new("item", DataType::Float32, true));
+    let mut fields = vec![
+        Field::new("key", DataType::UInt64, false),
+        Field::new(
+            "vector",
+            DataType::FixedSizeList(item, dimensions as i32),
+            false,
+        ),
+    ];
+    if payloads {
+        fields.push(Field::new("payload", DataType::Utf8, true));
+    }
+    Schema::new(fields)
+}
+
+impl Index {
+    /// Writes the keys and vectors of the index to a Parquet file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where the contents will be written.
+    ///
+    /// # Returns
+    ///
+    /// The number of written rows, one per stored vector.
+    pub fn export_parquet<P: AsRef<Path>>(&self, path: P) -> Result<usize, IndexError> {
+        self.write_parquet(path, None::<fn(Key) -> Option<String>>)
+    }
+
+    /// Writes the keys and vectors of the index to a Parquet file, with a payload for every key.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where the contents will be written.
+    /// * `payloads` - Returns the payload of a key, or `None` to leave it null.
+    pub fn export_parquet_with_payloads<P, F>(
+        &self,
+        path: P,
+        payloads: F,
+    ) -> Result<usize, IndexError>
+    where
+        P: AsRef<Path>,
+        F: Fn(Key) -> Option<String>,
+    {
+        self.write_parquet(path, Some(payloads))
+    }
+
+    /// Adds the rows of a Parquet file to the index, growing the capacity as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path from where the rows will be read.
+    ///
+    /// # Returns
+    ///
+    /// The number of added vectors, or `IndexError::DimensionMismatch` if the vectors
+    /// in the file don't match the dimensions of the index.
+    pub fn import_parquet<P: AsRef<Path>>(&self, path: P) -> Result<usize, IndexError> {
+        self.import_parquet_with_payloads(path, |_, _| {})
+    }
+
+    /// Adds the rows of a Parquet file to the index, passing every non-null payload to a callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path from where the rows will be read.
+    /// * `on_payload` - Receives the key and the payload of every row with a payload.
+    pub fn import_parquet_with_payloads<P, F>(
+        &self,
+        path: P,
+        mut on_payload: F,
+    ) -> Result<usize, IndexError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(Key, &str),
+    {
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)
+            .and_then(|builder| builder.with_batch_size(ROWS_PER_BATCH).build())
+            .map_err(invalid_data)?;
+
+        let mut added = 0;
+        for batch in reader {
+            let batch = batch.map_err(invalid_data)?;
+            let keys = batch
+                .column_by_name("key")
+                .and_then(|column| column.as_any().downcast_ref::<UInt64Array>())
+                .ok_or_else(|| invalid_data("Expected a `key` column of UInt64"))?;
+            let vectors = batch
+                .column_by_name("vector")
+                .and_then(|column| column.as_any().downcast_ref::<FixedSizeListArray>())
+                .ok_or_else(|| invalid_data("Expected a `vector` column of fixed-size lists"))?;
+            let payloads = batch
+                .column_by_name("payload")
+                .and_then(|column| column.as_any().downcast_ref::<StringArray>());
+
+            added += self.add_arrow(keys, vectors)?;
+            if let Some(payloads) = payloads {
+                for row in 0..batch.num_rows() {
+                    if payloads.is_valid(row) && vectors.is_valid(row) {
+                        on_payload(keys.value(row), payloads.value(row));
+                    }
+                }
+            }
+        }
+        Ok(added)
+    }
+
+    fn write_parquet<P, F>(&self, path: P, payloads: Option<F>) -> Result<usize, IndexError>
+    where
+        P: AsRef<Path>,
+        F: Fn(Key) -> Option<String>,
+    {
+        let dimensions = self.dimensions();
+        let schema = Arc::new(schema(dimensions, payloads.is_some()));
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut writer =
+            ArrowWriter::try_new(File::create(path)?, schema.clone(), Some(properties))
+                .map_err(invalid_data)?;
+
+        let mut keys = self.keys();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let mut written = 0;
+        let mut vector: Vec<f32> = Vec::new();
+        for chunk in keys.chunks(ROWS_PER_BATCH) {
+            let mut row_keys = Vec::with_capacity(chunk.len());
+            let mut values = Vec::with_capacity(chunk.len() * dimensions);
+            for &key in chunk {
+                let count = self.export(key, &mut vector)?;
+                values.extend_from_slice(&vector);
+                row_keys.extend(std::iter::repeat_n(key, count));
+            }
+
+            let item = Arc::new(Field::new("item", DataType::Float32, true));
+            let vectors = FixedSizeListArray::try_new(
+                item,
+                dimensions as i32,
+                Arc::new(Float32Array::from(values)),
+                None,
+            )
+            .map_err(invalid_data)?;
+            let mut columns: Vec<ArrayRef> = vec![
+                Arc::new(UInt64Array::from(row_keys.clone())),
+                Arc::new(vectors),
+            ];
+            if let Some(payloads) = &payloads {
+                let payloads: StringArray = row_keys.iter().map(|&key| payloads(key)).collect();
+                columns.push(Arc::new(payloads));
+            }
+
+            let batch = RecordBatch::try_new(schema.clone(), columns).map_err(invalid_data)?;
+            writer.write(&batch).map_err(invalid_data)?;
+            written += row_keys.len();
+        }
+        writer.close().map_err(invalid_data)?;
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(100).unwrap();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32, 1.0, -1.0]).unwrap();
+        }
+
+        let path = std::env::temp_dir().join("usearch-parquet-round-trip.parquet");
+        let written = index
+            .export_parquet_with_payloads(&path, |key| (key % 2 == 0).then(|| key.to_string()))
+            .unwrap();
+        assert_eq!(written, 100);
+
+        let imported = Index::new(&options).unwrap();
+        let mut payloads = HashMap::new();
+        let added = imported
+            .import_parquet_with_payloads(&path, |key, payload| {
+                payloads.insert(key, payload.to_owned());
+            })
+            .unwrap();
+        assert_eq!(added, 100);
+        assert_eq!(payloads.len(), 50);
+        assert_eq!(payloads[&42], "42");
+
+        let mut vector: Vec<f32> = Vec::new();
+        imported.export(7, &mut vector).unwrap();
+        assert_eq!(vector, vec![7.0, 1.0, -1.0]);
+
+        let narrow = Index::new(&IndexOptions {
+            dimensions: 2,
+            ..options
+        })
+        .unwrap();
+        assert!(matches!(
+            narrow.import_parquet(&path),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 3
+            })
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+}
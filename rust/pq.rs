@@ -0,0 +1,553 @@
+//! Product quantization, keeping compact codes of vectors to re-rank coarse search results.
+//!
+//! At hundreds of millions of vectors, full-precision copies don't fit in memory, while
+//! `i8` indexes alone lose too much recall. A [`ProductQuantizer`] splits vectors into
+//! subspaces and learns a codebook of up to 256 centroids for each, so a vector is stored as one
+//! byte per subspace. [`PqIndex`] pairs such codes with a graph over narrow scalars: the graph
+//! proposes candidates, and the codes re-rank them with asymmetric distances, comparing the
+//! exact query with the reconstructed vectors through one lookup table per query.
+
+use std::collections::HashMap;
+#[cfg(not(no_fs))]
+use std::fs::File;
+#[cfg(not(no_fs))]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::ffi::Matches;
+use crate::{Distance, Index, IndexError, IndexOptions, Key, MetricKind};
+
+const MAGIC: &[u8; 8] = b"USPQCB01";
+
+/// Codebooks mapping every subspace of a vector to one of up to 256 centroids.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::ProductQuantizer;
+///
+/// let sample: Vec<Vec<f32>> = (0..64)
+///     .map(|i| vec![(i % 8) as f32, (i / 8) as f32, 0.0, 1.0])
+///     .collect();
+/// let quantizer = ProductQuantizer::train(&sample, 2, 64, 10).unwrap();
+/// let code = quantizer.encode(&[3.0, 5.0, 0.0, 1.0]);
+/// assert_eq!(code.len(), 2);
+/// assert_eq!(quantizer.decode(&code), vec![3.0, 5.0, 0.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductQuantizer {
+    dimensions: usize,
+    subspaces: usize,
+    centroids: usize,
+    /// Centroids of all subspaces, one after another, each `dimensions / subspaces` long.
+    codebooks: Vec<f32>,
+}
+
+fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+impl ProductQuantizer {
+    /// Learns the codebooks from a sample with a few iterations of k-means in every subspace.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - Vectors representative of the ones to encode, at least `centroids` of them.
+    /// * `subspaces` - The number of subspaces, dividing the dimensions, and of bytes per code.
+    /// * `centroids` - The number of centroids per subspace, from 1 to 256.
+    /// * `iterations` - The number of k-means refinements, with about 10 being typical.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if the parameters don't fit the sample, and
+    /// `IndexError::DimensionMismatch` if the sample vectors have different lengths.
+    pub fn train<V: AsRef<[f32]>>(
+        sample: &[V],
+        subspaces: usize,
+        centroids: usize,
+        iterations: usize,
+    ) -> Result<Self, IndexError> {
+        let invalid = |reason: String| Err(IndexError::InvalidOptions { reason });
+        let dimensions = sample.first().map_or(0, |vector| vector.as_ref().len());
+        if let Some(vector) = sample.iter().find(|v| v.as_ref().len() != dimensions) {
+            return Err(IndexError::DimensionMismatch {
+                expected: dimensions,
+                got: vector.as_ref().len(),
+            });
+        }
+        if subspaces == 0 || dimensions == 0 || !dimensions.is_multiple_of(subspaces) {
+            return invalid(format!(
+                "{subspaces} subspaces don't divide {dimensions} dimensions"
+            ));
+        }
+        if !(1..=256).contains(&centroids) || sample.len() < centroids {
+            return invalid(format!(
+                "Training {} centroids requires between 1 and 256 of them, and as many samples, got {}",
+                centroids,
+                sample.len()
+            ));
+        }
+
+        let width = dimensions / subspaces;
+        let mut codebooks = Vec::with_capacity(subspaces * centroids * width);
+        let mut points: Vec<&[f32]> = Vec::with_capacity(sample.len());
+        for subspace in 0..subspaces {
+            let range = subspace * width..(subspace + 1) * width;
+            points.clear();
+            points.extend(sample.iter().map(|v| &v.as_ref()[range.clone()]));
+            codebooks.extend(Self::kmeans(&points, centroids, iterations));
+        }
+        Ok(Self {
+            dimensions,
+            subspaces,
+            centroids,
+            codebooks,
+        })
+    }
+
+    /// Clusters points with Lloyd's algorithm, seeded with points spread across the sample.
+    fn kmeans(points: &[&[f32]], count: usize, iterations: usize) -> Vec<f32> {
+        let width = points[0].len();
+        let mut centroids: Vec<f32> = (0..count)
+            .flat_map(|i| points[i * points.len() / count].iter().copied())
+            .collect();
+        let mut sums = vec![0.0_f64; count * width];
+        let mut members = vec![0_usize; count];
+        for _ in 0..iterations {
+            sums.fill(0.0);
+            members.fill(0);
+            for point in points {
+                let nearest = Self::nearest(&centroids, width, point);
+                members[nearest] += 1;
+                let sum = &mut sums[nearest * width..(nearest + 1) * width];
+                for (sum, &value) in sum.iter_mut().zip(point.iter()) {
+                    *sum += value as f64;
+                }
+            }
+            // Empty clusters keep their previous centroid.
+            for (cluster, &size) in members.iter().enumerate().filter(|(_, &size)| size > 0) {
+                for j in cluster * width..(cluster + 1) * width {
+                    centroids[j] = (sums[j] / size as f64) as f32;
+                }
+            }
+        }
+        centroids
+    }
+
+    fn nearest(centroids: &[f32], width: usize, point: &[f32]) -> usize {
+        centroids
+            .chunks_exact(width)
+            .map(|centroid| squared_distance(centroid, point))
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0, |(index, _)| index)
+    }
+
+    /// Number of dimensions of the encoded vectors.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Number of subspaces, which is also the length of codes in bytes.
+    pub fn subspaces(&self) -> usize {
+        self.subspaces
+    }
+
+    /// Number of centroids per subspace.
+    pub fn centroids(&self) -> usize {
+        self.centroids
+    }
+
+    fn width(&self) -> usize {
+        self.dimensions / self.subspaces
+    }
+
+    fn codebook(&self, subspace: usize) -> &[f32] {
+        let length = self.centroids * self.width();
+        &self.codebooks[subspace * length..(subspace + 1) * length]
+    }
+
+    fn check(&self, length: usize) {
+        assert_eq!(
+            length, self.dimensions,
+            "Vector length must match the quantizer dimensions"
+        );
+    }
+
+    /// Encodes a vector as the index of the nearest centroid in every subspace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector length differs from the quantizer dimensions.
+    pub fn encode(&self, vector: &[f32]) -> Vec<u8> {
+        self.check(vector.len());
+        let width = self.width();
+        vector
+            .chunks_exact(width)
+            .enumerate()
+            .map(|(subspace, part)| Self::nearest(self.codebook(subspace), width, part) as u8)
+            .collect()
+    }
+
+    /// Reconstructs the approximate vector of a code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the code length differs from the number of subspaces.
+    pub fn decode(&self, code: &[u8]) -> Vec<f32> {
+        assert_eq!(
+            code.len(),
+            self.subspaces,
+            "Code length must match the subspaces"
+        );
+        let width = self.width();
+        code.iter()
+            .enumerate()
+            .flat_map(|(subspace, &centroid)| {
+                let start = centroid as usize * width;
+                self.codebook(subspace)[start..start + width]
+                    .iter()
+                    .copied()
+            })
+            .collect()
+    }
+
+    /// Precomputes the products of a query with every centroid, to measure distances to codes.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` for metrics other than `L2sq`, `IP`, and `Cos`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query length differs from the quantizer dimensions.
+    pub fn distance_table(
+        &self,
+        query: &[f32],
+        metric: MetricKind,
+    ) -> Result<DistanceTable, IndexError> {
+        if !matches!(metric, MetricKind::L2sq | MetricKind::IP | MetricKind::Cos) {
+            return Err(IndexError::InvalidOptions {
+                reason: format!("Product quantization doesn't support the {metric:?} metric"),
+            });
+        }
+        self.check(query.len());
+        let width = self.width();
+        let mut products = Vec::with_capacity(self.subspaces * self.centroids);
+        let mut norms = Vec::with_capacity(self.subspaces * self.centroids);
+        for (subspace, part) in query.chunks_exact(width).enumerate() {
+            for centroid in self.codebook(subspace).chunks_exact(width) {
+                products.push(part.iter().zip(centroid).map(|(q, c)| q * c).sum());
+                norms.push(centroid.iter().map(|c| c * c).sum());
+            }
+        }
+        Ok(DistanceTable {
+            metric,
+            centroids: self.centroids,
+            query_norm: query.iter().map(|q| q * q).sum(),
+            products,
+            norms,
+        })
+    }
+
+    /// Serializes the codebooks into a writer, as little-endian floats after a header.
+    pub fn save_to_writer<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        for size in [self.dimensions, self.subspaces, self.centroids] {
+            writer.write_all(&(size as u64).to_le_bytes())?;
+        }
+        for value in &self.codebooks {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes codebooks from a reader, written by [`ProductQuantizer::save_to_writer`].
+    pub fn load_from_reader<R: Read>(mut reader: R) -> std::io::Result<Self> {
+        let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("Not a USearch product quantizer"));
+        }
+        let mut sizes = [0_usize; 3];
+        for size in &mut sizes {
+            let mut bytes = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            *size = u64::from_le_bytes(bytes) as usize;
+        }
+        let [dimensions, subspaces, centroids] = sizes;
+        if subspaces == 0
+            || !dimensions.is_multiple_of(subspaces)
+            || !(1..=256).contains(&centroids)
+        {
+            return Err(invalid("Corrupted product quantizer header"));
+        }
+        let length = dimensions.saturating_mul(centroids);
+        let mut codebooks = Vec::with_capacity(length.min(1 << 24));
+        for _ in 0..length {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+            codebooks.push(f32::from_le_bytes(bytes));
+        }
+        Ok(Self {
+            dimensions,
+            subspaces,
+            centroids,
+            codebooks,
+        })
+    }
+
+    /// Saves the codebooks to a specified file.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save_to_writer(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Loads codebooks from a specified file.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::load_from_reader(BufReader::new(File::open(path)?))
+    }
+}
+
+/// Products of one query with all centroids, measuring distances to codes in one lookup
+/// per subspace, as created by [`ProductQuantizer::distance_table`].
+#[derive(Debug, Clone)]
+pub struct DistanceTable {
+    metric: MetricKind,
+    centroids: usize,
+    query_norm: f32,
+    products: Vec<f32>,
+    norms: Vec<f32>,
+}
+
+impl DistanceTable {
+    /// The distance between the query and the vector reconstructed from a code.
+    pub fn distance(&self, code: &[u8]) -> Distance {
+        let (mut product, mut norm) = (0.0, 0.0);
+        for (subspace, &centroid) in code.iter().enumerate() {
+            let entry = subspace * self.centroids + centroid as usize;
+            product += self.products[entry];
+            norm += self.norms[entry];
+        }
+        match self.metric {
+            MetricKind::L2sq => (self.query_norm - 2.0 * product + norm).max(0.0),
+            MetricKind::IP => 1.0 - product,
+            _ if self.query_norm == 0.0 || norm == 0.0 => 1.0,
+            _ => 1.0 - product / (self.query_norm * norm).sqrt(),
+        }
+    }
+}
+
+/// An index proposing candidates from a graph over narrow scalars, re-ranked with
+/// product-quantized codes of the full vectors.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{IndexOptions, MetricKind, PqIndex, ProductQuantizer, ScalarKind};
+///
+/// let vectors: Vec<Vec<f32>> = (0..256)
+///     .map(|i| vec![(i % 16) as f32 / 16.0, (i / 16) as f32 / 16.0])
+///     .collect();
+/// let quantizer = ProductQuantizer::train(&vectors, 2, 16, 10).unwrap();
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::I8,
+///     ..Default::default()
+/// };
+/// let mut index = PqIndex::new(&options, quantizer).unwrap();
+/// index.reserve(vectors.len()).unwrap();
+/// for (key, vector) in vectors.iter().enumerate() {
+///     index.add(key as u64, vector).unwrap();
+/// }
+///
+/// let matches = index.search(&[0.25, 0.5], 1, 0).unwrap();
+/// assert_eq!(matches.keys, vec![8 * 16 + 4]);
+/// ```
+pub struct PqIndex {
+    index: Index,
+    metric: MetricKind,
+    quantizer: ProductQuantizer,
+    codes: HashMap<Key, Vec<u8>>,
+}
+
+impl PqIndex {
+    /// `I8` or `F16` quantization, and codes of the trained quantizer.
+    /// `I8` or `B1` quantization, and codes of the trained quantizer.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the quantizer was trained on vectors of other
+    /// dimensions, and `IndexError::InvalidOptions` for metrics other than `L2sq`, `IP`, and `Cos`.
+    pub fn new(options: &IndexOptions, quantizer: ProductQuantizer) -> Result<Self, IndexError> {
+        if quantizer.dimensions() != options.dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: options.dimensions,
+                got: quantizer.dimensions(),
+            });
+        }
+        quantizer.distance_table(&vec![0.0; options.dimensions], options.metric)?;
+        Ok(Self {
+            index: Index::new(options)?,
+            metric: options.metric,
+            quantizer,
+            codes: HashMap::new(),
+        })
+    }
+
+    /// The underlying graph index.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// The quantizer encoding the vectors.
+    pub fn quantizer(&self) -> &ProductQuantizer {
+        &self.quantizer
+    }
+
+    /// The code of the vector stored under a key.
+    pub fn code(&self, key: Key) -> Option<&[u8]> {
+        self.codes.get(&key).map(Vec::as_slice)
+    }
+
+    /// The approximate vector stored under a key, reconstructed from its code.
+    pub fn reconstruct(&self, key: Key) -> Option<Vec<f32>> {
+        Some(self.quantizer.decode(self.code(key)?))
+    }
+
+    /// Number of bytes taken by the codes, excluding the bookkeeping of their map.
+    pub fn codes_bytes(&self) -> usize {
+        self.codes.len() * self.quantizer.subspaces()
+    }
+
+    /// Reserves memory in the graph for the specified number of vectors.
+    pub fn reserve(&mut self, capacity: usize) -> Result<(), cxx::Exception> {
+        self.codes
+            .reserve(capacity.saturating_sub(self.codes.len()));
+        self.index.reserve(capacity)
+    }
+
+    /// Adds a vector to the graph, and its code to the store, replacing the previous code.
+    pub fn add(&mut self, key: Key, vector: &[f32]) -> Result<(), IndexError> {
+        if vector.len() != self.quantizer.dimensions() {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.quantizer.dimensions(),
+                got: vector.len(),
+            });
+        }
+        self.index.add(key, vector)?;
+        self.codes.insert(key, self.quantizer.encode(vector));
+        Ok(())
+    }
+
+    /// Removes the vectors and the code stored under a key.
+    pub fn remove(&mut self, key: Key) -> Result<usize, IndexError> {
+        self.codes.remove(&key);
+        Ok(self.index.remove(key)?)
+    }
+
+    /// Searches the graph for `pool` candidates and re-ranks them by their distance to the
+    /// query, measured on their codes. Zero stands for four times `count`.
+    pub fn search(&self, query: &[f32], count: usize, pool: usize) -> Result<Matches, IndexError> {
+        let table = self.quantizer.distance_table(query, self.metric)?;
+        let pool = match pool {
+            0 => count * 4,
+            pool => pool.max(count),
+        };
+        let candidates = self.index.search(query, pool)?;
+
+        let mut ranked: Vec<(Distance, Key)> = candidates
+            .keys
+            .iter()
+            .filter_map(|key| Some((table.distance(self.codes.get(key)?), *key)))
+            .collect();
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ranked.dedup_by_key(|&mut (_, key)| key);
+        ranked.truncate(count);
+        Ok(Matches {
+            keys: ranked.iter().map(|&(_, key)| key).collect(),
+            distances: ranked.iter().map(|&(distance, _)| distance).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::distance;
+    use crate::ScalarKind;
+
+    #[test]
+    fn test_product_quantization() {
+        // Pseudo-random vectors, reproducible without a random number generator.
+        let vectors: Vec<Vec<f32>> = (0..1000u64)
+            .map(|key| {
+                (0..8u64)
+                    .map(|i| ((key * 7919 + i * 104729 + key * i * 31) % 1000) as f32 / 1000.0)
+                    .collect()
+            })
+            .collect();
+        let quantizer = ProductQuantizer::train(&vectors, 4, 64, 8).unwrap();
+        assert_eq!((quantizer.subspaces(), quantizer.centroids()), (4, 64));
+
+        // Reconstructions are far closer to their vectors than vectors to each other.
+        let error: f32 = vectors
+            .iter()
+            .map(|v| squared_distance(v, &quantizer.decode(&quantizer.encode(v))))
+            .sum::<f32>()
+            / vectors.len() as f32;
+        let spread = squared_distance(&vectors[0], &vectors[1]);
+        assert!(error < spread / 4.0, "{error} vs {spread}");
+
+        let table = quantizer
+            .distance_table(&vectors[7], MetricKind::Cos)
+            .unwrap();
+        let code = quantizer.encode(&vectors[3]);
+        let exact = distance(MetricKind::Cos, &vectors[7], &quantizer.decode(&code));
+        assert!((table.distance(&code) - exact).abs() < 1e-5);
+        assert!(quantizer
+            .distance_table(&vectors[7], MetricKind::Hamming)
+            .is_err());
+
+        let mut buffer = Vec::new();
+        quantizer.save_to_writer(&mut buffer).unwrap();
+        assert_eq!(
+            ProductQuantizer::load_from_reader(&buffer[..]).unwrap(),
+            quantizer
+        );
+        assert!(ProductQuantizer::train(&vectors, 3, 64, 1).is_err());
+        assert!(ProductQuantizer::train(&vectors[..10], 4, 64, 1).is_err());
+
+        let options = IndexOptions {
+            dimensions: 8,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            ..Default::default()
+        };
+        let mut index = PqIndex::new(&options, quantizer).unwrap();
+        index.reserve(vectors.len()).unwrap();
+        for (key, vector) in vectors.iter().enumerate() {
+            index.add(key as Key, vector).unwrap();
+        }
+        assert_eq!(index.codes_bytes(), 4000);
+        let found = [3, 123, 456, 789]
+            .iter()
+            .filter(|&&key| {
+                let matches = index.search(&vectors[key], 1, 100).unwrap();
+                matches.keys == vec![key as Key]
+            })
+            .count();
+        assert!(found >= 3, "{found}");
+        assert_eq!(index.remove(3).unwrap(), 1);
+        assert!(index.reconstruct(3).is_none());
+        assert_eq!(index.reconstruct(4).unwrap().len(), 8);
+    }
+}
@@ -0,0 +1,156 @@
+//! Property-based tests of the invariants the FFI layer has to preserve for arbitrary inputs:
+//! vectors read back exactly as added in their own scalar kind, indexes serialized and loaded
+//! back unchanged, and renames and removals keeping the key counts consistent.
+
+use std::collections::BTreeMap;
+
+use proptest::collection::{btree_map, vec};
+use proptest::prelude::*;
+
+use crate::{b1x8, f16, Index, IndexOptions, Key, MetricKind, ScalarKind, VectorType};
+
+const DIMENSIONS: usize = 8;
+
+fn options(metric: MetricKind, quantization: ScalarKind, multi: bool) -> IndexOptions {
+    IndexOptions {
+        dimensions: DIMENSIONS,
+        metric,
+        quantization,
+        multi,
+        ..Default::default()
+    }
+}
+
+/// Adds every vector under its key, and reads them all back.
+fn round_trip<T>(index: &Index, vectors: &BTreeMap<Key, Vec<T>>) -> Vec<Vec<T>>
+where
+    T: VectorType + Default + Clone,
+{
+    for (&key, vector) in vectors {
+        index.add(key, vector).unwrap();
+    }
+    vectors
+        .iter()
+        .map(|(&key, vector)| {
+            // Buffers hold `dimensions` scalars, even for binary vectors packing 8 per byte.
+            let mut stored = vec![T::default(); index.dimensions()];
+            assert_eq!(index.get(key, &mut stored).unwrap(), 1);
+            stored.truncate(vector.len());
+            stored
+        })
+        .collect()
+}
+
+fn keyed<T: std::fmt::Debug>(
+    component: impl Strategy<Value = T>,
+    dimensions: usize,
+) -> impl Strategy<Value = BTreeMap<Key, Vec<T>>> {
+    btree_map(any::<Key>(), vec(component, dimensions), 1..32)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn add_get_f32(vectors in keyed(-1e6_f32..1e6, DIMENSIONS)) {
+        let index = Index::new(&options(MetricKind::L2sq, ScalarKind::F32, false)).unwrap();
+        let stored = round_trip(&index, &vectors);
+        prop_assert_eq!(stored, vectors.into_values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn add_get_f64(vectors in keyed(-1e6_f64..1e6, DIMENSIONS)) {
+        let index = Index::new(&options(MetricKind::L2sq, ScalarKind::F64, false)).unwrap();
+        let stored = round_trip(&index, &vectors);
+        prop_assert_eq!(stored, vectors.into_values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn add_get_f16(vectors in keyed(-2048_i16..2048, DIMENSIONS)) {
+        let index = Index::new(&options(MetricKind::L2sq, ScalarKind::F16, false)).unwrap();
+        let halves: BTreeMap<Key, Vec<f16>> = vectors
+            .iter()
+            .map(|(&key, vector)| (key, vector.iter().map(|&x| f16::from_f32(x as f32)).collect()))
+            .collect();
+        let stored = round_trip(&index, &halves);
+        for (stored, expected) in stored.iter().zip(halves.values()) {
+            prop_assert_eq!(f16::to_i16s(stored), f16::to_i16s(expected));
+        }
+    }
+
+    #[test]
+    fn add_get_i8(vectors in keyed(any::<i8>(), DIMENSIONS)) {
+        let index = Index::new(&options(MetricKind::L2sq, ScalarKind::I8, false)).unwrap();
+        let stored = round_trip(&index, &vectors);
+        prop_assert_eq!(stored, vectors.into_values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn add_get_b1(vectors in keyed(any::<u8>(), DIMENSIONS / 8)) {
+        let index = Index::new(&options(MetricKind::Hamming, ScalarKind::B1, false)).unwrap();
+        let bits: BTreeMap<Key, Vec<b1x8>> = vectors
+            .iter()
+            .map(|(&key, vector)| (key, b1x8::from_u8s(vector).to_vec()))
+            .collect();
+        let stored = round_trip(&index, &bits);
+        prop_assert_eq!(stored, bits.into_values().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn save_load(vectors in keyed(-1.0_f32..1.0, DIMENSIONS), query in vec(-1.0_f32..1.0, DIMENSIONS)) {
+        let options = options(MetricKind::Cos, ScalarKind::F32, false);
+        let index = Index::new(&options).unwrap();
+        let stored = round_trip(&index, &vectors);
+        let mut buffer = vec![0; index.serialized_length()];
+        index.save_to_buffer(&mut buffer).unwrap();
+
+        let mut loaded = Index::new(&options).unwrap();
+        loaded.load_from_buffer(&buffer).unwrap();
+        let mut viewed = Index::new(&options).unwrap();
+        viewed.view_from_buffer(&buffer).unwrap();
+        let expected = index.search(&query, 5).unwrap();
+        for copy in [&loaded, &viewed] {
+            prop_assert_eq!(copy.size(), index.size());
+            let mut keys = copy.keys();
+            keys.sort_unstable();
+            prop_assert_eq!(&keys, &vectors.keys().copied().collect::<Vec<_>>());
+            for (&key, vector) in vectors.keys().zip(&stored) {
+                let mut copied = vec![0.0_f32; DIMENSIONS];
+                copy.get(key, &mut copied).unwrap();
+                prop_assert_eq!(&copied, vector);
+            }
+            prop_assert_eq!(copy.search(&query, 5).unwrap().keys, expected.keys.clone());
+        }
+    }
+
+    #[test]
+    fn rename_remove(
+        vectors in vec((0..16_u64, vec(-1.0_f32..1.0, DIMENSIONS)), 1..48),
+        operations in vec((any::<bool>(), 0..16_u64, 0..16_u64), 1..16),
+    ) {
+        let index = Index::new(&options(MetricKind::L2sq, ScalarKind::F32, true)).unwrap();
+        let mut counts: BTreeMap<Key, usize> = BTreeMap::new();
+        for (key, vector) in &vectors {
+            index.add(*key, vector).unwrap();
+            *counts.entry(*key).or_default() += 1;
+        }
+
+        for (rename, from, to) in operations {
+            let expected = counts.remove(&from).unwrap_or(0);
+            if rename {
+                prop_assert_eq!(index.rename(from, to).unwrap(), expected);
+                if expected > 0 {
+                    *counts.entry(to).or_default() += expected;
+                }
+            } else {
+                prop_assert_eq!(index.remove(from).unwrap(), expected);
+            }
+            for key in 0..16 {
+                let count = counts.get(&key).copied().unwrap_or(0);
+                prop_assert_eq!(index.count(key), count);
+                prop_assert_eq!(index.contains(key), count > 0);
+            }
+            prop_assert_eq!(index.size(), counts.values().sum::<usize>());
+        }
+    }
+}
@@ -0,0 +1,806 @@
+//! A slower, dependency-free HNSW backend written in pure Rust.
+//!
+//! Enabled with the `pure-rust` feature, for audit-sensitive environments and targets where
+//! the native layer is hard to cross-compile. [`Index`] mirrors the method names and signatures
+//! of the native index. Built with `default-features = false, features = ["pure-rust"]`, the crate
+//! compiles no C++ and doesn't depend on `cxx`, and [`crate::Index`] is this backend, leaving out
+//! the modules built on the native layer.
+//!
+//! Differences from the native backend:
+//!
+//! - Vectors are always stored as `f32`, and `ScalarKind::B1` vectors as unpacked `0.0`/`1.0` values.
+//!   Other scalar kinds are converted on the way in and out, following [`crate::convert`].
+//! - Removed entries are tombstoned, and keep serving as routing nodes until [`Index::reset`].
+//! - The [`Index::save`] format is specific to this backend and can't be loaded by the native one.
+//! - There is no SIMD acceleration, and no custom metrics.
+//! - The level parameters, `auto_normalize`, and `lock_memory` of [`IndexOptions`] are ignored.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+#[cfg(not(no_fs))]
+use std::fs::File;
+#[cfg(not(no_fs))]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
+#[cfg(not(no_fs))]
+use std::path::Path;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+pub use crate::convert::Scalar;
+use crate::ffi::Matches;
+use crate::metrics::distance;
+use crate::{Distance, IndexError, IndexOptions, Key, MetricKind};
+
+const MAGIC: &[u8; 8] = b"USPURE01";
+const DEFAULT_CONNECTIVITY: usize = 16;
+const DEFAULT_EXPANSION_ADD: usize = 128;
+const DEFAULT_EXPANSION_SEARCH: usize = 64;
+const NO_ENTRY: u64 = u64::MAX;
+
+/// A search candidate, ordered by distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: Distance,
+    slot: u32,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.slot.cmp(&other.slot))
+    }
+}
+
+struct Node {
+    key: Key,
+    deleted: bool,
+    /// Neighbor slots on every level, from the base level up.
+    neighbors: Vec<Vec<u32>>,
+}
+
+struct Graph {
+    dimensions: usize,
+    metric: MetricKind,
+    connectivity: usize,
+    expansion_add: usize,
+    expansion_search: usize,
+    multi: bool,
+    vectors: Vec<f32>,
+    nodes: Vec<Node>,
+    slots: HashMap<Key, Vec<u32>>,
+    entry: Option<u32>,
+    max_level: usize,
+    size: usize,
+    reserved: usize,
+    random_state: u64,
+}
+
+impl Graph {
+    fn new(options: &IndexOptions) -> Self {
+        let or_default = |value: usize, default: usize| if value == 0 { default } else { value };
+        Self {
+            dimensions: options.dimensions,
+            metric: options.metric,
+            connectivity: or_default(options.connectivity, DEFAULT_CONNECTIVITY),
+            expansion_add: or_default(options.expansion_add, DEFAULT_EXPANSION_ADD),
+            expansion_search: or_default(options.expansion_search, DEFAULT_EXPANSION_SEARCH),
+            multi: options.multi,
+            vectors: Vec::new(),
+            nodes: Vec::new(),
+            slots: HashMap::new(),
+            entry: None,
+            max_level: 0,
+            size: 0,
+            reserved: 0,
+            random_state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    fn vector(&self, slot: u32) -> &[f32] {
+        let start = slot as usize * self.dimensions;
+        &self.vectors[start..start + self.dimensions]
+    }
+
+    fn distance_to(&self, query: &[f32], slot: u32) -> Distance {
+        distance(self.metric, query, self.vector(slot))
+    }
+
+    fn level_limit(&self, level: usize) -> usize {
+        if level == 0 {
+            self.connectivity * 2
+        } else {
+            self.connectivity
+        }
+    }
+
+    /// Draws a level from the exponential distribution, using a xorshift generator.
+    fn random_level(&mut self) -> usize {
+        let mut x = self.random_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.random_state = x;
+        let uniform = ((x >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let multiplier = 1.0 / (self.connectivity as f64).ln();
+        (-uniform.ln() * multiplier) as usize
+    }
+
+    /// Best-first search within one level, returning up to `expansion` closest candidates in ascending order.
+    fn search_level(
+        &self,
+        query: &[f32],
+        entry_points: &[Candidate],
+        expansion: usize,
+        level: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<u32> = entry_points.iter().map(|c| c.slot).collect();
+        let mut frontier: BinaryHeap<std::cmp::Reverse<Candidate>> = entry_points
+            .iter()
+            .copied()
+            .map(std::cmp::Reverse)
+            .collect();
+        let mut results: BinaryHeap<Candidate> = entry_points.iter().copied().collect();
+
+        while let Some(std::cmp::Reverse(closest)) = frontier.pop() {
+            let furthest = results.peek().map_or(Distance::INFINITY, |c| c.distance);
+            if closest.distance > furthest && results.len() >= expansion {
+                break;
+            }
+            let neighbors = &self.nodes[closest.slot as usize].neighbors;
+            let Some(neighbors) = neighbors.get(level) else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate {
+                    distance: self.distance_to(query, neighbor),
+                    slot: neighbor,
+                };
+                let furthest = results.peek().map_or(Distance::INFINITY, |c| c.distance);
+                if results.len() < expansion || candidate.distance < furthest {
+                    frontier.push(std::cmp::Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > expansion {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        results.into_sorted_vec()
+    }
+
+    /// Greedily descends from the top level down to `level`, exclusive.
+    fn descend(&self, query: &[f32], level: usize) -> Vec<Candidate> {
+        let entry = self.entry.expect("Descending requires a non-empty graph");
+        let mut closest = vec![Candidate {
+            distance: self.distance_to(query, entry),
+            slot: entry,
+        }];
+        for current in (level + 1..=self.max_level).rev() {
+            closest = self.search_level(query, &closest, 1, current);
+        }
+        closest
+    }
+
+    fn insert(&mut self, key: Key, vector: Vec<f32>) {
+        let slot = self.nodes.len() as u32;
+        let level = self.random_level();
+        self.vectors.extend_from_slice(&vector);
+        self.nodes.push(Node {
+            key,
+            deleted: false,
+            neighbors: vec![Vec::new(); level + 1],
+        });
+        self.slots.entry(key).or_default().push(slot);
+        self.size += 1;
+
+        if self.entry.is_none() {
+            self.entry = Some(slot);
+            self.max_level = level;
+            return;
+        }
+
+        let mut closest = self.descend(&vector, level);
+        for current in (0..=level.min(self.max_level)).rev() {
+            let found = self.search_level(&vector, &closest, self.expansion_add, current);
+            let limit = self.level_limit(current);
+            let neighbors: Vec<u32> = found.iter().take(limit).map(|c| c.slot).collect();
+            for &neighbor in &neighbors {
+                self.connect(neighbor, slot, current);
+            }
+            self.nodes[slot as usize].neighbors[current] = neighbors;
+            closest = found;
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry = Some(slot);
+        }
+    }
+
+    /// Adds a back-link, pruning the furthest neighbors if the list overflows.
+    fn connect(&mut self, from: u32, to: u32, level: usize) {
+        let limit = self.level_limit(level);
+        let mut neighbors = std::mem::take(&mut self.nodes[from as usize].neighbors[level]);
+        neighbors.push(to);
+        if neighbors.len() > limit {
+            let origin = self.vector(from).to_vec();
+            let mut ranked: Vec<Candidate> = neighbors
+                .iter()
+                .map(|&slot| Candidate {
+                    distance: self.distance_to(&origin, slot),
+                    slot,
+                })
+                .collect();
+            ranked.sort();
+            neighbors = ranked.into_iter().take(limit).map(|c| c.slot).collect();
+        }
+        self.nodes[from as usize].neighbors[level] = neighbors;
+    }
+
+    fn search<F: Fn(Key) -> bool>(&self, query: &[f32], count: usize, filter: F) -> Matches {
+        let mut matches = Matches {
+            keys: Vec::with_capacity(count),
+            distances: Vec::with_capacity(count),
+        };
+        if self.entry.is_none() || count == 0 {
+            return matches;
+        }
+
+        let closest = self.descend(query, 0);
+        let mut expansion = self.expansion_search.max(count);
+        loop {
+            let found = self.search_level(query, &closest, expansion, 0);
+            matches.keys.clear();
+            matches.distances.clear();
+            for candidate in &found {
+                let node = &self.nodes[candidate.slot as usize];
+                if node.deleted || !filter(node.key) {
+                    continue;
+                }
+                matches.keys.push(node.key);
+                matches.distances.push(candidate.distance);
+                if matches.keys.len() == count {
+                    return matches;
+                }
+            }
+            // Filters and tombstones may have rejected too many candidates, so widen the search.
+            if expansion >= self.nodes.len() {
+                return matches;
+            }
+            expansion *= 2;
+        }
+    }
+
+    fn save_to_writer<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let u64s = |writer: &mut W, values: &[u64]| -> std::io::Result<()> {
+            values
+                .iter()
+                .try_for_each(|value| writer.write_all(&value.to_le_bytes()))
+        };
+        writer.write_all(MAGIC)?;
+        u64s(
+            &mut writer,
+            &[
+                self.dimensions as u64,
+                self.metric.repr as u64,
+                self.connectivity as u64,
+                self.expansion_add as u64,
+                self.expansion_search as u64,
+                self.multi as u64,
+                self.nodes.len() as u64,
+                self.entry.map_or(NO_ENTRY, u64::from),
+                self.max_level as u64,
+                self.random_state,
+            ],
+        )?;
+        for node in &self.nodes {
+            u64s(
+                &mut writer,
+                &[node.key, node.deleted as u64, node.neighbors.len() as u64],
+            )?;
+            for neighbors in &node.neighbors {
+                writer.write_all(&(neighbors.len() as u32).to_le_bytes())?;
+                for neighbor in neighbors {
+                    writer.write_all(&neighbor.to_le_bytes())?;
+                }
+            }
+        }
+        for value in &self.vectors {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+
+    fn load_from_reader<R: Read>(
+        options: &IndexOptions,
+        mut reader: R,
+    ) -> Result<Self, IndexError> {
+        fn read_u64<R: Read>(reader: &mut R) -> std::io::Result<u64> {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        }
+        fn read_u32<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+            let mut bytes = [0u8; 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes))
+        }
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(IndexError::IncompatibleFormat {
+                found: String::from_utf8_lossy(&magic).into_owned(),
+                expected: String::from_utf8_lossy(MAGIC).into_owned(),
+            });
+        }
+
+        let mut graph = Graph::new(options);
+        graph.dimensions = read_u64(&mut reader)? as usize;
+        graph.metric = MetricKind {
+            repr: read_u64(&mut reader)? as i32,
+        };
+        graph.connectivity = read_u64(&mut reader)? as usize;
+        graph.expansion_add = read_u64(&mut reader)? as usize;
+        graph.expansion_search = read_u64(&mut reader)? as usize;
+        graph.multi = read_u64(&mut reader)? != 0;
+        let count = read_u64(&mut reader)? as usize;
+        graph.entry = match read_u64(&mut reader)? {
+            NO_ENTRY => None,
+            entry => Some(entry as u32),
+        };
+        graph.max_level = read_u64(&mut reader)? as usize;
+        graph.random_state = read_u64(&mut reader)?;
+
+        for slot in 0..count {
+            let key = read_u64(&mut reader)?;
+            let deleted = read_u64(&mut reader)? != 0;
+            let levels = read_u64(&mut reader)? as usize;
+            let mut neighbors = Vec::with_capacity(levels);
+            for _ in 0..levels {
+                let length = read_u32(&mut reader)? as usize;
+                let level = (0..length)
+                    .map(|_| read_u32(&mut reader))
+                    .collect::<std::io::Result<Vec<u32>>>()?;
+                neighbors.push(level);
+            }
+            if !deleted {
+                graph.slots.entry(key).or_default().push(slot as u32);
+                graph.size += 1;
+            }
+            graph.nodes.push(Node {
+                key,
+                deleted,
+                neighbors,
+            });
+        }
+
+        graph.vectors.reserve(count * graph.dimensions);
+        let mut bytes = [0u8; 4];
+        for _ in 0..count * graph.dimensions {
+            reader.read_exact(&mut bytes)?;
+            graph.vectors.push(f32::from_le_bytes(bytes));
+        }
+        graph.reserved = count;
+        Ok(graph)
+    }
+}
+
+/// A pure-Rust approximate nearest neighbors index, mirroring the API of the native [`crate::Index`].
+///
+/// # Examples
+///
+/// ```
+/// use usearch::pure::Index;
+/// use usearch::{IndexOptions, MetricKind};
+///
+/// let options = IndexOptions { dimensions: 2, metric: MetricKind::L2sq, ..Default::default() };
+/// let index = Index::new(&options).unwrap();
+/// index.reserve(10).unwrap();
+/// index.add(1, &[1.0_f32, 0.0]).unwrap();
+/// index.add(2, &[0.0_f32, 1.0]).unwrap();
+///
+/// let results = index.search(&[0.9_f32, 0.1], 1).unwrap();
+/// assert_eq!(results.keys, vec![1]);
+/// ```
+pub struct Index {
+    options: IndexOptions,
+    graph: RwLock<Graph>,
+}
+
+impl Index {
+    /// Creates a new index, after validating the options with `IndexOptions::validate`.
+    pub fn new(options: &IndexOptions) -> Result<Self, IndexError> {
+        options.validate()?;
+        Ok(Self {
+            options: options.clone(),
+            graph: RwLock::new(Graph::new(options)),
+        })
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Graph> {
+        self.graph
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Graph> {
+        self.graph
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn decode<T: Scalar>(&self, vector: &[T]) -> Result<Vec<f32>, IndexError> {
+        let dimensions = self.dimensions();
+        let expected = T::encoded_length(dimensions);
+        if vector.len() != expected {
+            return Err(IndexError::DimensionMismatch {
+                expected,
+                got: vector.len(),
+            });
+        }
+        let mut decoded = Vec::with_capacity(dimensions);
+        T::decode(vector, dimensions, &mut decoded);
+        Ok(decoded)
+    }
+
+    /// Retrieves the expansion value used during index creation.
+    pub fn expansion_add(&self) -> usize {
+        self.read().expansion_add
+    }
+
+    /// Retrieves the expansion value used during search.
+    pub fn expansion_search(&self) -> usize {
+        self.read().expansion_search
+    }
+
+    /// Updates the expansion value used during index creation.
+    pub fn change_expansion_add(&self, n: usize) {
+        self.write().expansion_add = n.max(1);
+    }
+
+    /// Updates the expansion value used during search operations.
+    pub fn change_expansion_search(&self, n: usize) {
+        self.write().expansion_search = n.max(1);
+    }
+
+    /// Retrieves the number of dimensions in the vectors indexed.
+    pub fn dimensions(&self) -> usize {
+        self.read().dimensions
+    }
+
+    /// Retrieves the connectivity parameter that limits connections-per-node in graph.
+    pub fn connectivity(&self) -> usize {
+        self.read().connectivity
+    }
+
+    /// Retrieves the current number of vectors in the index.
+    pub fn size(&self) -> usize {
+        self.read().size
+    }
+
+    /// Retrieves the total capacity of the index, including reserved space.
+    pub fn capacity(&self) -> usize {
+        let graph = self.read();
+        graph.reserved.max(graph.nodes.len())
+    }
+
+    /// Reports the SIMD capabilities used by the index, which is always `"serial"` here.
+    pub fn hardware_acceleration(&self) -> String {
+        "serial".to_owned()
+    }
+
+    /// Reports the memory usage of the index in bytes.
+    pub fn memory_usage(&self) -> usize {
+        let graph = self.read();
+        let links: usize = graph
+            .nodes
+            .iter()
+            .flat_map(|node| node.neighbors.iter())
+            .map(|level| level.capacity() * std::mem::size_of::<u32>())
+            .sum();
+        graph.vectors.capacity() * std::mem::size_of::<f32>()
+            + graph.nodes.capacity() * std::mem::size_of::<Node>()
+            + links
+    }
+
+    /// Reserves memory for a specified number of incoming vectors.
+    pub fn reserve(&self, capacity: usize) -> Result<(), IndexError> {
+        let mut graph = self.write();
+        let additional = capacity.saturating_sub(graph.nodes.len());
+        let dimensions = graph.dimensions;
+        graph.vectors.reserve(additional * dimensions);
+        graph.nodes.reserve(additional);
+        graph.reserved = graph.reserved.max(capacity);
+        Ok(())
+    }
+
+    /// Adds a vector with a specified key to the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the vector.
+    /// * `vector` - A slice containing the vector data.
+    pub fn add<T: Scalar>(&self, key: Key, vector: &[T]) -> Result<(), IndexError> {
+        let decoded = self.decode(vector)?;
+        let mut graph = self.write();
+        if !graph.multi && graph.slots.contains_key(&key) {
+            return Err(IndexError::DuplicateKey { key });
+        }
+        graph.insert(key, decoded);
+        Ok(())
+    }
+
+    /// Performs k-Approximate Nearest Neighbors (kANN) Search for closest vectors to the provided query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to search for.
+    pub fn search<T: Scalar>(&self, query: &[T], count: usize) -> Result<Matches, IndexError> {
+        self.filtered_search(query, count, |_| true)
+    }
+
+    /// Performs k-Approximate Nearest Neighbors Search, skipping keys rejected by the predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to search for.
+    /// * `filter` - A closure that takes a `Key` and returns `true` if the corresponding vector should be included.
+    pub fn filtered_search<T: Scalar, F>(
+        &self,
+        query: &[T],
+        count: usize,
+        filter: F,
+    ) -> Result<Matches, IndexError>
+    where
+        F: Fn(Key) -> bool,
+    {
+        let decoded = self.decode(query)?;
+        Ok(self.read().search(&decoded, count, filter))
+    }
+
+    /// Retrieves the vectors stored under a key, filling the buffer with as many as fit.
+    /// Returns the number of retrieved vectors.
+    pub fn get<T: Scalar>(&self, key: Key, buffer: &mut [T]) -> Result<usize, IndexError> {
+        let graph = self.read();
+        let stride = T::encoded_length(graph.dimensions);
+        if !buffer.len().is_multiple_of(stride) {
+            return Err(IndexError::DimensionMismatch {
+                expected: stride,
+                got: buffer.len(),
+            });
+        }
+        let Some(slots) = graph.slots.get(&key) else {
+            return Ok(0);
+        };
+        let mut count = 0;
+        for (output, &slot) in buffer.chunks_exact_mut(stride).zip(slots) {
+            T::encode(graph.vector(slot), output);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Removes all vectors associated with a key. Returns the number of removed vectors.
+    pub fn remove(&self, key: Key) -> Result<usize, IndexError> {
+        let mut graph = self.write();
+        let Some(slots) = graph.slots.remove(&key) else {
+            return Ok(0);
+        };
+        for &slot in &slots {
+            graph.nodes[slot as usize].deleted = true;
+        }
+        graph.size -= slots.len();
+        Ok(slots.len())
+    }
+
+    /// Renames the vectors under one key to another. Returns the number of renamed vectors.
+    pub fn rename(&self, from: Key, to: Key) -> Result<usize, IndexError> {
+        let mut graph = self.write();
+        if from == to {
+            return Ok(graph.slots.get(&from).map_or(0, Vec::len));
+        }
+        if !graph.multi && graph.slots.contains_key(&to) {
+            return Err(IndexError::DuplicateKey { key: to });
+        }
+        let Some(slots) = graph.slots.remove(&from) else {
+            return Ok(0);
+        };
+        for &slot in &slots {
+            graph.nodes[slot as usize].key = to;
+        }
+        let renamed = slots.len();
+        graph.slots.entry(to).or_default().extend(slots);
+        Ok(renamed)
+    }
+
+    /// Checks if the index contains a vector with a specified key.
+    pub fn contains(&self, key: Key) -> bool {
+        self.read().slots.contains_key(&key)
+    }
+
+    /// Counts the number of vectors associated with a key.
+    pub fn count(&self, key: Key) -> usize {
+        self.read().slots.get(&key).map_or(0, Vec::len)
+    }
+
+    /// Saves the index to a specified file, in a format specific to this backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where the index will be saved.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), IndexError> {
+        let writer = BufWriter::new(File::create(path)?);
+        Ok(self.read().save_to_writer(writer)?)
+    }
+
+    /// Loads the index from a file produced by [`Index::save`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path from where the index will be loaded.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<(), IndexError> {
+        let reader = BufReader::new(File::open(path)?);
+        let graph = Graph::load_from_reader(&self.options, reader)?;
+        *self.write() = graph;
+        Ok(())
+    }
+
+    /// Computes the number of bytes [`Index::save_to_buffer`] needs.
+    pub fn serialized_length(&self) -> usize {
+        let graph = self.read();
+        let links: usize = graph
+            .nodes
+            .iter()
+            .map(|node| {
+                24 + node
+                    .neighbors
+                    .iter()
+                    .map(|level| 4 + level.len() * 4)
+                    .sum::<usize>()
+            })
+            .sum();
+        MAGIC.len() + 10 * 8 + links + graph.vectors.len() * 4
+    }
+
+    /// Saves the index to a buffer of at least [`Index::serialized_length`] bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A mutable slice of bytes where the index will be saved.
+    pub fn save_to_buffer(&self, buffer: &mut [u8]) -> Result<(), IndexError> {
+        Ok(self.read().save_to_writer(buffer)?)
+    }
+
+    /// Loads the index from a buffer produced by [`Index::save_to_buffer`].
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - A slice of bytes containing the serialized index.
+    pub fn load_from_buffer(&mut self, buffer: &[u8]) -> Result<(), IndexError> {
+        let graph = Graph::load_from_reader(&self.options, buffer)?;
+        *self.write() = graph;
+        Ok(())
+    }
+
+    /// Erases all members from the index, including tombstones, and releases the memory.
+    pub fn reset(&mut self) -> Result<(), IndexError> {
+        let mut graph = self.write();
+        let mut fresh = Graph::new(&self.options);
+        fresh.expansion_add = graph.expansion_add;
+        fresh.expansion_search = graph.expansion_search;
+        *graph = fresh;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{b1x8, ScalarKind};
+
+    #[test]
+    fn test_recall_and_persistence() {
+        let options = IndexOptions {
+            dimensions: 8,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 8,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(500).unwrap();
+        let vector = |key: u64| -> Vec<f32> {
+            let mut state = key.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+            (0..8)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state % 1000) as f32 / 1000.0
+                })
+                .collect()
+        };
+        for key in 0..500 {
+            index.add(key, &vector(key)).unwrap();
+        }
+        assert_eq!(index.size(), 500);
+        assert!(matches!(
+            index.add(0, &vector(0)),
+            Err(IndexError::DuplicateKey { key: 0 })
+        ));
+        assert!(matches!(
+            index.add(1000, &[0.0_f32; 3]),
+            Err(IndexError::DimensionMismatch {
+                expected: 8,
+                got: 3
+            })
+        ));
+
+        let found = (0..500)
+            .filter(|&key| index.search(&vector(key), 1).unwrap().keys == vec![key])
+            .count();
+        assert!(found >= 495, "Recall too low: {}", found);
+
+        assert_eq!(index.remove(7).unwrap(), 1);
+        assert_eq!(index.rename(8, 7).unwrap(), 1);
+        let results = index
+            .filtered_search(&vector(8), 3, |key| key % 2 == 1)
+            .unwrap();
+        assert_eq!(results.keys[0], 7);
+        assert!(results.keys.iter().all(|key| key % 2 == 1));
+
+        let mut buffer = vec![0u8; index.serialized_length()];
+        index.save_to_buffer(&mut buffer).unwrap();
+        let mut restored = Index::new(&options).unwrap();
+        restored.load_from_buffer(&buffer).unwrap();
+        assert_eq!(restored.size(), 499);
+
+        #[cfg(not(no_fs))]
+        {
+            let path = std::env::temp_dir().join("usearch.rust.pure.usearch");
+            index.save(&path).unwrap();
+            restored.reset().unwrap();
+            restored.load(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(restored.size(), 499);
+        }
+        assert_eq!(restored.search(&vector(8), 1).unwrap().keys, vec![7]);
+
+        let mut buffer = [0.0_f32; 8];
+        assert_eq!(restored.get(7, &mut buffer).unwrap(), 1);
+        assert_eq!(buffer.to_vec(), vector(8));
+    }
+
+    #[test]
+    fn test_binary_vectors() {
+        let index = Index::new(&IndexOptions {
+            dimensions: 8,
+            metric: MetricKind::Hamming,
+            quantization: ScalarKind::B1,
+            ..Default::default()
+        })
+        .unwrap();
+        index.add(42, &[b1x8(0b0000_1111)]).unwrap();
+        index.add(43, &[b1x8(0b1111_0000)]).unwrap();
+        let results = index.search(&[b1x8(0b0111_1000)], 2).unwrap();
+        assert_eq!(results.keys, vec![43, 42]);
+        assert_eq!(results.distances, vec![2.0, 6.0]);
+    }
+}
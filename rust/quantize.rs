@@ -0,0 +1,400 @@
+//! Calibrated scalar quantization into `i8` and `f16`.
+//!
+//! The native `i8` cast expects every value in `[-1, 1]`, which few embeddings respect: values
+//! outside are clamped, and narrow dimensions use a handful of levels. A [`Calibration`] learns
+//! the range of every dimension from a sample, by extremes or percentiles, and maps it onto the
+//! full `i8` range. Saved next to the index, it quantizes later queries exactly like the stored
+//! vectors, through [`Index::add_quantized`] and [`Index::search_quantized`].
+
+#[cfg(not(no_fs))]
+use std::fs::File;
+#[cfg(not(no_fs))]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::convert::{dequantize_slice, quantize_slice, Quantizer};
+use crate::ffi::Matches;
+use crate::{f16, Index, IndexError, Key};
+
+const MAGIC: &[u8; 8] = b"USCALB01";
+
+/// The largest magnitude of calibrated `i8` values, keeping the range symmetric.
+const I8_LEVELS: f32 = 127.0;
+
+/// Per-dimension value ranges, mapped onto `[-127, 127]` when quantizing into `i8`,
+/// and onto `[-1, 1]` when quantizing into `f16`.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::quantize::Calibration;
+///
+/// let sample = [[0.0_f32, -10.0], [1.0, 10.0], [0.5, 0.0]];
+/// let calibration = Calibration::min_max(&sample).unwrap();
+/// assert_eq!(calibration.quantize(&[0.5, 10.0]), vec![0, 127]);
+/// assert_eq!(calibration.quantize(&[2.0, -20.0]), vec![127, -127]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    lower: Vec<f32>,
+    upper: Vec<f32>,
+}
+
+impl Calibration {
+    /// Creates a calibration from explicit ranges, one per dimension.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the bounds have different lengths, and
+    /// `IndexError::InvalidOptions` if a lower bound exceeds its upper bound.
+    pub fn from_bounds(lower: Vec<f32>, upper: Vec<f32>) -> Result<Self, IndexError> {
+        if lower.len() != upper.len() {
+            return Err(IndexError::DimensionMismatch {
+                expected: lower.len(),
+                got: upper.len(),
+            });
+        }
+        if let Some(dimension) = (0..lower.len())
+            .find(|&i| lower[i] > upper[i] || lower[i].is_nan() || upper[i].is_nan())
+        {
+            return Err(IndexError::InvalidOptions {
+                reason: format!(
+                    "Dimension {dimension} has an empty range, from {} to {}",
+                    lower[dimension], upper[dimension]
+                ),
+            });
+        }
+        Ok(Self { lower, upper })
+    }
+
+    /// Fits the ranges to the smallest and largest value of every dimension in the sample.
+    pub fn min_max<V: AsRef<[f32]>>(sample: &[V]) -> Result<Self, IndexError> {
+        Self::percentile(sample, 0.0)
+    }
+
+    /// Fits the ranges between the `fraction` and `1 - fraction` quantiles of every dimension,
+    /// so that rare outliers are clamped rather than stretching the range of all other values.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if the sample is empty or `fraction` is outside `[0, 0.5)`,
+    /// and `IndexError::DimensionMismatch` if the sample vectors have different lengths.
+    pub fn percentile<V: AsRef<[f32]>>(sample: &[V], fraction: f64) -> Result<Self, IndexError> {
+        let invalid = |reason: String| Err(IndexError::InvalidOptions { reason });
+        if !(0.0..0.5).contains(&fraction) {
+            return invalid(format!(
+                "Quantile fractions must be in [0, 0.5), got {fraction}"
+            ));
+        }
+        let Some(first) = sample.first() else {
+            return invalid("Calibration requires at least one sample vector".to_owned());
+        };
+        let dimensions = first.as_ref().len();
+        if let Some(vector) = sample.iter().find(|v| v.as_ref().len() != dimensions) {
+            return Err(IndexError::DimensionMismatch {
+                expected: dimensions,
+                got: vector.as_ref().len(),
+            });
+        }
+
+        let last = sample.len() - 1;
+        let low_rank = (fraction * last as f64).round() as usize;
+        let high_rank = last - low_rank;
+        let mut values = Vec::with_capacity(sample.len());
+        let (mut lower, mut upper) = (Vec::with_capacity(dimensions), Vec::new());
+        for dimension in 0..dimensions {
+            values.clear();
+            values.extend(sample.iter().map(|v| v.as_ref()[dimension]));
+            values.sort_unstable_by(f32::total_cmp);
+            lower.push(values[low_rank]);
+            upper.push(values[high_rank]);
+        }
+        Self::from_bounds(lower, upper)
+    }
+
+    /// Widens every range to be symmetric around zero, so that quantization preserves the
+    /// direction of vectors, as needed by the `Cos` and `IP` metrics.
+    pub fn symmetric(mut self) -> Self {
+        for (lower, upper) in self.lower.iter_mut().zip(&mut self.upper) {
+            let magnitude = lower.abs().max(upper.abs());
+            (*lower, *upper) = (-magnitude, magnitude);
+        }
+        self
+    }
+
+    /// Number of dimensions of the calibrated vectors.
+    pub fn dimensions(&self) -> usize {
+        self.lower.len()
+    }
+
+    /// The lower bound of every dimension.
+    pub fn lower(&self) -> &[f32] {
+        &self.lower
+    }
+
+    /// The upper bound of every dimension.
+    pub fn upper(&self) -> &[f32] {
+        &self.upper
+    }
+
+    /// Maps a value into `[-1, 1]`, clamping it to the range of its dimension.
+    fn normalize(&self, value: f32, dimension: usize) -> f32 {
+        let (lower, upper) = (self.lower[dimension], self.upper[dimension]);
+        if upper == lower {
+            return 0.0;
+        }
+        ((value - lower) / (upper - lower) * 2.0 - 1.0).clamp(-1.0, 1.0)
+    }
+
+    /// Maps a value of `[-1, 1]` back into the range of its dimension.
+    fn denormalize(&self, value: f32, dimension: usize) -> f32 {
+        let (lower, upper) = (self.lower[dimension], self.upper[dimension]);
+        lower + (value + 1.0) / 2.0 * (upper - lower)
+    }
+
+    /// Quantizes a vector into `i8` scalars.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector length differs from the calibrated dimensions.
+    pub fn quantize(&self, vector: &[f32]) -> Vec<i8> {
+        let mut output = vec![0; vector.len()];
+        self.check(vector.len());
+        quantize_slice(vector, self, &mut output);
+        output
+    }
+
+    /// Approximately reconstructs a vector from its `i8` quantized form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector length differs from the calibrated dimensions.
+    pub fn dequantize(&self, vector: &[i8]) -> Vec<f32> {
+        let mut output = vec![0.0; vector.len()];
+        self.check(vector.len());
+        dequantize_slice(vector, self, &mut output);
+        output
+    }
+
+    /// Normalizes a vector into `[-1, 1]` and converts it into `f16` scalars, which keeps
+    /// values with large magnitudes finite and the precision of narrow dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector length differs from the calibrated dimensions.
+    pub fn quantize_f16(&self, vector: &[f32]) -> Vec<f16> {
+        self.check(vector.len());
+        vector
+            .iter()
+            .enumerate()
+            .map(|(dimension, &value)| f16::from_f32(self.normalize(value, dimension)))
+            .collect()
+    }
+
+    /// Approximately reconstructs a vector from its `f16` quantized form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector length differs from the calibrated dimensions.
+    pub fn dequantize_f16(&self, vector: &[f16]) -> Vec<f32> {
+        self.check(vector.len());
+        vector
+            .iter()
+            .enumerate()
+            .map(|(dimension, value)| self.denormalize(value.to_f32(), dimension))
+            .collect()
+    }
+
+    fn check(&self, length: usize) {
+        assert_eq!(
+            length,
+            self.dimensions(),
+            "Vector length must match the calibrated dimensions"
+        );
+    }
+
+    /// Serializes the calibration into a writer, as little-endian bounds after a header.
+    pub fn save_to_writer<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(self.dimensions() as u64).to_le_bytes())?;
+        for bound in self.lower.iter().chain(&self.upper) {
+            writer.write_all(&bound.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes a calibration from a reader, written by [`Calibration::save_to_writer`].
+    pub fn load_from_reader<R: Read>(mut reader: R) -> std::io::Result<Self> {
+        let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("Not a USearch calibration"));
+        }
+        let mut length = [0; 8];
+        reader.read_exact(&mut length)?;
+        let dimensions = u64::from_le_bytes(length) as usize;
+        let mut bounds = Vec::with_capacity(dimensions.min(1 << 20) * 2);
+        for _ in 0..dimensions.saturating_mul(2) {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+            bounds.push(f32::from_le_bytes(bytes));
+        }
+        let upper = bounds.split_off(dimensions);
+        Self::from_bounds(bounds, upper).map_err(|_| invalid("Corrupted calibration bounds"))
+    }
+
+    /// Saves the calibration to a specified file.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save_to_writer(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Loads a calibration from a specified file.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::load_from_reader(BufReader::new(File::open(path)?))
+    }
+}
+
+impl Quantizer for Calibration {
+    #[inline]
+    fn quantize(&self, value: f32, dimension: usize) -> i8 {
+        (self.normalize(value, dimension) * I8_LEVELS).round() as i8
+    }
+
+    #[inline]
+    fn dequantize(&self, value: i8, dimension: usize) -> f32 {
+        self.denormalize(value as f32 / I8_LEVELS, dimension)
+    }
+}
+
+impl Index {
+    /// Quantizes a vector into `i8` scalars with the quantizer and adds it under the key.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the vector length differs from the index dimensions.
+    pub fn add_quantized<Q: Quantizer + ?Sized>(
+        &self,
+        key: Key,
+        vector: &[f32],
+        quantizer: &Q,
+    ) -> Result<(), IndexError> {
+        let quantized = self.quantize_with(vector, quantizer)?;
+        self.add(key, &quantized)
+    }
+
+    /// Quantizes a query into `i8` scalars with the quantizer used for insertions, and searches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::quantize::Calibration;
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::I8,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.reserve(3).unwrap();
+    ///
+    /// // Values far outside of the `[-1, 1]` range expected by the native cast.
+    /// let vectors = [[120.0_f32, 3.0], [180.0, 3.0], [150.0, 9.0]];
+    /// let calibration = Calibration::min_max(&vectors).unwrap();
+    /// for (key, vector) in vectors.iter().enumerate() {
+    ///     index.add_quantized(key as u64, vector, &calibration).unwrap();
+    /// }
+    ///
+    /// let matches = index.search_quantized(&[175.0, 3.5], 1, &calibration).unwrap();
+    /// assert_eq!(matches.keys, vec![1]);
+    /// ```
+    pub fn search_quantized<Q: Quantizer + ?Sized>(
+        &self,
+        query: &[f32],
+        count: usize,
+        quantizer: &Q,
+    ) -> Result<Matches, IndexError> {
+        let quantized = self.quantize_with(query, quantizer)?;
+        self.search(&quantized, count)
+    }
+
+    fn quantize_with<Q: Quantizer + ?Sized>(
+        &self,
+        vector: &[f32],
+        quantizer: &Q,
+    ) -> Result<Vec<i8>, IndexError> {
+        if vector.len() != self.dimensions() {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.dimensions(),
+                got: vector.len(),
+            });
+        }
+        let mut quantized = vec![0; vector.len()];
+        quantize_slice(vector, quantizer, &mut quantized);
+        Ok(quantized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_calibration() {
+        // Dimensions of very different scales, with one outlier in the first.
+        let mut sample: Vec<Vec<f32>> = (0..101)
+            .map(|i| vec![i as f32 / 100.0, 1000.0 + i as f32 * 10.0])
+            .collect();
+        sample[50][0] = 50.0;
+
+        let extremes = Calibration::min_max(&sample).unwrap();
+        assert_eq!(extremes.lower(), &[0.0, 1000.0]);
+        assert_eq!(extremes.upper(), &[50.0, 2000.0]);
+        let robust = Calibration::percentile(&sample, 0.01).unwrap();
+        assert_eq!(robust.lower(), &[0.01, 1010.0]);
+        assert_eq!(robust.upper(), &[1.0, 1990.0]);
+        assert_eq!(robust.quantize(&[50.0, 0.0]), vec![127, -127]);
+
+        // Values within the ranges keep about 8 bits of precision in every dimension.
+        for vector in sample[1..100].iter().filter(|v| v[0] <= 1.0) {
+            let restored = robust.dequantize(&robust.quantize(vector));
+            assert!((restored[0] - vector[0]).abs() <= 0.01, "{restored:?}");
+            assert!((restored[1] - vector[1]).abs() <= 10.0, "{restored:?}");
+            let restored = robust.dequantize_f16(&robust.quantize_f16(vector));
+            assert!((restored[1] - vector[1]).abs() <= 1.0, "{restored:?}");
+        }
+        assert_eq!(robust.clone().symmetric().lower(), &[-1.0, -1990.0]);
+
+        let mut buffer = Vec::new();
+        robust.save_to_writer(&mut buffer).unwrap();
+        assert_eq!(Calibration::load_from_reader(&buffer[..]).unwrap(), robust);
+        assert!(Calibration::load_from_reader(&buffer[..20]).is_err());
+        assert!(Calibration::percentile(&sample, 0.5).is_err());
+        assert!(Calibration::min_max::<Vec<f32>>(&[]).is_err());
+        assert!(Calibration::from_bounds(vec![1.0], vec![0.0]).is_err());
+
+        let index = Index::new(&IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            ..Default::default()
+        })
+        .unwrap();
+        index.reserve(sample.len()).unwrap();
+        for (key, vector) in sample.iter().enumerate() {
+            index.add_quantized(key as Key, vector, &robust).unwrap();
+        }
+        let matches = index.search_quantized(&[0.3, 1300.0], 1, &robust).unwrap();
+        assert_eq!(matches.keys, vec![30]);
+        assert!(index.search_quantized(&[0.3], 1, &robust).is_err());
+    }
+}
@@ -0,0 +1,469 @@
+//! A small JSON search DSL, so clients outside of Rust can express common searches.
+//!
+//! Enabled with the `query` feature. A query names the query vector, the number of results,
+//! keys to exclude, a minimum similarity, and an optional boolean filter over payload fields:
+//!
+//! ```json
+//! {
+//!     "vector": [0.2, 0.1, 0.2],
+//!     "k": 10,
+//!     "exclude": [42],
+//!     "min_similarity": 0.5,
+//!     "filter": { "and": [
+//!         { "field": "lang", "in": ["en", "de"] },
+//!         { "field": "year", "gte": 2020 },
+//!         { "not": { "keys": [7, 8] } }
+//!     ] }
+//! }
+//! ```
+//!
+//! Key lists are compiled into Roaring bitmaps, and the whole filter into a single predicate
+//! passed to [`Index::filtered_search`]. The crate doesn't store payloads, so field conditions
+//! are resolved through a user-provided [`PayloadSource`].
+
+use std::collections::HashMap;
+
+use roaring::RoaringTreemap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::ffi::Matches;
+use crate::metrics::MetricType;
+use crate::{Index, IndexError, Key};
+
+fn default_k() -> usize {
+    10
+}
+
+/// A parsed search request.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchQuery {
+    /// The query vector, converted to the index scalar kind by the native layer.
+    pub vector: Vec<f32>,
+    /// The maximum number of results, 10 by default.
+    #[serde(default = "default_k")]
+    pub k: usize,
+    /// Keys that must never be returned.
+    #[serde(default)]
+    pub exclude: Vec<Key>,
+    /// The minimum similarity of returned results, on the [`MetricType`] scale of the index
+    /// metric. For the `Cos` and `IP` metrics that is `1 - distance`, the cosine similarity and
+    /// the inner product.
+    #[serde(default)]
+    pub min_similarity: Option<f32>,
+    /// A boolean filter over keys and payload fields.
+    #[serde(default)]
+    pub filter: Option<Filter>,
+}
+
+/// A boolean filter expression.
+///
+/// Every filter is a JSON object with a single operator, like `and` or `keys`, or a `field`
+/// with at least one condition. Objects mixing operators, or holding unknown keys, are rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    /// Matches if all sub-filters match.
+    And {
+        /// The sub-filters.
+        and: Vec<Filter>,
+    },
+    /// Matches if any sub-filter matches.
+    Or {
+        /// The sub-filters.
+        or: Vec<Filter>,
+    },
+    /// Matches if the sub-filter doesn't.
+    Not {
+        /// The negated sub-filter.
+        not: Box<Filter>,
+    },
+    /// Matches the listed keys only.
+    Keys {
+        /// The allowed keys.
+        keys: Vec<Key>,
+    },
+    /// Matches if the payload field satisfies all listed conditions.
+    Field {
+        /// The payload field name.
+        field: String,
+        /// The conditions on its value.
+        condition: Condition,
+    },
+}
+
+/// Conditions on a payload field value. All present conditions must hold.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Condition {
+    /// The value must be equal.
+    #[serde(default)]
+    pub eq: Option<Value>,
+    /// The value must differ.
+    #[serde(default)]
+    pub ne: Option<Value>,
+    /// The value must be one of the listed ones.
+    #[serde(default, rename = "in")]
+    pub one_of: Option<Vec<Value>>,
+    /// The numeric value must be greater.
+    #[serde(default)]
+    pub gt: Option<f64>,
+    /// The numeric value must be greater or equal.
+    #[serde(default)]
+    pub gte: Option<f64>,
+    /// The numeric value must be smaller.
+    #[serde(default)]
+    pub lt: Option<f64>,
+    /// The numeric value must be smaller or equal.
+    #[serde(default)]
+    pub lte: Option<f64>,
+    /// The field must be present, or absent.
+    #[serde(default)]
+    pub exists: Option<bool>,
+}
+
+impl<'de> Deserialize<'de> for Filter {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut object = serde_json::Map::<String, Value>::deserialize(deserializer)?;
+        if let Some(field) = object.remove("field") {
+            let field = String::deserialize(field).map_err(D::Error::custom)?;
+            let condition =
+                Condition::deserialize(Value::Object(object)).map_err(D::Error::custom)?;
+            if condition == Condition::default() {
+                return Err(D::Error::custom(format!(
+                    "the filter on field `{}` has no condition",
+                    field
+                )));
+            }
+            return Ok(Filter::Field { field, condition });
+        }
+
+        let mut operators = object.into_iter();
+        let (Some((operator, operand)), None) = (operators.next(), operators.next()) else {
+            return Err(D::Error::custom(
+                "a filter must hold exactly one of `and`, `or`, `not`, `keys`, or `field`",
+            ));
+        };
+        let filter = match operator.as_str() {
+            "and" => Filter::And {
+                and: Vec::deserialize(operand).map_err(D::Error::custom)?,
+            },
+            "or" => Filter::Or {
+                or: Vec::deserialize(operand).map_err(D::Error::custom)?,
+            },
+            "not" => Filter::Not {
+                not: Box::deserialize(operand).map_err(D::Error::custom)?,
+            },
+            "keys" => Filter::Keys {
+                keys: Vec::deserialize(operand).map_err(D::Error::custom)?,
+            },
+            _ => {
+                return Err(D::Error::unknown_field(
+                    &operator,
+                    &["and", "or", "not", "keys", "field"],
+                ))
+            }
+        };
+        Ok(filter)
+    }
+}
+
+impl Condition {
+    fn matches(&self, value: Option<&Value>) -> bool {
+        if let Some(exists) = self.exists {
+            if exists != value.is_some() {
+                return false;
+            }
+        }
+        let numeric_bounds = [self.gt, self.gte, self.lt, self.lte];
+        let needs_value = self.eq.is_some()
+            || self.one_of.is_some()
+            || numeric_bounds.iter().any(Option::is_some);
+        let Some(value) = value else {
+            return !needs_value;
+        };
+
+        if self.eq.as_ref().is_some_and(|eq| eq != value)
+            || self.ne.as_ref().is_some_and(|ne| ne == value)
+            || self.one_of.as_ref().is_some_and(|set| !set.contains(value))
+        {
+            return false;
+        }
+        if numeric_bounds.iter().any(Option::is_some) {
+            let Some(number) = value.as_f64() else {
+                return false;
+            };
+            if self.gt.is_some_and(|bound| number <= bound)
+                || self.gte.is_some_and(|bound| number < bound)
+                || self.lt.is_some_and(|bound| number >= bound)
+                || self.lte.is_some_and(|bound| number > bound)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Resolves payload fields referenced by [`Filter::Field`] conditions.
+pub trait PayloadSource {
+    /// Returns the value of the field attached to the key, if any.
+    fn field(&self, key: Key, name: &str) -> Option<&Value>;
+}
+
+/// Searches without payloads, where every field is absent.
+impl PayloadSource for () {
+    fn field(&self, _key: Key, _name: &str) -> Option<&Value> {
+        None
+    }
+}
+
+/// Payloads stored as JSON objects in a map.
+impl PayloadSource for HashMap<Key, Value> {
+    fn field(&self, key: Key, name: &str) -> Option<&Value> {
+        self.get(&key)?.get(name)
+    }
+}
+
+/// Errors raised while parsing or executing a query.
+#[derive(Debug)]
+pub enum QueryError {
+    /// The query isn't valid JSON, or doesn't follow the DSL.
+    Parse(serde_json::Error),
+    /// The query vector doesn't match the index dimensions.
+    InvalidVector {
+        /// The number of dimensions of the index.
+        expected: usize,
+        /// The number of dimensions of the query vector.
+        got: usize,
+    },
+    /// The index failed to execute the search.
+    Index(IndexError),
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::Parse(err) => write!(f, "Malformed query: {}", err),
+            QueryError::InvalidVector { expected, got } => write!(
+                f,
+                "Query vector has {} dimensions, the index expects {}",
+                got, expected
+            ),
+            QueryError::Index(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            QueryError::Parse(err) => Some(err),
+            QueryError::Index(err) => Some(err),
+            QueryError::InvalidVector { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for QueryError {
+    fn from(err: serde_json::Error) -> Self {
+        QueryError::Parse(err)
+    }
+}
+
+impl From<IndexError> for QueryError {
+    fn from(err: IndexError) -> Self {
+        QueryError::Index(err)
+    }
+}
+
+impl From<cxx::Exception> for QueryError {
+    fn from(err: cxx::Exception) -> Self {
+        QueryError::Index(err.into())
+    }
+}
+
+/// A filter with all key lists compiled into bitmaps.
+enum CompiledFilter {
+    And(Vec<CompiledFilter>),
+    Or(Vec<CompiledFilter>),
+    Not(Box<CompiledFilter>),
+    Keys(RoaringTreemap),
+    Field(String, Condition),
+}
+
+impl CompiledFilter {
+    fn compile(filter: &Filter) -> Self {
+        match filter {
+            Filter::And { and } => CompiledFilter::And(and.iter().map(Self::compile).collect()),
+            Filter::Or { or } => CompiledFilter::Or(or.iter().map(Self::compile).collect()),
+            Filter::Not { not } => CompiledFilter::Not(Box::new(Self::compile(not))),
+            Filter::Keys { keys } => CompiledFilter::Keys(keys.iter().copied().collect()),
+            Filter::Field { field, condition } => {
+                CompiledFilter::Field(field.clone(), condition.clone())
+            }
+        }
+    }
+
+    fn matches<P: PayloadSource + ?Sized>(&self, key: Key, payloads: &P) -> bool {
+        match self {
+            CompiledFilter::And(filters) => filters.iter().all(|f| f.matches(key, payloads)),
+            CompiledFilter::Or(filters) => filters.iter().any(|f| f.matches(key, payloads)),
+            CompiledFilter::Not(filter) => !filter.matches(key, payloads),
+            CompiledFilter::Keys(keys) => keys.contains(key),
+            CompiledFilter::Field(name, condition) => condition.matches(payloads.field(key, name)),
+        }
+    }
+}
+
+impl SearchQuery {
+    /// Parses a query from its JSON representation.
+    pub fn from_json(json: &str) -> Result<Self, QueryError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Parses a query from an already decoded JSON value.
+    pub fn from_value(value: Value) -> Result<Self, QueryError> {
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Executes the query against an index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index to search in.
+    /// * `payloads` - The source of payload fields referenced by the filter, or `&()` if there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use usearch::query::SearchQuery;
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.reserve(10).unwrap();
+    /// index.add(1, &[1.0_f32, 0.0]).unwrap();
+    /// index.add(2, &[0.9_f32, 0.1]).unwrap();
+    ///
+    /// let payloads: HashMap<u64, serde_json::Value> = [
+    ///     (1, serde_json::json!({ "lang": "en" })),
+    ///     (2, serde_json::json!({ "lang": "de" })),
+    /// ].into_iter().collect();
+    ///
+    /// let query = SearchQuery::from_json(r#"{
+    ///     "vector": [1.0, 0.0], "k": 5,
+    ///     "filter": { "field": "lang", "eq": "de" }
+    /// }"#).unwrap();
+    /// assert_eq!(query.execute(&index, &payloads).unwrap().keys, vec![2]);
+    /// ```
+    pub fn execute<P: PayloadSource + ?Sized>(
+        &self,
+        index: &Index,
+        payloads: &P,
+    ) -> Result<Matches, QueryError> {
+        if self.vector.len() != index.dimensions() {
+            return Err(QueryError::InvalidVector {
+                expected: index.dimensions(),
+                got: self.vector.len(),
+            });
+        }
+
+        let excluded: RoaringTreemap = self.exclude.iter().copied().collect();
+        let filter = self.filter.as_ref().map(CompiledFilter::compile);
+        let mut matches = match filter {
+            None if excluded.is_empty() => index.search(&self.vector, self.k)?,
+            _ => index.filtered_search(&self.vector, self.k, |key| {
+                !excluded.contains(key)
+                    && filter
+                        .as_ref()
+                        .is_none_or(|filter| filter.matches(key, payloads))
+            })?,
+        };
+
+        if let Some(min_similarity) = self.min_similarity {
+            let max_distance = index
+                .inner
+                .metric_kind()
+                .distance_for_similarity(min_similarity);
+            let kept = matches
+                .distances
+                .iter()
+                .take_while(|&&distance| distance <= max_distance)
+                .count();
+            matches.keys.truncate(kept);
+            matches.distances.truncate(kept);
+        }
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexOptions;
+    use serde_json::json;
+
+    #[test]
+    fn test_query_filters_and_thresholds() {
+        let options = IndexOptions {
+            dimensions: 2,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(10).unwrap();
+        let mut payloads = HashMap::new();
+        for key in 1..=6u64 {
+            let angle = key as f32 * 0.2;
+            index.add(key, &[angle.cos(), angle.sin()]).unwrap();
+            payloads.insert(key, json!({ "year": 2018 + key, "even": key % 2 == 0 }));
+        }
+
+        let query = SearchQuery::from_value(json!({
+            "vector": [1.0, 0.0],
+            "exclude": [2],
+            "filter": { "or": [
+                { "field": "even", "eq": true },
+                { "and": [{ "field": "year", "gte": 2021 }, { "not": { "keys": [5] } }] }
+            ] }
+        }))
+        .unwrap();
+        assert_eq!(query.k, 10);
+        assert_eq!(
+            query.execute(&index, &payloads).unwrap().keys,
+            vec![3, 4, 6]
+        );
+
+        let query =
+            SearchQuery::from_json(r#"{ "vector": [1.0, 0.0], "min_similarity": 0.9 }"#).unwrap();
+        assert_eq!(query.execute(&index, &()).unwrap().keys, vec![1, 2]);
+
+        assert!(matches!(
+            SearchQuery::from_json(r#"{ "vector": [1.0], "unknown": 1 }"#),
+            Err(QueryError::Parse(_))
+        ));
+        // Misspelled operators, conditions without operators, and mixed objects are rejected.
+        for filter in [
+            json!({ "field": "year", "gtee": 2021 }),
+            json!({ "field": "year" }),
+            json!({ "keys": [1], "field": "year", "eq": 2021 }),
+            json!({ "keys": [1], "not": { "keys": [2] } }),
+            json!({ "and": [{ "field": "year", "gte": 2021, "lang": "en" }] }),
+            json!({ "keyz": [1] }),
+            json!({}),
+        ] {
+            let query = SearchQuery::from_value(json!({ "vector": [1.0, 0.0], "filter": filter }));
+            assert!(matches!(query, Err(QueryError::Parse(_))), "{:?}", query);
+        }
+        let query = SearchQuery::from_json(r#"{ "vector": [1.0] }"#).unwrap();
+        assert!(matches!(
+            query.execute(&index, &()),
+            Err(QueryError::InvalidVector {
+                expected: 2,
+                got: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+//! Approximate range counting, estimating how many matches clear a similarity bar.
+//!
+//! Interfaces often have to choose between showing a handful of results and a long list.
+//! [`Index::suggest_k`] probes the neighborhood of a query with searches of growing size,
+//! stopping as soon as a match falls below the bar, instead of over-fetching blindly.
+
+use crate::metrics::MetricType;
+use crate::{Index, IndexError, VectorType};
+
+/// The number of neighbors fetched by the first probe.
+const FIRST_PROBE: usize = 16;
+
+/// The largest number of neighbors fetched by a single probe.
+const LAST_PROBE: usize = 4096;
+
+impl Index {
+    /// Estimates the number of stored vectors at least `target_similarity` similar to the query.
+    /// Similarity follows the [`MetricType`] scale of the index metric, like the `min_similarity`
+    /// of the query DSL, so for the `Cos` and `IP` metrics it is `1 - distance`, and for `L2sq`
+    /// it is `exp(-distance)`.
+    ///
+    /// The neighborhood is probed with searches of doubling size, up to 4096 neighbors,
+    /// so the estimate is approximate and saturates for very dense neighborhoods.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `target_similarity` - The minimum similarity of the counted matches.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the suggested number of results to fetch, or the errors of
+    /// [`Index::search`], like `IndexError::DimensionMismatch`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::Cos,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.reserve(10).unwrap();
+    /// index.add(1, &[1.0_f32, 0.0]).unwrap();
+    /// index.add(2, &[0.9_f32, 0.1]).unwrap();
+    /// index.add(3, &[0.0_f32, 1.0]).unwrap();
+    ///
+    /// assert_eq!(index.suggest_k(&[1.0_f32, 0.0], 0.9).unwrap(), 2);
+    /// ```
+    pub fn suggest_k<T: VectorType>(
+        &self,
+        query: &[T],
+        target_similarity: f32,
+    ) -> Result<usize, IndexError> {
+        let max_distance = self
+            .inner
+            .metric_kind()
+            .distance_for_similarity(target_similarity);
+        let size = self.size();
+        if size == 0 {
+            return Ok(0);
+        }
+        let mut count = FIRST_PROBE.min(size);
+        loop {
+            let matches = self.search(query, count)?;
+            let above = matches
+                .distances
+                .iter()
+                .take_while(|&&distance| distance <= max_distance)
+                .count();
+            if above < matches.keys.len() || count >= size || count >= LAST_PROBE {
+                return Ok(above);
+            }
+            count = (count * 2).min(size).min(LAST_PROBE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_suggest_k() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        assert_eq!(index.suggest_k(&[0.0_f32, 0.0], 0.5).unwrap(), 0);
+
+        index.reserve(200).unwrap();
+        for key in 0..200u64 {
+            // The first 50 vectors lie within a distance of 0.25 from the origin.
+            let offset = if key < 50 { 0.0 } else { 10.0 };
+            index.add(key, &[offset + key as f32 * 0.01, 0.0]).unwrap();
+        }
+
+        assert_eq!(index.suggest_k(&[0.0_f32, 0.0], 0.75).unwrap(), 50);
+        // Similarities of L2sq decay as `exp(-distance)`, and 32 keys lie within 0.1 of the origin.
+        let similarity = (-0.1_f32).exp();
+        assert_eq!(index.suggest_k(&[0.0_f32, 0.0], similarity).unwrap(), 32);
+        assert_eq!(index.suggest_k(&[0.0_f32, 0.0], -1000.0).unwrap(), 200);
+        assert_eq!(index.suggest_k(&[100.0_f32, 0.0], 0.75).unwrap(), 0);
+        assert!(matches!(
+            index.suggest_k(&[0.0_f32], 0.75),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+    }
+}
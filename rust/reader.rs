@@ -0,0 +1,101 @@
+//! Read-only handles to a shared index.
+//!
+//! Web handlers and worker pools usually only search, while a single owner keeps inserting.
+//! [`IndexReader`] is a cloneable handle exposing only the read operations of an index shared
+//! through an `Arc`, so handlers can hold their own copy instead of an `Arc<Mutex<Index>>`.
+//! Writes continue through the owning `Arc<Index>`, and replacing the contents with
+//! [`Index::load`] or [`Index::reset`] needs every reader dropped first.
+
+use std::sync::Arc;
+
+use crate::ffi::Matches;
+use crate::frozen::read_accessors;
+use crate::{Index, IndexError, Key, VectorType};
+
+/// A cheap, cloneable, read-only handle to a shared index.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let index = Arc::new(Index::new(&options).unwrap());
+/// index.add(1, &[0.0_f32, 1.0]).unwrap();
+///
+/// let reader = index.reader();
+/// let handle = std::thread::spawn(move || reader.search(&[0.1_f32, 0.9], 1).unwrap().keys);
+/// index.add(2, &[1.0_f32, 0.0]).unwrap();
+/// assert_eq!(handle.join().unwrap(), vec![1]);
+/// ```
+#[derive(Clone)]
+pub struct IndexReader {
+    index: Arc<Index>,
+}
+
+impl Index {
+    /// Creates a read-only handle sharing the index, which can be cloned and sent to other threads.
+    pub fn reader(self: &Arc<Self>) -> IndexReader {
+        IndexReader {
+            index: Arc::clone(self),
+        }
+    }
+}
+
+impl IndexReader {
+    read_accessors!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_readers_alongside_writes() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let mut index = Arc::new(Index::new(&options).unwrap());
+        index.add(0, &[0.0_f32, 0.0]).unwrap();
+
+        let reader = index.reader();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let reader = reader.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        assert_eq!(reader.search(&[0.0_f32, 0.0], 1).unwrap().keys, vec![0]);
+                        assert!(reader.contains(0));
+                    }
+                    reader.size()
+                })
+            })
+            .collect();
+        for key in 1..200u64 {
+            index.add(key, &[key as f32, 1.0]).unwrap();
+        }
+        for handle in handles {
+            assert!(handle.join().unwrap() >= 1);
+        }
+        assert_eq!(reader.size(), 200);
+        let mut vector: Vec<f32> = Vec::new();
+        assert_eq!(reader.export(7, &mut vector).unwrap(), 1);
+        assert_eq!(vector, vec![7.0, 1.0]);
+
+        // Replacing the contents waits for the last reader to go.
+        assert!(Arc::get_mut(&mut index).is_none());
+        drop(reader);
+        Arc::get_mut(&mut index).unwrap().reset().unwrap();
+        assert_eq!(index.size(), 0);
+    }
+}
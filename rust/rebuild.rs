@@ -0,0 +1,199 @@
+//! Rebuilding an index with different options.
+//!
+//! Connectivity, the scalar kind and the metric are fixed when the native index is created,
+//! while the right values often only show up under production load. [`Index::rebuild`]
+//! re-inserts every stored vector into a fresh index created with new options, in parallel,
+//! and [`Index::rebuild_with_progress`] reports how far it got along the way.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use crate::compaction::Transfer;
+use crate::{b1x8, f16, Index, IndexError, IndexOptions, Progress, ScalarKind};
+
+impl Index {
+    /// Creates a copy of the index with new options, re-inserting every stored vector with all
+    /// available threads.
+    ///
+    /// See [`Index::rebuild_with_progress`] for the details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     connectivity: 8,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// for key in 0..100u64 {
+    ///     index.add(key, &[key as f32, 0.0]).unwrap();
+    /// }
+    ///
+    /// let denser = index
+    ///     .rebuild(&IndexOptions { connectivity: 32, ..options })
+    ///     .unwrap();
+    /// assert_eq!(denser.connectivity(), 32);
+    /// assert_eq!(denser.size(), 100);
+    /// assert_eq!(denser.search(&[42.0_f32, 0.0], 1).unwrap().keys, vec![42]);
+    /// ```
+    pub fn rebuild(&self, options: &IndexOptions) -> Result<Index, IndexError> {
+        self.rebuild_with_progress(options, 0, |_| {})
+    }
+
+    /// Creates a copy of the index with new options, re-inserting every stored vector.
+    ///
+    /// Vectors are read back in the scalar kind they are stored in when it doesn't change, so
+    /// quantized values don't drift, and as `f32`, or `f64` from `F64` indexes, otherwise.
+    /// Keys are preserved, and the index itself is left untouched. Custom metrics aren't
+    /// carried over, as they are owned by this index: the copy compares vectors with the
+    /// metric kind of the options, until [`Index::change_metric`] is called on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options of the new index, with the same dimensions as this one.
+    /// * `threads` - The number of threads re-inserting vectors, zero for all available ones,
+    ///   capped by the available parallelism like for [`Index::compact`].
+    /// * `progress` - A callback receiving a [`Progress`] snapshot every thousand or so vectors,
+    ///   called from the rebuilding threads.
+    ///
+    /// # Returns
+    ///
+    /// The new index, `IndexError::DimensionMismatch` if the dimensions of the options differ,
+    /// or the error of the first vector that couldn't be inserted, like repeated keys when
+    /// rebuilding a `multi` index into a regular one.
+    pub fn rebuild_with_progress<P>(
+        &self,
+        options: &IndexOptions,
+        threads: usize,
+        progress: P,
+    ) -> Result<Index, IndexError>
+    where
+        P: Fn(Progress) + Sync,
+    {
+        if options.dimensions != self.dimensions() {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.dimensions(),
+                got: options.dimensions,
+            });
+        }
+        let available = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let threads = match threads {
+            0 => available,
+            threads => threads.min(available),
+        };
+        let mut keys = self.keys();
+        keys.sort_unstable();
+        keys.dedup();
+        let total = self.size();
+        let rebuilt = Index::new(options)?;
+        rebuilt.reserve(total)?;
+
+        let started = Instant::now();
+        let processed = AtomicUsize::new(0);
+        let copied = |count: usize| {
+            progress(Progress {
+                processed: processed.fetch_add(count, Ordering::Relaxed) + count,
+                total,
+                elapsed: started.elapsed(),
+            })
+        };
+        let transfer = Transfer {
+            source: self,
+            target: &rebuilt,
+            copied: Some(&copied),
+        };
+        let stored = self.inner.scalar_kind();
+        match stored {
+            _ if stored != options.quantization && stored != ScalarKind::F64 => {
+                transfer.copy_all::<f32>(&keys, threads)?
+            }
+            ScalarKind::B1 => transfer.copy_all::<b1x8>(&keys, threads)?,
+            ScalarKind::I8 => transfer.copy_all::<i8>(&keys, threads)?,
+            ScalarKind::F16 => transfer.copy_all::<f16>(&keys, threads)?,
+            ScalarKind::F64 => transfer.copy_all::<f64>(&keys, threads)?,
+            _ => transfer.copy_all::<f32>(&keys, threads)?,
+        }
+        Ok(rebuilt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{f16, Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_rebuild() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            connectivity: 4,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(5000).unwrap();
+        for key in 0..5000u64 {
+            index.add(key, &[key as f32 / 7.0, 1.0]).unwrap();
+        }
+
+        let reports = Mutex::new(Vec::new());
+        let rebuilt = index
+            .rebuild_with_progress(
+                &IndexOptions {
+                    connectivity: 24,
+                    expansion_search: 128,
+                    ..options.clone()
+                },
+                2,
+                |progress| reports.lock().unwrap().push(progress),
+            )
+            .unwrap();
+        assert_eq!(rebuilt.size(), 5000);
+        assert_eq!(rebuilt.connectivity(), 24);
+        assert_eq!(rebuilt.expansion_search(), 128);
+        let mut stored = [f16::default(); 2];
+        let mut copied = [f16::default(); 2];
+        index.get(1234, &mut stored).unwrap();
+        rebuilt.get(1234, &mut copied).unwrap();
+        assert_eq!(stored, copied);
+        let matches = rebuilt.search(&[100.0_f32, 1.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![700]);
+
+        // Progress grows monotonically per thread, and sums up to the whole index.
+        let reports = reports.into_inner().unwrap();
+        assert!(reports.len() >= 2);
+        assert!(reports.iter().all(|report| report.total == 5000));
+        let last = reports.iter().map(|report| report.processed).max();
+        assert_eq!(last, Some(5000));
+
+        // Changing the metric and scalar kind at once.
+        let cosine = index
+            .rebuild(&IndexOptions {
+                metric: MetricKind::Cos,
+                quantization: ScalarKind::F32,
+                ..options.clone()
+            })
+            .unwrap();
+        let matches = cosine.search(&[0.0_f32, 1.0], 1).unwrap();
+        assert_eq!(matches.keys, vec![0]);
+
+        let wider = IndexOptions {
+            dimensions: 3,
+            ..options
+        };
+        assert!(matches!(
+            index.rebuild(&wider),
+            Err(IndexError::DimensionMismatch {
+                expected: 2,
+                got: 3
+            })
+        ));
+    }
+}
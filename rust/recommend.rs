@@ -0,0 +1,252 @@
+//! Recommendations from positive and negative examples, rather than from a single query.
+//!
+//! Recommenders usually know a few items a user liked and a few they didn't.
+//! [`Index::recommend`] turns such examples into a single query vector, moving the average of
+//! the positive examples away from the negative ones, while [`RecommendStrategy::BestScore`]
+//! gathers the neighbors of every positive example, and ranks them by their closest positive
+//! example, pushing back those even closer to a negative one.
+
+use std::collections::HashSet;
+
+use crate::convert::Scalar;
+use crate::ffi::Matches;
+use crate::metrics::distance;
+use crate::{Distance, Index, IndexError, Key, VectorType};
+
+/// Number of neighbors gathered per positive example and returned result by
+/// [`RecommendStrategy::BestScore`].
+const POOL_FACTOR: usize = 2;
+
+/// How [`Index::recommend_with`] combines the examples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecommendStrategy {
+    /// Searches for `p + negative_weight * (p - n)`, where `p` and `n` are the averages of the
+    /// positive and negative examples. Without negative examples, `p` alone is searched for.
+    AverageVector {
+        /// How far the query moves away from the negative examples, `1.0` by default.
+        negative_weight: f32,
+    },
+    /// Searches for the neighbors of every positive example, and ranks them by their distance to
+    /// the closest one. Candidates closer to a negative example than to every positive one come
+    /// last, the furthest from the negative examples first. Distances are measured on the stored
+    /// vectors with the metric kind of the index, ignoring custom metrics.
+    BestScore,
+}
+
+impl Default for RecommendStrategy {
+    fn default() -> Self {
+        RecommendStrategy::AverageVector {
+            negative_weight: 1.0,
+        }
+    }
+}
+
+/// Decodes the examples into floats, checking their dimensions.
+fn decode_examples<T: Scalar>(
+    examples: &[&[T]],
+    dimensions: usize,
+) -> Result<Vec<Vec<f32>>, IndexError> {
+    examples
+        .iter()
+        .map(|example| {
+            let mut decoded = Vec::with_capacity(dimensions);
+            T::decode(example, dimensions, &mut decoded);
+            if decoded.len() != dimensions {
+                return Err(IndexError::DimensionMismatch {
+                    expected: dimensions,
+                    got: decoded.len(),
+                });
+            }
+            Ok(decoded)
+        })
+        .collect()
+}
+
+fn average(vectors: &[Vec<f32>], dimensions: usize) -> Vec<f32> {
+    let mut sum = vec![0.0_f32; dimensions];
+    for vector in vectors {
+        sum.iter_mut().zip(vector).for_each(|(s, x)| *s += x);
+    }
+    sum.iter_mut().for_each(|s| *s /= vectors.len() as f32);
+    sum
+}
+
+impl Index {
+    /// Recommends the `count` vectors closest to the positive examples and furthest from the
+    /// negative ones, with the default [`RecommendStrategy::AverageVector`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[1.0_f32, 1.0]).unwrap();
+    /// index.add(2, &[1.0_f32, -1.0]).unwrap();
+    ///
+    /// // Liked something on the right, disliked something at the bottom.
+    /// let positives: [&[f32]; 1] = [&[1.0, 0.0]];
+    /// let negatives: [&[f32]; 1] = [&[0.0, -1.0]];
+    /// let matches = index.recommend(&positives, &negatives, 1).unwrap();
+    /// assert_eq!(matches.keys, vec![1]);
+    /// ```
+    pub fn recommend<T: VectorType + Scalar + Default + Clone>(
+        &self,
+        positives: &[&[T]],
+        negatives: &[&[T]],
+        count: usize,
+    ) -> Result<Matches, IndexError> {
+        self.recommend_with(positives, negatives, count, RecommendStrategy::default())
+    }
+
+    /// Recommends the `count` vectors closest to the positive examples and furthest from the
+    /// negative ones, combining the examples with the given strategy.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` without positive examples, and
+    /// `IndexError::DimensionMismatch` for examples not matching the index dimensions.
+    pub fn recommend_with<T: VectorType + Scalar + Default + Clone>(
+        &self,
+        positives: &[&[T]],
+        negatives: &[&[T]],
+        count: usize,
+        strategy: RecommendStrategy,
+    ) -> Result<Matches, IndexError> {
+        if positives.is_empty() {
+            return Err(IndexError::InvalidOptions {
+                reason: "Recommendations need at least one positive example".to_owned(),
+            });
+        }
+        let dimensions = self.dimensions();
+        let positives = decode_examples(positives, dimensions)?;
+        let negatives = decode_examples(negatives, dimensions)?;
+
+        match strategy {
+            RecommendStrategy::AverageVector { negative_weight } => {
+                let mut query = average(&positives, dimensions);
+                if !negatives.is_empty() {
+                    let away = average(&negatives, dimensions);
+                    for (q, n) in query.iter_mut().zip(&away) {
+                        *q += negative_weight * (*q - n);
+                    }
+                }
+                self.search(&query, count)
+            }
+            RecommendStrategy::BestScore => {
+                self.recommend_best_score::<T>(&positives, &negatives, count)
+            }
+        }
+    }
+
+    fn recommend_best_score<T: VectorType + Scalar + Default + Clone>(
+        &self,
+        positives: &[Vec<f32>],
+        negatives: &[Vec<f32>],
+        count: usize,
+    ) -> Result<Matches, IndexError> {
+        let mut seen = HashSet::new();
+        let mut candidates: Vec<Key> = Vec::new();
+        for positive in positives {
+            let neighbors = self.search(positive, count * POOL_FACTOR)?;
+            candidates.extend(neighbors.keys.into_iter().filter(|&key| seen.insert(key)));
+        }
+
+        let metric = self.inner.metric_kind();
+        let dimensions = self.dimensions();
+        let closest = |examples: &[Vec<f32>], vector: &[f32]| {
+            examples
+                .iter()
+                .map(|example| distance(metric, example, vector))
+                .fold(Distance::INFINITY, Distance::min)
+        };
+        let mut decoded = Vec::with_capacity(dimensions);
+        let mut ranked: Vec<(bool, Distance, Distance, Key)> = self
+            .stored_vectors::<T>(&candidates)?
+            .iter()
+            .zip(candidates)
+            .map(|(vector, key)| {
+                decoded.clear();
+                T::decode(vector, dimensions, &mut decoded);
+                let positive = closest(positives, &decoded);
+                let negative = closest(negatives, &decoded);
+                // Rejected candidates sort by their negated distance to the negative examples.
+                let rejected = negative < positive;
+                let rank = if rejected { -negative } else { positive };
+                (rejected, rank, positive, key)
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        ranked.truncate(count);
+        Ok(Matches {
+            keys: ranked.iter().map(|&(_, _, _, key)| key).collect(),
+            distances: ranked.iter().map(|&(_, _, distance, _)| distance).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_recommend() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        // A grid of points, named after their coordinates.
+        for x in 0..10u64 {
+            for y in 0..10u64 {
+                index.add(x * 10 + y, &[x as f32, y as f32]).unwrap();
+            }
+        }
+
+        // The average of two corners is the center.
+        let positives: [&[f32]; 2] = [&[0.0, 0.0], &[8.0, 8.0]];
+        let matches = index.recommend(&positives, &[], 1).unwrap();
+        assert_eq!(matches.keys, vec![44]);
+
+        // Moving away from a negative example, twice as far with twice the weight.
+        let positives: [&[f32]; 1] = [&[4.0, 4.0]];
+        let negatives: [&[f32]; 1] = [&[4.0, 3.0]];
+        let matches = index.recommend(&positives, &negatives, 1).unwrap();
+        assert_eq!(matches.keys, vec![45]);
+        let strategy = RecommendStrategy::AverageVector {
+            negative_weight: 2.0,
+        };
+        let matches = index
+            .recommend_with(&positives, &negatives, 1, strategy)
+            .unwrap();
+        assert_eq!(matches.keys, vec![46]);
+
+        // Neighbors of both positive examples, those nearer to the negative example last.
+        let positives: [&[f32]; 2] = [&[0.0, 0.0], &[9.0, 9.0]];
+        let negatives: [&[f32]; 1] = [&[0.0, 1.0]];
+        let matches = index
+            .recommend_with(&positives, &negatives, 4, RecommendStrategy::BestScore)
+            .unwrap();
+        assert_eq!(&matches.keys[..2], &[0, 99]);
+        assert_eq!(&matches.distances[..2], &[0.0, 0.0]);
+        assert!(!matches.keys[..3].contains(&1));
+        let all = index
+            .recommend_with(&positives, &negatives, 100, RecommendStrategy::BestScore)
+            .unwrap();
+        assert_eq!(all.keys.len(), 100);
+        assert_eq!(all.keys.last(), Some(&1));
+
+        assert!(index.recommend::<f32>(&[], &negatives, 1).is_err());
+        let short: [&[f32]; 1] = [&[1.0]];
+        assert!(index.recommend(&short, &[], 1).is_err());
+    }
+}
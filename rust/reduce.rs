@@ -0,0 +1,483 @@
+//! Dimensionality reduction, shrinking long embeddings before they are indexed.
+//!
+//! Recent embedding models produce vectors of thousands of dimensions, most of which an index
+//! of moderate size doesn't need to tell neighbors apart. A [`Projection`] maps vectors linearly
+//! onto fewer dimensions, either with a seeded random matrix, which preserves distances up to a
+//! small error without any training, or onto the principal components of a sample, which
+//! keeps most of the variance of data resembling it. [`ReducedIndex`] applies a projection to
+//! insertions and queries alike, so callers keep working with the original vectors.
+
+#[cfg(not(no_fs))]
+use std::fs::File;
+#[cfg(not(no_fs))]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, IndexOptions, Key};
+
+const MAGIC: &[u8; 8] = b"USPROJ01";
+
+/// Generates the reproducible pseudo-random sequence of a seed, with SplitMix64.
+struct Generator(u64);
+
+impl Generator {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Fills a matrix with `1 / sqrt(rows)` of random signs.
+    fn signs(&mut self, rows: usize, columns: usize) -> Vec<f32> {
+        let scale = 1.0 / (rows as f32).sqrt();
+        let mut matrix = Vec::with_capacity(rows * columns);
+        while matrix.len() < rows * columns {
+            let bits = self.next();
+            let take = (rows * columns - matrix.len()).min(64);
+            matrix.extend((0..take).map(|i| if bits >> i & 1 == 1 { scale } else { -scale }));
+        }
+        matrix
+    }
+}
+
+/// Turns the rows of a matrix into an orthonormal basis of their span, with the modified
+/// Gram-Schmidt process. Rows dependent on the previous ones are zeroed.
+fn orthonormalize(matrix: &mut [f64], columns: usize) {
+    let rows = matrix.len() / columns;
+    for row in 0..rows {
+        let (previous, rest) = matrix.split_at_mut(row * columns);
+        let current = &mut rest[..columns];
+        let scale = current.iter().map(|x| x * x).sum::<f64>().sqrt();
+        for basis in previous.chunks_exact(columns) {
+            let dot: f64 = basis.iter().zip(current.iter()).map(|(a, b)| a * b).sum();
+            current
+                .iter_mut()
+                .zip(basis)
+                .for_each(|(x, b)| *x -= dot * b);
+        }
+        let norm = current.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let inverse = if norm > scale * 1e-9 { 1.0 / norm } else { 0.0 };
+        current.iter_mut().for_each(|x| *x *= inverse);
+    }
+}
+
+/// A linear map from vectors of `from` dimensions to vectors of `to` dimensions.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::reduce::Projection;
+///
+/// let projection = Projection::random(1024, 64, 42).unwrap();
+/// let reduced = projection.project(&vec![1.0; 1024]);
+/// assert_eq!(reduced.len(), 64);
+/// assert_eq!(Projection::random(1024, 64, 42).unwrap(), projection);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    from: usize,
+    to: usize,
+    /// Subtracted from vectors before projecting them, empty for random projections.
+    mean: Vec<f32>,
+    /// `to` rows of `from` weights each.
+    matrix: Vec<f32>,
+}
+
+fn check_dimensions(from: usize, to: usize) -> Result<(), IndexError> {
+    if to == 0 || to > from {
+        return Err(IndexError::InvalidOptions {
+            reason: format!("Can't project {from} dimensions onto {to}"),
+        });
+    }
+    Ok(())
+}
+
+impl Projection {
+    /// Creates a random projection with entries of random signs, scaled to preserve the
+    /// expected norm of vectors. The same seed always yields the same projection.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` unless `to` is positive and at most `from`.
+    pub fn random(from: usize, to: usize, seed: u64) -> Result<Self, IndexError> {
+        check_dimensions(from, to)?;
+        Ok(Self {
+            from,
+            to,
+            mean: Vec::new(),
+            matrix: Generator(seed).signs(to, from),
+        })
+    }
+
+    /// Fits a projection onto the `to` principal components of a sample, centered on its mean,
+    /// with subspace iterations started from a random projection.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - Vectors representative of the ones to project, at least `to` of them.
+    /// * `to` - The number of dimensions to keep.
+    /// * `iterations` - The number of power iterations, with about 10 being typical.
+    /// * `seed` - The seed of the starting projection.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if the parameters don't fit the sample, and
+    /// `IndexError::DimensionMismatch` if the sample vectors have different lengths.
+    pub fn pca<V: AsRef<[f32]>>(
+        sample: &[V],
+        to: usize,
+        iterations: usize,
+        seed: u64,
+    ) -> Result<Self, IndexError> {
+        let from = sample.first().map_or(0, |vector| vector.as_ref().len());
+        if let Some(vector) = sample.iter().find(|v| v.as_ref().len() != from) {
+            return Err(IndexError::DimensionMismatch {
+                expected: from,
+                got: vector.as_ref().len(),
+            });
+        }
+        check_dimensions(from, to)?;
+        if sample.len() < to {
+            return Err(IndexError::InvalidOptions {
+                reason: format!(
+                    "Fitting {} components requires as many samples, got {}",
+                    to,
+                    sample.len()
+                ),
+            });
+        }
+
+        let mut mean = vec![0.0_f64; from];
+        for vector in sample {
+            mean.iter_mut()
+                .zip(vector.as_ref())
+                .for_each(|(sum, &x)| *sum += f64::from(x));
+        }
+        mean.iter_mut().for_each(|sum| *sum /= sample.len() as f64);
+        let centered: Vec<Vec<f64>> = sample
+            .iter()
+            .map(|vector| {
+                vector
+                    .as_ref()
+                    .iter()
+                    .zip(&mean)
+                    .map(|(&x, mean)| f64::from(x) - mean)
+                    .collect()
+            })
+            .collect();
+
+        let mut basis: Vec<f64> = Generator(seed)
+            .signs(to, from)
+            .into_iter()
+            .map(f64::from)
+            .collect();
+        orthonormalize(&mut basis, from);
+        let mut products = vec![0.0_f64; to];
+        let mut next = vec![0.0_f64; to * from];
+        for _ in 0..iterations {
+            // Multiplies the basis by the covariance of the sample, one vector at a time.
+            next.fill(0.0);
+            for vector in &centered {
+                for (product, row) in products.iter_mut().zip(basis.chunks_exact(from)) {
+                    *product = row.iter().zip(vector).map(|(a, b)| a * b).sum();
+                }
+                for (&product, row) in products.iter().zip(next.chunks_exact_mut(from)) {
+                    row.iter_mut()
+                        .zip(vector)
+                        .for_each(|(x, &v)| *x += product * v);
+                }
+            }
+            std::mem::swap(&mut basis, &mut next);
+            orthonormalize(&mut basis, from);
+        }
+
+        Ok(Self {
+            from,
+            to,
+            mean: mean.into_iter().map(|x| x as f32).collect(),
+            matrix: basis.into_iter().map(|x| x as f32).collect(),
+        })
+    }
+
+    /// The number of dimensions of the vectors to project.
+    pub fn from_dimensions(&self) -> usize {
+        self.from
+    }
+
+    /// The number of dimensions of the projected vectors.
+    pub fn to_dimensions(&self) -> usize {
+        self.to
+    }
+
+    /// Projects a vector onto the reduced dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector length differs from the dimensions of the projection.
+    pub fn project(&self, vector: &[f32]) -> Vec<f32> {
+        assert_eq!(
+            vector.len(),
+            self.from,
+            "Vector length must match the projection dimensions"
+        );
+        let centered: Vec<f32> = if self.mean.is_empty() {
+            vector.to_vec()
+        } else {
+            vector.iter().zip(&self.mean).map(|(x, m)| x - m).collect()
+        };
+        self.matrix
+            .chunks_exact(self.from)
+            .map(|row| row.iter().zip(&centered).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    /// Serializes the projection into a writer, as little-endian floats after a header.
+    pub fn save_to_writer<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        let centered = !self.mean.is_empty();
+        for size in [self.from, self.to, centered as usize] {
+            writer.write_all(&(size as u64).to_le_bytes())?;
+        }
+        for value in self.mean.iter().chain(&self.matrix) {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes a projection from a reader, written by [`Projection::save_to_writer`].
+    pub fn load_from_reader<R: Read>(mut reader: R) -> std::io::Result<Self> {
+        let invalid = |message| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("Not a USearch projection"));
+        }
+        let mut sizes = [0_usize; 3];
+        for size in &mut sizes {
+            let mut bytes = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            *size = u64::from_le_bytes(bytes) as usize;
+        }
+        let [from, to, centered] = sizes;
+        if to == 0 || to > from || centered > 1 {
+            return Err(invalid("Corrupted projection header"));
+        }
+        let mut read_floats = |length: usize| -> std::io::Result<Vec<f32>> {
+            let mut values = Vec::with_capacity(length.min(1 << 24));
+            for _ in 0..length {
+                let mut bytes = [0; 4];
+                reader.read_exact(&mut bytes)?;
+                values.push(f32::from_le_bytes(bytes));
+            }
+            Ok(values)
+        };
+        let mean = read_floats(from * centered)?;
+        let matrix = read_floats(from.saturating_mul(to))?;
+        Ok(Self {
+            from,
+            to,
+            mean,
+            matrix,
+        })
+    }
+
+    /// Saves the projection to a specified file.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save_to_writer(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Loads a projection from a specified file.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::load_from_reader(BufReader::new(File::open(path)?))
+    }
+}
+
+/// An index over projected vectors, projecting insertions and queries alike.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::reduce::{Projection, ReducedIndex};
+/// use usearch::{IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 32,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let projection = Projection::random(256, 32, 7).unwrap();
+/// let index = ReducedIndex::new(&options, projection).unwrap();
+/// index.add(1, &vec![1.0; 256]).unwrap();
+/// index.add(2, &vec![-1.0; 256]).unwrap();
+///
+/// let matches = index.search(&vec![0.9; 256], 1).unwrap();
+/// assert_eq!(matches.keys, vec![1]);
+/// ```
+pub struct ReducedIndex {
+    index: Index,
+    projection: Projection,
+}
+
+impl ReducedIndex {
+    /// Creates an empty index over vectors of the projected dimensions.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the projection doesn't map onto the index dimensions.
+    pub fn new(options: &IndexOptions, projection: Projection) -> Result<Self, IndexError> {
+        if projection.to_dimensions() != options.dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: options.dimensions,
+                got: projection.to_dimensions(),
+            });
+        }
+        Ok(Self {
+            index: Index::new(options)?,
+            projection,
+        })
+    }
+
+    /// The underlying index over the projected vectors.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// The projection applied to insertions and queries.
+    pub fn projection(&self) -> &Projection {
+        &self.projection
+    }
+
+    fn project(&self, vector: &[f32]) -> Result<Vec<f32>, IndexError> {
+        if vector.len() != self.projection.from_dimensions() {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.projection.from_dimensions(),
+                got: vector.len(),
+            });
+        }
+        Ok(self.projection.project(vector))
+    }
+
+    /// Reserves memory for the specified number of vectors.
+    pub fn reserve(&self, capacity: usize) -> Result<(), cxx::Exception> {
+        self.index.reserve(capacity)
+    }
+
+    /// Projects a vector and adds it under the specified key.
+    pub fn add(&self, key: Key, vector: &[f32]) -> Result<(), IndexError> {
+        let projected = self.project(vector)?;
+        self.index.add(key, &projected)
+    }
+
+    /// Removes the vectors stored under a key.
+    pub fn remove(&self, key: Key) -> Result<usize, IndexError> {
+        Ok(self.index.remove(key)?)
+    }
+
+    /// Projects the query and searches for its nearest neighbors.
+    pub fn search(&self, query: &[f32], count: usize) -> Result<Matches, IndexError> {
+        let projected = self.project(query)?;
+        self.index.search(&projected, count)
+    }
+
+    /// Projects the query and searches for its nearest neighbors accepted by the filter.
+    pub fn filtered_search<F>(
+        &self,
+        query: &[f32],
+        count: usize,
+        filter: F,
+    ) -> Result<Matches, IndexError>
+    where
+        F: Fn(Key) -> bool,
+    {
+        let projected = self.project(query)?;
+        self.index.filtered_search(&projected, count, filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetricKind, ScalarKind};
+
+    #[test]
+    fn test_projections() {
+        let squared =
+            |a: &[f32], b: &[f32]| -> f32 { a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum() };
+
+        // Random projections roughly preserve distances, on average.
+        let mut generator = Generator(1);
+        let vectors: Vec<Vec<f32>> = (0..100).map(|_| generator.signs(1, 512)).collect();
+        let projection = Projection::random(512, 128, 3).unwrap();
+        let projected: Vec<Vec<f32>> = vectors.iter().map(|v| projection.project(v)).collect();
+        let ratio = (1..vectors.len())
+            .map(|i| squared(&projected[0], &projected[i]) / squared(&vectors[0], &vectors[i]))
+            .sum::<f32>()
+            / (vectors.len() - 1) as f32;
+        assert!((ratio - 1.0).abs() < 0.1, "{ratio}");
+        assert_ne!(Projection::random(512, 128, 4).unwrap(), projection);
+        assert!(Projection::random(4, 8, 0).is_err());
+
+        // Data spanning a few directions keeps its distances exactly through PCA.
+        let directions = [generator.signs(1, 64), generator.signs(1, 64)];
+        let sample: Vec<Vec<f32>> = (0..50)
+            .map(|i| {
+                let (a, b) = ((i % 7) as f32, (i / 7) as f32 - 3.0);
+                (0..64)
+                    .map(|d| 1.0 + a * directions[0][d] + b * directions[1][d])
+                    .collect()
+            })
+            .collect();
+        let pca = Projection::pca(&sample, 4, 10, 0).unwrap();
+        for (a, b) in [(0, 1), (3, 40), (12, 27)] {
+            let original = squared(&sample[a], &sample[b]);
+            let reduced = squared(&pca.project(&sample[a]), &pca.project(&sample[b]));
+            assert!(
+                (original - reduced).abs() < 1e-3 * original,
+                "{original} vs {reduced}"
+            );
+        }
+        assert!(Projection::pca(&sample[..2], 4, 10, 0).is_err());
+
+        let mut buffer = Vec::new();
+        pca.save_to_writer(&mut buffer).unwrap();
+        assert_eq!(Projection::load_from_reader(&buffer[..]).unwrap(), pca);
+        buffer.clear();
+        projection.save_to_writer(&mut buffer).unwrap();
+        assert_eq!(
+            Projection::load_from_reader(&buffer[..]).unwrap(),
+            projection
+        );
+
+        let options = IndexOptions {
+            dimensions: 128,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = ReducedIndex::new(&options, projection.clone()).unwrap();
+        for (key, vector) in vectors.iter().enumerate() {
+            index.add(key as Key, vector).unwrap();
+        }
+        assert_eq!(index.index().dimensions(), 128);
+        for key in [0, 42, 99] {
+            let matches = index.search(&vectors[key], 1).unwrap();
+            assert_eq!(matches.keys, vec![key as Key]);
+        }
+        let matches = index
+            .filtered_search(&vectors[5], 2, |key| key != 5)
+            .unwrap();
+        assert!(!matches.keys.contains(&5));
+        assert!(index.add(100, &[0.0; 128]).is_err());
+        assert!(ReducedIndex::new(&options, pca).is_err());
+    }
+}
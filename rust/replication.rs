@@ -0,0 +1,601 @@
+//! Read replicas following a writer through the changes since a sequence number.
+//!
+//! Shipping a full snapshot to every replica after each batch of updates costs as much as the
+//! index itself. Once [`Index::enable_replication`] is called, every mutation of a key stamps it
+//! with the next sequence number, and [`Index::changes_since`] collects the current vectors, or
+//! the removal, of the keys stamped after a sequence the replica already holds. The replica
+//! applies them with [`Index::apply`] and asks for the changes since [`ChangeLog::sequence`] next.
+//!
+//! Only the last change to every key is kept, so change logs grow with the number of keys
+//! touched rather than the number of mutations. Removed keys are remembered to report their
+//! removal to replicas lagging behind. The sequence numbers live in memory, and start over when
+//! the writer restarts, in which case replicas reload a snapshot instead.
+//!
+//! [`ChangeLog::to_bytes`] encodes a log as `USREPL01`, a header of the sequence range and the
+//! vector layout, the changes, and a CRC32 of everything before it.
+
+use std::collections::HashMap;
+use std::sync::PoisonError;
+
+use crate::checksum::crc32;
+use crate::{b1x8, f16, Index, IndexError, Key, ScalarKind, VectorType};
+
+const MAGIC: &[u8; 8] = b"USREPL01";
+
+/// Bytes of the header: the magic, both sequence numbers, the dimensions, the scalar kind, and
+/// the number of changes.
+const HEADER_LENGTH: usize = MAGIC.len() + 8 + 8 + 8 + 1 + 8;
+
+const OP_PUT: u8 = 1;
+const OP_REMOVE: u8 = 2;
+
+/// The sequence of the last mutation, and the sequence of the last change to every key.
+#[derive(Default)]
+pub(crate) struct ReplicationLog {
+    sequence: u64,
+    changed: HashMap<Key, u64>,
+}
+
+impl ReplicationLog {
+    fn stamp(&mut self, key: Key) {
+        self.sequence += 1;
+        self.changed.insert(key, self.sequence);
+    }
+}
+
+/// The state of a key after its last change, as collected by [`Index::changes_since`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The key holds these vectors, as the raw bytes of [`ChangeLog::quantization`].
+    Put {
+        key: Key,
+        sequence: u64,
+        vectors: Vec<u8>,
+    },
+    /// The key was removed, or renamed to another one.
+    Remove { key: Key, sequence: u64 },
+}
+
+impl Change {
+    /// The key that changed.
+    pub fn key(&self) -> Key {
+        match *self {
+            Change::Put { key, .. } | Change::Remove { key, .. } => key,
+        }
+    }
+
+    /// The sequence number of the last change to the key.
+    pub fn sequence(&self) -> u64 {
+        match *self {
+            Change::Put { sequence, .. } | Change::Remove { sequence, .. } => sequence,
+        }
+    }
+}
+
+/// The changes of an index between two sequence numbers, ordered by sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeLog {
+    /// The sequence the changes were collected after.
+    pub since: u64,
+    /// The sequence of the last mutation included, to collect the next changes after.
+    pub sequence: u64,
+    /// Number of dimensions of the vectors.
+    pub dimensions: usize,
+    /// The scalar kind the vectors are encoded in, which is the one they are stored in, or
+    /// [`ScalarKind::F32`] for kinds without a Rust counterpart, like `bf16`.
+    pub quantization: ScalarKind,
+    /// The last change to every key, in increasing sequence order.
+    pub changes: Vec<Change>,
+}
+
+impl ChangeLog {
+    /// Encodes the log to be sent to a replica.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH + 4);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&self.since.to_le_bytes());
+        bytes.extend_from_slice(&self.sequence.to_le_bytes());
+        bytes.extend_from_slice(&(self.dimensions as u64).to_le_bytes());
+        bytes.push(self.quantization.repr as u8);
+        bytes.extend_from_slice(&(self.changes.len() as u64).to_le_bytes());
+        for change in &self.changes {
+            let (op, vectors): (u8, &[u8]) = match change {
+                Change::Put { vectors, .. } => (OP_PUT, vectors),
+                Change::Remove { .. } => (OP_REMOVE, &[]),
+            };
+            bytes.push(op);
+            bytes.extend_from_slice(&change.key().to_le_bytes());
+            bytes.extend_from_slice(&change.sequence().to_le_bytes());
+            bytes.extend_from_slice(&(vectors.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(vectors);
+        }
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a log written by [`ChangeLog::to_bytes`].
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::IncompatibleFormat` if the bytes aren't a change log, and `IndexError::Io`
+    /// if they are truncated or corrupted.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, IndexError> {
+        if !bytes.starts_with(MAGIC) {
+            let found = bytes.get(..MAGIC.len()).unwrap_or(bytes);
+            return Err(IndexError::IncompatibleFormat {
+                found: String::from_utf8_lossy(found).into_owned(),
+                expected: String::from_utf8_lossy(MAGIC).into_owned(),
+            });
+        }
+        if bytes.len() < HEADER_LENGTH + 4 {
+            return Err(malformed_log());
+        }
+        let (contents, checksum) = bytes.split_at(bytes.len() - 4);
+        if crc32(contents) != u32::from_le_bytes(checksum.try_into().unwrap()) {
+            return Err(malformed_log());
+        }
+
+        let mut reader = Fields(&contents[MAGIC.len()..]);
+        let since = reader.u64()?;
+        let sequence = reader.u64()?;
+        let dimensions = reader.u64()? as usize;
+        let quantization = ScalarKind {
+            repr: reader.take(1)?[0] as i32,
+        };
+        let count = reader.u64()? as usize;
+        let mut changes = Vec::with_capacity(count.min(reader.0.len() / 25));
+        for _ in 0..count {
+            let op = reader.take(1)?[0];
+            let key = reader.u64()?;
+            let sequence = reader.u64()?;
+            let length = reader.u64()? as usize;
+            let vectors = reader.take(length)?.to_vec();
+            changes.push(match op {
+                OP_PUT => Change::Put {
+                    key,
+                    sequence,
+                    vectors,
+                },
+                OP_REMOVE => Change::Remove { key, sequence },
+                _ => return Err(malformed_log()),
+            });
+        }
+        if !reader.0.is_empty() {
+            return Err(malformed_log());
+        }
+        Ok(Self {
+            since,
+            sequence,
+            dimensions,
+            quantization,
+            changes,
+        })
+    }
+}
+
+/// The fields of an encoded log not read yet.
+struct Fields<'a>(&'a [u8]);
+
+impl<'a> Fields<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], IndexError> {
+        if length > self.0.len() {
+            return Err(malformed_log());
+        }
+        let (field, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(field)
+    }
+
+    fn u64(&mut self) -> Result<u64, IndexError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+impl Index {
+    /// Starts stamping mutations with sequence numbers, for [`Index::changes_since`].
+    ///
+    /// Every key already present is stamped once, so that the changes since zero hold the
+    /// whole index. Calling it again keeps the current sequence numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let options = IndexOptions { dimensions: 2, ..Default::default() };
+    /// let writer = Index::new(&options).unwrap();
+    /// writer.enable_replication();
+    /// writer.add(1, &[0.6_f32, 0.8]).unwrap();
+    ///
+    /// let replica = Index::new(&options).unwrap();
+    /// let changes = writer.changes_since(0).unwrap();
+    /// replica.apply(&changes).unwrap();
+    /// assert!(replica.contains(1));
+    ///
+    /// writer.remove(1).unwrap();
+    /// replica.apply(&writer.changes_since(changes.sequence).unwrap()).unwrap();
+    /// assert!(!replica.contains(1));
+    /// ```
+    pub fn enable_replication(&self) {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let mut replication = self
+            .replication
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if replication.is_none() {
+            let mut log = ReplicationLog::default();
+            let mut keys = self.keys();
+            keys.sort_unstable();
+            keys.dedup();
+            for key in keys {
+                log.stamp(key);
+            }
+            *replication = Some(log);
+        }
+    }
+
+    /// The sequence number of the last mutation, or zero before [`Index::enable_replication`].
+    pub fn sequence(&self) -> u64 {
+        let replication = self
+            .replication
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        replication.as_ref().map_or(0, |log| log.sequence)
+    }
+
+    /// Collects the keys changed after a sequence number, with their current vectors.
+    ///
+    /// Mutations wait for the changes to be collected, as they do for [`Index::snapshot`].
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if replication isn't enabled, or if the sequence is ahead of
+    /// the index, as happens once the writer restarted.
+    pub fn changes_since(&self, sequence: u64) -> Result<ChangeLog, IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let replication = self
+            .replication
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let log = replication
+            .as_ref()
+            .ok_or_else(|| IndexError::InvalidOptions {
+                reason: "replication isn't enabled, call `Index::enable_replication` first".into(),
+            })?;
+        if sequence > log.sequence {
+            return Err(IndexError::InvalidOptions {
+                reason: format!(
+                    "sequence {} is ahead of the index, which is at {}",
+                    sequence, log.sequence
+                ),
+            });
+        }
+
+        let mut changed: Vec<(u64, Key)> = log
+            .changed
+            .iter()
+            .filter(|&(_, &stamped)| stamped > sequence)
+            .map(|(&key, &stamped)| (stamped, key))
+            .collect();
+        changed.sort_unstable();
+        let mut changes = Vec::with_capacity(changed.len());
+        for (sequence, key) in changed {
+            changes.push(if self.contains(key) {
+                let mut vectors = Vec::new();
+                self.export_raw(key, &mut vectors)?;
+                Change::Put {
+                    key,
+                    sequence,
+                    vectors,
+                }
+            } else {
+                Change::Remove { key, sequence }
+            });
+        }
+        Ok(ChangeLog {
+            since: sequence,
+            sequence: log.sequence,
+            dimensions: self.dimensions(),
+            quantization: self.raw_kind(),
+            changes,
+        })
+    }
+
+    /// Applies the changes collected from another index, replacing the vectors of their keys.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` or `IndexError::IncompatibleFormat` if the vectors of the
+    /// log don't match the layout of the index, and `IndexError::Io` if they are malformed.
+    pub fn apply(&self, log: &ChangeLog) -> Result<(), IndexError> {
+        if log.dimensions != self.dimensions() {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.dimensions(),
+                got: log.dimensions,
+            });
+        }
+        let quantization = self.raw_kind();
+        if log.quantization != quantization {
+            return Err(IndexError::IncompatibleFormat {
+                found: format!("{:?} vectors", log.quantization),
+                expected: format!("{:?} vectors", quantization),
+            });
+        }
+        for change in &log.changes {
+            self.remove(change.key())?;
+            if let Change::Put { key, vectors, .. } = change {
+                if !self.insert_raw(*key, vectors)? {
+                    return Err(malformed_log());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Notes keys whose vectors changed, for [`Index::sync`] and [`Index::changes_since`].
+    pub(crate) fn track_changes(&self, keys: &[Key]) {
+        #[cfg(not(no_fs))]
+        self.journal_changes(keys);
+        let mut replication = self
+            .replication
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(log) = replication.as_mut() {
+            for &key in keys {
+                log.stamp(key);
+            }
+        }
+    }
+
+    /// Stamps every key known before or after the contents of the index were replaced.
+    pub(crate) fn track_replaced(&self) {
+        let mut replication = self
+            .replication
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(log) = replication.as_mut() {
+            let mut keys: Vec<Key> = log.changed.keys().copied().collect();
+            keys.extend(self.keys());
+            keys.sort_unstable();
+            keys.dedup();
+            for key in keys {
+                log.stamp(key);
+            }
+        }
+    }
+
+    /// The scalar kind of the bytes exchanged by [`Index::export_raw`] and [`Index::insert_raw`].
+    ///
+    /// Kinds the bridge can't express, like `bf16`, are reported as `Unknown` and exchanged as
+    /// `f32`, which the native layer casts from and to the stored kind.
+    pub(crate) fn raw_kind(&self) -> ScalarKind {
+        match self.inner.scalar_kind() {
+            kind @ (ScalarKind::B1 | ScalarKind::I8 | ScalarKind::F16 | ScalarKind::F64) => kind,
+            _ => ScalarKind::F32,
+        }
+    }
+
+    /// Appends the vectors of a key, as the raw bytes of [`Index::raw_kind`].
+    ///
+    /// Callers hold the growth lock, as they collect several keys at once.
+    pub(crate) fn export_raw(&self, key: Key, bytes: &mut Vec<u8>) -> Result<(), IndexError> {
+        match self.raw_kind() {
+            ScalarKind::B1 => self.export_bytes::<b1x8>(key, bytes),
+            ScalarKind::I8 => self.export_bytes::<i8>(key, bytes),
+            ScalarKind::F16 => self.export_bytes::<f16>(key, bytes),
+            ScalarKind::F64 => self.export_bytes::<f64>(key, bytes),
+            _ => self.export_bytes::<f32>(key, bytes),
+        }
+    }
+
+    /// Adds the vectors exported by [`Index::export_raw`] under a key, or returns `false` if
+    /// the bytes don't hold a whole number of vectors.
+    pub(crate) fn insert_raw(&self, key: Key, bytes: &[u8]) -> Result<bool, IndexError> {
+        match self.raw_kind() {
+            ScalarKind::B1 => self.insert_bytes::<b1x8>(key, bytes),
+            ScalarKind::I8 => self.insert_bytes::<i8>(key, bytes),
+            ScalarKind::F16 => self.insert_bytes::<f16>(key, bytes),
+            ScalarKind::F64 => self.insert_bytes::<f64>(key, bytes),
+            _ => self.insert_bytes::<f32>(key, bytes),
+        }
+    }
+
+    fn export_bytes<T: VectorType + Default + Clone>(
+        &self,
+        key: Key,
+        bytes: &mut Vec<u8>,
+    ) -> Result<(), IndexError> {
+        let mut vectors: Vec<T> = Vec::new();
+        self.export_unlocked(key, &mut vectors)?;
+        // All supported scalar types are plain old data.
+        let exported = unsafe {
+            std::slice::from_raw_parts(
+                vectors.as_ptr() as *const u8,
+                std::mem::size_of_val(vectors.as_slice()),
+            )
+        };
+        bytes.extend_from_slice(exported);
+        Ok(())
+    }
+
+    fn insert_bytes<T: VectorType + Copy>(
+        &self,
+        key: Key,
+        bytes: &[u8],
+    ) -> Result<bool, IndexError> {
+        let dimensions = self.dimensions();
+        if !bytes
+            .len()
+            .is_multiple_of(dimensions * std::mem::size_of::<T>())
+        {
+            return Ok(false);
+        }
+        for vector in decode::<T>(bytes).chunks_exact(dimensions) {
+            self.add(key, vector)?;
+        }
+        Ok(true)
+    }
+}
+
+/// Copies raw bytes into a properly aligned vector of plain-old-data scalars.
+pub(crate) fn decode<T: Copy>(bytes: &[u8]) -> Vec<T> {
+    let count = bytes.len() / std::mem::size_of::<T>();
+    let mut decoded = Vec::<T>::with_capacity(count);
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            bytes.as_ptr(),
+            decoded.as_mut_ptr() as *mut u8,
+            count * std::mem::size_of::<T>(),
+        );
+        decoded.set_len(count);
+    }
+    decoded
+}
+
+fn malformed_log() -> IndexError {
+    IndexError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Malformed replication change log",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind};
+
+    #[test]
+    fn test_replication() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            multi: true,
+            ..Default::default()
+        };
+        let writer = Index::new(&options).unwrap();
+        assert!(matches!(
+            writer.changes_since(0),
+            Err(IndexError::InvalidOptions { .. })
+        ));
+        for key in 0..10u64 {
+            writer.add(key, &[key as f32; 4]).unwrap();
+        }
+        writer.enable_replication();
+        assert_eq!(writer.sequence(), 10);
+
+        let replica = Index::new(&options).unwrap();
+        let initial = writer.changes_since(0).unwrap();
+        assert_eq!((initial.since, initial.sequence), (0, 10));
+        assert_eq!(initial.changes.len(), 10);
+        replica.apply(&initial).unwrap();
+        assert_eq!(replica.size(), 10);
+
+        // Only the last change to every key is shipped, in sequence order.
+        writer.add(3, &[30.0_f32; 4]).unwrap();
+        writer.add(3, &[31.0_f32; 4]).unwrap();
+        writer.remove(5).unwrap();
+        writer.rename(7, 70).unwrap();
+        writer.add(11, &[11.0_f32; 4]).unwrap();
+        let delta = writer.changes_since(initial.sequence).unwrap();
+        assert_eq!(delta.sequence, writer.sequence());
+        let keys: Vec<Key> = delta.changes.iter().map(Change::key).collect();
+        assert_eq!(keys, vec![3, 5, 7, 70, 11]);
+        assert!(delta
+            .changes
+            .windows(2)
+            .all(|w| w[0].sequence() < w[1].sequence()));
+        assert!(matches!(delta.changes[1], Change::Remove { key: 5, .. }));
+
+        let decoded = ChangeLog::from_bytes(&delta.to_bytes()).unwrap();
+        assert_eq!(decoded, delta);
+        replica.apply(&decoded).unwrap();
+        let mut expected = writer.keys();
+        let mut replicated = replica.keys();
+        expected.sort_unstable();
+        replicated.sort_unstable();
+        assert_eq!(replicated, expected);
+        let mut vectors: Vec<f32> = Vec::new();
+        replica.export(3, &mut vectors).unwrap();
+        assert_eq!(vectors.len(), 12);
+        assert_eq!(replica.search(&[7.0_f32; 4], 1).unwrap().keys, vec![70]);
+
+        // Replacing the contents ships every key, present or gone.
+        let mut buffer = vec![0; writer.serialized_length()];
+        writer.save_to_buffer(&mut buffer).unwrap();
+        let mut writer = writer;
+        let position = writer.sequence();
+        writer.reset().unwrap();
+        let cleared = writer.changes_since(position).unwrap();
+        assert!(cleared
+            .changes
+            .iter()
+            .all(|change| matches!(change, Change::Remove { .. })));
+        replica.apply(&cleared).unwrap();
+        assert_eq!(replica.size(), 0);
+        writer.load_from_buffer(&buffer).unwrap();
+        replica
+            .apply(&writer.changes_since(cleared.sequence).unwrap())
+            .unwrap();
+        assert_eq!(replica.size(), writer.size());
+        assert!(writer
+            .changes_since(writer.sequence())
+            .unwrap()
+            .changes
+            .is_empty());
+        assert!(writer.changes_since(writer.sequence() + 1).is_err());
+
+        let mut corrupted = delta.to_bytes();
+        corrupted[HEADER_LENGTH] ^= 1;
+        assert!(matches!(
+            ChangeLog::from_bytes(&corrupted),
+            Err(IndexError::Io(_))
+        ));
+        assert!(matches!(
+            ChangeLog::from_bytes(b"not a change log"),
+            Err(IndexError::IncompatibleFormat { .. })
+        ));
+        let other = Index::new(&IndexOptions {
+            dimensions: 3,
+            ..options.clone()
+        })
+        .unwrap();
+        assert!(matches!(
+            other.apply(&delta),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_failed_batch_ships_inserted_keys() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let writer = Index::new(&options).unwrap();
+        writer.add(2, &[2.0_f32; 2]).unwrap();
+        writer.enable_replication();
+        let position = writer.sequence();
+
+        // The repeated key fails the batch, which may still insert the other vectors.
+        let keys = [1, 2, 3, 4];
+        let vectors: Vec<f32> = keys.iter().flat_map(|&key| [key as f32; 2]).collect();
+        assert!(writer.add_many(&keys, &vectors).is_err());
+        let mut shipped: Vec<Key> = writer
+            .changes_since(position)
+            .unwrap()
+            .changes
+            .iter()
+            .map(Change::key)
+            .collect();
+        shipped.sort_unstable();
+        let inserted: Vec<Key> = [1, 3, 4]
+            .into_iter()
+            .filter(|&key| writer.contains(key))
+            .collect();
+        assert_eq!(shipped, inserted);
+    }
+}
@@ -0,0 +1,96 @@
+//! Converting an existing index to another scalar kind.
+//!
+//! Memory budgets often tighten after the embeddings were indexed and discarded. Rather than
+//! re-embedding the corpus, [`Index::convert_to`] reads the stored vectors back, and inserts
+//! them into a fresh index of narrower scalars, like `f16` or `i8`, under the same keys.
+
+use crate::{Index, IndexError, IndexOptions, ScalarKind};
+
+impl Index {
+    /// Creates a copy of the index storing its vectors as `quantization`.
+    ///
+    /// Vectors are read back as `f32`, or `f64` from `F64` indexes, and re-quantized by the
+    /// new index as they are inserted, in parallel, like with [`Index::rebuild`]. Keys, including repeated ones of `multi`
+    /// indexes, and all other options are preserved, except for custom metrics, which read a
+    /// specific scalar kind: the copy compares vectors with the metric kind of the index, and
+    /// needs [`Index::change_metric`] to use another one. Converting to narrower scalars loses
+    /// precision, and converting back doesn't restore it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::Cos,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// for key in 0..100u64 {
+    ///     index.add(key, &[1.0_f32, key as f32 / 100.0]).unwrap();
+    /// }
+    ///
+    /// let smaller = index.convert_to(ScalarKind::I8).unwrap();
+    /// assert_eq!(smaller.size(), 100);
+    /// assert!(smaller.memory_usage() < index.memory_usage());
+    /// assert_eq!(smaller.search(&[1.0_f32, 0.5], 1).unwrap().keys, vec![50]);
+    /// ```
+    pub fn convert_to(&self, quantization: ScalarKind) -> Result<Index, IndexError> {
+        self.rebuild(&IndexOptions {
+            quantization,
+            ..self.options()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{f16, Index, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_convert_to() {
+        let options = IndexOptions {
+            dimensions: 4,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            multi: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(301).unwrap();
+        for key in 0..300u64 {
+            let x = key as f32 / 300.0;
+            index.add(key, &[x, 1.0 - x, 0.5, -x]).unwrap();
+        }
+        index.add(7, &[0.25_f32, 0.25, 0.25, 0.25]).unwrap();
+        index.remove(299).unwrap();
+
+        let half = index.convert_to(ScalarKind::F16).unwrap();
+        assert_eq!(half.size(), 300);
+        assert_eq!(half.count(7), 2);
+        assert!(!half.contains(299));
+        assert_eq!(half.options().quantization, ScalarKind::F16);
+        assert!(half.options().multi);
+        assert!(half.memory_usage() < index.memory_usage());
+        let mut vector = [f16::default(); 4];
+        half.get(150, &mut vector).unwrap();
+        assert_eq!(vector, [0.5, 0.5, 0.5, -0.5].map(f16::from_f32));
+        let matches = half.search(&[0.1_f32, 0.9, 0.5, -0.1], 1).unwrap();
+        assert_eq!(matches.keys, vec![30]);
+
+        // Narrower scalars lose precision, but keep the neighborhoods.
+        let bytes = index.convert_to(ScalarKind::I8).unwrap();
+        let widened = bytes.convert_to(ScalarKind::F64).unwrap();
+        assert_eq!(widened.size(), 300);
+        let mut vector = [0.0_f64; 4];
+        widened.get(150, &mut vector).unwrap();
+        assert!(vector
+            .iter()
+            .zip([0.5, 0.5, 0.5, -0.5])
+            .all(|(a, b)| (a - b).abs() < 0.01));
+        let matches = widened.search(&[0.1_f64, 0.9, 0.5, -0.1], 3).unwrap();
+        assert!(matches.keys.contains(&30));
+    }
+}
@@ -0,0 +1,694 @@
+//! Configurable searches, returning more than keys and distances when asked to.
+//!
+//! [`Index::search`] mirrors the native call and only reports keys and distances.
+//! [`Index::search_with`] takes [`SearchParams`] instead, and can attach the stored
+//! vectors of every match to the [`SearchResults`], for client-side re-ranking or
+//! visualization, without a separate `get` call per match. It can also re-rank a larger pool
+//! of candidates with a secondary metric, when the graph is built with a cheaper one, which
+//! [`Index::search_rerank`] wraps into a single call. [`SearchParams::with_stats`] attaches
+//! the cost of the graph traversal, in nodes visited, distances computed, and hops taken, to
+//! tune the expansion factors with measurements rather than guesses.
+//!
+//! Both kinds of results can be walked as [`ResultElement`]s, which convert their distance
+//! into a normalized similarity for the metric of the index.
+
+use std::sync::Arc;
+
+use crate::convert::Scalar;
+use crate::exact;
+use crate::ffi::Matches;
+use crate::metrics::MetricType;
+use crate::{Distance, Index, IndexError, Key, MetricKind, SearchStats, VectorType};
+
+/// A single match of a search, with its key and raw distance.
+///
+/// The key is the one of the index, unless translated into application identifiers with
+/// [`Matches::map_keys`]. Elements are ordered by distance, closest first, and by key between
+/// equal distances.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResultElement<K = Key> {
+    /// The key of the matched vector.
+    pub key: K,
+    /// The distance from the query to the matched vector.
+    pub distance: Distance,
+}
+
+impl<K> ResultElement<K> {
+    /// The distance converted to a similarity in the `[0, 1]` range, for the given metric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::Cos,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[1.0_f32, 0.0]).unwrap();
+    /// index.add(2, &[0.0_f32, 1.0]).unwrap();
+    ///
+    /// let matches = index.search(&[1.0_f32, 0.1], 2).unwrap();
+    /// let relevant: Vec<_> = matches
+    ///     .elements()
+    ///     .filter(|element| element.similarity(&options.metric) > 0.9)
+    ///     .map(|element| element.key)
+    ///     .collect();
+    /// assert_eq!(relevant, vec![1]);
+    /// ```
+    pub fn similarity<M: MetricType + ?Sized>(&self, metric: &M) -> f32 {
+        metric.similarity(self.distance)
+    }
+}
+
+impl<K: Ord> PartialOrd for ResultElement<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.distance.partial_cmp(&other.distance)? {
+            std::cmp::Ordering::Equal => Some(self.key.cmp(&other.key)),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+/// Accessors shared by the result types, ordered from the closest match.
+macro_rules! result_accessors {
+    () => {
+        /// Iterates over the matches, from the closest one.
+        pub fn elements(&self) -> impl Iterator<Item = ResultElement> + '_ {
+            elements(&self.keys, &self.distances)
+        }
+
+        /// The closest match, if any.
+        pub fn best(&self) -> Option<ResultElement> {
+            self.elements().next()
+        }
+
+        /// Keys of the matched vectors.
+        pub fn keys(&self) -> &[Key] {
+            &self.keys
+        }
+
+        /// Distances from the query to the matched vectors.
+        pub fn distances(&self) -> &[Distance] {
+            &self.distances
+        }
+
+        /// Translates the keys of the matches, like into the identifiers of an external
+        /// database, keeping their distances and order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+        ///
+        /// let options = IndexOptions {
+        ///     dimensions: 1,
+        ///     metric: MetricKind::L2sq,
+        ///     quantization: ScalarKind::F32,
+        ///     ..Default::default()
+        /// };
+        /// let index = Index::new(&options).unwrap();
+        /// index.add(0, &[0.0_f32]).unwrap();
+        /// index.add(1, &[1.0_f32]).unwrap();
+        ///
+        /// let names = ["zero", "one"];
+        /// let matches = index.search(&[0.75_f32], 2).unwrap();
+        /// let named = matches.map_keys(|key| names[key as usize]);
+        /// assert_eq!(named[0].key, "one");
+        /// assert_eq!(named[1].distance, 0.5625);
+        /// ```
+        pub fn map_keys<K, F>(&self, mut f: F) -> Vec<ResultElement<K>>
+        where
+            F: FnMut(Key) -> K,
+        {
+            self.elements()
+                .map(|element| ResultElement {
+                    key: f(element.key),
+                    distance: element.distance,
+                })
+                .collect()
+        }
+
+        /// Number of matches.
+        pub fn len(&self) -> usize {
+            self.keys.len()
+        }
+
+        /// Whether nothing matched.
+        pub fn is_empty(&self) -> bool {
+            self.keys.is_empty()
+        }
+    };
+}
+
+impl Matches {
+    result_accessors!();
+
+    /// Converts the distances of all matches to similarities for the given metric.
+    pub fn similarities<M: MetricType + ?Sized>(&self, metric: &M) -> Vec<f32> {
+        self.elements()
+            .map(|element| element.similarity(metric))
+            .collect()
+    }
+}
+
+/// Lists the matches as `key: distance` pairs, from the closest one, like `[42: 0.5, 7: 1.25]`.
+impl std::fmt::Display for Matches {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[")?;
+        for (position, element) in self.elements().enumerate() {
+            if position > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}: {}", element.key, element.distance)?;
+        }
+        f.write_str("]")
+    }
+}
+
+pub(crate) fn elements<'a>(
+    keys: &'a [Key],
+    distances: &'a [Distance],
+) -> impl Iterator<Item = ResultElement> + 'a {
+    keys.iter()
+        .zip(distances)
+        .map(|(&key, &distance)| ResultElement { key, distance })
+}
+
+/// A custom distance function over decoded `f32` vectors.
+pub type RerankFunction = Arc<dyn Fn(&[f32], &[f32]) -> Distance + Send + Sync>;
+
+/// The secondary metric used to re-order the candidates of a search.
+#[derive(Clone)]
+pub enum RerankMetric {
+    /// One of the built-in metrics, evaluated like the scans of [`crate::ExactSearch`].
+    Kind(MetricKind),
+    /// A custom distance function, called with the query and a candidate.
+    Custom(RerankFunction),
+}
+
+impl RerankMetric {
+    /// Computes the distances of the query to every candidate, packed row-major.
+    fn distances(&self, query: &[f32], candidates: &[f32]) -> Vec<Distance> {
+        match self {
+            RerankMetric::Kind(metric) => exact::distances(*metric, query.len(), query, candidates),
+            RerankMetric::Custom(function) => candidates
+                .chunks_exact(query.len().max(1))
+                .map(|candidate| function(query, candidate))
+                .collect(),
+        }
+    }
+}
+
+impl From<MetricKind> for RerankMetric {
+    fn from(metric: MetricKind) -> Self {
+        RerankMetric::Kind(metric)
+    }
+}
+
+impl std::fmt::Debug for RerankMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RerankMetric::Kind(metric) => f.debug_tuple("Kind").field(metric).finish(),
+            RerankMetric::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl PartialEq for RerankMetric {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RerankMetric::Kind(a), RerankMetric::Kind(b)) => a == b,
+            (RerankMetric::Custom(a), RerankMetric::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Parameters of a single [`Index::search_with`] call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchParams {
+    /// The maximum number of neighbors to search for.
+    pub count: usize,
+    /// Whether to return the stored vector of every match.
+    pub include_vectors: bool,
+    /// The metric used to recompute the distances of the candidates before returning them.
+    pub rerank_metric: Option<RerankMetric>,
+    /// The number of candidates fetched for re-ranking, at least `count`.
+    /// Zero stands for four times `count`.
+    pub rerank_pool: usize,
+    /// Whether to return the cost of the graph traversal.
+    pub include_stats: bool,
+}
+
+impl Default for SearchParams {
+    fn default() -> Self {
+        Self {
+            count: 10,
+            include_vectors: false,
+            rerank_metric: None,
+            rerank_pool: 0,
+            include_stats: false,
+        }
+    }
+}
+
+impl SearchParams {
+    /// Creates parameters searching for up to `count` neighbors.
+    pub fn new(count: usize) -> Self {
+        Self {
+            count,
+            ..Default::default()
+        }
+    }
+
+    /// Requests the stored vector of every match.
+    pub fn with_vectors(mut self) -> Self {
+        self.include_vectors = true;
+        self
+    }
+
+    /// Re-ranks the candidates with a secondary metric, fetching `pool` of them first.
+    /// Pass zero as the `pool` to fetch four times `count` candidates.
+    pub fn with_rerank(mut self, metric: RerankMetric, pool: usize) -> Self {
+        self.rerank_metric = Some(metric);
+        self.rerank_pool = pool;
+        self
+    }
+
+    /// Requests the cost of the graph traversal, counted for the candidates when re-ranking.
+    pub fn with_stats(mut self) -> Self {
+        self.include_stats = true;
+        self
+    }
+}
+
+/// Results of [`Index::search_with`], ordered from the closest match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResults<T> {
+    /// Keys of the matched vectors.
+    pub keys: Vec<Key>,
+    /// Distances from the query to the matched vectors.
+    pub distances: Vec<Distance>,
+    /// Stored vectors of the matches, if `SearchParams::include_vectors` was set.
+    /// For keys with several vectors, the first one stored is returned.
+    pub vectors: Option<Vec<Vec<T>>>,
+    /// Cost of the graph traversal, if `SearchParams::include_stats` was set.
+    pub stats: Option<SearchStats>,
+}
+
+impl<T> SearchResults<T> {
+    result_accessors!();
+}
+
+impl Index {
+    /// Performs k-Approximate Nearest Neighbors (kANN) Search with the given parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `params` - The number of neighbors and the extra data to return.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the matches found, with their vectors if requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind, SearchParams};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.reserve(10).unwrap();
+    /// index.add(1, &[1.0_f32, 0.0]).unwrap();
+    ///
+    /// let results = index.search_with(&[0.9_f32, 0.1], &SearchParams::new(1).with_vectors()).unwrap();
+    /// assert_eq!(results.vectors, Some(vec![vec![1.0, 0.0]]));
+    /// ```
+    pub fn search_with<T: VectorType + Scalar + Default + Clone>(
+        &self,
+        query: &[T],
+        params: &SearchParams,
+    ) -> Result<SearchResults<T>, IndexError> {
+        let Some(metric) = &params.rerank_metric else {
+            let (matches, stats) = self.search_counted(query, params.count, params)?;
+            let vectors = if params.include_vectors {
+                Some(self.stored_vectors(&matches.keys)?)
+            } else {
+                None
+            };
+            return Ok(SearchResults {
+                keys: matches.keys,
+                distances: matches.distances,
+                vectors,
+                stats,
+            });
+        };
+
+        let pool = match params.rerank_pool {
+            0 => params.count * 4,
+            pool => pool.max(params.count),
+        };
+        let (candidates, stats) = self.search_counted(query, pool, params)?;
+        let candidate_vectors = self.stored_vectors::<T>(&candidates.keys)?;
+
+        let dimensions = self.dimensions();
+        let mut decoded_query = Vec::with_capacity(dimensions);
+        T::decode(query, dimensions, &mut decoded_query);
+        let mut decoded = Vec::with_capacity(dimensions * candidate_vectors.len());
+        for vector in &candidate_vectors {
+            T::decode(vector, dimensions, &mut decoded);
+        }
+        let distances = metric.distances(&decoded_query, &decoded);
+        let mut ranked: Vec<(Distance, Key, Vec<T>)> = distances
+            .into_iter()
+            .zip(candidates.keys)
+            .zip(candidate_vectors)
+            .map(|((distance, key), vector)| (distance, key, vector))
+            .collect();
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ranked.truncate(params.count);
+
+        let mut results = SearchResults {
+            keys: Vec::with_capacity(ranked.len()),
+            distances: Vec::with_capacity(ranked.len()),
+            vectors: params
+                .include_vectors
+                .then(|| Vec::with_capacity(ranked.len())),
+            stats,
+        };
+        for (distance, key, vector) in ranked {
+            results.keys.push(key);
+            results.distances.push(distance);
+            if let Some(vectors) = &mut results.vectors {
+                vectors.push(vector);
+            }
+        }
+        Ok(results)
+    }
+
+    /// Retrieves `rerank_count` candidates with the metric of the index, then returns the
+    /// `count` closest of them by a secondary metric, evaluated over their stored vectors.
+    /// A shorthand for [`Index::search_with`] with [`SearchParams::with_rerank`].
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to return.
+    /// * `rerank_count` - The number of candidates to re-score, at least `count`.
+    /// * `secondary_metric` - The metric used to re-score, like a `MetricKind` or a [`RerankMetric`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::IP,
+    ///     quantization: ScalarKind::I8,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    /// index.add(2, &[0.8_f32, 0.6]).unwrap();
+    ///
+    /// let matches = index.search_rerank(&[0.7_f32, 0.7], 1, 2, MetricKind::Cos).unwrap();
+    /// assert_eq!(matches.keys.len(), 1);
+    /// ```
+    pub fn search_rerank<T, M>(
+        &self,
+        query: &[T],
+        count: usize,
+        rerank_count: usize,
+        secondary_metric: M,
+    ) -> Result<Matches, IndexError>
+    where
+        T: VectorType + Scalar + Default + Clone,
+        M: Into<RerankMetric>,
+    {
+        let params = SearchParams::new(count).with_rerank(secondary_metric.into(), rerank_count);
+        let results = self.search_with(query, &params)?;
+        Ok(Matches {
+            keys: results.keys,
+            distances: results.distances,
+        })
+    }
+
+    /// Runs the native search, counting its cost if the parameters ask for it.
+    fn search_counted<T: VectorType>(
+        &self,
+        query: &[T],
+        count: usize,
+        params: &SearchParams,
+    ) -> Result<(Matches, Option<SearchStats>), IndexError> {
+        if !params.include_stats {
+            let matches = self.guard_metric(|| T::search(self, query, count))?;
+            return Ok((matches?, None));
+        }
+        let traced = self.guard_metric(|| T::search_traced(self, query, count))??;
+        Ok((traced.matches, Some(traced.stats)))
+    }
+
+    /// Fetches the first stored vector of every key.
+    pub(crate) fn stored_vectors<T: VectorType + Default + Clone>(
+        &self,
+        keys: &[Key],
+    ) -> Result<Vec<Vec<T>>, IndexError> {
+        let dimensions = self.dimensions();
+        let mut buffer = vec![T::default(); dimensions * keys.len()];
+        for (&key, vector) in keys.iter().zip(buffer.chunks_exact_mut(dimensions)) {
+            T::get(self, key, vector)?;
+        }
+        Ok(buffer.chunks_exact(dimensions).map(<[T]>::to_vec).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_search_with_rerank() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(10).unwrap();
+        index.add(1, &[1.0_f32, 0.0]).unwrap();
+        index.add(2, &[4.0_f32, 1.0]).unwrap();
+        index.add(3, &[0.0_f32, 1.0]).unwrap();
+
+        // By Euclidean distance the short vector is closer, but by angle the long one is.
+        let query = [2.0_f32, 0.5];
+        let plain = index.search_with(&query, &SearchParams::new(1)).unwrap();
+        assert_eq!(plain.keys, vec![1]);
+
+        let params = SearchParams::new(2).with_rerank(RerankMetric::Kind(MetricKind::Cos), 3);
+        let reranked = index.search_with(&query, &params.with_vectors()).unwrap();
+        assert_eq!(reranked.keys, vec![2, 1]);
+        assert!(reranked.distances[0] < 1e-6);
+        assert_eq!(reranked.vectors.unwrap()[0], vec![4.0, 1.0]);
+
+        let opposite: RerankFunction = Arc::new(|a, b| a[0] * b[0] + a[1] * b[1]);
+        let params = SearchParams::new(1).with_rerank(RerankMetric::Custom(opposite), 0);
+        let reranked = index.search_with(&query, &params).unwrap();
+        assert_eq!(reranked.keys, vec![3]);
+    }
+
+    #[test]
+    fn test_search_with_vectors() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            multi: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(10).unwrap();
+        index.add(1, &[0.1_f32, 0.2, 0.3]).unwrap();
+        index.add(2, &[0.3_f32, 0.2, 0.1]).unwrap();
+        index.add(2, &[0.4_f32, 0.2, 0.1]).unwrap();
+
+        let plain = index
+            .search_with(&[0.3_f32, 0.2, 0.1], &SearchParams::new(2))
+            .unwrap();
+        assert_eq!(plain.keys, vec![2, 2]);
+        assert!(plain.vectors.is_none());
+
+        let query = [0.1_f32, 0.2, 0.25];
+        let results = index
+            .search_with(&query, &SearchParams::new(3).with_vectors())
+            .unwrap();
+        let vectors = results.vectors.unwrap();
+        assert_eq!(results.keys.len(), vectors.len());
+        assert_eq!(results.keys[0], 1);
+        assert_eq!(vectors[0], vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_search_with_stats() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 4,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..2000u64 {
+            index
+                .add(key, &[(key % 50) as f32, (key / 50) as f32])
+                .unwrap();
+        }
+        let query = [49.0_f32, 39.0];
+        let plain = index.search_with(&query, &SearchParams::new(5)).unwrap();
+        assert!(plain.stats.is_none());
+
+        index.change_expansion_search(10);
+        let narrow = index
+            .search_with(&query, &SearchParams::new(5).with_stats())
+            .unwrap();
+        let stats = narrow.stats.unwrap();
+        assert_eq!(narrow.keys[0], 1999);
+        assert!(stats.visited > 0 && stats.distance_computations >= stats.visited);
+        assert!(stats.hops <= stats.visited);
+
+        // Wider expansions pay with more distance computations.
+        index.change_expansion_search(200);
+        let wide = index
+            .search_with(&query, &SearchParams::new(5).with_stats())
+            .unwrap();
+        assert!(wide.stats.unwrap().distance_computations > stats.distance_computations);
+
+        let params = SearchParams::new(1)
+            .with_rerank(RerankMetric::Kind(MetricKind::Cos), 0)
+            .with_stats();
+        assert!(index.search_with(&query, &params).unwrap().stats.is_some());
+    }
+
+    #[test]
+    fn test_similarities() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add(1, &[0.0_f32, 0.0]).unwrap();
+        index.add(2, &[1.0_f32, 1.0]).unwrap();
+
+        let matches = index.search(&[0.0_f32, 0.0], 2).unwrap();
+        let similarities = matches.similarities(&options.metric);
+        assert_eq!(similarities[0], 1.0);
+        assert!((similarities[1] - (-2.0_f32).exp()).abs() < 1e-6);
+
+        // Custom scales plug in as closures.
+        let linear = |distance: Distance| 1.0 - distance / 4.0;
+        let elements: Vec<_> = matches.elements().collect();
+        assert_eq!(elements[1].key, 2);
+        assert_eq!(elements[1].similarity(&linear), 0.5);
+        assert_eq!(MetricKind::Cos.similarity(1.5), 0.0);
+
+        // Thresholds map back to the largest distance meeting them.
+        for metric in [MetricKind::L2sq, MetricKind::Cos, MetricKind::Haversine] {
+            let distance = metric.distance_for_similarity(0.3);
+            assert!((metric.similarity(distance) - 0.3).abs() < 1e-6);
+        }
+        assert!((linear.distance_for_similarity(0.5) - 2.0).abs() < 1e-6);
+        assert_eq!(
+            MetricKind::L2sq.distance_for_similarity(0.0),
+            Distance::INFINITY
+        );
+        assert_eq!(linear.distance_for_similarity(1.5), Distance::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_result_accessors() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let empty = index.search(&[0.0_f32, 0.0], 3).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.best(), None);
+
+        index.add(1, &[1.0_f32, 0.0]).unwrap();
+        index.add(2, &[0.0_f32, 2.0]).unwrap();
+        index.add(3, &[0.0_f32, -1.5]).unwrap();
+        let matches = index.search(&[0.0_f32, 0.0], 3).unwrap();
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches.keys(), &[1, 3, 2]);
+        assert_eq!(matches.distances(), &[1.0, 2.25, 4.0]);
+        assert_eq!(
+            matches.best(),
+            Some(ResultElement {
+                key: 1,
+                distance: 1.0
+            })
+        );
+        let results = index
+            .search_with(&[0.0_f32, 2.0], &SearchParams::new(2))
+            .unwrap();
+        assert_eq!(results.best().map(|element| element.key), Some(2));
+        assert_eq!(results.len(), 2);
+
+        // Elements order by distance, then by key.
+        let mut elements: Vec<_> = matches.elements().collect();
+        elements.push(ResultElement {
+            key: 0,
+            distance: 2.25,
+        });
+        elements.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let keys: Vec<_> = elements.iter().map(|element| element.key).collect();
+        assert_eq!(keys, vec![1, 0, 3, 2]);
+        assert!(elements[0] < elements[1]);
+
+        // Translated keys keep the distances, and the ordering of their own type.
+        let names = results.map_keys(|key| format!("doc-{key}"));
+        assert_eq!(names[0].key, "doc-2");
+        assert_eq!(names[1].distance, results.distances[1]);
+        assert!(names[0] < names[1]);
+    }
+
+    #[test]
+    fn test_search_rerank() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F16,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.add(1, &[1.0_f32, 0.0]).unwrap();
+        index.add(2, &[4.0_f32, 1.0]).unwrap();
+        index.add(3, &[0.0_f32, 1.0]).unwrap();
+
+        let query = [2.0_f32, 0.5];
+        let matches = index.search_rerank(&query, 2, 3, MetricKind::Cos).unwrap();
+        assert_eq!(matches.keys, vec![2, 1]);
+        assert!(matches.distances[0] < 1e-3);
+
+        // With a single candidate there is nothing to re-order.
+        let matches = index.search_rerank(&query, 1, 1, MetricKind::Cos).unwrap();
+        assert_eq!(matches.keys, vec![1]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Warmup-time self-benchmark of the host the service landed on.
+//!
+//! The same binary can run on very different machines, and the effective SIMD backend is only
+//! picked at runtime. [`self_check`] runs a short micro-benchmark on synthetic data, verifies the
+//! dispatched kernels return sane results, and reports the numbers in a structure that can be logged.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::{capabilities, Capabilities, Index, IndexError, IndexOptions, MetricKind, ScalarKind};
+
+/// Number of dimensions of the synthetic vectors.
+const DIMENSIONS: usize = 256;
+/// Number of synthetic vectors indexed.
+const SAMPLES: usize = 1024;
+/// Number of timed single-vector queries.
+const QUERIES: usize = 128;
+
+/// Performance characteristics and configuration of the host, measured by [`self_check`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfCheckReport {
+    /// Features of the compiled binary, as reported by [`capabilities`].
+    pub capabilities: Capabilities,
+    /// The SIMD instruction set dispatched for `f32` cosine distances, like `"haswell"` or `"serial"`.
+    pub isa: String,
+    /// Whether the dispatched kernels agree with a scalar reference implementation.
+    pub simd_verified: bool,
+    /// Estimated number of `f32` distance evaluations per second, from exhaustive searches.
+    pub distances_per_second: f64,
+    /// Number of vectors inserted per second into an empty index.
+    pub inserts_per_second: f64,
+    /// Median latency of a single top-10 query with the default expansion.
+    pub search_latency_p50: Duration,
+    /// 99th percentile latency of a single top-10 query with the default expansion.
+    pub search_latency_p99: Duration,
+    /// Share of the sampled vectors found as their own closest match.
+    pub recall: f64,
+    /// Wall-clock time spent on the whole check.
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for SelfCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "isa={} verified={} distances/s={:.0} inserts/s={:.0} p50={:?} p99={:?} recall={:.3}",
+            self.isa,
+            self.simd_verified,
+            self.distances_per_second,
+            self.inserts_per_second,
+            self.search_latency_p50,
+            self.search_latency_p99,
+            self.recall
+        )
+    }
+}
+
+/// Deterministic vectors with components in `[-1, 1)`, generated with a xorshift sequence.
+fn synthetic_vectors(count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    (0..count)
+        .map(|_| {
+            (0..dimensions)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    (state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    let (mut ab, mut a2, mut b2) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        ab += x * y;
+        a2 += x * x;
+        b2 += y * y;
+    }
+    1.0 - ab / (a2 * b2).sqrt()
+}
+
+fn percentile(sorted: &[Duration], share: f64) -> Duration {
+    let position = ((sorted.len() - 1) as f64 * share).round() as usize;
+    sorted[position]
+}
+
+/// Runs a short micro-benchmark and configuration check, meant to be called once on startup.
+///
+/// Builds a 1024-vector index of 256-dimensional `f32` vectors, times insertions, single-query
+/// searches, and exhaustive searches, then compares the returned distances against a scalar
+/// reference implementation. Takes well under a second on most hosts.
+///
+/// # Returns
+///
+/// A [`SelfCheckReport`], or the first error raised by the native layer.
+///
+/// # Examples
+///
+/// ```
+/// let report = usearch::self_check().unwrap();
+/// assert!(report.simd_verified);
+/// println!("usearch self-check: {}", report);
+/// ```
+pub fn self_check() -> Result<SelfCheckReport, IndexError> {
+    let started = Instant::now();
+    let vectors = synthetic_vectors(SAMPLES, DIMENSIONS);
+    let index = Index::new(&IndexOptions {
+        dimensions: DIMENSIONS,
+        metric: MetricKind::Cos,
+        quantization: ScalarKind::F32,
+        ..Default::default()
+    })?;
+    index.reserve(SAMPLES)?;
+
+    let inserting = Instant::now();
+    for (key, vector) in vectors.iter().enumerate() {
+        index.add(key as u64, vector)?;
+    }
+    let inserts_per_second = SAMPLES as f64 / inserting.elapsed().as_secs_f64();
+
+    let stride = SAMPLES / QUERIES;
+    let mut latencies = Vec::with_capacity(QUERIES);
+    let mut found = 0;
+    for key in (0..SAMPLES).step_by(stride) {
+        let searching = Instant::now();
+        let matches = index.search(&vectors[key], 10)?;
+        latencies.push(searching.elapsed());
+        if matches.keys.first() == Some(&(key as u64)) {
+            found += 1;
+        }
+    }
+    latencies.sort();
+
+    // With the expansion covering the whole index, every query visits nearly every vector,
+    // so the throughput is dominated by the distance kernel.
+    index.change_expansion_search(SAMPLES);
+    let mut simd_verified = true;
+    let exhaustive = Instant::now();
+    for key in (0..SAMPLES).step_by(stride) {
+        let matches = index.search(&vectors[key], SAMPLES)?;
+        for (&other, &distance) in matches.keys.iter().zip(&matches.distances) {
+            let expected = cosine_distance(&vectors[key], &vectors[other as usize]);
+            simd_verified &= (distance - expected).abs() <= 1e-3;
+        }
+    }
+    let distances_per_second = (QUERIES * SAMPLES) as f64 / exhaustive.elapsed().as_secs_f64();
+
+    Ok(SelfCheckReport {
+        capabilities: capabilities(),
+        isa: index.hardware_acceleration(),
+        simd_verified,
+        distances_per_second,
+        inserts_per_second,
+        search_latency_p50: percentile(&latencies, 0.5),
+        search_latency_p99: percentile(&latencies, 0.99),
+        recall: found as f64 / latencies.len() as f64,
+        elapsed: started.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_check() {
+        let report = self_check().unwrap();
+        assert!(report.simd_verified);
+        assert!(report.recall >= 0.95, "Recall too low: {}", report.recall);
+        assert!(report.distances_per_second > 0.0);
+        assert!(report.search_latency_p50 <= report.search_latency_p99);
+        assert!(!report.isa.is_empty());
+        assert!(report.to_string().starts_with("isa="));
+    }
+}
@@ -0,0 +1,220 @@
+//! Text search over an index, delegating the embedding of documents to an [`Embedder`].
+//!
+//! Most applications wrap the index in the same glue: embed a document with some model, add
+//! the vector, keep the text aside, and map the matched keys back to texts after every search.
+//! [`SemanticIndex`] packages that glue, leaving only the embedding model to the user.
+
+use std::collections::HashMap;
+
+use crate::{Distance, Index, IndexError, IndexOptions, Key};
+
+/// A model turning texts into vectors, like a sentence transformer or a remote embedding API.
+///
+/// Implemented for closures, so simple embedders don't need a dedicated type.
+pub trait Embedder {
+    /// Embeds the text into a vector, matching the dimensions of the index.
+    fn embed(&self, text: &str) -> Vec<f32>;
+}
+
+impl<F: Fn(&str) -> Vec<f32>> Embedder for F {
+    fn embed(&self, text: &str) -> Vec<f32> {
+        self(text)
+    }
+}
+
+/// A document matched by [`SemanticIndex::search_text`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DocumentMatch<'a> {
+    /// The key of the document.
+    pub key: Key,
+    /// The distance from the query to the document.
+    pub distance: Distance,
+    /// The text of the document.
+    pub text: &'a str,
+}
+
+/// An index storing raw documents, embedded with `E`, and returning their texts from searches.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::semantic::SemanticIndex;
+/// use usearch::{IndexOptions, MetricKind, ScalarKind};
+///
+/// // A toy embedder, counting vowels and consonants.
+/// let embedder = |text: &str| {
+///     let vowels = text.chars().filter(|c| "aeiou".contains(*c)).count() as f32;
+///     vec![vowels, text.len() as f32 - vowels]
+/// };
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let mut index = SemanticIndex::new(&options, embedder).unwrap();
+/// index.add(1, "aaa").unwrap();
+/// index.add(2, "bcd").unwrap();
+///
+/// let matches = index.search_text("eio", 1).unwrap();
+/// assert_eq!(matches[0].text, "aaa");
+/// ```
+pub struct SemanticIndex<E: Embedder> {
+    index: Index,
+    embedder: E,
+    documents: HashMap<Key, String>,
+}
+
+impl<E: Embedder> SemanticIndex<E> {
+    /// Creates an empty index embedding documents with the given model.
+    pub fn new(options: &IndexOptions, embedder: E) -> Result<Self, IndexError> {
+        Ok(Self {
+            index: Index::new(options)?,
+            embedder,
+            documents: HashMap::new(),
+        })
+    }
+
+    /// The underlying index, for vector-level operations like `search`.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// The embedding model.
+    pub fn embedder(&self) -> &E {
+        &self.embedder
+    }
+
+    /// The number of stored documents.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Checks if no documents are stored.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// The text of the document stored under a key, if any.
+    pub fn document(&self, key: Key) -> Option<&str> {
+        self.documents.get(&key).map(String::as_str)
+    }
+
+    /// Embeds and stores a document, replacing any previous document under the same key.
+    /// The capacity of the index grows as needed.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the embedder returns a vector of the wrong length.
+    pub fn add<S: Into<String>>(&mut self, key: Key, text: S) -> Result<(), IndexError> {
+        let text = text.into();
+        let vector = self.embed(&text)?;
+        if self.index.contains(key) {
+            self.index.remove(key)?;
+        }
+        if self.index.size() >= self.index.capacity() {
+            self.index.reserve((self.index.capacity() * 2).max(64))?;
+        }
+        self.index.add(key, &vector)?;
+        self.documents.insert(key, text);
+        Ok(())
+    }
+
+    /// Removes the document stored under a key, returning its text.
+    pub fn remove(&mut self, key: Key) -> Result<Option<String>, IndexError> {
+        self.index.remove(key)?;
+        Ok(self.documents.remove(&key))
+    }
+
+    /// Embeds the query and returns up to `count` closest documents, from the closest one.
+    pub fn search_text(
+        &self,
+        query: &str,
+        count: usize,
+    ) -> Result<Vec<DocumentMatch<'_>>, IndexError> {
+        let vector = self.embed(query)?;
+        let matches = self.index.search(&vector, count)?;
+        Ok(matches
+            .keys
+            .iter()
+            .zip(&matches.distances)
+            .filter_map(|(&key, &distance)| {
+                let text = self.documents.get(&key)?;
+                Some(DocumentMatch {
+                    key,
+                    distance,
+                    text,
+                })
+            })
+            .collect())
+    }
+
+    fn embed(&self, text: &str) -> Result<Vec<f32>, IndexError> {
+        let vector = self.embedder.embed(text);
+        if vector.len() != self.index.dimensions() {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.index.dimensions(),
+                got: vector.len(),
+            });
+        }
+        Ok(vector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetricKind, ScalarKind};
+
+    struct Letters;
+
+    impl Embedder for Letters {
+        fn embed(&self, text: &str) -> Vec<f32> {
+            let mut counts = vec![0.0; 3];
+            for c in text.chars() {
+                match c {
+                    'a' => counts[0] += 1.0,
+                    'b' => counts[1] += 1.0,
+                    'c' => counts[2] += 1.0,
+                    _ => {}
+                }
+            }
+            counts
+        }
+    }
+
+    #[test]
+    fn test_semantic_index() {
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::Cos,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let mut index = SemanticIndex::new(&options, Letters).unwrap();
+        index.add(1, "aaab").unwrap();
+        index.add(2, "bbbc").unwrap();
+        index.add(3, "cccca").unwrap();
+        assert_eq!(index.len(), 3);
+
+        let matches = index.search_text("bb", 2).unwrap();
+        assert_eq!(matches[0].key, 2);
+        assert_eq!(matches[0].text, "bbbc");
+
+        index.add(2, "aaaa").unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.search_text("a", 1).unwrap()[0].text, "aaaa");
+
+        assert_eq!(index.remove(1).unwrap().as_deref(), Some("aaab"));
+        assert_eq!(index.document(1), None);
+
+        let wrong = SemanticIndex::new(&options, |_: &str| vec![1.0; 4]).unwrap();
+        assert!(matches!(
+            wrong.search_text("a", 1),
+            Err(IndexError::DimensionMismatch {
+                expected: 3,
+                got: 4
+            })
+        ));
+    }
+}
@@ -0,0 +1,418 @@
+//! A gRPC vector search service, wrapping an [`Index`] for concurrent remote access.
+//!
+//! Enabled with the `server` feature, which also builds the `usearch-server` binary. The
+//! interface is defined in `rust/proto/usearch.proto`, so clients in any language can generate
+//! their stubs from it. Additions, searches, and removals run concurrently, while saving
+//! waits for the calls in flight, and holds the next ones back only until the index is
+//! serialized in memory, not while it is written to the disk.
+//!
+//! With the `telemetry` feature, [`IndexService::with_metrics`] traces every call and records
+//! its latency, and the size of the index after every addition and removal.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+
+#[cfg(feature = "telemetry")]
+use crate::telemetry::IndexMetrics;
+use crate::{Distance, Index, IndexError, Key};
+
+/// Messages and service stubs generated from `rust/proto/usearch.proto`.
+#[allow(missing_docs, clippy::all)]
+pub mod proto {
+    tonic::include_proto!("usearch.v1");
+}
+
+use proto::vector_search_server::{VectorSearch, VectorSearchServer};
+use proto::{
+    AddRequest, AddResponse, RemoveRequest, RemoveResponse, SaveRequest, SaveResponse,
+    SearchRequest, SearchResponse, StatsRequest, StatsResponse,
+};
+
+fn status(err: IndexError) -> Status {
+    match err {
+        IndexError::InvalidOptions { .. } | IndexError::DimensionMismatch { .. } => {
+            Status::invalid_argument(err.to_string())
+        }
+        IndexError::DuplicateKey { .. } => Status::already_exists(err.to_string()),
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+/// The `VectorSearch` gRPC service over a single index.
+///
+/// # Examples
+///
+/// ```no_run
+/// use usearch::server::IndexService;
+/// use usearch::{Index, IndexOptions};
+///
+/// # async fn serve() -> Result<(), Box<dyn std::error::Error>> {
+/// let index = Index::new(&IndexOptions { dimensions: 256, ..Default::default() })?;
+/// let service = IndexService::new(index).with_path("index.usearch");
+/// tonic::transport::Server::builder()
+///     .add_service(service.into_server())
+///     .serve("0.0.0.0:50051".parse()?)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct IndexService {
+    index: Arc<Index>,
+    path: Option<PathBuf>,
+    #[cfg(feature = "telemetry")]
+    metrics: Option<IndexMetrics>,
+}
+
+impl IndexService {
+    /// Wraps an index, without a path to save it to.
+    pub fn new(index: Index) -> Self {
+        Self {
+            index: Arc::new(index),
+            path: None,
+            #[cfg(feature = "telemetry")]
+            metrics: None,
+        }
+    }
+
+    /// Sets the path the `Save` RPC writes to. Clients can't choose the path themselves.
+    pub fn with_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Records every call and the state of the index after every mutation with `metrics`.
+    #[cfg(feature = "telemetry")]
+    pub fn with_metrics(mut self, metrics: IndexMetrics) -> Self {
+        metrics.record_index(&self.index);
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Wraps the service into a tonic server, ready to be added to a router.
+    pub fn into_server(self) -> VectorSearchServer<Self> {
+        VectorSearchServer::new(self)
+    }
+
+    /// Saves a [`Snapshot`](crate::Snapshot) of the index to the configured path, if any.
+    pub fn save_index(&self) -> Result<Option<PathBuf>, IndexError> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+        self.index.snapshot()?.save(path)?;
+        Ok(Some(path.clone()))
+    }
+
+    /// Runs a blocking closure over the shared index on the blocking thread pool, observed as
+    /// `method` when metrics are attached.
+    async fn blocking<T, F>(&self, method: &'static str, function: F) -> Result<T, Status>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Index) -> Result<T, IndexError> + Send + 'static,
+    {
+        let index = self.index.clone();
+        #[cfg(feature = "telemetry")]
+        let metrics = self.metrics.clone();
+        tokio::task::spawn_blocking(move || {
+            #[cfg(feature = "telemetry")]
+            if let Some(metrics) = &metrics {
+                return metrics.observe(method, || function(&index));
+            }
+            #[cfg(not(feature = "telemetry"))]
+            let _ = method;
+            function(&index)
+        })
+        .await
+        .map_err(|err| Status::internal(err.to_string()))?
+        .map_err(status)
+    }
+
+    /// Records the size, capacity, and memory usage of the index, when metrics are attached.
+    fn record_index(&self) {
+        #[cfg(feature = "telemetry")]
+        if let Some(metrics) = &self.metrics {
+            metrics.record_index(&self.index);
+        }
+    }
+
+    /// Adds vectors under their keys, growing the capacity as needed.
+    pub(crate) async fn add_vectors(&self, vectors: Vec<(Key, Vec<f32>)>) -> Result<u64, Status> {
+        let added = self
+            .blocking("Add", move |index| {
+                for (key, vector) in &vectors {
+                    index.add(*key, vector)?;
+                }
+                Ok(vectors.len() as u64)
+            })
+            .await;
+        self.record_index();
+        added
+    }
+
+    /// Searches for the `count` nearest neighbors of the query.
+    pub(crate) async fn search_vectors(
+        &self,
+        query: Vec<f32>,
+        count: usize,
+    ) -> Result<(Vec<Key>, Vec<Distance>), Status> {
+        self.blocking("Search", move |index| {
+            let matches = index.search(&query, count)?;
+            Ok((matches.keys, matches.distances))
+        })
+        .await
+    }
+
+    /// Removes all vectors stored under the given keys, returning the number of removed vectors.
+    pub(crate) async fn remove_vectors(&self, keys: Vec<Key>) -> Result<u64, Status> {
+        let removed = self
+            .blocking("Remove", move |index| {
+                let mut removed = 0;
+                for key in keys {
+                    removed += index.remove(key)? as u64;
+                }
+                Ok(removed)
+            })
+            .await;
+        self.record_index();
+        removed
+    }
+
+    /// Saves the index to the configured path, failing if there is none.
+    pub(crate) async fn save_vectors(&self) -> Result<PathBuf, Status> {
+        let service = self.clone();
+        self.blocking("Save", move |_| service.save_index())
+            .await?
+            .ok_or_else(|| Status::failed_precondition("The server was started without a path"))
+    }
+
+    /// Reports the size and configuration of the index.
+    pub(crate) fn stats_response(&self) -> StatsResponse {
+        #[cfg(feature = "telemetry")]
+        if let Some(metrics) = &self.metrics {
+            let stats = metrics.observe("Stats", || {
+                Ok::<_, std::convert::Infallible>(self.collect_stats())
+            });
+            return stats.unwrap_or_else(|never| match never {});
+        }
+        self.collect_stats()
+    }
+
+    fn collect_stats(&self) -> StatsResponse {
+        let index = &self.index;
+        StatsResponse {
+            size: index.size() as u64,
+            capacity: index.capacity() as u64,
+            dimensions: index.dimensions() as u64,
+            connectivity: index.connectivity() as u64,
+            expansion_add: index.expansion_add() as u64,
+            expansion_search: index.expansion_search() as u64,
+            memory_usage: index.memory_usage() as u64,
+            hardware_acceleration: index.hardware_acceleration(),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl VectorSearch for IndexService {
+    async fn add(&self, request: Request<AddRequest>) -> Result<Response<AddResponse>, Status> {
+        let vectors = request
+            .into_inner()
+            .vectors
+            .into_iter()
+            .map(|vector| (vector.key, vector.values))
+            .collect();
+        let added = self.add_vectors(vectors).await?;
+        Ok(Response::new(AddResponse { added }))
+    }
+
+    async fn search(
+        &self,
+        request: Request<SearchRequest>,
+    ) -> Result<Response<SearchResponse>, Status> {
+        let SearchRequest { query, count } = request.into_inner();
+        let (keys, distances) = self.search_vectors(query, count as usize).await?;
+        Ok(Response::new(SearchResponse { keys, distances }))
+    }
+
+    async fn remove(
+        &self,
+        request: Request<RemoveRequest>,
+    ) -> Result<Response<RemoveResponse>, Status> {
+        let removed = self.remove_vectors(request.into_inner().keys).await?;
+        Ok(Response::new(RemoveResponse { removed }))
+    }
+
+    async fn save(&self, _: Request<SaveRequest>) -> Result<Response<SaveResponse>, Status> {
+        let path = self.save_vectors().await?;
+        Ok(Response::new(SaveResponse {
+            path: path.to_string_lossy().into_owned(),
+        }))
+    }
+
+    async fn stats(&self, _: Request<StatsRequest>) -> Result<Response<StatsResponse>, Status> {
+        Ok(Response::new(self.stats_response()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+    use proto::Vector;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrent_requests() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let service = IndexService::new(Index::new(&options).unwrap());
+
+        let writers = (0..8u64).map(|writer| {
+            let service = service.clone();
+            tokio::spawn(async move {
+                let vectors = (0..100u64)
+                    .map(|i| Vector {
+                        key: writer * 100 + i,
+                        values: vec![writer as f32, i as f32],
+                    })
+                    .collect();
+                service
+                    .add(Request::new(AddRequest { vectors }))
+                    .await
+                    .unwrap()
+            })
+        });
+        for writer in writers {
+            assert_eq!(writer.await.unwrap().into_inner().added, 100);
+        }
+
+        let stats = service.stats(Request::new(StatsRequest {})).await.unwrap();
+        assert_eq!(stats.into_inner().size, 800);
+
+        let found = service
+            .search(Request::new(SearchRequest {
+                query: vec![3.0, 42.0],
+                count: 1,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(found.keys, vec![342]);
+
+        let removed = service
+            .remove(Request::new(RemoveRequest {
+                keys: vec![342, 342, 9999],
+            }))
+            .await
+            .unwrap();
+        assert_eq!(removed.into_inner().removed, 1);
+
+        let save = service.save(Request::new(SaveRequest {})).await;
+        assert_eq!(save.unwrap_err().code(), tonic::Code::FailedPrecondition);
+
+        let path = std::env::temp_dir().join("usearch.rust.server.usearch");
+        let service = service.with_path(&path);
+        let saved = service.save(Request::new(SaveRequest {})).await.unwrap();
+        assert_eq!(saved.into_inner().path, path.to_string_lossy());
+        let mut loaded = Index::new(&options).unwrap();
+        loaded.load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.size(), 799);
+        assert!(!loaded.contains(342));
+    }
+
+    #[cfg(feature = "telemetry")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_metrics_after_request() {
+        use std::sync::Mutex;
+
+        use opentelemetry::metrics::MeterProvider;
+        use opentelemetry_sdk::error::OTelSdkResult;
+        use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics};
+        use opentelemetry_sdk::metrics::exporter::PushMetricExporter;
+        use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider, Temporality};
+
+        /// Keeps the exported histogram counts and gauge values, by metric name and method.
+        #[derive(Clone, Default)]
+        struct Scraper(Arc<Mutex<Vec<(String, String, u64)>>>);
+
+        impl PushMetricExporter for Scraper {
+            async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+                let mut scraped = self.0.lock().unwrap();
+                for metric in metrics.scope_metrics().flat_map(|scope| scope.metrics()) {
+                    let name = metric.name().to_owned();
+                    match metric.data() {
+                        AggregatedMetrics::F64(MetricData::Histogram(histogram)) => {
+                            for point in histogram.data_points() {
+                                let method = point
+                                    .attributes()
+                                    .find(|attribute| attribute.key.as_str() == "rpc.method")
+                                    .map(|attribute| attribute.value.to_string())
+                                    .unwrap_or_default();
+                                scraped.push((name.clone(), method, point.count()));
+                            }
+                        }
+                        AggregatedMetrics::U64(MetricData::Gauge(gauge)) => {
+                            for point in gauge.data_points() {
+                                scraped.push((name.clone(), String::new(), point.value()));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(())
+            }
+            fn force_flush(&self) -> OTelSdkResult {
+                Ok(())
+            }
+            fn shutdown_with_timeout(&self, _: std::time::Duration) -> OTelSdkResult {
+                Ok(())
+            }
+            fn temporality(&self) -> Temporality {
+                Temporality::Cumulative
+            }
+        }
+
+        let scraper = Scraper::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(scraper.clone()).build())
+            .build();
+        let metrics = IndexMetrics::with_meter(&provider.meter("usearch"), Vec::new());
+
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let service = IndexService::new(Index::new(&options).unwrap()).with_metrics(metrics);
+        let vectors = vec![Vector {
+            key: 7,
+            values: vec![1.0, 2.0],
+        }];
+        service
+            .add(Request::new(AddRequest { vectors }))
+            .await
+            .unwrap();
+
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+        let scraped = scraper.0.lock().unwrap();
+        let find = |name: &str, method: &str| {
+            scraped
+                .iter()
+                .find(|(n, m, _)| n == name && m == method)
+                .map(|(_, _, value)| *value)
+        };
+        assert_eq!(find("usearch.rpc.duration", "Add"), Some(1));
+        assert_eq!(find("usearch.rpc.duration", "Search"), None);
+        assert_eq!(find("usearch.index.size", ""), Some(1));
+    }
+}
@@ -0,0 +1,326 @@
+//! Consistent point-in-time copies of an index, taken while it keeps serving writes.
+//!
+//! Saving a file from a live index can capture a half-applied insertion, so online services
+//! used to pause ingestion for the whole save. [`Index::snapshot`] instead serializes the index
+//! into an owned buffer under a short exclusive lock, which only waits for in-flight mutations.
+//! The resulting [`Snapshot`] can then be written to disk, or restored, at leisure.
+//! [`Index::try_clone`] takes the same path to fork an index in memory.
+
+#[cfg(not(no_fs))]
+use std::fs::{self, File};
+#[cfg(not(no_fs))]
+use std::io::Write;
+#[cfg(not(no_fs))]
+use std::path::{Path, PathBuf};
+#[cfg(not(no_fs))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::PoisonError;
+
+use crate::{buffer_metadata, checksum, Index, IndexError, IndexMetadata, IndexOptions};
+
+/// An immutable serialized copy of an index, as produced by [`Index::snapshot`].
+#[derive(Clone)]
+pub struct Snapshot {
+    buffer: Vec<u8>,
+    metadata: IndexMetadata,
+    options: IndexOptions,
+}
+
+impl std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("bytes", &self.buffer.len())
+            .field("metadata", &self.metadata)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl Snapshot {
+    /// Properties of the captured index, like its size and metric.
+    pub fn metadata(&self) -> &IndexMetadata {
+        &self.metadata
+    }
+
+    /// The options of the captured index, including the runtime settings missing from the
+    /// serialized index, like the expansion values and `auto_normalize`.
+    pub fn options(&self) -> &IndexOptions {
+        &self.options
+    }
+
+    /// The serialized index, in the format read by [`Index::load_from_buffer`].
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Unwraps the serialized index.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Creates a new index holding the captured state, with the options of the captured index.
+    pub fn restore(&self) -> Result<Index, IndexError> {
+        let mut index = Index::new(&self.options)?;
+        index.load_from_buffer(&self.buffer)?;
+        Ok(index)
+    }
+
+    /// Writes the snapshot to a file, readable by [`Index::load`].
+    ///
+    /// The file is written next to its destination under a name unique to the call, flushed to
+    /// the disk, and atomically renamed, so readers and concurrent saves never observe a partial
+    /// snapshot, even after a crash.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), IndexError> {
+        let path = path.as_ref();
+        let temporary = temporary_path(path);
+        let renamed =
+            write_synced(&temporary, &self.buffer).and_then(|()| fs::rename(&temporary, path));
+        if let Err(err) = renamed {
+            let _ = fs::remove_file(&temporary);
+            return Err(err.into());
+        }
+        sync_parent(path)?;
+        Ok(())
+    }
+}
+
+/// A path next to the destination, unique to the process and the call.
+#[cfg(not(no_fs))]
+pub(crate) fn temporary_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(name)
+}
+
+#[cfg(not(no_fs))]
+pub(crate) fn write_synced(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+/// Flushes a file written at a temporary path to the disk, and atomically renames it over its
+/// destination, removing it if either step fails.
+#[cfg(not(no_fs))]
+pub(crate) fn rename_synced(temporary: &Path, path: &Path) -> std::io::Result<()> {
+    let renamed = fs::OpenOptions::new()
+        .write(true)
+        .open(temporary)
+        .and_then(|file| file.sync_all())
+        .and_then(|()| fs::rename(temporary, path));
+    if renamed.is_err() {
+        let _ = fs::remove_file(temporary);
+    }
+    renamed?;
+    sync_parent(path)
+}
+
+/// Persists the rename of a file into its directory.
+#[cfg(all(not(no_fs), unix))]
+pub(crate) fn sync_parent(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all(),
+    }
+}
+
+/// Directories can't be opened for flushing on other platforms, where renames are durable once
+/// the file itself is.
+#[cfg(all(not(no_fs), not(unix)))]
+pub(crate) fn sync_parent(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+impl Index {
+    /// Captures a consistent copy of the index without pausing writers for longer than the copy.
+    ///
+    /// Insertions, removals, and renames in flight are completed first, and new ones wait
+    /// until the index is serialized into memory. Searches are never blocked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[0.0_f32, 1.0]).unwrap();
+    /// let snapshot = index.snapshot().unwrap();
+    /// index.add(2, &[1.0_f32, 0.0]).unwrap();
+    ///
+    /// assert_eq!(snapshot.metadata().size, 1);
+    /// assert_eq!(snapshot.restore().unwrap().keys(), vec![1]);
+    /// ```
+    pub fn snapshot(&self) -> Result<Snapshot, IndexError> {
+        self.inject_fault("save")?;
+        let (mut buffer, options) = {
+            let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+            (self.serialize()?, self.options())
+        };
+        checksum::seal_buffer(&mut buffer)?;
+        let metadata = buffer_metadata(&buffer)?;
+        Ok(Snapshot {
+            buffer,
+            metadata,
+            options,
+        })
+    }
+
+    /// Serializes the index into memory, while the caller excludes concurrent mutations.
+    fn serialize(&self) -> Result<Vec<u8>, cxx::Exception> {
+        let mut buffer = vec![0; self.inner.serialized_length()];
+        self.inner.save_to_buffer(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Creates an independent deep copy of the index, with the same options and contents.
+    ///
+    /// Views of files, created by [`Index::view`], are copied into memory. `Index` doesn't
+    /// implement `Clone`, as copies can fail on allocation.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` if the index uses a custom metric function,
+    /// which can't be duplicated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    ///
+    /// let fork = index.try_clone().unwrap();
+    /// fork.change_expansion_search(256);
+    /// fork.add(2, &[0.8_f32, 0.6]).unwrap();
+    /// assert_eq!((index.size(), fork.size()), (1, 2));
+    /// assert_ne!(index.expansion_search(), fork.expansion_search());
+    /// ```
+    pub fn try_clone(&self) -> Result<Index, IndexError> {
+        let custom = !(self.metric_fn.lock())
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty();
+        if custom {
+            return Err(IndexError::InvalidOptions {
+                reason: "Indexes with custom metric functions can't be cloned".to_owned(),
+            });
+        }
+        let (buffer, options) = {
+            let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+            (self.serialize()?, self.options())
+        };
+        let clone = Index::new(&options)?;
+        clone.inner.load_from_buffer(&buffer)?;
+        Ok(clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetricKind, ScalarKind};
+
+    #[test]
+    fn test_snapshot_and_clone() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = &Index::new(&options).unwrap();
+        let mut snapshots = std::thread::scope(|scope| {
+            let writer = scope.spawn(move || {
+                for key in 0..2000u64 {
+                    index.add(key, &[key as f32, 0.0]).unwrap();
+                }
+            });
+            let mut snapshots = Vec::new();
+            while !writer.is_finished() && snapshots.len() < 64 {
+                snapshots.push(index.snapshot().unwrap());
+            }
+            snapshots
+        });
+        snapshots.push(index.snapshot().unwrap());
+
+        for snapshot in &snapshots {
+            let restored = snapshot.restore().unwrap();
+            let size = snapshot.metadata().size;
+            assert_eq!(restored.size(), size);
+            // Insertions are sequential, so every snapshot holds a prefix of the keys.
+            let mut keys = restored.keys();
+            keys.sort_unstable();
+            assert_eq!(keys, (0..size as u64).collect::<Vec<_>>());
+        }
+        let last = snapshots.last().unwrap();
+        assert_eq!(last.metadata().size, 2000);
+
+        // Concurrent saves to the same path never collide, and leave no temporary files behind.
+        let directory = std::env::temp_dir().join("usearch-snapshot");
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("index.usearch");
+        std::thread::scope(|scope| {
+            for snapshot in snapshots.iter().rev().take(4) {
+                let path = &path;
+                scope.spawn(move || snapshot.save(path).unwrap());
+            }
+        });
+        last.save(&path).unwrap();
+        let mut loaded = Index::new(&options).unwrap();
+        loaded.load(&path).unwrap();
+        assert_eq!(loaded.size(), 2000);
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+        fs::remove_dir_all(&directory).unwrap();
+
+        // Restoring keeps the settings missing from the serialized index.
+        let tuned = Index::new(&IndexOptions {
+            expansion_search: 99,
+            multi: true,
+            auto_normalize: true,
+            ..options.clone()
+        })
+        .unwrap();
+        tuned.add(1, &[3.0_f32, 4.0]).unwrap();
+        tuned.change_expansion_add(55);
+        let restored = tuned.snapshot().unwrap().restore().unwrap();
+        assert_eq!(restored.options(), tuned.options());
+        assert_eq!(restored.expansion_add(), 55);
+
+        let original = index.try_clone().unwrap();
+        original.change_expansion_add(77);
+        let clone = original.try_clone().unwrap();
+        assert_eq!(clone.expansion_add(), 77);
+        clone.remove(0).unwrap();
+        assert!(original.contains(0) && !clone.contains(0));
+        assert_eq!(clone.search(&[5.0_f32, 0.0], 1).unwrap().keys, vec![5]);
+    }
+
+    #[test]
+    fn test_clone_rejects_custom_metric() {
+        let index = Index::new(&IndexOptions {
+            dimensions: 2,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        })
+        .unwrap();
+        index.add(1, &[1.0_f32, 0.0]).unwrap();
+        index.change_metric::<f32>(Box::new(|_, _| 1.0));
+        assert!(matches!(
+            index.try_clone(),
+            Err(IndexError::InvalidOptions { .. })
+        ));
+    }
+}
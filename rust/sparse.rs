@@ -0,0 +1,314 @@
+//! Indexes of sparse vectors, like the term weights of SPLADE or BM25-style encoders.
+//!
+//! Sparse encoders produce vectors over vocabularies of tens of thousands of terms, with only a
+//! few hundred non-zero weights each. A [`SparseIndex`] stores them in a regular dense index, as
+//! fixed-size lists of `(term, weight)` pairs sorted by term and padded to the maximum number
+//! of non-zero entries, and compares them with a custom metric merging the two lists. Terms are
+//! stored as `f32`, so they must stay below 2^24, which covers the vocabularies of common
+//! tokenizers.
+
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::ffi::Matches;
+use crate::{Distance, Index, IndexError, IndexOptions, Key, MetricKind, ScalarKind};
+
+/// Terms are stored as `f32`, representing every integer up to this bound exactly.
+pub const MAX_TERM: u32 = 1 << 24;
+
+/// The term of the padding entries, sorting after every real term.
+const PADDING: f32 = f32::INFINITY;
+
+/// The inner product of two packed sparse vectors, holding `capacity` terms then as many weights.
+fn sparse_dot(a: &[f32], b: &[f32], capacity: usize) -> f32 {
+    let (a_terms, a_weights) = a.split_at(capacity);
+    let (b_terms, b_weights) = b.split_at(capacity);
+    let (mut i, mut j, mut dot) = (0, 0, 0.0);
+    while i < capacity && j < capacity && a_terms[i] != PADDING && b_terms[j] != PADDING {
+        if a_terms[i] < b_terms[j] {
+            i += 1;
+        } else if a_terms[i] > b_terms[j] {
+            j += 1;
+        } else {
+            dot += a_weights[i] * b_weights[j];
+            i += 1;
+            j += 1;
+        }
+    }
+    dot
+}
+
+/// An index of sparse vectors, compared by their inner product or their cosine similarity.
+///
+/// The `dimensions` of its options are the maximum number of non-zero entries per vector,
+/// and its metric either `IP` or `Cos`. With `Cos`, weights are normalized when added, so
+/// [`SparseIndex::get`] returns the normalized ones.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::sparse::SparseIndex;
+/// use usearch::{IndexOptions, MetricKind, ScalarKind};
+///
+/// let options = IndexOptions {
+///     dimensions: 4,
+///     metric: MetricKind::IP,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let index = SparseIndex::new(&options).unwrap();
+/// index.add(1, &[(7, 0.5), (30_000, 0.5)]).unwrap();
+/// index.add(2, &[(7, 0.1), (42, 0.9)]).unwrap();
+///
+/// let matches = index.search(&[(30_000, 1.0)], 1).unwrap();
+/// assert_eq!(matches.keys, vec![1]);
+/// assert_eq!(matches.distances, vec![0.5]);
+/// ```
+pub struct SparseIndex {
+    index: Index,
+    capacity: usize,
+    cosine: bool,
+}
+
+impl SparseIndex {
+    /// Creates an empty index of vectors with up to `options.dimensions` non-zero entries.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` for metrics other than `IP` and `Cos`, and scalars other
+    /// than `F32`, which stores the terms exactly.
+    pub fn new(options: &IndexOptions) -> Result<Self, IndexError> {
+        let cosine = match options.metric {
+            MetricKind::IP => false,
+            MetricKind::Cos => true,
+            metric => {
+                return Err(IndexError::InvalidOptions {
+                    reason: format!("Sparse vectors are compared by IP or Cos, got {:?}", metric),
+                })
+            }
+        };
+        if options.quantization != ScalarKind::F32 {
+            return Err(IndexError::InvalidOptions {
+                reason: format!(
+                    "Sparse vectors require F32 scalars, got {:?}",
+                    options.quantization
+                ),
+            });
+        }
+        let capacity = options.dimensions;
+        let packed = IndexOptions {
+            dimensions: capacity * 2,
+            metric: MetricKind::IP,
+            auto_normalize: false,
+            ..options.clone()
+        };
+        let index = Self {
+            index: Index::new(&packed)?,
+            capacity,
+            cosine,
+        };
+        index.register_metric();
+        Ok(index)
+    }
+
+    fn register_metric(&self) {
+        let capacity = self.capacity;
+        self.index.change_metric::<f32>(Box::new(
+            move |a: *const f32, b: *const f32| -> Distance {
+                // SAFETY: The native index only compares vectors of its dimensions.
+                let (a, b) = unsafe {
+                    (
+                        std::slice::from_raw_parts(a, capacity * 2),
+                        std::slice::from_raw_parts(b, capacity * 2),
+                    )
+                };
+                1.0 - sparse_dot(a, b, capacity)
+            },
+        ));
+    }
+
+    /// The underlying index of packed vectors, compared with the sparse metric.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// The maximum number of non-zero entries per vector.
+    pub fn max_nonzeros(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sorts the entries by term, and packs their terms and weights into a dense vector.
+    fn pack(&self, vector: &[(u32, f32)]) -> Result<Vec<f32>, IndexError> {
+        if vector.len() > self.capacity {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.capacity,
+                got: vector.len(),
+            });
+        }
+        let mut entries: Vec<(u32, f32)> = vector.to_vec();
+        entries.sort_unstable_by_key(|&(term, _)| term);
+        let invalid = |reason: String| Err(IndexError::InvalidOptions { reason });
+        if let Some(&(term, _)) = entries.iter().find(|&&(term, _)| term >= MAX_TERM) {
+            return invalid(format!("Sparse terms must be below {MAX_TERM}, got {term}"));
+        }
+        if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return invalid(format!("Sparse term {} appears twice", pair[0].0));
+        }
+
+        let mut packed = vec![PADDING; self.capacity];
+        packed.resize(self.capacity * 2, 0.0);
+        for (position, &(term, weight)) in entries.iter().enumerate() {
+            packed[position] = term as f32;
+            packed[self.capacity + position] = weight;
+        }
+        if self.cosine {
+            crate::normalize::normalize(&mut packed[self.capacity..]);
+        }
+        Ok(packed)
+    }
+
+    /// Reserves memory for the specified number of vectors.
+    pub fn reserve(&self, capacity: usize) -> Result<(), cxx::Exception> {
+        self.index.reserve(capacity)
+    }
+
+    /// Adds a sparse vector, given as `(term, weight)` pairs in any order.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the vector has more entries than the index allows,
+    /// and `IndexError::InvalidOptions` for repeated terms and terms beyond [`MAX_TERM`].
+    pub fn add(&self, key: Key, vector: &[(u32, f32)]) -> Result<(), IndexError> {
+        let packed = self.pack(vector)?;
+        self.index.add(key, &packed)
+    }
+
+    /// Retrieves the entries of the vector stored under a key, sorted by term.
+    pub fn get(&self, key: Key) -> Result<Option<Vec<(u32, f32)>>, IndexError> {
+        let mut packed = vec![0.0_f32; self.capacity * 2];
+        if self.index.get(key, &mut packed)? == 0 {
+            return Ok(None);
+        }
+        let (terms, weights) = packed.split_at(self.capacity);
+        Ok(Some(
+            terms
+                .iter()
+                .take_while(|&&term| term != PADDING)
+                .zip(weights)
+                .map(|(&term, &weight)| (term as u32, weight))
+                .collect(),
+        ))
+    }
+
+    /// Removes the vectors stored under a key.
+    pub fn remove(&self, key: Key) -> Result<usize, IndexError> {
+        Ok(self.index.remove(key)?)
+    }
+
+    /// Searches for the vectors with the largest inner product or cosine similarity with the
+    /// query, reported as `1 - similarity` distances.
+    pub fn search(&self, query: &[(u32, f32)], count: usize) -> Result<Matches, IndexError> {
+        let packed = self.pack(query)?;
+        self.index.search(&packed, count)
+    }
+
+    /// Searches for the nearest neighbors of the query accepted by the filter.
+    pub fn filtered_search<F>(
+        &self,
+        query: &[(u32, f32)],
+        count: usize,
+        filter: F,
+    ) -> Result<Matches, IndexError>
+    where
+        F: Fn(Key) -> bool,
+    {
+        let packed = self.pack(query)?;
+        self.index.filtered_search(&packed, count, filter)
+    }
+
+    /// Saves the index to a specified file.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), IndexError> {
+        self.index.save(path)
+    }
+
+    /// Loads an index saved by [`SparseIndex::save`], replacing the current contents, and
+    /// compares the loaded vectors with the sparse metric again.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<(), IndexError> {
+        self.index.load(path)?;
+        self.register_metric();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_index() {
+        let options = IndexOptions {
+            dimensions: 8,
+            metric: MetricKind::IP,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = SparseIndex::new(&options).unwrap();
+        assert_eq!(index.index().dimensions(), 16);
+        // Every vector shares a common term, one of ten group terms, and a term of its own.
+        for key in 0..50u32 {
+            let vector = [(1000 + key * 37, 1.0), (5, 0.1), (10 + key % 10, 0.2)];
+            index.add(key as Key, &vector).unwrap();
+        }
+        assert_eq!(
+            index.get(3).unwrap().unwrap(),
+            vec![(5, 0.1), (13, 0.2), (1111, 1.0)]
+        );
+        assert!(index.get(1000).unwrap().is_none());
+
+        let matches = index.search(&[(1000 + 42 * 37, 2.0), (5, 1.0)], 3).unwrap();
+        assert_eq!(matches.keys[0], 42);
+        assert!((matches.distances[0] - (1.0 - 2.1)).abs() < 1e-6);
+        assert!((matches.distances[1] - 0.9).abs() < 1e-6);
+        let matches = index
+            .filtered_search(&[(1000 + 42 * 37, 2.0)], 1, |key| key != 42)
+            .unwrap();
+        assert_ne!(matches.keys, vec![42]);
+
+        assert!(index.add(1, &[(0, 1.0); 9]).is_err());
+        assert!(index.add(1, &[(2, 1.0), (2, 0.5)]).is_err());
+        assert!(index.add(1, &[(MAX_TERM, 1.0)]).is_err());
+        let invalid = IndexOptions {
+            metric: MetricKind::L2sq,
+            ..options.clone()
+        };
+        assert!(SparseIndex::new(&invalid).is_err());
+
+        // Cosine ignores the scale of the weights, and survives saving and loading.
+        let cosine = SparseIndex::new(&IndexOptions {
+            metric: MetricKind::Cos,
+            ..options.clone()
+        })
+        .unwrap();
+        cosine.add(1, &[(10, 3.0), (20, 4.0)]).unwrap();
+        cosine.add(2, &[(10, 4.0), (30, 3.0)]).unwrap();
+        let matches = cosine.search(&[(20, 40.0), (10, 30.0)], 2).unwrap();
+        assert_eq!(matches.keys, vec![1, 2]);
+        assert!(matches.distances[0].abs() < 1e-6);
+
+        let path = std::env::temp_dir().join("usearch-sparse-test.usearch");
+        let path = path.to_str().unwrap();
+        cosine.save(path).unwrap();
+        let mut loaded = SparseIndex::new(&IndexOptions {
+            metric: MetricKind::Cos,
+            ..options
+        })
+        .unwrap();
+        loaded.load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let reloaded = loaded.search(&[(20, 40.0), (10, 30.0)], 2).unwrap();
+        assert_eq!(reloaded.keys, matches.keys);
+        assert_eq!(reloaded.distances, matches.distances);
+    }
+}
@@ -0,0 +1,134 @@
+//! Searches offloaded from async tasks to the blocking thread pool.
+//!
+//! Native searches block their thread for the whole traversal, stalling every other task of a
+//! runtime worker. [`Index::search_spawned`] runs them on the blocking pool of the runtime
+//! instead, and bounds how many run at once: the native layer holds one search context per
+//! hardware thread, so extra searches would only queue on the pool, holding one of its threads
+//! each. Searches beyond the bound wait asynchronously for a running one to finish, pushing the
+//! backpressure back to their callers.
+
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, VectorType};
+
+/// Permits for the searches spawned onto the blocking pool, one per native search context.
+pub(crate) fn spawn_limit() -> Arc<Semaphore> {
+    let contexts = std::thread::available_parallelism().map_or(1, |n| n.get());
+    Arc::new(Semaphore::new(contexts))
+}
+
+impl Index {
+    /// Performs k-Approximate Nearest Neighbors (kANN) Search on the blocking thread pool,
+    /// like [`Index::search`], without blocking the calling task.
+    ///
+    /// At most one search per hardware thread runs at a time, and the future waits for a running
+    /// one to finish before spawning, so bursts of queries don't pile up on the blocking pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query vector, moved to the blocking thread.
+    /// * `count` - The maximum number of neighbors to search for.
+    ///
+    /// # Panics
+    ///
+    /// Resumes the panic of the search, if any, and panics if the runtime shuts down meanwhile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Arc::new(Index::new(&options).unwrap());
+    /// index.add(1, &[0.0_f32, 1.0]).unwrap();
+    /// index.add(2, &[1.0_f32, 0.0]).unwrap();
+    ///
+    /// let matches = index.search_spawned(vec![0.9_f32, 0.1], 1).await.unwrap();
+    /// assert_eq!(matches.keys, vec![2]);
+    /// # }
+    /// ```
+    pub async fn search_spawned<T>(
+        self: &Arc<Self>,
+        query: Vec<T>,
+        count: usize,
+    ) -> Result<Matches, IndexError>
+    where
+        T: VectorType + Send + 'static,
+    {
+        let permit = Arc::clone(&self.spawned)
+            .acquire_owned()
+            .await
+            .expect("the spawn limit is never closed");
+        let index = Arc::clone(self);
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            index.search(&query, count)
+        })
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_spawned_searches() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Arc::new(Index::new(&options).unwrap());
+        for key in 0..100u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+        let limit = index.spawned.available_permits();
+
+        // Many more searches than permits, all completing, and all permits returned.
+        let tasks: Vec<_> = (0..64u64)
+            .map(|key| {
+                let index = Arc::clone(&index);
+                tokio::spawn(async move {
+                    let matches = index.search_spawned(vec![key as f32, 0.0], 1).await;
+                    (key, matches.unwrap().keys)
+                })
+            })
+            .collect();
+        for task in tasks {
+            let (key, keys) = task.await.unwrap();
+            assert_eq!(keys, vec![key]);
+        }
+        assert_eq!(index.spawned.available_permits(), limit);
+
+        // Waiting searches only start once a permit is released.
+        let held = Arc::clone(&index.spawned)
+            .acquire_many_owned(limit as u32)
+            .await
+            .unwrap();
+        let waiting = tokio::spawn({
+            let index = Arc::clone(&index);
+            async move { index.search_spawned(vec![7.0_f32, 0.0], 1).await }
+        });
+        for _ in 0..16 {
+            tokio::task::yield_now().await;
+        }
+        assert!(!waiting.is_finished());
+        drop(held);
+        assert_eq!(waiting.await.unwrap().unwrap().keys, vec![7]);
+    }
+}
@@ -0,0 +1,341 @@
+//! Indexes keeping the graph in memory and the full-precision vectors on disk.
+//!
+//! A billion 768-dimensional `f32` vectors take 3 TB, while the graph over them, with `i8`
+//! copies of the vectors, takes a fraction of it. A [`SpilledIndex`] searches such a compact
+//! in-memory index for candidates, then re-ranks them exactly with the full vectors, read from
+//! an append-only file through the page cache, so that only the hot vectors occupy memory.
+//!
+//! The file starts with a 16-byte header, `USSPILL1` and the dimensions as a little-endian
+//! `u64`, followed by fixed-size records of a key, a liveness flag, and the vector, so that
+//! removals append tombstones. Reopening the file replays it into a fresh graph.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::ffi::Matches;
+use crate::metrics::distance;
+use crate::{Distance, Index, IndexError, IndexOptions, Key, MetricKind};
+
+const MAGIC: &[u8; 8] = b"USSPILL1";
+const HEADER: u64 = 16;
+/// The key and the liveness flag preceding every spilled vector.
+const RECORD_PREFIX: usize = 16;
+
+const LIVE: u64 = 1;
+const REMOVED: u64 = 0;
+
+#[cfg(unix)]
+fn read_at(file: &File, buffer: &mut [u8], offset: u64) -> std::io::Result<()> {
+    std::os::unix::fs::FileExt::read_exact_at(file, buffer, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, mut buffer: &mut [u8], mut offset: u64) -> std::io::Result<()> {
+    while !buffer.is_empty() {
+        match std::os::windows::fs::FileExt::seek_read(file, buffer, offset)? {
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            read => {
+                buffer = &mut buffer[read..];
+                offset += read as u64;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An in-memory graph over compact vectors, re-ranked with full vectors spilled to a file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use usearch::{IndexOptions, MetricKind, ScalarKind, SpilledIndex};
+///
+/// let options = IndexOptions {
+///     dimensions: 3,
+///     metric: MetricKind::Cos,
+///     quantization: ScalarKind::I8,
+///     ..Default::default()
+/// };
+/// let mut index = SpilledIndex::open("vectors.spill", &options).unwrap();
+/// index.add(42, &[0.2, 0.1, 0.2]).unwrap();
+/// let matches = index.search(&[0.2, 0.1, 0.2], 10, 0).unwrap();
+/// ```
+pub struct SpilledIndex {
+    index: Index,
+    metric: MetricKind,
+    path: PathBuf,
+    file: File,
+    /// The record of the live vector of every key.
+    records: HashMap<Key, u64>,
+    /// Number of records in the file, live or not.
+    written: u64,
+}
+
+impl SpilledIndex {
+    /// Opens the file of spilled vectors, creating it if needed, and indexes its live vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file holding the full vectors.
+    /// * `options` - The options of the in-memory index, typically with `I8` or `F16`
+    ///   quantization, and the same dimensions as the existing file.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the file holds vectors of other dimensions, and
+    /// `IndexError::InvalidOptions` for multi-vector options, as every key has one vector.
+    pub fn open<P: AsRef<Path>>(path: P, options: &IndexOptions) -> Result<Self, IndexError> {
+        if options.multi {
+            return Err(IndexError::InvalidOptions {
+                reason: "Spilled indexes store a single vector per key".to_owned(),
+            });
+        }
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        let index = Index::new(options)?;
+        let mut spilled = Self {
+            index,
+            metric: options.metric,
+            path,
+            file: file.try_clone()?,
+            records: HashMap::new(),
+            written: 0,
+        };
+
+        if file.metadata()?.len() == 0 {
+            file.write_all(MAGIC)?;
+            file.write_all(&(options.dimensions as u64).to_le_bytes())?;
+        } else {
+            spilled.replay(&mut file, options.dimensions)?;
+        }
+        Ok(spilled)
+    }
+
+    /// Rebuilds the graph and the record map from the file.
+    fn replay(&mut self, file: &mut File, dimensions: usize) -> Result<(), IndexError> {
+        let mut reader = BufReader::new(file);
+        let mut header = [0; HEADER as usize];
+        reader.read_exact(&mut header)?;
+        if &header[..8] != MAGIC {
+            return Err(IndexError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Not a USearch spilled vectors file",
+            )));
+        }
+        let stored = u64::from_le_bytes(header[8..].try_into().unwrap()) as usize;
+        if stored != dimensions {
+            return Err(IndexError::DimensionMismatch {
+                expected: dimensions,
+                got: stored,
+            });
+        }
+
+        // The last record of every key wins, and a torn record at the tail is ignored.
+        let mut record = vec![0; self.record_length()];
+        let mut live: HashMap<Key, (u64, Vec<f32>)> = HashMap::new();
+        let mut written = 0;
+        while reader.read_exact(&mut record).is_ok() {
+            let (key, vector) = self.parse(&record);
+            match vector {
+                Some(vector) => live.insert(key, (written, vector)),
+                None => live.remove(&key),
+            };
+            written += 1;
+        }
+        self.written = written;
+        self.index.reserve(live.len())?;
+        let mut live: Vec<_> = live.into_iter().collect();
+        live.sort_unstable_by_key(|&(_, (position, _))| position);
+        for (key, (position, vector)) in live {
+            self.index.add(key, &vector)?;
+            self.records.insert(key, position);
+        }
+        self.file
+            .set_len(HEADER + written * self.record_length() as u64)?;
+        Ok(())
+    }
+
+    fn record_length(&self) -> usize {
+        RECORD_PREFIX + self.index.dimensions() * std::mem::size_of::<f32>()
+    }
+
+    fn offset(&self, record: u64) -> u64 {
+        HEADER + record * self.record_length() as u64
+    }
+
+    fn parse(&self, record: &[u8]) -> (Key, Option<Vec<f32>>) {
+        let key = Key::from_le_bytes(record[..8].try_into().unwrap());
+        if u64::from_le_bytes(record[8..16].try_into().unwrap()) != LIVE {
+            return (key, None);
+        }
+        let vector = record[RECORD_PREFIX..]
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        (key, Some(vector))
+    }
+
+    fn append(&mut self, key: Key, flag: u64, vector: &[f32]) -> Result<u64, IndexError> {
+        let mut record = Vec::with_capacity(self.record_length());
+        record.extend_from_slice(&key.to_le_bytes());
+        record.extend_from_slice(&flag.to_le_bytes());
+        for value in vector {
+            record.extend_from_slice(&value.to_le_bytes());
+        }
+        record.resize(self.record_length(), 0);
+        let position = self.written;
+        self.file.seek(SeekFrom::Start(self.offset(position)))?;
+        self.file.write_all(&record)?;
+        self.written += 1;
+        Ok(position)
+    }
+
+    /// The in-memory index over the compact vectors.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// The file holding the full vectors.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of live vectors.
+    pub fn size(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Checks if a key has a live vector.
+    pub fn contains(&self, key: Key) -> bool {
+        self.records.contains_key(&key)
+    }
+
+    /// Adds a vector under a key, replacing its previous vector, if any.
+    pub fn add(&mut self, key: Key, vector: &[f32]) -> Result<(), IndexError> {
+        if vector.len() != self.index.dimensions() {
+            return Err(IndexError::DimensionMismatch {
+                expected: self.index.dimensions(),
+                got: vector.len(),
+            });
+        }
+        if self.records.contains_key(&key) {
+            self.index.remove(key)?;
+        }
+        let position = self.append(key, LIVE, vector)?;
+        self.index.add(key, vector)?;
+        self.records.insert(key, position);
+        Ok(())
+    }
+
+    /// Removes the vector of a key, returning how many were removed.
+    pub fn remove(&mut self, key: Key) -> Result<usize, IndexError> {
+        if self.records.remove(&key).is_none() {
+            return Ok(0);
+        }
+        self.append(key, REMOVED, &[])?;
+        Ok(self.index.remove(key)?)
+    }
+
+    /// Reads the full vector of a key from the file.
+    pub fn get(&self, key: Key) -> Result<Option<Vec<f32>>, IndexError> {
+        let Some(&position) = self.records.get(&key) else {
+            return Ok(None);
+        };
+        let mut record = vec![0; self.record_length()];
+        read_at(&self.file, &mut record, self.offset(position))?;
+        Ok(self.parse(&record).1)
+    }
+
+    /// Flushes the spilled vectors to the storage device.
+    pub fn sync(&self) -> Result<(), IndexError> {
+        Ok(self.file.sync_data()?)
+    }
+
+    /// Searches the in-memory index for `pool` candidates, and re-ranks them by the distance
+    /// between the query and their full vectors. Zero stands for four times `count`.
+    pub fn search(&self, query: &[f32], count: usize, pool: usize) -> Result<Matches, IndexError> {
+        let pool = match pool {
+            0 => count * 4,
+            pool => pool.max(count),
+        };
+        let candidates = self.index.search(query, pool)?;
+
+        let mut ranked: Vec<(Distance, Key)> = Vec::with_capacity(candidates.keys.len());
+        for &key in &candidates.keys {
+            if let Some(vector) = self.get(key)? {
+                ranked.push((distance(self.metric, query, &vector), key));
+            }
+        }
+        ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ranked.truncate(count);
+        Ok(Matches {
+            keys: ranked.iter().map(|&(_, key)| key).collect(),
+            distances: ranked.iter().map(|&(distance, _)| distance).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScalarKind;
+
+    #[test]
+    fn test_spilled_index() {
+        let path = std::env::temp_dir().join("usearch-spilled.spill");
+        let _ = std::fs::remove_file(&path);
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            ..Default::default()
+        };
+
+        let mut index = SpilledIndex::open(&path, &options).unwrap();
+        for key in 0..100u64 {
+            // Groups of ten vectors too close to one another for `i8` scalars to tell apart.
+            let vector = [(key / 10) as f32 * 0.1, 0.5 + (key % 10) as f32 * 1e-4];
+            index.add(key, &vector).unwrap();
+        }
+        let matches = index.search(&[0.4, 0.5002], 1, 20).unwrap();
+        assert_eq!(matches.keys, vec![42]);
+        assert!(matches.distances[0] < 1e-7);
+
+        index.add(42, &[-0.5, -0.5]).unwrap();
+        assert_eq!(index.remove(41).unwrap(), 1);
+        assert_eq!(index.remove(41).unwrap(), 0);
+        assert_eq!(index.get(42).unwrap(), Some(vec![-0.5, -0.5]));
+        assert_eq!(index.size(), 99);
+        index.sync().unwrap();
+        drop(index);
+
+        // Tear the last record, as a crash mid-write would.
+        let file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.set_len(file.metadata().unwrap().len() - 3).unwrap();
+        drop(file);
+
+        let mut index = SpilledIndex::open(&path, &options).unwrap();
+        assert_eq!(index.size(), 100);
+        assert!(index.contains(41));
+        assert_eq!(index.get(42).unwrap(), Some(vec![-0.5, -0.5]));
+        assert_eq!(index.search(&[-0.4, -0.4], 1, 0).unwrap().keys, vec![42]);
+        index.add(100, &[1.0, 1.0]).unwrap();
+        assert_eq!(index.get(100).unwrap(), Some(vec![1.0, 1.0]));
+        assert!(index.add(101, &[1.0]).is_err());
+        drop(index);
+
+        let other = IndexOptions {
+            dimensions: 3,
+            ..options
+        };
+        assert!(SpilledIndex::open(&path, &other).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
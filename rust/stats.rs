@@ -0,0 +1,384 @@
+//! Point-in-time statistics of an index, and the differences between two snapshots.
+//!
+//! [`Index::stats`] captures the size, memory footprint, and per-level graph histogram of an
+//! index, and [`IndexStats::diff`] compares two captures. [`compare_snapshots`] goes further for
+//! saved files, like the output of a nightly rebuild, and also measures the recall of both
+//! snapshots on a query set against exact search, so regressions surface before promotion.
+
+use std::collections::HashSet;
+#[cfg(not(no_fs))]
+use std::path::Path;
+use std::sync::PoisonError;
+
+pub use crate::ffi::{LevelStats, MemoryBreakdown};
+#[cfg(not(no_fs))]
+use crate::file_metadata;
+#[cfg(not(no_fs))]
+use crate::IndexOptions;
+use crate::{exact, Index, IndexError, Key, MetricKind};
+
+/// A snapshot of the size, memory usage, and graph structure of an index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexStats {
+    /// Number of vectors present in the index.
+    pub size: usize,
+    /// Total capacity of the index, including reserved space.
+    pub capacity: usize,
+    /// Number of dimensions of every vector.
+    pub dimensions: usize,
+    /// Maximum number of edges per node on the upper levels.
+    pub connectivity: usize,
+    /// Memory usage of the index in bytes.
+    pub memory_usage: usize,
+    /// Graph statistics of every level, from the base level up.
+    pub levels: Vec<LevelStats>,
+}
+
+/// Changes between two [`IndexStats`], as `after - before`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsDiff {
+    /// Change in the number of vectors.
+    pub size: i64,
+    /// Change in the capacity.
+    pub capacity: i64,
+    /// Change in the memory usage, in bytes.
+    pub memory_usage: i64,
+    /// Change in the number of nodes of every level, from the base level up,
+    /// covering the levels of both snapshots.
+    pub level_nodes: Vec<i64>,
+    /// Change in the number of edges of every level, from the base level up.
+    pub level_edges: Vec<i64>,
+}
+
+impl StatsDiff {
+    /// Whether both snapshots have the same size, capacity, memory usage, and graph histogram.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+            && self.capacity == 0
+            && self.memory_usage == 0
+            && self.level_nodes.iter().all(|&delta| delta == 0)
+            && self.level_edges.iter().all(|&delta| delta == 0)
+    }
+}
+
+fn delta(before: usize, after: usize) -> i64 {
+    after as i64 - before as i64
+}
+
+impl MemoryBreakdown {
+    /// The sum of all components, equal to [`Index::memory_usage`] when both are measured together.
+    pub fn total(&self) -> usize {
+        self.graph_bytes + self.vectors_bytes + self.tombstones_bytes + self.slack_bytes
+    }
+}
+
+impl IndexStats {
+    /// Computes the changes from this snapshot to a later one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.reserve(10).unwrap();
+    /// let before = index.stats();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    ///
+    /// let diff = before.diff(&index.stats());
+    /// assert_eq!(diff.size, 1);
+    /// assert_eq!(diff.level_nodes[0], 1);
+    /// ```
+    pub fn diff(&self, other: &IndexStats) -> StatsDiff {
+        let levels = self.levels.len().max(other.levels.len());
+        let level = |stats: &IndexStats, i: usize| stats.levels.get(i).copied().unwrap_or_default();
+        StatsDiff {
+            size: delta(self.size, other.size),
+            capacity: delta(self.capacity, other.capacity),
+            memory_usage: delta(self.memory_usage, other.memory_usage),
+            level_nodes: (0..levels)
+                .map(|i| delta(level(self, i).nodes, level(other, i).nodes))
+                .collect(),
+            level_edges: (0..levels)
+                .map(|i| delta(level(self, i).edges, level(other, i).edges))
+                .collect(),
+        }
+    }
+}
+
+impl Index {
+    /// Captures the size, memory usage, and per-level graph statistics of the index.
+    /// Visits every node once per level, so it is linear in the size of the index.
+    pub fn stats(&self) -> IndexStats {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        let levels = if self.size() == 0 {
+            Vec::new()
+        } else {
+            (0..=self.inner.max_level())
+                .map(|level| self.inner.level_stats(level))
+                .collect()
+        };
+        IndexStats {
+            size: self.size(),
+            capacity: self.capacity(),
+            dimensions: self.dimensions(),
+            connectivity: self.connectivity(),
+            memory_usage: self.inner.memory_usage(),
+            levels,
+        }
+    }
+
+    /// Splits [`Index::memory_usage`] into the graph, the vectors, the members removed but not
+    /// yet replaced, and the arena space allocated but unused, which add up to it.
+    ///
+    /// Graph and vector bytes grow with the size of the index, so dividing them by
+    /// [`Index::size`] gives the cost of every further member. Visits every slot when members
+    /// were removed, so it is linear in the size of the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, ScalarKind};
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 64,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.reserve(100).unwrap();
+    /// for key in 0..100u64 {
+    ///     index.add(key, &[key as f32; 64]).unwrap();
+    /// }
+    /// index.remove(7).unwrap();
+    ///
+    /// let breakdown = index.memory_breakdown();
+    /// assert_eq!(breakdown.total(), index.memory_usage());
+    /// assert!(breakdown.vectors_bytes >= 99 * 64 * 4);
+    /// assert!(breakdown.tombstones_bytes >= 64 * 4);
+    /// ```
+    pub fn memory_breakdown(&self) -> MemoryBreakdown {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        self.inner.memory_breakdown()
+    }
+
+    /// Measures the share of exact nearest neighbors found by the approximate search,
+    /// averaged over the queries. Exact neighbors are found by brute force over the `f32`
+    /// exports of all stored vectors, so this is meant for offline evaluation.
+    ///
+    /// # Arguments
+    ///
+    /// * `metric` - The metric the index was built with.
+    /// * `queries` - The query vectors.
+    /// * `count` - The number of neighbors to compare per query.
+    ///
+    /// # Returns
+    ///
+    /// The recall between `0.0` and `1.0`, or `1.0` if there is nothing to find.
+    pub fn recall<Q: AsRef<[f32]>>(
+        &self,
+        metric: MetricKind,
+        queries: &[Q],
+        count: usize,
+    ) -> Result<f64, IndexError> {
+        let dimensions = self.dimensions();
+        let mut flat = Vec::with_capacity(queries.len() * dimensions);
+        for query in queries {
+            let query = query.as_ref();
+            if query.len() != dimensions {
+                return Err(IndexError::DimensionMismatch {
+                    expected: dimensions,
+                    got: query.len(),
+                });
+            }
+            flat.extend_from_slice(query);
+        }
+        let mut keys = self.keys();
+        keys.sort_unstable();
+        keys.dedup();
+        let mut stored_keys: Vec<Key> = Vec::with_capacity(keys.len());
+        let mut stored: Vec<f32> = Vec::with_capacity(keys.len() * dimensions);
+        let mut vectors: Vec<f32> = Vec::new();
+        for key in keys {
+            let found = self.export(key, &mut vectors)?;
+            stored_keys.extend(std::iter::repeat_n(key, found));
+            stored.extend_from_slice(&vectors);
+        }
+
+        let mut exact_keys: Vec<HashSet<Key>> = Vec::with_capacity(queries.len());
+        exact::scan(metric, dimensions, &flat, &stored, |_, row| {
+            let mut exact: Vec<(f32, Key)> = row
+                .iter()
+                .copied()
+                .zip(stored_keys.iter().copied())
+                .collect();
+            exact.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut keys = HashSet::new();
+            for (_, key) in exact {
+                if keys.len() == count {
+                    break;
+                }
+                keys.insert(key);
+            }
+            exact_keys.push(keys);
+        });
+
+        let mut found = 0;
+        let mut expected = 0;
+        for (query, exact_keys) in queries.iter().zip(exact_keys) {
+            let approximate: HashSet<Key> = self
+                .search(query.as_ref(), count)?
+                .keys
+                .into_iter()
+                .collect();
+            found += exact_keys.intersection(&approximate).count();
+            expected += exact_keys.len();
+        }
+        Ok(if expected == 0 {
+            1.0
+        } else {
+            found as f64 / expected as f64
+        })
+    }
+}
+
+/// Statistics and recall of two saved snapshots of an index, produced by [`compare_snapshots`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotComparison {
+    /// Statistics of the earlier snapshot.
+    pub before: IndexStats,
+    /// Statistics of the later snapshot.
+    pub after: IndexStats,
+    /// Changes from the earlier snapshot to the later one.
+    pub diff: StatsDiff,
+    /// Recall of the earlier snapshot on the query set.
+    pub recall_before: f64,
+    /// Recall of the later snapshot on the query set.
+    pub recall_after: f64,
+}
+
+impl SnapshotComparison {
+    /// Change in recall from the earlier snapshot to the later one.
+    pub fn recall_delta(&self) -> f64 {
+        self.recall_after - self.recall_before
+    }
+}
+
+/// Loads two saved indexes and reports the changes in size, memory, level histograms,
+/// and recall on a query set. Both files are configured from their own headers.
+///
+/// # Arguments
+///
+/// * `before` - The path of the earlier snapshot, like the index currently serving.
+/// * `after` - The path of the later snapshot, like the output of a rebuild.
+/// * `queries` - The query vectors used to measure recall.
+/// * `count` - The number of neighbors to compare per query.
+#[cfg(not(no_fs))]
+pub fn compare_snapshots<P: AsRef<Path>, Q: AsRef<[f32]>>(
+    before: P,
+    after: P,
+    queries: &[Q],
+    count: usize,
+) -> Result<SnapshotComparison, IndexError> {
+    let measure = |path: &Path| -> Result<(IndexStats, f64), IndexError> {
+        let metadata = file_metadata(path)?;
+        let mut index = Index::new(&IndexOptions {
+            dimensions: metadata.dimensions,
+            metric: metadata.metric,
+            quantization: metadata.quantization,
+            multi: metadata.multi,
+            ..Default::default()
+        })?;
+        index.load(path)?;
+        let recall = index.recall(metadata.metric, queries, count)?;
+        Ok((index.stats(), recall))
+    };
+
+    let (before, recall_before) = measure(before.as_ref())?;
+    let (after, recall_after) = measure(after.as_ref())?;
+    Ok(SnapshotComparison {
+        diff: before.diff(&after),
+        before,
+        after,
+        recall_before,
+        recall_after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScalarKind;
+
+    #[test]
+    fn test_compare_snapshots() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(200).unwrap();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32, (key % 7) as f32]).unwrap();
+        }
+        let directory = std::env::temp_dir();
+        let before = directory.join("usearch-stats-before.usearch");
+        index.save(&before).unwrap();
+
+        for key in 100..200u64 {
+            index.add(key, &[key as f32, (key % 7) as f32]).unwrap();
+        }
+        let after = directory.join("usearch-stats-after.usearch");
+        index.save(&after).unwrap();
+
+        let queries = vec![vec![10.0_f32, 3.0], vec![150.0, 1.0]];
+        let comparison = compare_snapshots(&before, &after, &queries, 5).unwrap();
+        assert_eq!(comparison.before.size, 100);
+        assert_eq!(comparison.after.size, 200);
+        assert_eq!(comparison.diff.size, 100);
+        assert_eq!(comparison.diff.level_nodes[0], 100);
+        assert!(comparison.diff.level_edges[0] > 0);
+        assert!(comparison.recall_before > 0.9 && comparison.recall_after > 0.9);
+        assert!(!comparison.diff.is_empty());
+        assert!(comparison.after.diff(&comparison.after).is_empty());
+
+        std::fs::remove_file(before).unwrap();
+        std::fs::remove_file(after).unwrap();
+    }
+
+    #[test]
+    fn test_memory_breakdown() {
+        let options = crate::IndexOptions {
+            dimensions: 32,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let empty = index.memory_breakdown();
+        assert_eq!(empty.total(), index.memory_usage());
+        assert_eq!(empty.vectors_bytes, 0);
+        assert_eq!(empty.tombstones_bytes, 0);
+
+        index.reserve(1000).unwrap();
+        for key in 0..1000u64 {
+            index.add(key, &[key as f32; 32]).unwrap();
+        }
+        let full = index.memory_breakdown();
+        assert_eq!(full.total(), index.memory_usage());
+        assert_eq!(full.tombstones_bytes, 0);
+        assert!(full.vectors_bytes >= 1000 * 32 * 4);
+        assert!(full.graph_bytes > empty.graph_bytes);
+
+        for key in 0..100u64 {
+            index.remove(key).unwrap();
+        }
+        let removed = index.memory_breakdown();
+        assert_eq!(removed.total(), index.memory_usage());
+        assert!(removed.tombstones_bytes >= 100 * 32 * 4);
+        assert!(removed.vectors_bytes < full.vectors_bytes);
+        assert!(removed.graph_bytes < full.graph_bytes);
+    }
+}
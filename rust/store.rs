@@ -0,0 +1,259 @@
+//! Snapshots streamed to and from object storage.
+//!
+//! Indexes served from several machines are usually persisted to S3, GCS, or Azure rather than
+//! to a local disk. [`Index::save_to_store`] and [`Index::load_from_store`] move snapshots through
+//! any [`ObjectStore`] of the `object_store` crate, staged in a temporary file so that multi-GB
+//! indexes never need a second copy in memory. Snapshots larger than a part are uploaded with a
+//! multipart upload, a few parts at a time, and aborted if any part fails.
+//!
+//! Both methods need a Tokio runtime, which the stores of `object_store` run their requests on.
+//! The staged file is read and written on the blocking thread pool. The index itself is saved
+//! and loaded with `block_in_place` on multi-threaded runtimes, which move the other tasks of the
+//! worker elsewhere meanwhile, and inline on current-thread ones.
+//! Enable the `aws`, `gcp`, or `azure` feature of your own `object_store` dependency, of the same
+//! major version, to reach the matching service.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use object_store::path::Path as StorePath;
+use object_store::{ObjectStore, PutPayload, WriteMultipart};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use crate::{Index, IndexError};
+
+/// Bytes uploaded or downloaded per request, above the 5 MiB minimum part of S3.
+const PART_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Parts of a multipart upload in flight at once.
+const PARTS_IN_FLIGHT: usize = 8;
+
+/// A temporary file, removed when dropped.
+struct Staged(PathBuf);
+
+impl Staged {
+    fn new() -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "usearch-store-{}-{}.usearch",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        Self(std::env::temp_dir().join(name))
+    }
+}
+
+impl Drop for Staged {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn store_error(err: object_store::Error) -> IndexError {
+    IndexError::Io(err.into())
+}
+
+/// Runs a blocking call borrowing the index, letting a multi-threaded runtime move the other
+/// tasks of the worker elsewhere meanwhile. Current-thread runtimes can't, so it runs inline there.
+fn block_in_place<T>(function: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(function)
+        }
+        _ => function(),
+    }
+}
+
+/// Runs file I/O over owned handles and buffers on the blocking thread pool.
+async fn spawn_io<T, F>(function: F) -> std::io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(function)
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+}
+
+/// Reads up to a part from a file, returning fewer bytes only at its end.
+fn read_part(file: &mut File, part: &mut Vec<u8>) -> std::io::Result<()> {
+    part.clear();
+    Read::by_ref(file)
+        .take(PART_LENGTH as u64)
+        .read_to_end(part)
+        .map(|_| ())
+}
+
+/// Reads the next part of a file on the blocking thread pool, handing back the file and buffer.
+async fn read_next(mut file: File, mut part: Vec<u8>) -> std::io::Result<(File, Vec<u8>)> {
+    spawn_io(move || read_part(&mut file, &mut part).map(|()| (file, part))).await
+}
+
+impl Index {
+    /// Saves the index to an object store, replacing any object at the location.
+    ///
+    /// The snapshot is written to a temporary file first, like with [`Index::save`], and read
+    /// back on the blocking thread pool. Snapshots of up to 16 MiB are uploaded with a single request, and larger
+    /// ones in parts of 16 MiB, eight at a time.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::Io` if the snapshot can't be staged or uploaded, after aborting the upload.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use object_store::memory::InMemory;
+    /// use object_store::path::Path;
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let store = InMemory::new();
+    /// let location = Path::from("indexes/products.usearch");
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.reserve(10).unwrap();
+    /// index.add(1, &[0.6_f32, 0.8]).unwrap();
+    /// index.save_to_store(&store, &location).await.unwrap();
+    ///
+    /// let mut loaded = Index::new(&index.options()).unwrap();
+    /// loaded.load_from_store(&store, &location).await.unwrap();
+    /// assert!(loaded.contains(1));
+    /// # }
+    /// ```
+    pub async fn save_to_store(
+        &self,
+        store: &dyn ObjectStore,
+        location: &StorePath,
+    ) -> Result<(), IndexError> {
+        let staged = Staged::new();
+        block_in_place(|| self.save(&staged.0))?;
+        let file = File::open(&staged.0)?;
+        let (mut file, mut part) = read_next(file, Vec::with_capacity(PART_LENGTH)).await?;
+        if part.len() < PART_LENGTH {
+            store
+                .put(location, PutPayload::from(part))
+                .await
+                .map_err(store_error)?;
+            return Ok(());
+        }
+
+        let upload = store.put_multipart(location).await.map_err(store_error)?;
+        let mut writer = WriteMultipart::new_with_chunk_size(upload, PART_LENGTH);
+        while !part.is_empty() {
+            let uploaded = writer.wait_for_capacity(PARTS_IN_FLIGHT).await;
+            if let Err(err) = uploaded {
+                let _ = writer.abort().await;
+                return Err(store_error(err));
+            }
+            writer.write(&part);
+            match read_next(file, part).await {
+                Ok(next) => (file, part) = next,
+                Err(err) => {
+                    let _ = writer.abort().await;
+                    return Err(err.into());
+                }
+            }
+        }
+        writer.finish().await.map_err(store_error)?;
+        Ok(())
+    }
+
+    /// Loads the index from an object store, replacing its contents.
+    ///
+    /// The object is downloaded in ranges of 16 MiB into a temporary file, written on the blocking
+    /// thread pool, and loaded from it like with [`Index::load`], which verifies the checksum of
+    /// the snapshot.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::Io` if the object can't be found or downloaded, with `NotFound` for a missing
+    /// one, and the errors of [`Index::load`] for truncated, corrupted, or incompatible snapshots.
+    pub async fn load_from_store(
+        &mut self,
+        store: &dyn ObjectStore,
+        location: &StorePath,
+    ) -> Result<(), IndexError> {
+        let size = store.head(location).await.map_err(store_error)?.size;
+        let staged = Staged::new();
+        let mut file = File::create(&staged.0)?;
+        let mut start = 0;
+        while start < size {
+            let end = size.min(start + PART_LENGTH as u64);
+            let part = store
+                .get_range(location, start..end)
+                .await
+                .map_err(store_error)?;
+            file = spawn_io(move || file.write_all(&part).map(|()| file)).await?;
+            start = end;
+        }
+        spawn_io(move || file.sync_all()).await?;
+        block_in_place(|| self.load(&staged.0))?;
+        // The staged file is removed, so there is nothing to sync to.
+        self.forget_sync();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use object_store::memory::InMemory;
+
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[tokio::test]
+    async fn test_object_store() {
+        let options = IndexOptions {
+            dimensions: 64,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 8,
+            ..Default::default()
+        };
+        let store = InMemory::new();
+        let small = Index::new(&options).unwrap();
+        small.reserve(10).unwrap();
+        small.add(1, &[1.0_f32; 64]).unwrap();
+        let location = StorePath::from("indexes/small.usearch");
+        small.save_to_store(&store, &location).await.unwrap();
+        let mut loaded = Index::new(&options).unwrap();
+        loaded.load_from_store(&store, &location).await.unwrap();
+        assert_eq!(loaded.size(), 1);
+
+        // Over two parts of vectors, uploaded in several parts and downloaded in several ranges.
+        let count = 2 * PART_LENGTH / (64 * 4) + 100;
+        let large = Index::new(&options).unwrap();
+        large.reserve(count).unwrap();
+        for key in 0..count as u64 {
+            large.add(key, &[key as f32; 64]).unwrap();
+        }
+        let location = StorePath::from("indexes/large.usearch");
+        large.save_to_store(&store, &location).await.unwrap();
+        let head = store.head(&location).await.unwrap();
+        assert_eq!(head.size as usize, large.serialized_length());
+        loaded.load_from_store(&store, &location).await.unwrap();
+        assert_eq!(loaded.size(), count);
+        assert_eq!(loaded.search(&[77.0_f32; 64], 1).unwrap().keys, vec![77]);
+
+        let missing = StorePath::from("indexes/missing.usearch");
+        match loaded.load_from_store(&store, &missing).await {
+            Err(IndexError::Io(err)) => assert_eq!(err.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("Unexpected result: {:?}", other.err()),
+        }
+        assert_eq!(loaded.size(), count);
+
+        // Truncated objects are rejected by the checksum.
+        let bytes = store.get(&location).await.unwrap().bytes().await.unwrap();
+        let truncated = StorePath::from("indexes/truncated.usearch");
+        let payload = PutPayload::from(bytes.slice(..bytes.len() - 1000));
+        store.put(&truncated, payload).await.unwrap();
+        assert!(matches!(
+            loaded.load_from_store(&store, &truncated).await,
+            Err(IndexError::CorruptSnapshot { .. })
+        ));
+    }
+}
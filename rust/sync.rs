@@ -0,0 +1,317 @@
+//! Persisting light churn by appending to the last saved file.
+//!
+//! [`Index::save`] rewrites the whole file, so large indexes with few updates pay the full
+//! serialization cost on every persist. [`Index::sync`] instead appends the vectors of the keys
+//! added, replaced, renamed, or removed since the previous sync to the end of the file, after the
+//! snapshot written by the last full rewrite. [`Index::load`] replays those records, inserting
+//! their vectors into the graph again. Once they outgrow a fraction of the snapshot, the next
+//! sync rewrites the file from scratch, dropping them.
+//!
+//! The appended section starts with `USSYNC01`, followed by records framed as
+//! `[length: u32][crc32: u32][payload]`, like the ones of the write-ahead log. A torn record at
+//! the tail is discarded on load, and the following sync rewrites the file.
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::PoisonError;
+
+use crate::checksum::crc32;
+use crate::snapshot::{rename_synced, temporary_path};
+use crate::{Index, IndexError, Key};
+
+const MAGIC: &[u8; 8] = b"USSYNC01";
+const FRAME_HEADER: usize = 8;
+
+const OP_PUT: u8 = 1;
+const OP_REMOVE: u8 = 2;
+
+/// The appended records may grow up to this fraction of the snapshot before a full rewrite.
+pub const DEFAULT_REWRITE_RATIO: f64 = 0.5;
+
+/// Outcome of an [`Index::sync`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Whether the whole file was rewritten, rather than appended to.
+    pub rewritten: bool,
+    /// Number of keys whose vectors, or removal, were written.
+    pub keys: usize,
+    /// Number of bytes written to the file.
+    pub bytes: u64,
+}
+
+/// What the file last synced from an index holds, and which keys changed since.
+pub(crate) struct SyncJournal {
+    path: PathBuf,
+    /// Length of the snapshot at the start of the file.
+    snapshot: u64,
+    /// Length of the file, snapshot and appended records.
+    length: u64,
+    changed: HashSet<Key>,
+}
+
+impl Index {
+    /// Notes keys whose vectors changed, if the index is synced to a file.
+    pub(crate) fn journal_changes(&self, keys: &[Key]) {
+        let mut journal = self.journal.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(journal) = journal.as_mut() {
+            journal.changed.extend(keys);
+        }
+    }
+
+    /// Stops tracking changes, after the contents of the index were replaced.
+    pub(crate) fn forget_sync(&self) {
+        *self.journal.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Persists the index to a file, appending the changes since the last sync to the same path.
+    ///
+    /// The first sync to a path, like the ones after the file was modified by someone else, or
+    /// once the appended records exceed [`DEFAULT_REWRITE_RATIO`] of the snapshot, saves the
+    /// whole index through a temporary file, atomically renamed. Other syncs only append the
+    /// vectors of changed keys. Files synced this way are read back with [`Index::load`],
+    /// while [`Index::view`] rejects them until the next full rewrite.
+    ///
+    /// Mutations wait for the sync to finish, as they do for [`Index::snapshot`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let options = IndexOptions { dimensions: 3, ..Default::default() };
+    /// let index = Index::new(&options).unwrap();
+    /// index.add(1, &[0.2_f32, 0.1, 0.2]).unwrap();
+    /// assert!(index.sync("index.usearch").unwrap().rewritten);
+    ///
+    /// index.add(2, &[0.1_f32, 0.2, 0.1]).unwrap();
+    /// let report = index.sync("index.usearch").unwrap();
+    /// assert_eq!((report.rewritten, report.keys), (false, 1));
+    /// ```
+    pub fn sync<P: AsRef<Path>>(&self, path: P) -> Result<SyncReport, IndexError> {
+        self.sync_with_ratio(path, DEFAULT_REWRITE_RATIO)
+    }
+
+    /// Persists the index like [`Index::sync`], rewriting the file once the appended records
+    /// exceed `rewrite_ratio` times the length of the snapshot. Zero always rewrites it.
+    pub fn sync_with_ratio<P: AsRef<Path>>(
+        &self,
+        path: P,
+        rewrite_ratio: f64,
+    ) -> Result<SyncReport, IndexError> {
+        let path = path.as_ref();
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let mut journal = self.journal.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let appendable = journal.as_ref().filter(|journal| {
+            journal.path == path
+                && fs::metadata(path).is_ok_and(|metadata| metadata.len() == journal.length)
+        });
+        if let Some(current) = appendable {
+            let mut changed: Vec<Key> = current.changed.iter().copied().collect();
+            changed.sort_unstable();
+            let mut records = Vec::new();
+            if current.length == current.snapshot {
+                records.extend_from_slice(MAGIC);
+            }
+            for &key in &changed {
+                self.append_record(key, &mut records)?;
+            }
+
+            let appended = current.length - current.snapshot + records.len() as u64;
+            if appended as f64 <= current.snapshot as f64 * rewrite_ratio {
+                let mut file = OpenOptions::new().write(true).open(path)?;
+                file.seek(SeekFrom::Start(current.length))?;
+                file.write_all(&records)?;
+                file.sync_data()?;
+
+                let current = journal.as_mut().unwrap();
+                current.length += records.len() as u64;
+                current.changed.clear();
+                return Ok(SyncReport {
+                    rewritten: false,
+                    keys: changed.len(),
+                    bytes: records.len() as u64,
+                });
+            }
+        }
+
+        let temporary = temporary_path(path);
+        if let Err(err) = self.save_exclusive(&temporary) {
+            let _ = fs::remove_file(&temporary);
+            return Err(err);
+        }
+        rename_synced(&temporary, path)?;
+        let length = fs::metadata(path)?.len();
+        *journal = Some(SyncJournal {
+            path: path.to_path_buf(),
+            snapshot: length,
+            length,
+            changed: HashSet::new(),
+        });
+        Ok(SyncReport {
+            rewritten: true,
+            keys: self.size(),
+            bytes: length,
+        })
+    }
+
+    /// Frames the vectors of a key, in the scalar kind they are stored in, or its removal.
+    fn append_record(&self, key: Key, records: &mut Vec<u8>) -> Result<(), IndexError> {
+        let mut payload = Vec::new();
+        if self.contains(key) {
+            payload.push(OP_PUT);
+            payload.extend_from_slice(&key.to_le_bytes());
+            self.export_raw(key, &mut payload)?;
+        } else {
+            payload.push(OP_REMOVE);
+            payload.extend_from_slice(&key.to_le_bytes());
+        }
+        records.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        records.extend_from_slice(&crc32(&payload).to_le_bytes());
+        records.extend_from_slice(&payload);
+        Ok(())
+    }
+
+    /// Replays the records appended to a loaded file, and tracks changes to sync it again.
+    pub(crate) fn replay_synced(&self, path: &Path) -> Result<(), IndexError> {
+        self.forget_sync();
+        let snapshot = self.serialized_length() as u64;
+        let mut file = File::open(path)?;
+        let length = file.metadata()?.len();
+        let mut valid = length;
+        if length > snapshot {
+            let mut contents = Vec::new();
+            file.seek(SeekFrom::Start(snapshot))?;
+            file.read_to_end(&mut contents)?;
+            if !contents.starts_with(MAGIC) {
+                return Err(malformed_records());
+            }
+            valid = snapshot + self.replay_records(&contents)? as u64;
+        }
+        *self.journal.lock().unwrap_or_else(PoisonError::into_inner) = Some(SyncJournal {
+            path: PathBuf::from(path),
+            snapshot,
+            length: valid,
+            changed: HashSet::new(),
+        });
+        Ok(())
+    }
+
+    /// Applies all intact records, returning the length of the valid prefix.
+    fn replay_records(&self, contents: &[u8]) -> Result<usize, IndexError> {
+        let mut offset = MAGIC.len();
+        while offset + FRAME_HEADER <= contents.len() {
+            let length =
+                u32::from_le_bytes(contents[offset..offset + 4].try_into().unwrap()) as usize;
+            let checksum = u32::from_le_bytes(contents[offset + 4..offset + 8].try_into().unwrap());
+            let start = offset + FRAME_HEADER;
+            if length < 9 || start + length > contents.len() {
+                break;
+            }
+            let payload = &contents[start..start + length];
+            if crc32(payload) != checksum {
+                break;
+            }
+            let key = Key::from_le_bytes(payload[1..9].try_into().unwrap());
+            self.remove(key)?;
+            match payload[0] {
+                OP_REMOVE => {}
+                OP_PUT if self.insert_raw(key, &payload[9..])? => {}
+                _ => return Err(malformed_records()),
+            }
+            offset = start + length;
+        }
+        Ok(offset)
+    }
+}
+
+/// Checks that a file holds no records appended by [`Index::sync`] after its snapshot.
+pub(crate) fn check_unsynced(path: &Path, snapshot: usize) -> Result<(), IndexError> {
+    if fs::metadata(path)?.len() > snapshot as u64 {
+        return Err(IndexError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "The file holds changes appended by `Index::sync`, load it instead of viewing it",
+        )));
+    }
+    Ok(())
+}
+
+fn malformed_records() -> IndexError {
+    IndexError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Malformed records appended by `Index::sync`",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_incremental_sync() {
+        let path = std::env::temp_dir().join("usearch-synced.usearch");
+        let _ = fs::remove_file(&path);
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            multi: true,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+        let report = index.sync(&path).unwrap();
+        assert!(report.rewritten);
+        let snapshot = fs::metadata(&path).unwrap().len();
+        assert_eq!(report.bytes, snapshot);
+
+        index.add(100, &[100.0_f32, 0.0]).unwrap();
+        index.add(100, &[100.0_f32, 1.0]).unwrap();
+        index.remove(5).unwrap();
+        index.rename(6, 600).unwrap();
+        let report = index.sync(&path).unwrap();
+        assert!(!report.rewritten);
+        assert_eq!(report.keys, 4);
+        assert_eq!(fs::metadata(&path).unwrap().len(), snapshot + report.bytes);
+        assert_eq!(index.sync(&path).unwrap().keys, 0);
+
+        let mut restored = Index::new(&options).unwrap();
+        restored.load(path.to_str().unwrap()).unwrap();
+        assert_eq!(restored.size(), 101);
+        assert_eq!(restored.count(100), 2);
+        assert!(!restored.contains(5) && !restored.contains(6));
+        let mut vector = [0.0_f32; 2];
+        restored.get(600, &mut vector).unwrap();
+        assert_eq!(vector, [6.0, 0.0]);
+        assert!(Index::new(&options)
+            .unwrap()
+            .view(path.to_str().unwrap())
+            .is_err());
+
+        // The restored index keeps appending, and a torn record is dropped by a full rewrite.
+        restored.add(200, &[200.0_f32, 0.0]).unwrap();
+        assert!(!restored.sync(&path).unwrap().rewritten);
+        let file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.set_len(file.metadata().unwrap().len() - 3).unwrap();
+        drop(file);
+        let mut torn = Index::new(&options).unwrap();
+        torn.load(path.to_str().unwrap()).unwrap();
+        assert!(!torn.contains(200) && torn.contains(600));
+        assert!(torn.sync(&path).unwrap().rewritten);
+
+        // Appended records beyond the ratio trigger a full rewrite.
+        for key in 300..400u64 {
+            torn.add(key, &[key as f32, 0.0]).unwrap();
+        }
+        assert!(torn.sync_with_ratio(&path, 0.01).unwrap().rewritten);
+        let mut viewed = Index::new(&options).unwrap();
+        viewed.view(path.to_str().unwrap()).unwrap();
+        assert_eq!(viewed.size(), 201);
+        fs::remove_file(&path).unwrap();
+    }
+}
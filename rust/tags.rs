@@ -0,0 +1,247 @@
+//! Lightweight boolean filters over up to 64 tags per key, without a payload store.
+//!
+//! Most filters only ask whether an entry is "published", "in stock", or "in English".
+//! A [`TagSet`] keeps those flags as one `u64` bitmask per key, next to the index, and
+//! [`Index::search_tagged`] checks them in the filter callback of the native search,
+//! with two bitwise operations per visited key.
+
+use std::collections::HashMap;
+#[cfg(not(no_fs))]
+use std::fs::File;
+#[cfg(not(no_fs))]
+use std::io::{BufReader, BufWriter};
+use std::io::{Read, Write};
+#[cfg(not(no_fs))]
+use std::path::Path;
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, Key, VectorType};
+
+const MAGIC: &[u8; 8] = b"USTAGS01";
+
+/// Tag bitmasks of the keys of an index. Keys without tags have an empty mask.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagSet {
+    masks: HashMap<Key, u64>,
+}
+
+impl TagSet {
+    /// Creates an empty tag set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the tags of a key, removing the key if the mask is empty.
+    pub fn set_tags(&mut self, key: Key, mask: u64) {
+        if mask == 0 {
+            self.masks.remove(&key);
+        } else {
+            self.masks.insert(key, mask);
+        }
+    }
+
+    /// Adds the tags of the mask to the key, keeping its other tags.
+    pub fn add_tags(&mut self, key: Key, mask: u64) {
+        self.set_tags(key, self.tags(key) | mask);
+    }
+
+    /// Removes the tags of the mask from the key, keeping its other tags.
+    pub fn remove_tags(&mut self, key: Key, mask: u64) {
+        self.set_tags(key, self.tags(key) & !mask);
+    }
+
+    /// The tags of a key, or an empty mask if it has none.
+    pub fn tags(&self, key: Key) -> u64 {
+        self.masks.get(&key).copied().unwrap_or(0)
+    }
+
+    /// Checks if the key has all `required` tags and none of the `forbidden` ones.
+    pub fn matches(&self, key: Key, required: u64, forbidden: u64) -> bool {
+        let mask = self.tags(key);
+        mask & required == required && mask & forbidden == 0
+    }
+
+    /// Number of keys with at least one tag.
+    pub fn len(&self) -> usize {
+        self.masks.len()
+    }
+
+    /// Checks if no key has tags.
+    pub fn is_empty(&self) -> bool {
+        self.masks.is_empty()
+    }
+
+    /// Removes the tags of all keys.
+    pub fn clear(&mut self) {
+        self.masks.clear()
+    }
+
+    /// Serializes the set into a writer, as little-endian key and mask pairs after a header.
+    pub fn save_to_writer<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(self.masks.len() as u64).to_le_bytes())?;
+        for (key, mask) in &self.masks {
+            writer.write_all(&key.to_le_bytes())?;
+            writer.write_all(&mask.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes the set from a reader, previously populated with [`TagSet::save_to_writer`].
+    pub fn load_from_reader<R: Read>(mut reader: R) -> std::io::Result<Self> {
+        fn read_u64<R: Read>(reader: &mut R) -> std::io::Result<u64> {
+            let mut bytes = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        }
+
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Not a USearch tag set",
+            ));
+        }
+        let count = read_u64(&mut reader)? as usize;
+        let mut masks = HashMap::with_capacity(count);
+        for _ in 0..count {
+            let key = read_u64(&mut reader)?;
+            masks.insert(key, read_u64(&mut reader)?);
+        }
+        Ok(Self { masks })
+    }
+
+    /// Saves the set to a specified file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where the set will be saved.
+    #[cfg(not(no_fs))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.save_to_writer(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Loads the set from a specified file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path from where the set will be loaded.
+    #[cfg(not(no_fs))]
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::load_from_reader(BufReader::new(File::open(path)?))
+    }
+}
+
+impl FromIterator<(Key, u64)> for TagSet {
+    fn from_iter<I: IntoIterator<Item = (Key, u64)>>(iter: I) -> Self {
+        let mut tags = Self::new();
+        for (key, mask) in iter {
+            tags.set_tags(key, mask);
+        }
+        tags
+    }
+}
+
+impl Index {
+    /// Performs k-Approximate Nearest Neighbors (kANN) Search, only returning keys that have
+    /// all `required` tags and none of the `forbidden` ones in the given tag set.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - A slice containing the query vector data.
+    /// * `count` - The maximum number of neighbors to search for.
+    /// * `tags` - The tags of the keys of this index.
+    /// * `required` - The mask of tags every match must have.
+    /// * `forbidden` - The mask of tags no match may have.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions, MetricKind, ScalarKind, TagSet};
+    ///
+    /// const PUBLISHED: u64 = 1 << 0;
+    /// const ARCHIVED: u64 = 1 << 1;
+    ///
+    /// let options = IndexOptions {
+    ///     dimensions: 2,
+    ///     metric: MetricKind::L2sq,
+    ///     quantization: ScalarKind::F32,
+    ///     ..Default::default()
+    /// };
+    /// let index = Index::new(&options).unwrap();
+    /// index.reserve(10).unwrap();
+    /// index.add(1, &[0.0_f32, 0.0]).unwrap();
+    /// index.add(2, &[0.1_f32, 0.0]).unwrap();
+    ///
+    /// let mut tags = TagSet::new();
+    /// tags.set_tags(1, PUBLISHED | ARCHIVED);
+    /// tags.set_tags(2, PUBLISHED);
+    ///
+    /// let matches = index.search_tagged(&[0.0_f32, 0.0], 2, &tags, PUBLISHED, ARCHIVED).unwrap();
+    /// assert_eq!(matches.keys, vec![2]);
+    /// ```
+    pub fn search_tagged<T: VectorType>(
+        &self,
+        query: &[T],
+        count: usize,
+        tags: &TagSet,
+        required: u64,
+        forbidden: u64,
+    ) -> Result<Matches, IndexError> {
+        if required == 0 && forbidden == 0 {
+            self.search(query, count)
+        } else {
+            self.filtered_search(query, count, |key| tags.matches(key, required, forbidden))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_search_tagged() {
+        let options = IndexOptions {
+            dimensions: 1,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        index.reserve(100).unwrap();
+        let mut tags = TagSet::new();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32]).unwrap();
+            // Bit 0 marks even keys, bit 1 marks multiples of three.
+            tags.set_tags(key, (key % 2 == 0) as u64 | ((key % 3 == 0) as u64) << 1);
+        }
+
+        let matches = index
+            .search_tagged(&[50.0_f32], 3, &tags, 0b01, 0b10)
+            .unwrap();
+        assert_eq!(matches.keys[0], 50);
+        assert!(matches.keys.iter().all(|key| key % 2 == 0 && key % 3 != 0));
+        let matches = index.search_tagged(&[50.0_f32], 3, &tags, 0b11, 0).unwrap();
+        assert!(!matches.keys.is_empty());
+        assert!(matches.keys.iter().all(|key| key % 6 == 0));
+        assert!(matches!(
+            index.search_tagged(&[50.0_f32, 0.0], 3, &tags, 0b11, 0),
+            Err(IndexError::DimensionMismatch { .. })
+        ));
+
+        tags.remove_tags(48, 0b10);
+        assert_eq!(tags.tags(48), 0b01);
+        tags.remove_tags(48, 0b01);
+        assert_eq!(tags.tags(48), 0);
+        assert_eq!(tags.len(), 66);
+
+        let mut buffer = Vec::new();
+        tags.save_to_writer(&mut buffer).unwrap();
+        assert_eq!(TagSet::load_from_reader(buffer.as_slice()).unwrap(), tags);
+    }
+}
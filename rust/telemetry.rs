@@ -0,0 +1,311 @@
+//! OpenTelemetry traces and metrics for services built around an [`Index`].
+//!
+//! Enabled with the `telemetry` feature. [`Telemetry::init`] installs global tracer and meter
+//! providers exporting over OTLP/gRPC, and [`IndexMetrics`] records per-operation latency
+//! histograms, error counters, and index size gauges through them. Without an initialized
+//! [`Telemetry`], all instruments fall back to the no-op global providers.
+//!
+//! The exporters are driven by `tonic`, so [`Telemetry::init`] must be called inside a Tokio runtime.
+
+use std::time::{Duration, Instant};
+
+use opentelemetry::metrics::{Counter, Gauge, Histogram, Meter};
+use opentelemetry::trace::{Status, TraceContextExt, Tracer};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::{ExporterBuildError, WithExportConfig};
+use opentelemetry_sdk::error::OTelSdkError;
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+
+use crate::Index;
+
+/// Instrumentation scope reported with every span and metric.
+const SCOPE: &str = "usearch";
+
+/// Configuration of the OTLP exporters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetryConfig {
+    /// The `service.name` resource attribute.
+    pub service_name: String,
+    /// The OTLP/gRPC collector endpoint, like `http://localhost:4317`.
+    /// If `None`, the exporter default or the `OTEL_EXPORTER_OTLP_ENDPOINT` variable is used.
+    pub endpoint: Option<String>,
+    /// Timeout for every export request.
+    pub timeout: Duration,
+    /// How often the collected metrics are pushed.
+    pub metrics_interval: Duration,
+    /// Whether to export traces.
+    pub traces: bool,
+    /// Whether to export metrics.
+    pub metrics: bool,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            service_name: "usearch".to_owned(),
+            endpoint: None,
+            timeout: Duration::from_secs(10),
+            metrics_interval: Duration::from_secs(60),
+            traces: true,
+            metrics: true,
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Creates the default configuration, overridden by the standard `OTEL_SERVICE_NAME`,
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT`, and `OTEL_METRIC_EXPORT_INTERVAL` (milliseconds) variables.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(name) = std::env::var("OTEL_SERVICE_NAME") {
+            config.service_name = name;
+        }
+        if let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            config.endpoint = Some(endpoint);
+        }
+        if let Some(millis) = std::env::var("OTEL_METRIC_EXPORT_INTERVAL")
+            .ok()
+            .and_then(|value| value.parse().ok())
+        {
+            config.metrics_interval = Duration::from_millis(millis);
+        }
+        config
+    }
+}
+
+/// Errors raised while setting up or shutting down the exporters.
+#[derive(Debug)]
+pub enum TelemetryError {
+    /// An exporter could not be constructed from the configuration.
+    Build(ExporterBuildError),
+    /// Pending telemetry could not be flushed on shutdown.
+    Shutdown(OTelSdkError),
+}
+
+impl std::fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TelemetryError::Build(err) => write!(f, "Failed to build OTLP exporter: {}", err),
+            TelemetryError::Shutdown(err) => write!(f, "Failed to shut down telemetry: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for TelemetryError {}
+
+impl From<ExporterBuildError> for TelemetryError {
+    fn from(err: ExporterBuildError) -> Self {
+        TelemetryError::Build(err)
+    }
+}
+
+impl From<OTelSdkError> for TelemetryError {
+    fn from(err: OTelSdkError) -> Self {
+        TelemetryError::Shutdown(err)
+    }
+}
+
+/// Owns the global tracer and meter providers installed by [`Telemetry::init`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn serve() -> Result<(), Box<dyn std::error::Error>> {
+/// use usearch::telemetry::{Telemetry, TelemetryConfig};
+///
+/// let telemetry = Telemetry::init(&TelemetryConfig::from_env())?;
+/// // ... serve requests ...
+/// telemetry.shutdown()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Telemetry {
+    tracer_provider: Option<SdkTracerProvider>,
+    meter_provider: Option<SdkMeterProvider>,
+}
+
+impl Telemetry {
+    /// Builds the OTLP exporters and installs them as the global providers.
+    pub fn init(config: &TelemetryConfig) -> Result<Self, TelemetryError> {
+        let resource = Resource::builder()
+            .with_service_name(config.service_name.clone())
+            .build();
+
+        let tracer_provider = if config.traces {
+            let mut exporter = opentelemetry_otlp::SpanExporter::builder()
+                .with_tonic()
+                .with_timeout(config.timeout);
+            if let Some(endpoint) = &config.endpoint {
+                exporter = exporter.with_endpoint(endpoint.clone());
+            }
+            let provider = SdkTracerProvider::builder()
+                .with_batch_exporter(exporter.build()?)
+                .with_resource(resource.clone())
+                .build();
+            global::set_tracer_provider(provider.clone());
+            Some(provider)
+        } else {
+            None
+        };
+
+        let meter_provider = if config.metrics {
+            let mut exporter = opentelemetry_otlp::MetricExporter::builder()
+                .with_tonic()
+                .with_timeout(config.timeout);
+            if let Some(endpoint) = &config.endpoint {
+                exporter = exporter.with_endpoint(endpoint.clone());
+            }
+            let reader = PeriodicReader::builder(exporter.build()?)
+                .with_interval(config.metrics_interval)
+                .build();
+            let provider = SdkMeterProvider::builder()
+                .with_reader(reader)
+                .with_resource(resource)
+                .build();
+            global::set_meter_provider(provider.clone());
+            Some(provider)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            tracer_provider,
+            meter_provider,
+        })
+    }
+
+    /// Flushes all pending spans and metrics, and stops the exporters.
+    pub fn shutdown(self) -> Result<(), TelemetryError> {
+        if let Some(provider) = self.tracer_provider {
+            provider.shutdown()?;
+        }
+        if let Some(provider) = self.meter_provider {
+            provider.shutdown()?;
+        }
+        Ok(())
+    }
+}
+
+/// Instruments reporting the latency of index operations and the state of the index.
+///
+/// - `usearch.rpc.duration` - histogram of operation latencies in seconds, by `rpc.method`.
+/// - `usearch.rpc.errors` - counter of failed operations, by `rpc.method`.
+/// - `usearch.index.size`, `usearch.index.capacity`, `usearch.index.memory` - gauges.
+#[derive(Clone)]
+pub struct IndexMetrics {
+    duration: Histogram<f64>,
+    errors: Counter<u64>,
+    size: Gauge<u64>,
+    capacity: Gauge<u64>,
+    memory: Gauge<u64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl Default for IndexMetrics {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl IndexMetrics {
+    /// Creates the instruments on the global meter provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `attributes` - Extra attributes attached to every measurement, like the index name.
+    pub fn new(attributes: Vec<KeyValue>) -> Self {
+        Self::with_meter(&global::meter(SCOPE), attributes)
+    }
+
+    /// Creates the instruments on a given meter, like one of a provider that isn't installed globally.
+    pub fn with_meter(meter: &Meter, attributes: Vec<KeyValue>) -> Self {
+        Self {
+            duration: meter
+                .f64_histogram("usearch.rpc.duration")
+                .with_unit("s")
+                .with_description("Latency of index operations")
+                .build(),
+            errors: meter
+                .u64_counter("usearch.rpc.errors")
+                .with_description("Number of failed index operations")
+                .build(),
+            size: meter
+                .u64_gauge("usearch.index.size")
+                .with_description("Number of vectors in the index")
+                .build(),
+            capacity: meter
+                .u64_gauge("usearch.index.capacity")
+                .with_description("Number of vectors the index can hold without reallocation")
+                .build(),
+            memory: meter
+                .u64_gauge("usearch.index.memory")
+                .with_unit("By")
+                .with_description("Memory used by the index")
+                .build(),
+            attributes,
+        }
+    }
+
+    /// Runs an operation within a span, recording its latency and failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The operation name, used as the span name and the `rpc.method` attribute.
+    /// * `operation` - The closure performing the operation.
+    pub fn observe<R, E, F>(&self, method: &'static str, operation: F) -> Result<R, E>
+    where
+        E: std::fmt::Display,
+        F: FnOnce() -> Result<R, E>,
+    {
+        global::tracer(SCOPE).in_span(method, |cx| {
+            let start = Instant::now();
+            let result = operation();
+            let mut attributes = self.attributes.clone();
+            attributes.push(KeyValue::new("rpc.method", method));
+            self.duration
+                .record(start.elapsed().as_secs_f64(), &attributes);
+            if let Err(err) = &result {
+                self.errors.add(1, &attributes);
+                cx.span().set_status(Status::error(err.to_string()));
+            }
+            result
+        })
+    }
+
+    /// Records the current size, capacity, and memory usage of the index.
+    pub fn record_index(&self, index: &Index) {
+        self.size.record(index.size() as u64, &self.attributes);
+        self.capacity
+            .record(index.capacity() as u64, &self.attributes);
+        self.memory
+            .record(index.memory_usage() as u64, &self.attributes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndexOptions;
+
+    #[test]
+    fn test_observe_without_exporters() {
+        let options = IndexOptions {
+            dimensions: 2,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let metrics = IndexMetrics::new(vec![KeyValue::new("index", "test")]);
+
+        metrics.observe("reserve", || index.reserve(10)).unwrap();
+        metrics
+            .observe("add", || index.add(1, &[1.0_f32, 0.0]))
+            .unwrap();
+        let results = metrics
+            .observe("search", || index.search(&[1.0_f32, 0.0], 1))
+            .unwrap();
+        assert_eq!(results.keys, vec![1]);
+        metrics.record_index(&index);
+    }
+}
@@ -0,0 +1,235 @@
+//! Many tenants sharing one index, each searching only its own vectors.
+//!
+//! Multi-tenant services often create one index per customer, multiplying the fixed costs of
+//! every graph. [`TenantIndex`] keeps all tenants in a single index instead, by reserving the
+//! high bits of every key for the tenant. Searches filter on those bits, a shift and a comparison
+//! per visited key, and hand back the keys of the tenant as they were inserted.
+
+use crate::ffi::Matches;
+use crate::{Index, IndexError, IndexOptions, Key, VectorType};
+
+/// Identifier of a tenant of a [`TenantIndex`].
+pub type TenantId = u32;
+
+/// The number of key bits reserved for tenants by [`TenantIndex::new`].
+pub const DEFAULT_TENANT_BITS: u32 = 16;
+
+/// An index partitioned between tenants, which don't see each other's vectors.
+///
+/// # Examples
+///
+/// ```
+/// use usearch::{IndexOptions, MetricKind, ScalarKind, TenantIndex};
+///
+/// let options = IndexOptions {
+///     dimensions: 2,
+///     metric: MetricKind::L2sq,
+///     quantization: ScalarKind::F32,
+///     ..Default::default()
+/// };
+/// let index = TenantIndex::new(&options).unwrap();
+/// index.reserve(2).unwrap();
+/// index.add(7, 1, &[0.0_f32, 1.0]).unwrap();
+/// index.add(8, 1, &[0.0_f32, 0.9]).unwrap();
+///
+/// let matches = index.search_tenant(7, &[0.0_f32, 0.9], 10).unwrap();
+/// assert_eq!(matches.keys, vec![1]);
+/// ```
+pub struct TenantIndex {
+    index: Index,
+    tenant_bits: u32,
+}
+
+impl TenantIndex {
+    /// Creates an empty index, reserving [`DEFAULT_TENANT_BITS`] of every key for tenants.
+    pub fn new(options: &IndexOptions) -> Result<Self, IndexError> {
+        Self::with_tenant_bits(Index::new(options)?, DEFAULT_TENANT_BITS)
+    }
+
+    /// Wraps an index, reserving the `tenant_bits` highest bits of every key for tenants.
+    ///
+    /// # Returns
+    ///
+    /// `IndexError::InvalidOptions` unless `tenant_bits` is between 1 and 32.
+    pub fn with_tenant_bits(index: Index, tenant_bits: u32) -> Result<Self, IndexError> {
+        if !(1..=TenantId::BITS).contains(&tenant_bits) {
+            return Err(IndexError::InvalidOptions {
+                reason: format!("Tenants need between 1 and 32 key bits, got {tenant_bits}"),
+            });
+        }
+        Ok(Self { index, tenant_bits })
+    }
+
+    /// The underlying index, holding the keys of all tenants.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Unwraps the underlying index.
+    pub fn into_index(self) -> Index {
+        self.index
+    }
+
+    /// The number of key bits reserved for tenants.
+    pub fn tenant_bits(&self) -> u32 {
+        self.tenant_bits
+    }
+
+    /// The largest key a tenant can use.
+    pub fn max_key(&self) -> Key {
+        Key::MAX >> self.tenant_bits
+    }
+
+    /// The largest tenant identifier.
+    pub fn max_tenant(&self) -> TenantId {
+        TenantId::MAX >> (TenantId::BITS - self.tenant_bits)
+    }
+
+    fn shift(&self) -> u32 {
+        Key::BITS - self.tenant_bits
+    }
+
+    /// Combines a tenant and one of its keys into a key of the underlying index.
+    pub fn encode(&self, tenant: TenantId, key: Key) -> Result<Key, IndexError> {
+        if tenant > self.max_tenant() || key > self.max_key() {
+            return Err(IndexError::InvalidOptions {
+                reason: format!(
+                    "Tenant {tenant} and key {key} don't fit in {} tenant bits",
+                    self.tenant_bits
+                ),
+            });
+        }
+        Ok((Key::from(tenant) << self.shift()) | key)
+    }
+
+    /// Splits a key of the underlying index into its tenant and the key of that tenant.
+    pub fn decode(&self, key: Key) -> (TenantId, Key) {
+        ((key >> self.shift()) as TenantId, key & self.max_key())
+    }
+
+    /// Reserves memory for the specified number of vectors, across all tenants.
+    pub fn reserve(&self, capacity: usize) -> Result<(), cxx::Exception> {
+        self.index.reserve(capacity)
+    }
+
+    /// Adds a vector under a key of the tenant.
+    pub fn add<T: VectorType>(
+        &self,
+        tenant: TenantId,
+        key: Key,
+        vector: &[T],
+    ) -> Result<(), IndexError> {
+        self.index.add(self.encode(tenant, key)?, vector)
+    }
+
+    /// Removes the vectors of a key of the tenant, returning how many were removed.
+    pub fn remove(&self, tenant: TenantId, key: Key) -> Result<usize, IndexError> {
+        Ok(self.index.remove(self.encode(tenant, key)?)?)
+    }
+
+    /// Checks if the tenant has a vector under the key.
+    pub fn contains(&self, tenant: TenantId, key: Key) -> bool {
+        self.encode(tenant, key)
+            .is_ok_and(|key| self.index.contains(key))
+    }
+
+    /// Collects the keys of a tenant, in no particular order.
+    pub fn tenant_keys(&self, tenant: TenantId) -> Vec<Key> {
+        self.index
+            .keys()
+            .into_iter()
+            .map(|key| self.decode(key))
+            .filter(|&(owner, _)| owner == tenant)
+            .map(|(_, key)| key)
+            .collect()
+    }
+
+    /// Removes every vector of a tenant, returning how many were removed.
+    pub fn remove_tenant(&self, tenant: TenantId) -> Result<usize, IndexError> {
+        let mut keys = self.tenant_keys(tenant);
+        keys.sort_unstable();
+        keys.dedup();
+        let mut removed = 0;
+        for key in keys {
+            removed += self.remove(tenant, key)?;
+        }
+        Ok(removed)
+    }
+
+    /// Performs kANN Search among the vectors of a tenant.
+    ///
+    /// # Returns
+    ///
+    /// The matches of the tenant, with its keys as they were inserted.
+    pub fn search_tenant<T: VectorType>(
+        &self,
+        tenant: TenantId,
+        query: &[T],
+        count: usize,
+    ) -> Result<Matches, IndexError> {
+        let shift = self.shift();
+        let owner = Key::from(tenant);
+        let mut matches = self
+            .index
+            .filtered_search(query, count, |key| key >> shift == owner)?;
+        for key in &mut matches.keys {
+            *key &= self.max_key();
+        }
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetricKind, ScalarKind};
+
+    #[test]
+    fn test_tenant_isolation() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = TenantIndex::new(&options).unwrap();
+        index.reserve(300).unwrap();
+        // Three tenants with the same keys and interleaved vectors.
+        for tenant in 0..3 {
+            for key in 0..100u64 {
+                let x = key as f32 * 3.0 + tenant as f32;
+                index.add(tenant, key, &[x, 0.0]).unwrap();
+            }
+        }
+        assert_eq!(index.index().size(), 300);
+
+        for tenant in 0..3 {
+            let query = [150.0 + tenant as f32, 0.0];
+            let matches = index.search_tenant(tenant, &query, 5).unwrap();
+            assert_eq!(matches.keys.len(), 5);
+            assert_eq!((matches.keys[0], matches.distances[0]), (50, 0.0));
+            assert!(matches.keys.iter().all(|&key| key < 100));
+            assert!(index.contains(tenant, 99));
+        }
+
+        assert_eq!(index.remove_tenant(1).unwrap(), 100);
+        assert!(index.tenant_keys(1).is_empty());
+        assert_eq!(index.tenant_keys(2).len(), 100);
+        assert!(index
+            .search_tenant(1, &[150.0_f32, 0.0], 5)
+            .unwrap()
+            .keys
+            .is_empty());
+
+        let max_key = index.max_key();
+        index.add(0xFFFF, max_key, &[0.0_f32, 0.0]).unwrap();
+        assert_eq!(
+            index.decode(index.encode(0xFFFF, max_key).unwrap()),
+            (0xFFFF, max_key)
+        );
+        assert!(index.add(0x10000, 0, &[0.0_f32, 0.0]).is_err());
+        assert!(index.add(0, max_key + 1, &[0.0_f32, 0.0]).is_err());
+        assert!(!index.contains(0x10000, 0));
+        assert!(TenantIndex::with_tenant_bits(index.into_index(), 33).is_err());
+    }
+}
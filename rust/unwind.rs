@@ -0,0 +1,285 @@
+//! Panic safety of the user callbacks called from native frames.
+//!
+//! Filters and custom metrics run inside the native search and insertion loops, and a panic
+//! unwinding through those C++ frames is undefined behavior. The trampolines catch it instead:
+//! a panicking filter rejects its key, and every key after it, while a panicking metric reports
+//! the largest distance. The panic message is kept aside, and surfaces as an error of the call
+//! that triggered it, once the native layer returned.
+//!
+//! Filters are owned by a single call. Metrics are shared by all calls to an index, so their
+//! panics are recorded on the thread they happened on, and only fail the call running there.
+//! Native worker threads of batched calls report to a slot of the index instead, which the
+//! batched calls running a custom metric take turns to own.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::{b1x8, f16, ffi, Distance, Key, MetricFunction};
+
+/// The first panic of a callback, waiting to be reported.
+#[derive(Default)]
+pub(crate) struct PanicSlot {
+    panicked: AtomicBool,
+    message: Mutex<Option<String>>,
+}
+
+impl PanicSlot {
+    /// Whether a callback panicked since the last [`PanicSlot::take`].
+    pub(crate) fn panicked(&self) -> bool {
+        self.panicked.load(Ordering::Relaxed)
+    }
+
+    /// Runs the callback, returning `fallback` and recording the panic if it panics.
+    fn call<R>(&self, fallback: R, callback: impl FnOnce() -> R) -> R {
+        catch_unwind(AssertUnwindSafe(callback)).unwrap_or_else(|payload| {
+            self.record(describe(payload.as_ref()));
+            fallback
+        })
+    }
+
+    /// Keeps the message, unless a panic is already waiting to be reported.
+    fn record(&self, message: String) {
+        let mut recorded = self.message.lock().unwrap_or_else(PoisonError::into_inner);
+        recorded.get_or_insert(message);
+        self.panicked.store(true, Ordering::Relaxed);
+    }
+
+    /// Drops the recorded panic, left by a call that didn't report it.
+    pub(crate) fn clear(&self) {
+        self.panicked.store(false, Ordering::Relaxed);
+        self.message
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+    }
+
+    /// Clears the recorded panic, turning it into a native error.
+    pub(crate) fn take(&self) -> Result<(), cxx::Exception> {
+        if !self.panicked.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let message = self
+            .message
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        ffi::raise_callback_panic(message.as_deref().unwrap_or_default())
+    }
+}
+
+/// The message of a panic payload, as formatted by `panic!`.
+fn describe(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => (*message).to_owned(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| "Box<dyn Any>".to_owned()),
+    }
+}
+
+/// A filter closure, with the slot its panics are recorded in.
+struct GuardedFilter<F> {
+    filter: F,
+    panic: PanicSlot,
+}
+
+/// Calls a filter closure on behalf of the native index, given the address of a `GuardedFilter`.
+extern "C" fn filter_trampoline<F: Fn(Key) -> bool>(key: Key, closure_address: usize) -> bool {
+    // SAFETY: The address comes from `with_filter`, whose `GuardedFilter` outlives the search.
+    let guarded = unsafe { &*(closure_address as *const GuardedFilter<F>) };
+    !guarded.panic.panicked() && guarded.panic.call(false, || (guarded.filter)(key))
+}
+
+/// Runs a native filtered search, passing it the trampoline and closure addresses for `filter`.
+///
+/// A panic of the filter takes precedence over the outcome of the search.
+pub(crate) fn with_filter<F, R>(
+    filter: F,
+    search: impl FnOnce(usize, usize) -> Result<R, cxx::Exception>,
+) -> Result<R, cxx::Exception>
+where
+    F: Fn(Key) -> bool,
+{
+    let guarded = GuardedFilter {
+        filter,
+        panic: PanicSlot::default(),
+    };
+    let trampoline_fn: usize = filter_trampoline::<F> as *const () as usize;
+    let closure_address: usize = &guarded as *const GuardedFilter<F> as usize;
+    let result = search(trampoline_fn, closure_address);
+    guarded.panic.take()?;
+    result
+}
+
+thread_local! {
+    /// The first panic of a custom metric on this thread, while a call guarded by
+    /// [`catch_metric_panics`] runs on it, or `None` outside of such calls.
+    static CALL_PANIC: RefCell<Option<Option<String>>> = const { RefCell::new(None) };
+}
+
+/// Restores the state of the enclosing call on drop, even if the guarded call unwinds.
+struct CallScope(Option<Option<String>>);
+
+impl Drop for CallScope {
+    fn drop(&mut self) {
+        CALL_PANIC.with(|slot| *slot.borrow_mut() = self.0.take());
+    }
+}
+
+/// Runs a native call, returning its result and the first panic of a custom metric called by it
+/// on the calling thread.
+pub(crate) fn catch_metric_panics<R>(call: impl FnOnce() -> R) -> (R, Option<String>) {
+    let _scope = CallScope(CALL_PANIC.with(|slot| slot.replace(Some(None))));
+    let result = call();
+    let panic = CALL_PANIC.with(|slot| slot.borrow_mut().take()).flatten();
+    (result, panic)
+}
+
+/// A custom metric, with the slot the panics on native worker threads are recorded in.
+pub(crate) struct GuardedMetric {
+    pub(crate) metric: MetricFunction,
+    /// Shared by all metrics of an index.
+    pub(crate) panic: Arc<PanicSlot>,
+}
+
+impl GuardedMetric {
+    /// Records a panic for the call running on this thread, or for the batched call owning the
+    /// slot of the index, if the metric runs on one of its native worker threads.
+    fn record(&self, message: String) {
+        let unclaimed = CALL_PANIC.with(|slot| match slot.borrow_mut().as_mut() {
+            Some(call) => {
+                call.get_or_insert(message);
+                None
+            }
+            None => Some(message),
+        });
+        if let Some(message) = unclaimed {
+            self.panic.record(message);
+        }
+    }
+}
+
+/// Calls a custom metric on behalf of the native index, given the address of a `GuardedMetric`.
+pub(crate) extern "C" fn metric_trampoline(
+    first: usize,
+    second: usize,
+    metric_address: usize,
+) -> Distance {
+    // SAFETY: The address comes from a `MetricState`, which outlives the native index using it.
+    let guarded = unsafe { &*(metric_address as *const GuardedMetric) };
+    let distance = catch_unwind(AssertUnwindSafe(|| match &guarded.metric {
+        MetricFunction::B1X8Metric(metric) => metric(first as *const b1x8, second as *const b1x8),
+        MetricFunction::I8Metric(metric) => metric(first as *const i8, second as *const i8),
+        MetricFunction::F16Metric(metric) => metric(first as *const f16, second as *const f16),
+        MetricFunction::F32Metric(metric) => metric(first as *const f32, second as *const f32),
+        MetricFunction::F64Metric(metric) => metric(first as *const f64, second as *const f64),
+    }));
+    distance.unwrap_or_else(|payload| {
+        guarded.record(describe(payload.as_ref()));
+        Distance::MAX
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_callback_panics() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        for key in 0..100u64 {
+            index.add(key, &[key as f32, 0.0]).unwrap();
+        }
+
+        // Panicking filters fail the search, which stays usable afterwards.
+        let error = index
+            .filtered_search(&[50.0_f32, 0.0], 10, |key| {
+                assert!(key != 50, "rejected key {}", key);
+                true
+            })
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("rejected key 50"),
+            "{}",
+            error.to_string()
+        );
+        let error = index
+            .filtered_search_with_deadline(
+                &[50.0_f32, 0.0],
+                10,
+                Duration::from_secs(1),
+                |_| -> bool { panic!("static message") },
+            )
+            .unwrap_err();
+        assert!(error.what().contains("static message"), "{}", error.what());
+        let matches = index
+            .filtered_search(&[50.0_f32, 0.0], 3, |key| key % 2 == 0)
+            .unwrap();
+        assert_eq!(matches.keys, vec![50, 48, 52]);
+
+        // Panicking metrics fail the insertion or search that called them.
+        let failing = Arc::new(AtomicBool::new(false));
+        let trigger = failing.clone();
+        index.change_metric::<f32>(Box::new(move |a, b| {
+            assert!(!trigger.load(Ordering::Relaxed), "metric failed");
+            let (a, b) = unsafe {
+                (
+                    std::slice::from_raw_parts(a, 2),
+                    std::slice::from_raw_parts(b, 2),
+                )
+            };
+            (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)
+        }));
+        assert_eq!(index.search(&[10.0_f32, 0.0], 1).unwrap().keys, vec![10]);
+        failing.store(true, Ordering::Relaxed);
+        let error = index.search(&[10.0_f32, 0.0], 1).unwrap_err();
+        assert!(
+            error.to_string().contains("metric failed"),
+            "{}",
+            error.to_string()
+        );
+        assert!(index.add(100, &[100.0_f32, 0.0]).is_err());
+        assert!(index.contains(100));
+        failing.store(false, Ordering::Relaxed);
+        assert_eq!(index.search(&[10.0_f32, 0.0], 1).unwrap().keys, vec![10]);
+
+        // Panics only fail the call they happened in, even with concurrent calls.
+        index.change_metric::<f32>(Box::new(|a, b| {
+            let (a, b) = unsafe {
+                (
+                    std::slice::from_raw_parts(a, 2),
+                    std::slice::from_raw_parts(b, 2),
+                )
+            };
+            assert!(a[1] >= 0.0 && b[1] >= 0.0, "negative query");
+            (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)
+        }));
+        let index = &index;
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                scope.spawn(move || {
+                    for round in 0..200 {
+                        let sign = if (thread + round) % 2 == 0 { 1.0 } else { -1.0 };
+                        let found = index.search(&[10.0_f32, sign], 1);
+                        assert_eq!(found.is_err(), sign < 0.0);
+                        let batch = index.search_many(&[10.0_f32, 1.0, 20.0, sign], 1);
+                        assert_eq!(batch.is_err(), sign < 0.0);
+                    }
+                });
+            }
+        });
+    }
+}
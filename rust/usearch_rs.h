@@ -0,0 +1,89 @@
+/**
+ *  @file usearch_rs.h
+ *  @brief C ABI of the USearch Rust crate, exported with the `capi` feature.
+ *
+ *  Build the shared library with `cargo rustc --release --features capi --crate-type cdylib`.
+ *  Every fallible function takes a trailing `usearch_rs_error_t*`, set to `NULL` on success,
+ *  or to a message owned by the library, valid until the next call on the same thread.
+ */
+#ifndef USEARCH_RS_H
+#define USEARCH_RS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct usearch_rs_index usearch_rs_index;
+typedef usearch_rs_index* usearch_rs_index_t;
+typedef uint64_t usearch_rs_key_t;
+typedef char const* usearch_rs_error_t;
+
+/** @brief Numeric values of the Rust `MetricKind` enum. */
+typedef enum usearch_rs_metric_kind_t {
+    usearch_rs_metric_unknown_k = 0,
+    usearch_rs_metric_ip_k = 1,
+    usearch_rs_metric_l2sq_k = 2,
+    usearch_rs_metric_cos_k = 3,
+    usearch_rs_metric_pearson_k = 4,
+    usearch_rs_metric_haversine_k = 5,
+    usearch_rs_metric_divergence_k = 6,
+    usearch_rs_metric_hamming_k = 7,
+    usearch_rs_metric_tanimoto_k = 8,
+    usearch_rs_metric_sorensen_k = 9,
+} usearch_rs_metric_kind_t;
+
+/** @brief Numeric values of the Rust `ScalarKind` enum. */
+typedef enum usearch_rs_scalar_kind_t {
+    usearch_rs_scalar_unknown_k = 0,
+    usearch_rs_scalar_f64_k = 1,
+    usearch_rs_scalar_f32_k = 2,
+    usearch_rs_scalar_f16_k = 3,
+    usearch_rs_scalar_i8_k = 4,
+    usearch_rs_scalar_b1_k = 5,
+} usearch_rs_scalar_kind_t;
+
+/** @brief Index options, zero values of the graph parameters select the defaults. */
+typedef struct usearch_rs_options_t {
+    size_t dimensions;
+    int32_t metric;
+    int32_t quantization;
+    size_t connectivity;
+    size_t expansion_add;
+    size_t expansion_search;
+    bool multi;
+} usearch_rs_options_t;
+
+char const* usearch_rs_version(void);
+
+usearch_rs_index_t usearch_rs_create(usearch_rs_options_t const* options, usearch_rs_error_t* error);
+void usearch_rs_free(usearch_rs_index_t index);
+
+size_t usearch_rs_size(usearch_rs_index_t index);
+size_t usearch_rs_capacity(usearch_rs_index_t index);
+size_t usearch_rs_dimensions(usearch_rs_index_t index);
+void usearch_rs_reserve(usearch_rs_index_t index, size_t capacity, usearch_rs_error_t* error);
+
+void usearch_rs_add(usearch_rs_index_t index, usearch_rs_key_t key, float const* vector, size_t dimensions,
+                    usearch_rs_error_t* error);
+size_t usearch_rs_search(usearch_rs_index_t index, float const* query, size_t dimensions, size_t count,
+                         usearch_rs_key_t* keys, float* distances, usearch_rs_error_t* error);
+size_t usearch_rs_get(usearch_rs_index_t index, usearch_rs_key_t key, float* vector, size_t length,
+                      usearch_rs_error_t* error);
+size_t usearch_rs_remove(usearch_rs_index_t index, usearch_rs_key_t key, usearch_rs_error_t* error);
+bool usearch_rs_contains(usearch_rs_index_t index, usearch_rs_key_t key);
+
+size_t usearch_rs_serialized_length(usearch_rs_index_t index);
+void usearch_rs_save_buffer(usearch_rs_index_t index, void* buffer, size_t length, usearch_rs_error_t* error);
+void usearch_rs_load_buffer(usearch_rs_index_t index, void const* buffer, size_t length, usearch_rs_error_t* error);
+void usearch_rs_save(usearch_rs_index_t index, char const* path, usearch_rs_error_t* error);
+void usearch_rs_load(usearch_rs_index_t index, char const* path, usearch_rs_error_t* error);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // USEARCH_RS_H
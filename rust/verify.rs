@@ -0,0 +1,229 @@
+//! Exhaustive consistency checks of the proximity graph.
+//!
+//! Unlike the sampled `Index::health_scan` of the `maintenance` feature, [`Index::verify`] walks
+//! every node and every edge: it follows the edges from the entry point to find live nodes that
+//! searches can no longer reach, counts links to removed or missing nodes, and reads every vector
+//! back at the dimensions of the index. Long sequences of removals and insertions are the usual source of
+//! orphaned nodes, which [`Index::repair`] re-links by inserting their vectors again.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::PoisonError;
+
+use crate::{b1x8, f16, Index, IndexError, Key, ScalarKind, VectorType};
+
+/// Findings of an [`Index::verify`] or [`Index::repair`] call.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VerificationReport {
+    /// Number of slots in the graph, live or removed.
+    pub nodes: usize,
+    /// Number of edges on all levels.
+    pub edges: usize,
+    /// Live keys with no vector reachable from the entry point.
+    pub unreachable: Vec<Key>,
+    /// Number of edges to removed nodes, which searches traverse but never return.
+    pub dangling_edges: usize,
+    /// Number of edges to slots out of range, or to nodes not reaching the level of the edge.
+    pub broken_edges: usize,
+    /// Keys whose stored vectors can't be read back whole at the dimensions of the index.
+    pub dimension_mismatches: Vec<Key>,
+    /// Keys inserted again by [`Index::repair`], always empty for [`Index::verify`].
+    pub relinked: Vec<Key>,
+}
+
+impl VerificationReport {
+    /// Whether every live node is reachable and every edge leads to a node on its level.
+    /// Dangling edges are expected after removals, and don't count.
+    pub fn is_consistent(&self) -> bool {
+        self.unreachable.is_empty()
+            && self.broken_edges == 0
+            && self.dimension_mismatches.is_empty()
+    }
+}
+
+impl Index {
+    /// Walks the whole graph, checking that every live node is reachable from the entry point,
+    /// that every edge leads to an existing node on its level, and that every vector can be
+    /// read back at the dimensions of the index.
+    ///
+    /// Insertions wait for the walk to finish, so that it checks a consistent graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// for key in 0..100u64 {
+    ///     index.add(key, &[key as f32, 1.0]).unwrap();
+    /// }
+    /// let report = index.verify().unwrap();
+    /// assert!(report.is_consistent());
+    /// assert_eq!(report.nodes, 100);
+    /// ```
+    pub fn verify(&self) -> Result<VerificationReport, IndexError> {
+        let _exclusive = self.growth.write().unwrap_or_else(PoisonError::into_inner);
+        let slots = self.inner.slots();
+        let free_key = self.inner.free_key();
+        let mut report = VerificationReport {
+            nodes: slots,
+            ..Default::default()
+        };
+
+        let mut keys = Vec::with_capacity(slots);
+        let mut levels = Vec::with_capacity(slots);
+        for slot in 0..slots {
+            keys.push(self.inner.slot_key(slot)?);
+            levels.push(self.inner.slot_level(slot)?);
+        }
+
+        // Valid edges of every node, merged across levels, as searches descend through them all.
+        let mut adjacency = Vec::with_capacity(slots);
+        for (slot, &level) in levels.iter().enumerate() {
+            let mut targets = Vec::new();
+            for l in 0..=level {
+                for neighbor in self.inner.slot_neighbors(slot, l)? {
+                    report.edges += 1;
+                    let neighbor = neighbor as usize;
+                    if neighbor >= slots || levels[neighbor] < l {
+                        report.broken_edges += 1;
+                        continue;
+                    }
+                    if keys[neighbor] == free_key {
+                        report.dangling_edges += 1;
+                    }
+                    targets.push(neighbor);
+                }
+            }
+            adjacency.push(targets);
+        }
+
+        let mut reached = vec![false; slots];
+        let entry = self.inner.entry_slot();
+        if self.size() > 0 && entry < slots {
+            let mut queue = VecDeque::from([entry]);
+            reached[entry] = true;
+            while let Some(slot) = queue.pop_front() {
+                for &neighbor in &adjacency[slot] {
+                    if !reached[neighbor] {
+                        reached[neighbor] = true;
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        // In multi-vector indexes, a key stays reachable as long as any of its vectors is.
+        let live: HashSet<Key> = keys.iter().copied().filter(|&k| k != free_key).collect();
+        let reachable: HashSet<Key> = (0..slots)
+            .filter(|&slot| reached[slot])
+            .map(|slot| keys[slot])
+            .collect();
+        report.unreachable = live.difference(&reachable).copied().collect();
+        report.unreachable.sort_unstable();
+
+        let dimensions = self.dimensions();
+        let mut buffer = Vec::new();
+        for &key in &live {
+            let count = self.count(key);
+            buffer.resize(count * dimensions, 0.0_f64);
+            // `Index::get` would wait for the growth lock held above.
+            if f64::get_unlocked(self, key, &mut buffer[..])? != count {
+                report.dimension_mismatches.push(key);
+            }
+        }
+        report.dimension_mismatches.sort_unstable();
+        Ok(report)
+    }
+
+    /// Verifies the graph, then removes every unreachable key and inserts its vectors again,
+    /// in the scalar kind they are stored in, linking them back to their nearest neighbors.
+    ///
+    /// # Returns
+    ///
+    /// The report of the verification preceding the repair, with the re-inserted keys listed
+    /// in `relinked`. Running [`Index::verify`] afterwards tells whether the repair succeeded.
+    pub fn repair(&self) -> Result<VerificationReport, IndexError> {
+        let mut report = self.verify()?;
+        for &key in &report.unreachable {
+            match self.inner.scalar_kind() {
+                ScalarKind::B1 => self.relink::<b1x8>(key)?,
+                ScalarKind::I8 => self.relink::<i8>(key)?,
+                ScalarKind::F16 => self.relink::<f16>(key)?,
+                ScalarKind::F64 => self.relink::<f64>(key)?,
+                _ => self.relink::<f32>(key)?,
+            }
+        }
+        report.relinked = report.unreachable.clone();
+        Ok(report)
+    }
+
+    /// Removes the vectors of a key and inserts them again.
+    fn relink<T: VectorType + Default + Clone>(&self, key: Key) -> Result<(), IndexError> {
+        let mut vectors: Vec<T> = Vec::new();
+        self.export(key, &mut vectors)?;
+        self.remove(key)?;
+        for vector in vectors.chunks_exact(self.dimensions()) {
+            self.add(key, vector)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndexOptions, MetricKind};
+
+    #[test]
+    fn test_verify() {
+        // Low connectivity over a coarse grid, with many duplicate points, orphans some nodes.
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 2,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        for key in 0..3000u64 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let x = ((state >> 40) as f32 / (1u64 << 20) as f32).round();
+            let y = ((state & 0xFFFF) as f32 / 4096.0).round();
+            index.add(key, &[x, y]).unwrap();
+        }
+
+        let report = index.verify().unwrap();
+        assert_eq!(report.nodes, 3000);
+        assert_eq!(
+            report.edges,
+            index.stats().levels.iter().map(|l| l.edges).sum::<usize>()
+        );
+        assert_eq!((report.dangling_edges, report.broken_edges), (0, 0));
+        assert!(report.dimension_mismatches.is_empty());
+        assert!(!report.unreachable.is_empty());
+        assert!(!report.is_consistent());
+
+        let repaired = index.repair().unwrap();
+        assert_eq!(repaired.unreachable, report.unreachable);
+        assert_eq!(repaired.relinked, report.unreachable);
+        assert_eq!(index.size(), 3000);
+        let after = index.verify().unwrap();
+        assert!(after.unreachable.len() < report.unreachable.len() / 4);
+        assert!(after.relinked.is_empty());
+        assert_eq!(after.broken_edges, 0);
+
+        // Removed nodes stay linked to, until their slots are recycled.
+        for key in 0..100u64 {
+            index.remove(key).unwrap();
+        }
+        let removed = index.verify().unwrap();
+        assert!(removed.dangling_edges > 0);
+        assert_eq!(removed.broken_edges, 0);
+
+        let empty = Index::new(&options).unwrap();
+        assert!(empty.verify().unwrap().is_consistent());
+    }
+}
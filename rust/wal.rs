@@ -0,0 +1,449 @@
+//! Crash-safe incremental updates through a write-ahead log.
+//!
+//! Snapshots produced by [`Index::save`] are all-or-nothing, so every update between two
+//! snapshots is lost on a crash. A [`DurableIndex`] appends every `add`, `remove`, and `rename`
+//! to a log file before applying it, and on [`DurableIndex::open`] replays the log over the
+//! last saved snapshot. [`DurableIndex::checkpoint`] writes a new snapshot and starts a new log.
+//!
+//! Snapshots and logs are numbered by generation, as `snapshot-<generation>.usearch` and
+//! `journal-<generation>.wal`, the log holding the operations following its snapshot. A
+//! checkpoint makes the next snapshot durable before starting its log and removing the previous
+//! pair, so a crash at any point leaves the newest complete snapshot with either its own log,
+//! or none yet, and never replays operations the snapshot already holds.
+//!
+//! Every log record is framed as `[length: u32][crc32: u32][payload]`, little-endian.
+//! A torn record at the tail, left by a crash mid-write, is detected and discarded on replay.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::checksum::crc32;
+use crate::replication::decode;
+use crate::snapshot::{rename_synced, sync_parent, temporary_path};
+use crate::{b1x8, f16, Index, IndexError, IndexOptions, Key, ScalarKind, VectorType};
+
+const SNAPSHOT_PREFIX: &str = "snapshot-";
+const SNAPSHOT_SUFFIX: &str = ".usearch";
+const LOG_PREFIX: &str = "journal-";
+const LOG_SUFFIX: &str = ".wal";
+const LOG_MAGIC: &[u8; 8] = b"USWAL001";
+const FRAME_HEADER: usize = 8;
+
+const OP_ADD: u8 = 1;
+const OP_REMOVE: u8 = 2;
+const OP_RENAME: u8 = 3;
+
+/// An index wrapper logging every mutation to a write-ahead log before applying it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use usearch::{DurableIndex, IndexOptions};
+///
+/// let options = IndexOptions { dimensions: 3, ..Default::default() };
+/// let mut index = DurableIndex::open(&options, "index-directory").unwrap().with_fsync(true);
+/// index.add(42, &[0.2_f32, 0.1, 0.2]).unwrap();
+/// index.remove(42).unwrap();
+/// index.checkpoint().unwrap(); // Persist a snapshot and start a new log
+/// ```
+pub struct DurableIndex {
+    index: Index,
+    directory: PathBuf,
+    generation: u64,
+    log: File,
+    fsync: bool,
+    pending: usize,
+}
+
+impl DurableIndex {
+    /// Opens a durable index stored in a directory, creating it if needed.
+    ///
+    /// The last snapshot is loaded, if present, and all operations logged after it are replayed.
+    /// Files of older generations, left by a checkpoint interrupted by a crash, are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options used to create the index, must match the ones of the existing snapshot.
+    /// * `directory` - The directory holding the snapshot and the log.
+    pub fn open<P: AsRef<Path>>(options: &IndexOptions, directory: P) -> Result<Self, IndexError> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+
+        let mut index = Index::new(options)?;
+        let generation = latest_snapshot(directory)?.unwrap_or(0);
+        let snapshot_path =
+            generation_path(directory, SNAPSHOT_PREFIX, generation, SNAPSHOT_SUFFIX);
+        if generation > 0 {
+            index.load(&snapshot_path)?;
+        }
+
+        let log_path = generation_path(directory, LOG_PREFIX, generation, LOG_SUFFIX);
+        let mut log = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&log_path)?;
+
+        let mut contents = Vec::new();
+        log.read_to_end(&mut contents)?;
+        let (pending, valid_length) = if contents.is_empty() {
+            log.write_all(LOG_MAGIC)?;
+            log.sync_all()?;
+            sync_parent(&log_path)?;
+            (0, LOG_MAGIC.len())
+        } else if contents.starts_with(LOG_MAGIC) {
+            replay(&index, &contents)?
+        } else {
+            return Err(IndexError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Not a USearch write-ahead log",
+            )));
+        };
+
+        // Discard the torn tail, if any, so new records follow the last valid one.
+        log.set_len(valid_length as u64)?;
+        log.seek(SeekFrom::End(0))?;
+        remove_older(directory, generation)?;
+
+        Ok(Self {
+            index,
+            directory: directory.to_path_buf(),
+            generation,
+            log,
+            fsync: false,
+            pending,
+        })
+    }
+
+    /// Enables or disables `fsync` after every logged operation.
+    ///
+    /// Without it, records survive process crashes, but not necessarily power losses.
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+
+    /// The underlying index, for read-only operations like `search`.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+
+    /// Number of operations logged since the last snapshot.
+    pub fn pending_operations(&self) -> usize {
+        self.pending
+    }
+
+    /// Logs and applies the addition of a vector, growing the capacity as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key associated with the vector.
+    /// * `vector` - A slice containing the vector data.
+    /// # Returns
+    ///
+    /// `IndexError::DimensionMismatch` if the vector doesn't have the dimensions of the index,
+    /// which is checked before the operation is logged.
+    pub fn add<T: VectorType>(&mut self, key: Key, vector: &[T]) -> Result<(), IndexError> {
+        self.index.check_dimensions::<T>(vector.len())?;
+        // All supported scalar types are plain old data.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(vector.as_ptr() as *const u8, std::mem::size_of_val(vector))
+        };
+        let mut payload = Vec::with_capacity(10 + bytes.len());
+        payload.push(OP_ADD);
+        payload.extend_from_slice(&key.to_le_bytes());
+        payload.push(T::SCALAR_KIND.repr as u8);
+        payload.extend_from_slice(bytes);
+
+        if self.index.size() >= self.index.capacity() {
+            self.index.reserve((self.index.capacity() * 2).max(64))?;
+        }
+        self.logged(&payload, |index| index.add(key, vector))
+    }
+
+    /// Logs and applies the removal of a key. Returns the number of removed vectors.
+    pub fn remove(&mut self, key: Key) -> Result<usize, IndexError> {
+        let mut payload = Vec::with_capacity(9);
+        payload.push(OP_REMOVE);
+        payload.extend_from_slice(&key.to_le_bytes());
+        self.logged(&payload, |index| Ok(index.remove(key)?))
+    }
+
+    /// Logs and applies the renaming of a key. Returns the number of renamed vectors.
+    pub fn rename(&mut self, from: Key, to: Key) -> Result<usize, IndexError> {
+        let mut payload = Vec::with_capacity(17);
+        payload.push(OP_RENAME);
+        payload.extend_from_slice(&from.to_le_bytes());
+        payload.extend_from_slice(&to.to_le_bytes());
+        self.logged(&payload, |index| Ok(index.rename(from, to)?))
+    }
+
+    /// Saves a new snapshot and starts a new log.
+    ///
+    /// The snapshot is written to a temporary file, flushed to the disk, and atomically renamed,
+    /// and only then the new log is started and the previous snapshot and log are removed, so a
+    /// crash during the checkpoint reopens either the previous snapshot with its log, or the new
+    /// snapshot alone.
+    pub fn checkpoint(&mut self) -> Result<(), IndexError> {
+        let generation = self.generation + 1;
+        let snapshot_path = generation_path(
+            &self.directory,
+            SNAPSHOT_PREFIX,
+            generation,
+            SNAPSHOT_SUFFIX,
+        );
+        let temporary = temporary_path(&snapshot_path);
+        if let Err(err) = self.index.save(&temporary) {
+            let _ = fs::remove_file(&temporary);
+            return Err(err);
+        }
+        rename_synced(&temporary, &snapshot_path)?;
+
+        let log_path = generation_path(&self.directory, LOG_PREFIX, generation, LOG_SUFFIX);
+        let mut log = File::create(&log_path)?;
+        log.write_all(LOG_MAGIC)?;
+        log.sync_all()?;
+        sync_parent(&log_path)?;
+
+        self.log = log;
+        self.generation = generation;
+        self.pending = 0;
+        remove_older(&self.directory, generation)
+    }
+
+    /// Appends a record, then applies the operation, rolling the log back if it fails.
+    fn logged<R, F>(&mut self, payload: &[u8], apply: F) -> Result<R, IndexError>
+    where
+        F: FnOnce(&Index) -> Result<R, IndexError>,
+    {
+        let position = self.log.stream_position()?;
+        let mut frame = Vec::with_capacity(FRAME_HEADER + payload.len());
+        frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&crc32(payload).to_le_bytes());
+        frame.extend_from_slice(payload);
+        self.log.write_all(&frame)?;
+        if self.fsync {
+            self.log.sync_data()?;
+        }
+
+        match apply(&self.index) {
+            Ok(result) => {
+                self.pending += 1;
+                Ok(result)
+            }
+            Err(err) => {
+                self.log.set_len(position)?;
+                self.log.seek(SeekFrom::Start(position))?;
+                Err(err)
+            }
+        }
+    }
+}
+
+/// The path of the snapshot or the log of a generation.
+fn generation_path(directory: &Path, prefix: &str, generation: u64, suffix: &str) -> PathBuf {
+    directory.join(format!("{}{:020}{}", prefix, generation, suffix))
+}
+
+/// Lists the generations of the files with a prefix and a suffix in a directory.
+fn generations(directory: &Path, prefix: &str, suffix: &str) -> Result<Vec<u64>, IndexError> {
+    let mut found = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let name = entry?.file_name();
+        let generation = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(prefix))
+            .and_then(|name| name.strip_suffix(suffix))
+            .and_then(|number| number.parse::<u64>().ok());
+        found.extend(generation);
+    }
+    Ok(found)
+}
+
+/// The generation of the newest snapshot, which is complete, as snapshots are renamed into place.
+fn latest_snapshot(directory: &Path) -> Result<Option<u64>, IndexError> {
+    Ok(generations(directory, SNAPSHOT_PREFIX, SNAPSHOT_SUFFIX)?
+        .into_iter()
+        .max())
+}
+
+/// Removes the snapshots and the logs of the generations preceding the current one.
+fn remove_older(directory: &Path, generation: u64) -> Result<(), IndexError> {
+    for (prefix, suffix) in [(SNAPSHOT_PREFIX, SNAPSHOT_SUFFIX), (LOG_PREFIX, LOG_SUFFIX)] {
+        for older in generations(directory, prefix, suffix)? {
+            if older < generation {
+                fs::remove_file(generation_path(directory, prefix, older, suffix))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replays all intact records, returning their count and the length of the valid prefix.
+fn replay(index: &Index, contents: &[u8]) -> Result<(usize, usize), IndexError> {
+    let mut offset = LOG_MAGIC.len();
+    let mut count = 0;
+    while offset + FRAME_HEADER <= contents.len() {
+        let length = u32::from_le_bytes(contents[offset..offset + 4].try_into().unwrap()) as usize;
+        let checksum = u32::from_le_bytes(contents[offset + 4..offset + 8].try_into().unwrap());
+        let start = offset + FRAME_HEADER;
+        if length == 0 || start + length > contents.len() {
+            break;
+        }
+        let payload = &contents[start..start + length];
+        if crc32(payload) != checksum {
+            break;
+        }
+        apply_record(index, payload)?;
+        offset = start + length;
+        count += 1;
+    }
+    Ok((count, offset))
+}
+
+fn apply_record(index: &Index, payload: &[u8]) -> Result<(), IndexError> {
+    let read_key = |at: usize| -> Result<Key, IndexError> {
+        payload
+            .get(at..at + 8)
+            .map(|bytes| Key::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or_else(malformed_record)
+    };
+
+    match payload[0] {
+        OP_ADD => {
+            let key = read_key(1)?;
+            let kind = *payload.get(9).ok_or_else(malformed_record)? as i32;
+            let bytes = &payload[10..];
+            if index.size() >= index.capacity() {
+                index.reserve((index.capacity() * 2).max(64))?;
+            }
+            match (ScalarKind { repr: kind }) {
+                ScalarKind::F64 => index.add(key, &decode::<f64>(bytes))?,
+                ScalarKind::F32 => index.add(key, &decode::<f32>(bytes))?,
+                ScalarKind::F16 => index.add(key, &decode::<f16>(bytes))?,
+                ScalarKind::I8 => index.add(key, &decode::<i8>(bytes))?,
+                ScalarKind::B1 => index.add(key, &decode::<b1x8>(bytes))?,
+                _ => return Err(malformed_record()),
+            }
+        }
+        OP_REMOVE => {
+            index.remove(read_key(1)?)?;
+        }
+        OP_RENAME => {
+            index.rename(read_key(1)?, read_key(9)?)?;
+        }
+        _ => return Err(malformed_record()),
+    }
+    Ok(())
+}
+
+fn malformed_record() -> IndexError {
+    IndexError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "Malformed write-ahead log record",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_after_crash() {
+        let directory = std::env::temp_dir().join("usearch.rust.wal");
+        let _ = fs::remove_dir_all(&directory);
+        let options = IndexOptions {
+            dimensions: 2,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+
+        {
+            let mut durable = DurableIndex::open(&options, &directory).unwrap();
+            durable.add(1, &[1.0_f32, 0.0]).unwrap();
+            durable.add(2, &[0.0_f64, 1.0]).unwrap();
+            durable.checkpoint().unwrap();
+            durable.add(3, &[1.0_f32, 1.0]).unwrap();
+            durable.remove(1).unwrap();
+            durable.rename(2, 20).unwrap();
+            assert!(matches!(
+                durable.add(4, &[1.0_f32]),
+                Err(IndexError::DimensionMismatch {
+                    expected: 2,
+                    got: 1
+                })
+            ));
+            assert_eq!(durable.pending_operations(), 3);
+        }
+
+        // Simulate a torn write at the end of the log.
+        let mut log = OpenOptions::new()
+            .append(true)
+            .open(generation_path(&directory, LOG_PREFIX, 1, LOG_SUFFIX))
+            .unwrap();
+        log.write_all(&[42, 0, 0, 0, 1, 2]).unwrap();
+        drop(log);
+
+        let durable = DurableIndex::open(&options, &directory).unwrap();
+        assert_eq!(durable.pending_operations(), 3);
+        let index = durable.index();
+        assert_eq!(index.size(), 2);
+        assert!(!index.contains(1));
+        assert!(index.contains(20));
+        assert!(index.contains(3));
+
+        let mut vector = [0.0_f32; 2];
+        index.get(20, &mut vector).unwrap();
+        assert_eq!(vector, [0.0, 1.0]);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_crash_between_snapshot_and_new_log() {
+        let directory = std::env::temp_dir().join("usearch.rust.wal.generations");
+        let _ = fs::remove_dir_all(&directory);
+        let options = IndexOptions {
+            dimensions: 2,
+            quantization: ScalarKind::F32,
+            ..Default::default()
+        };
+
+        {
+            let mut durable = DurableIndex::open(&options, &directory).unwrap();
+            durable.add(1, &[1.0_f32, 0.0]).unwrap();
+            durable.checkpoint().unwrap();
+            durable.add(2, &[0.0_f32, 1.0]).unwrap();
+            durable.add(3, &[1.0_f32, 1.0]).unwrap();
+
+            // Crash once the next snapshot is renamed into place, before its log is started.
+            let snapshot = generation_path(&directory, SNAPSHOT_PREFIX, 2, SNAPSHOT_SUFFIX);
+            durable.index().save(&snapshot).unwrap();
+        }
+
+        // The logged additions are already in the newest snapshot, and aren't applied again.
+        let mut durable = DurableIndex::open(&options, &directory).unwrap();
+        assert_eq!(durable.pending_operations(), 0);
+        assert_eq!(durable.index().size(), 3);
+        let mut names: Vec<_> = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "journal-00000000000000000002.wal",
+                "snapshot-00000000000000000002.usearch"
+            ]
+        );
+
+        durable.add(4, &[0.5_f32, 0.5]).unwrap();
+        drop(durable);
+        let durable = DurableIndex::open(&options, &directory).unwrap();
+        assert_eq!(durable.pending_operations(), 1);
+        assert_eq!(durable.index().size(), 4);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+//! Faulting in the pages of a viewed index before it serves queries.
+//!
+//! [`Index::view`] maps a file without reading it, so the first searches after a restart wait
+//! for every node and vector they visit to be read from disk. [`Index::warmup`] reads those pages
+//! ahead of time, starting with the ones every search visits: the upper levels of the graph, and
+//! the base-level neighborhood of the entry point.
+
+use std::sync::PoisonError;
+
+use crate::Index;
+
+impl Index {
+    /// Reads the nodes and vectors searches visit first, until `budget_bytes` are covered.
+    ///
+    /// The graph is walked breadth-first from the entry point, level by level from the top, so
+    /// a small budget covers the levels every search passes through, and a budget larger than
+    /// [`Index::serialized_length`] covers the whole index. Loaded indexes are already in memory,
+    /// so this only pays off for views of files that aren't in the page cache.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes of nodes and vectors read, which may exceed the budget by one node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use usearch::{Index, IndexOptions};
+    ///
+    /// let index = Index::new(&IndexOptions { dimensions: 2, ..Default::default() }).unwrap();
+    /// index.reserve(100).unwrap();
+    /// for key in 0..100u64 {
+    ///     index.add(key, &[key as f32, 1.0]).unwrap();
+    /// }
+    /// let path = std::env::temp_dir().join("usearch-warmup-example.usearch");
+    /// index.save(&path).unwrap();
+    ///
+    /// let mut view = Index::new(&index.options()).unwrap();
+    /// view.view(&path).unwrap();
+    /// assert!(view.warmup(64 * 1024 * 1024) > 0);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn warmup(&self, budget_bytes: usize) -> usize {
+        let _shared = self.growth.read().unwrap_or_else(PoisonError::into_inner);
+        self.inner.warmup(budget_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Index, IndexOptions, MetricKind, ScalarKind};
+
+    #[test]
+    fn test_warmup() {
+        let options = IndexOptions {
+            dimensions: 8,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::F32,
+            connectivity: 8,
+            ..Default::default()
+        };
+        let index = Index::new(&options).unwrap();
+        assert_eq!(index.warmup(1 << 20), 0);
+        index.reserve(2000).unwrap();
+        for key in 0..2000u64 {
+            index.add(key, &[key as f32; 8]).unwrap();
+        }
+
+        let everything = index.warmup(usize::MAX);
+        assert!(everything >= 2000 * 8 * 4);
+        assert_eq!(index.warmup(0), 0);
+        let partial = index.warmup(16 * 1024);
+        assert!(partial >= 16 * 1024 && partial < everything);
+
+        #[cfg(not(no_fs))]
+        {
+            let path = std::env::temp_dir().join("usearch-warmup-test.usearch");
+            index.save(&path).unwrap();
+            let mut view = Index::new(&options).unwrap();
+            view.view(&path).unwrap();
+            assert_eq!(view.warmup(usize::MAX), everything);
+            assert_eq!(view.search(&[7.0_f32; 8], 1).unwrap().keys, vec![7]);
+            drop(view);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+}